@@ -0,0 +1,683 @@
+//! Self-exciting (Hawkes) order arrival model.
+//!
+//! [`OrderIntensityEstimator`](super::intensity::OrderIntensityEstimator)
+//! treats arrivals as a Poisson process whose rate depends only on the quoted
+//! distance. Real fills cluster: one fill makes the next one more likely for a
+//! short while. A Hawkes process with an exponential kernel captures this:
+//!
+//! ```text
+//! λ(t) = μ + Σ_{tᵢ < t} α · exp(-β · (t - tᵢ))
+//! ```
+//!
+//! Where:
+//! - `μ` = baseline arrival rate (events per second)
+//! - `α` = jump in intensity caused by each event (events per second)
+//! - `β` = decay rate of the excitation (per second)
+//!
+//! The branching ratio `n = α / β` is the expected number of events directly
+//! triggered by one event; the process is stationary for `n < 1`, with mean
+//! intensity `μ / (1 - n)`.
+//!
+//! # Estimation Method
+//!
+//! Parameters are fitted by maximum likelihood over the event times inside the
+//! estimation window, using Ozaki's recursion for the log-likelihood:
+//!
+//! ```text
+//! ℓ = Σᵢ ln(μ + α·Rᵢ) - μ·T - (α/β) · Σᵢ (1 - exp(-β·(T - tᵢ)))
+//! Rᵢ = exp(-β·(tᵢ - tᵢ₋₁)) · (1 + Rᵢ₋₁),  R₁ = 0
+//! ```
+//!
+//! The likelihood is maximized with a Nelder-Mead search over
+//! `(ln μ, logit n, ln β)`, which keeps every candidate stationary.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::analytics::hawkes::{HawkesConfig, HawkesEstimator};
+//! use market_maker_rs::analytics::intensity::FillObservation;
+//! use market_maker_rs::dec;
+//!
+//! let config = HawkesConfig::new(600_000, 10).unwrap();
+//! let mut estimator = HawkesEstimator::new(config);
+//!
+//! // Bursts of fills every 10 seconds.
+//! for burst in 0..20u64 {
+//!     for i in 0..3u64 {
+//!         let timestamp = burst * 10_000 + i * 150;
+//!         estimator.record_fill(&FillObservation::new(dec!(0.001), 500, timestamp));
+//!     }
+//! }
+//!
+//! let estimate = estimator.fit(200_000).unwrap();
+//! assert!(estimate.parameters.branching_ratio() > dec!(0.0));
+//!
+//! // Right after a burst the intensity is above its long-run mean.
+//! let ratio = estimator.intensity_ratio(190_400).unwrap();
+//! assert!(ratio > dec!(1.0));
+//! ```
+
+use std::collections::VecDeque;
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+use crate::Decimal;
+use crate::analytics::intensity::FillObservation;
+use crate::types::error::{MMError, MMResult};
+use crate::types::numerics::nelder_mead;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Largest branching ratio the fit may return.
+const MAX_BRANCHING_RATIO: f64 = 0.999;
+
+/// Configuration for Hawkes estimation.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::analytics::hawkes::HawkesConfig;
+///
+/// let config = HawkesConfig::new(
+///     300_000, // 5 minute estimation window
+///     20,      // minimum 20 events for a fit
+/// )
+/// .unwrap()
+/// .with_max_iterations(1_000);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HawkesConfig {
+    /// Window for estimation in milliseconds.
+    pub estimation_window_ms: u64,
+
+    /// Minimum events required for a fit.
+    pub min_events: usize,
+
+    /// Maximum Nelder-Mead iterations per starting point.
+    pub max_iterations: usize,
+
+    /// Convergence tolerance on the log-likelihood spread of the simplex.
+    pub tolerance: f64,
+}
+
+impl HawkesConfig {
+    /// Creates a new `HawkesConfig` with validation.
+    ///
+    /// # Arguments
+    ///
+    /// * `estimation_window_ms` - Window for estimation in milliseconds
+    /// * `min_events` - Minimum events required for a fit (at least 2)
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if parameters are invalid.
+    pub fn new(estimation_window_ms: u64, min_events: usize) -> MMResult<Self> {
+        if estimation_window_ms == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "estimation_window_ms must be positive".to_string(),
+            ));
+        }
+
+        if min_events < 2 {
+            return Err(MMError::InvalidConfiguration(
+                "min_events must be at least 2".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            estimation_window_ms,
+            min_events,
+            max_iterations: 500,
+            tolerance: 1e-8,
+        })
+    }
+
+    /// Sets the maximum number of optimizer iterations.
+    #[must_use]
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations.max(1);
+        self
+    }
+
+    /// Sets the optimizer convergence tolerance.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// Parameters of an exponential-kernel Hawkes process.
+///
+/// All rates are per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HawkesParameters {
+    /// Baseline arrival rate μ.
+    pub baseline: Decimal,
+
+    /// Excitation α added to the intensity by each event.
+    pub excitation: Decimal,
+
+    /// Decay rate β of the excitation.
+    pub decay: Decimal,
+}
+
+impl HawkesParameters {
+    /// Creates validated Hawkes parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `baseline` or `decay` is not
+    /// positive, `excitation` is negative, or the process is not stationary
+    /// (`excitation >= decay`).
+    pub fn new(baseline: Decimal, excitation: Decimal, decay: Decimal) -> MMResult<Self> {
+        if baseline <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "baseline must be positive".to_string(),
+            ));
+        }
+        if decay <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "decay must be positive".to_string(),
+            ));
+        }
+        if excitation < Decimal::ZERO || excitation >= decay {
+            return Err(MMError::InvalidConfiguration(
+                "excitation must be in [0, decay) for a stationary process".to_string(),
+            ));
+        }
+        Ok(Self {
+            baseline,
+            excitation,
+            decay,
+        })
+    }
+
+    /// Returns the branching ratio `α / β`.
+    #[must_use]
+    pub fn branching_ratio(&self) -> Decimal {
+        self.excitation / self.decay
+    }
+
+    /// Returns the long-run mean intensity `μ / (1 - α/β)`.
+    #[must_use]
+    pub fn stationary_intensity(&self) -> Decimal {
+        self.baseline / (Decimal::ONE - self.branching_ratio())
+    }
+
+    /// Returns the half-life of the excitation in milliseconds.
+    #[must_use]
+    pub fn half_life_ms(&self) -> Decimal {
+        Decimal::from_f64(std::f64::consts::LN_2 * 1000.0 / to_f64(self.decay))
+            .unwrap_or(Decimal::ZERO)
+    }
+}
+
+/// Result of a Hawkes fit.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HawkesEstimate {
+    /// Fitted parameters.
+    pub parameters: HawkesParameters,
+
+    /// Maximized log-likelihood.
+    pub log_likelihood: Decimal,
+
+    /// Log-likelihood of the best homogeneous Poisson fit to the same data.
+    ///
+    /// Twice the difference to `log_likelihood` is a likelihood-ratio
+    /// statistic for the presence of clustering.
+    pub poisson_log_likelihood: Decimal,
+
+    /// Number of events used in the fit.
+    pub sample_size: usize,
+
+    /// Timestamp when the estimate was computed.
+    pub timestamp: u64,
+}
+
+/// Hawkes process estimator for clustered fill and trade arrivals.
+///
+/// Collects event timestamps (typically from [`FillObservation`]s), fits
+/// [`HawkesParameters`] by maximum likelihood and exposes the resulting
+/// time-varying intensity.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HawkesEstimator {
+    /// Configuration parameters.
+    config: HawkesConfig,
+
+    /// Event timestamps in milliseconds, sorted ascending.
+    events: VecDeque<u64>,
+
+    /// Current estimate (if available).
+    current_estimate: Option<HawkesEstimate>,
+
+    /// Total events recorded.
+    total_events: u64,
+}
+
+impl HawkesEstimator {
+    /// Creates a new Hawkes estimator.
+    #[must_use]
+    pub fn new(config: HawkesConfig) -> Self {
+        Self {
+            config,
+            events: VecDeque::new(),
+            current_estimate: None,
+            total_events: 0,
+        }
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn config(&self) -> &HawkesConfig {
+        &self.config
+    }
+
+    /// Returns the number of events in the window.
+    #[must_use]
+    pub fn event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns total events recorded (including expired ones).
+    #[must_use]
+    pub fn total_events(&self) -> u64 {
+        self.total_events
+    }
+
+    /// Records an event (fill or trade) at `timestamp` milliseconds.
+    ///
+    /// Out-of-order timestamps are inserted at their sorted position.
+    pub fn record_event(&mut self, timestamp: u64) {
+        if self.events.back().is_none_or(|&last| last <= timestamp) {
+            self.events.push_back(timestamp);
+        } else {
+            let index = self.events.partition_point(|&t| t <= timestamp);
+            self.events.insert(index, timestamp);
+        }
+        self.total_events += 1;
+    }
+
+    /// Records the timestamp of a fill observation.
+    pub fn record_fill(&mut self, observation: &FillObservation) {
+        self.record_event(observation.timestamp);
+    }
+
+    /// Fits the Hawkes parameters to the events in the window.
+    ///
+    /// The observation period runs from the first event in the window to
+    /// `current_time`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if there are fewer than
+    /// `min_events` events or they span no time, and `MMError::NumericalError`
+    /// if the optimizer fails to produce finite parameters.
+    pub fn fit(&mut self, current_time: u64) -> MMResult<HawkesEstimate> {
+        self.cleanup(current_time);
+
+        if self.events.len() < self.config.min_events {
+            return Err(MMError::InvalidMarketState(format!(
+                "insufficient events: {} < {}",
+                self.events.len(),
+                self.config.min_events
+            )));
+        }
+
+        let start = self.events[0];
+        let horizon = ms_to_seconds(current_time.saturating_sub(start));
+        if horizon <= 0.0 {
+            return Err(MMError::InvalidMarketState(
+                "events must span a positive time".to_string(),
+            ));
+        }
+        let times: Vec<f64> = self
+            .events
+            .iter()
+            .map(|&t| ms_to_seconds(t - start))
+            .collect();
+
+        let count = times.len() as f64;
+        let poisson_rate = count / horizon;
+        let poisson_log_likelihood = count * poisson_rate.ln() - poisson_rate * horizon;
+
+        let objective = |x: &[f64; 3]| {
+            let (mu, alpha, beta) = unpack(x);
+            -log_likelihood(&times, horizon, mu, alpha, beta)
+        };
+
+        let mut best: Option<([f64; 3], f64)> = None;
+        for decay_multiple in [0.5, 2.0, 10.0] {
+            let start_point = [
+                (0.5 * poisson_rate).ln(),
+                0.0,
+                (decay_multiple * poisson_rate).ln(),
+            ];
+            let (point, value) = nelder_mead(
+                objective,
+                start_point,
+                self.config.max_iterations,
+                self.config.tolerance,
+            );
+            if value.is_finite() && best.is_none_or(|(_, v)| value < v) {
+                best = Some((point, value));
+            }
+        }
+
+        let Some((point, value)) = best else {
+            return Err(MMError::NumericalError(
+                "Hawkes likelihood optimization failed".to_string(),
+            ));
+        };
+        let (mu, alpha, beta) = unpack(&point);
+
+        let parameters = HawkesParameters {
+            baseline: from_f64(mu)?,
+            excitation: from_f64(alpha)?,
+            decay: from_f64(beta)?,
+        };
+        let estimate = HawkesEstimate {
+            parameters,
+            log_likelihood: from_f64(-value)?,
+            poisson_log_likelihood: from_f64(poisson_log_likelihood)?,
+            sample_size: self.events.len(),
+            timestamp: current_time,
+        };
+
+        self.current_estimate = Some(estimate.clone());
+        Ok(estimate)
+    }
+
+    /// Gets the current estimate if available.
+    #[must_use]
+    pub fn get_estimate(&self) -> Option<&HawkesEstimate> {
+        self.current_estimate.as_ref()
+    }
+
+    /// Returns the conditional intensity λ(t) in events per second.
+    ///
+    /// Only events strictly before `timestamp` contribute. Returns `None` if
+    /// no estimate is available.
+    #[must_use]
+    pub fn intensity(&self, timestamp: u64) -> Option<Decimal> {
+        let parameters = self.current_estimate.as_ref()?.parameters;
+        Decimal::from_f64(self.intensity_f64(&parameters, timestamp))
+    }
+
+    /// Returns λ(t) divided by the stationary intensity.
+    ///
+    /// Values above 1 mean arrivals are currently clustered; values below 1
+    /// mean the market is quieter than its long-run average.
+    #[must_use]
+    pub fn intensity_ratio(&self, timestamp: u64) -> Option<Decimal> {
+        let parameters = self.current_estimate.as_ref()?.parameters;
+        let stationary = to_f64(parameters.stationary_intensity());
+        Decimal::from_f64(self.intensity_f64(&parameters, timestamp) / stationary)
+    }
+
+    /// Scales a constant order intensity by the current intensity ratio.
+    ///
+    /// Lets strategies that take a fixed `order_intensity` follow the
+    /// clustering of arrivals. Falls back to `base` when no estimate is
+    /// available.
+    #[must_use]
+    pub fn order_intensity(&self, base: Decimal, timestamp: u64) -> Decimal {
+        self.intensity_ratio(timestamp)
+            .map_or(base, |ratio| base * ratio)
+    }
+
+    /// Returns the expected number of events in `(t, t + horizon]`, ignoring
+    /// excitation from events inside the horizon.
+    ///
+    /// Uses `μ·h + (λ(t) - μ) · (1 - exp(-β·h)) / β`.
+    #[must_use]
+    pub fn expected_events(&self, timestamp: u64, horizon_ms: u64) -> Option<Decimal> {
+        let parameters = self.current_estimate.as_ref()?.parameters;
+        let mu = to_f64(parameters.baseline);
+        let beta = to_f64(parameters.decay);
+        let lambda = self.intensity_f64(&parameters, timestamp);
+        let h = ms_to_seconds(horizon_ms);
+        Decimal::from_f64(mu * h + (lambda - mu) * (1.0 - (-beta * h).exp()) / beta)
+    }
+
+    /// Returns the probability of at least one event within the horizon.
+    #[must_use]
+    pub fn event_probability(&self, timestamp: u64, horizon_ms: u64) -> Option<Decimal> {
+        let expected = self.expected_events(timestamp, horizon_ms)?;
+        Decimal::from_f64(1.0 - (-to_f64(expected)).exp())
+    }
+
+    /// Clears events outside the estimation window.
+    pub fn cleanup(&mut self, current_time: u64) {
+        let window_start = current_time.saturating_sub(self.config.estimation_window_ms);
+        while self.events.front().is_some_and(|&t| t < window_start) {
+            self.events.pop_front();
+        }
+    }
+
+    /// Resets the estimator, clearing all events and estimates.
+    pub fn reset(&mut self) {
+        self.events.clear();
+        self.current_estimate = None;
+        self.total_events = 0;
+    }
+
+    fn intensity_f64(&self, parameters: &HawkesParameters, timestamp: u64) -> f64 {
+        let mu = to_f64(parameters.baseline);
+        let alpha = to_f64(parameters.excitation);
+        let beta = to_f64(parameters.decay);
+        let excitation: f64 = self
+            .events
+            .iter()
+            .take_while(|&&t| t < timestamp)
+            .map(|&t| (-beta * ms_to_seconds(timestamp - t)).exp())
+            .sum();
+        mu + alpha * excitation
+    }
+}
+
+fn ms_to_seconds(ms: u64) -> f64 {
+    ms as f64 / 1000.0
+}
+
+fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
+fn from_f64(value: f64) -> MMResult<Decimal> {
+    Decimal::from_f64(value)
+        .ok_or_else(|| MMError::NumericalError(format!("cannot represent {value} as a decimal")))
+}
+
+/// Maps unconstrained optimizer coordinates to `(μ, α, β)`.
+fn unpack(x: &[f64; 3]) -> (f64, f64, f64) {
+    let mu = x[0].exp();
+    let branching = MAX_BRANCHING_RATIO / (1.0 + (-x[1]).exp());
+    let beta = x[2].exp();
+    (mu, branching * beta, beta)
+}
+
+/// Exponential-kernel Hawkes log-likelihood on `[0, horizon]` (seconds).
+fn log_likelihood(times: &[f64], horizon: f64, mu: f64, alpha: f64, beta: f64) -> f64 {
+    let mut recursion = 0.0;
+    let mut sum_log = 0.0;
+    let mut previous: Option<f64> = None;
+    for &t in times {
+        if let Some(p) = previous {
+            recursion = (-beta * (t - p)).exp() * (1.0 + recursion);
+        }
+        sum_log += (mu + alpha * recursion).ln();
+        previous = Some(t);
+    }
+    let compensator: f64 = times
+        .iter()
+        .map(|&t| 1.0 - (-beta * (horizon - t)).exp())
+        .sum();
+    let value = sum_log - mu * horizon - alpha / beta * compensator;
+    if value.is_finite() {
+        value
+    } else {
+        f64::NEG_INFINITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+
+    /// Simulates a Hawkes process by Ogata thinning with a fixed LCG seed.
+    fn simulate(mu: f64, alpha: f64, beta: f64, horizon_s: f64, seed: u64) -> Vec<u64> {
+        let mut state = seed;
+        let mut uniform = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        };
+
+        let mut events: Vec<f64> = Vec::new();
+        let mut t = 0.0;
+        let intensity = |t: f64, events: &[f64]| {
+            mu + events
+                .iter()
+                .map(|&s| alpha * (-beta * (t - s)).exp())
+                .sum::<f64>()
+        };
+        while t < horizon_s {
+            let upper = intensity(t, &events) + alpha;
+            t += -uniform().ln() / upper;
+            if t < horizon_s && uniform() * upper <= intensity(t, &events) {
+                events.push(t);
+            }
+        }
+        events.iter().map(|&s| (s * 1000.0) as u64).collect()
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(HawkesConfig::new(60_000, 10).is_ok());
+        assert!(HawkesConfig::new(0, 10).is_err());
+        assert!(HawkesConfig::new(60_000, 1).is_err());
+        assert_eq!(
+            HawkesConfig::new(60_000, 10)
+                .unwrap()
+                .with_max_iterations(0)
+                .max_iterations,
+            1
+        );
+    }
+
+    #[test]
+    fn test_parameters() {
+        let params = HawkesParameters::new(dec!(1.0), dec!(0.5), dec!(2.0)).unwrap();
+        assert_eq!(params.branching_ratio(), dec!(0.25));
+        assert_eq!(params.stationary_intensity().round_dp(6), dec!(1.333333));
+
+        assert!(HawkesParameters::new(dec!(0.0), dec!(0.5), dec!(2.0)).is_err());
+        assert!(HawkesParameters::new(dec!(1.0), dec!(2.0), dec!(2.0)).is_err());
+        assert!(HawkesParameters::new(dec!(1.0), dec!(-0.1), dec!(2.0)).is_err());
+    }
+
+    #[test]
+    fn test_fit_requires_events() {
+        let mut estimator = HawkesEstimator::new(HawkesConfig::new(60_000, 5).unwrap());
+        estimator.record_event(1_000);
+        assert!(estimator.fit(2_000).is_err());
+        assert!(estimator.intensity(2_000).is_none());
+        assert_eq!(estimator.order_intensity(dec!(1.5), 2_000), dec!(1.5));
+    }
+
+    #[test]
+    fn test_out_of_order_events_are_sorted() {
+        let mut estimator = HawkesEstimator::new(HawkesConfig::new(60_000, 2).unwrap());
+        estimator.record_event(3_000);
+        estimator.record_event(1_000);
+        estimator.record_event(2_000);
+        assert_eq!(
+            estimator.events.iter().copied().collect::<Vec<_>>(),
+            vec![1_000, 2_000, 3_000]
+        );
+        assert_eq!(estimator.total_events(), 3);
+    }
+
+    #[test]
+    fn test_recovers_simulated_parameters() {
+        let events = simulate(0.5, 1.0, 2.0, 4_000.0, 7);
+        let mut estimator = HawkesEstimator::new(HawkesConfig::new(u64::MAX / 2, 50).unwrap());
+        for &t in &events {
+            estimator.record_event(t);
+        }
+
+        let estimate = estimator.fit(4_000_000).unwrap();
+        let params = estimate.parameters;
+        let branching = to_f64(params.branching_ratio());
+        let baseline = to_f64(params.baseline);
+        assert!((branching - 0.5).abs() < 0.1, "branching {branching}");
+        assert!((baseline - 0.5).abs() < 0.15, "baseline {baseline}");
+        assert!(estimate.log_likelihood > estimate.poisson_log_likelihood);
+    }
+
+    #[test]
+    fn test_poisson_data_has_low_branching() {
+        // Evenly spaced arrivals show no clustering at all.
+        let mut estimator = HawkesEstimator::new(HawkesConfig::new(600_000, 10).unwrap());
+        for i in 0..200u64 {
+            estimator.record_event(i * 1_000);
+        }
+        let estimate = estimator.fit(200_000).unwrap();
+        assert!(estimate.parameters.branching_ratio() < dec!(0.1));
+        let stationary = to_f64(estimate.parameters.stationary_intensity());
+        assert!((stationary - 1.0).abs() < 0.1, "stationary {stationary}");
+    }
+
+    #[test]
+    fn test_intensity_decays_after_event() {
+        let mut estimator = HawkesEstimator::new(HawkesConfig::new(600_000, 2).unwrap());
+        estimator.record_event(0);
+        estimator.record_event(1_000);
+        estimator.current_estimate = Some(HawkesEstimate {
+            parameters: HawkesParameters::new(dec!(1.0), dec!(1.0), dec!(2.0)).unwrap(),
+            log_likelihood: Decimal::ZERO,
+            poisson_log_likelihood: Decimal::ZERO,
+            sample_size: 2,
+            timestamp: 1_000,
+        });
+
+        // Just after the second event: μ + α(e^{-2} + 1) ≈ 2.135
+        let just_after = to_f64(estimator.intensity(1_001).unwrap());
+        assert!((just_after - (1.0 + (-2.002f64).exp() + (-0.002f64).exp())).abs() < 1e-6);
+
+        let later = estimator.intensity(10_000).unwrap();
+        assert!(later < estimator.intensity(1_001).unwrap());
+        assert!((to_f64(later) - 1.0).abs() < 1e-6);
+
+        // Stationary intensity is 2, so the ratio starts above 1 and falls below it.
+        assert!(estimator.intensity_ratio(1_001).unwrap() > Decimal::ONE);
+        assert!(estimator.order_intensity(dec!(1.5), 10_000) < dec!(1.5));
+
+        let expected = to_f64(estimator.expected_events(10_000, 1_000).unwrap());
+        assert!((expected - 1.0).abs() < 1e-3);
+        let probability = to_f64(estimator.event_probability(10_000, 1_000).unwrap());
+        assert!((probability - (1.0 - (-expected).exp())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cleanup_and_reset() {
+        let mut estimator = HawkesEstimator::new(HawkesConfig::new(10_000, 2).unwrap());
+        estimator.record_fill(&FillObservation::new(dec!(0.001), 100, 1_000));
+        estimator.record_fill(&FillObservation::new(dec!(0.001), 100, 20_000));
+        estimator.cleanup(25_000);
+        assert_eq!(estimator.event_count(), 1);
+
+        estimator.reset();
+        assert_eq!(estimator.event_count(), 0);
+        assert_eq!(estimator.total_events(), 0);
+        assert!(estimator.get_estimate().is_none());
+    }
+}
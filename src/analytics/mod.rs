@@ -9,6 +9,7 @@
 //! - `order_flow`: Order flow imbalance analysis and trade tracking
 //! - `vpin`: VPIN (Volume-Synchronized Probability of Informed Trading) calculation
 //! - `intensity`: Dynamic order intensity estimation for A-S model
+//! - `hawkes`: Self-exciting (Hawkes) arrival model for clustered fills
 //! - `live_metrics`: Real-time operational metrics tracking
 //! - `prometheus_export`: Prometheus metrics export (feature: `prometheus`)
 //!
@@ -34,6 +35,9 @@ pub mod vpin;
 /// Dynamic order intensity estimation.
 pub mod intensity;
 
+/// Self-exciting (Hawkes) order arrival model.
+pub mod hawkes;
+
 /// Live metrics tracking for real-time monitoring.
 pub mod live_metrics;
 
@@ -41,6 +45,7 @@ pub mod live_metrics;
 #[cfg(feature = "prometheus")]
 pub mod prometheus_export;
 
+pub use hawkes::{HawkesConfig, HawkesEstimate, HawkesEstimator, HawkesParameters};
 pub use intensity::{
     FillObservation, FillSide, IntensityEstimate, ObservationStats, OrderIntensityConfig,
     OrderIntensityEstimator,
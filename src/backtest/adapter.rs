@@ -0,0 +1,292 @@
+//! Bridge between [`QuotingStrategy`] and [`BacktestStrategy`].
+//!
+//! Any quoting model, including a `Box<dyn QuotingStrategy>` built from a
+//! [`QuotingStrategyConfig`](crate::strategy::quoting::QuotingStrategyConfig),
+//! can be backtested by wrapping it in a [`QuotingStrategyAdapter`]. On each
+//! tick the adapter builds a [`QuoteContext`] from the tick's top of book and
+//! current position, and forwards the best level of the resulting
+//! [`QuoteSet`](crate::strategy::quoting::QuoteSet) to the engine.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::backtest::{
+//!     BacktestConfig, BacktestEngine, MarketTick, QuotingStrategyAdapter, VecDataSource,
+//! };
+//! use market_maker_rs::strategy::grid::GridConfig;
+//! use market_maker_rs::strategy::quoting::QuotingStrategyConfig;
+//! use market_maker_rs::dec;
+//!
+//! let strategy = QuotingStrategyConfig::Grid(
+//!     GridConfig::new(2, dec!(0.001), dec!(1.0), dec!(10.0)).unwrap(),
+//! )
+//! .build()
+//! .unwrap();
+//!
+//! let ticks = vec![
+//!     MarketTick::new(1000, dec!(100.0), dec!(1.0), dec!(100.2), dec!(1.0)),
+//!     MarketTick::new(1001, dec!(100.1), dec!(1.0), dec!(100.3), dec!(1.0)),
+//! ];
+//!
+//! let mut engine = BacktestEngine::new(
+//!     BacktestConfig::default(),
+//!     QuotingStrategyAdapter::new(strategy, dec!(0.2)),
+//!     VecDataSource::new(ticks),
+//! );
+//! let result = engine.run();
+//! assert_eq!(result.num_ticks, 2);
+//! ```
+
+use crate::Decimal;
+use crate::backtest::data::MarketTick;
+use crate::backtest::engine::{BacktestStrategy, SimulatedFill};
+use crate::execution::{BookLevel, OrderBookSnapshot};
+use crate::position::inventory::InventoryPosition;
+use crate::strategy::quote::Quote;
+use crate::strategy::quoting::{QuoteContext, QuotingStrategy};
+use crate::types::error::MMError;
+
+/// Runs a [`QuotingStrategy`] inside the [`BacktestEngine`](crate::backtest::BacktestEngine).
+///
+/// The engine only simulates one quote per side, so only the best level of
+/// each side is forwarded. Ticks for which the strategy returns an error or
+/// quotes only one side produce no quote.
+pub struct QuotingStrategyAdapter<Q: QuotingStrategy> {
+    strategy: Q,
+    volatility: Decimal,
+    symbol: String,
+    last_error: Option<MMError>,
+}
+
+impl<Q: QuotingStrategy> QuotingStrategyAdapter<Q> {
+    /// Creates a new adapter using a constant volatility estimate.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - Quoting strategy to run
+    /// * `volatility` - Annualized volatility passed in every context
+    #[must_use]
+    pub fn new(strategy: Q, volatility: Decimal) -> Self {
+        Self {
+            strategy,
+            volatility,
+            symbol: String::from("BACKTEST"),
+            last_error: None,
+        }
+    }
+
+    /// Sets the symbol used for the order book snapshots passed to the strategy.
+    #[must_use]
+    pub fn with_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = symbol.into();
+        self
+    }
+
+    /// Updates the volatility estimate used for subsequent ticks.
+    pub fn set_volatility(&mut self, volatility: Decimal) {
+        self.volatility = volatility;
+    }
+
+    /// Returns the volatility estimate.
+    #[must_use]
+    pub fn volatility(&self) -> Decimal {
+        self.volatility
+    }
+
+    /// Returns the wrapped strategy.
+    #[must_use]
+    pub fn strategy(&self) -> &Q {
+        &self.strategy
+    }
+
+    /// Returns a mutable reference to the wrapped strategy.
+    pub fn strategy_mut(&mut self) -> &mut Q {
+        &mut self.strategy
+    }
+
+    /// Returns the error produced by the most recent tick, if any.
+    #[must_use]
+    pub fn last_error(&self) -> Option<&MMError> {
+        self.last_error.as_ref()
+    }
+
+    /// Builds the quote context for a tick.
+    fn context(&self, tick: &MarketTick, position: &InventoryPosition) -> QuoteContext {
+        let mut book = OrderBookSnapshot::new(self.symbol.clone(), tick.timestamp);
+        book.bids
+            .push(BookLevel::new(tick.bid_price, tick.bid_size));
+        book.asks
+            .push(BookLevel::new(tick.ask_price, tick.ask_size));
+
+        QuoteContext::new(
+            tick.mid_price(),
+            self.volatility,
+            position.quantity,
+            tick.timestamp,
+        )
+        .with_orderbook(book)
+    }
+}
+
+impl<Q: QuotingStrategy> std::fmt::Debug for QuotingStrategyAdapter<Q> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuotingStrategyAdapter")
+            .field("strategy", &self.strategy.name())
+            .field("volatility", &self.volatility)
+            .field("symbol", &self.symbol)
+            .field("last_error", &self.last_error)
+            .finish()
+    }
+}
+
+impl<Q: QuotingStrategy> BacktestStrategy for QuotingStrategyAdapter<Q> {
+    fn on_tick(&mut self, tick: &MarketTick, position: &InventoryPosition) -> Option<Quote> {
+        let context = self.context(tick, position);
+        match self.strategy.generate_quotes(&context) {
+            Ok(quotes) => {
+                self.last_error = None;
+                quotes.to_quote()
+            }
+            Err(err) => {
+                self.last_error = Some(err);
+                None
+            }
+        }
+    }
+
+    fn on_fill(&mut self, fill: &SimulatedFill) {
+        self.strategy
+            .on_fill(fill.side, fill.price, fill.quantity, fill.timestamp);
+    }
+
+    fn reset(&mut self) {
+        self.last_error = None;
+        self.strategy.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::data::VecDataSource;
+    use crate::backtest::engine::{BacktestConfig, BacktestEngine};
+    use crate::dec;
+    use crate::execution::Side;
+    use crate::strategy::depth_based::DepthBasedOffering;
+    use crate::strategy::quoting::{DepthBasedQuoter, QuoteSet};
+    use crate::types::error::MMResult;
+
+    struct CountingStrategy {
+        fills: usize,
+        resets: usize,
+    }
+
+    impl QuotingStrategy for CountingStrategy {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+            // Quote aggressively so that every tick crosses
+            Ok(QuoteSet::single(
+                context.mid_price + dec!(1.0),
+                dec!(1.0),
+                context.mid_price + dec!(2.0),
+                dec!(1.0),
+                context.timestamp,
+            ))
+        }
+
+        fn on_fill(&mut self, _side: Side, _price: Decimal, _quantity: Decimal, _timestamp: u64) {
+            self.fills += 1;
+        }
+
+        fn reset(&mut self) {
+            self.resets += 1;
+        }
+    }
+
+    fn ticks() -> Vec<MarketTick> {
+        vec![
+            MarketTick::new(1000, dec!(100.0), dec!(5.0), dec!(100.2), dec!(5.0)),
+            MarketTick::new(1001, dec!(100.1), dec!(5.0), dec!(100.3), dec!(5.0)),
+        ]
+    }
+
+    #[test]
+    fn test_adapter_builds_context_from_tick() {
+        let adapter = QuotingStrategyAdapter::new(
+            CountingStrategy {
+                fills: 0,
+                resets: 0,
+            },
+            dec!(0.3),
+        )
+        .with_symbol("BTC");
+        let mut position = InventoryPosition::new();
+        position.quantity = dec!(2.0);
+
+        let context = adapter.context(&ticks()[0], &position);
+        assert_eq!(context.mid_price, dec!(100.1));
+        assert_eq!(context.volatility, dec!(0.3));
+        assert_eq!(context.inventory, dec!(2.0));
+        let book = context.orderbook.unwrap();
+        assert_eq!(book.symbol, "BTC");
+        assert_eq!(book.best_bid(), Some(dec!(100.0)));
+        assert_eq!(book.best_ask(), Some(dec!(100.2)));
+    }
+
+    #[test]
+    fn test_adapter_forwards_fills_and_reset() {
+        let adapter = QuotingStrategyAdapter::new(
+            CountingStrategy {
+                fills: 0,
+                resets: 0,
+            },
+            dec!(0.2),
+        );
+        let mut engine = BacktestEngine::new(
+            BacktestConfig::default(),
+            adapter,
+            VecDataSource::new(ticks()),
+        );
+
+        let result = engine.run();
+        assert_eq!(result.num_trades, 2);
+        assert_eq!(engine.strategy().strategy().fills, 2);
+
+        engine.reset();
+        assert_eq!(engine.strategy().strategy().resets, 1);
+    }
+
+    #[test]
+    fn test_adapter_records_strategy_errors() {
+        // Improving both sides by a tick wider than the book spread crosses the quotes
+        let quoter =
+            DepthBasedQuoter::new(DepthBasedOffering::new(dec!(10.0), dec!(1.0)), dec!(0.5))
+                .unwrap();
+        let mut adapter = QuotingStrategyAdapter::new(quoter, dec!(0.2));
+
+        let tick = MarketTick::new(1000, dec!(100.0), dec!(5.0), dec!(100.2), dec!(5.0));
+        let quote = adapter.on_tick(&tick, &InventoryPosition::new());
+        assert!(quote.is_none());
+        assert!(matches!(
+            adapter.last_error(),
+            Some(MMError::InvalidQuoteGeneration(_))
+        ));
+    }
+
+    #[test]
+    fn test_adapter_with_boxed_strategy() {
+        let boxed: Box<dyn QuotingStrategy> = Box::new(CountingStrategy {
+            fills: 0,
+            resets: 0,
+        });
+        let mut adapter = QuotingStrategyAdapter::new(boxed, dec!(0.2));
+        let quote = adapter
+            .on_tick(&ticks()[0], &InventoryPosition::new())
+            .unwrap();
+        assert_eq!(quote.bid_price, dec!(101.1));
+        assert!(format!("{adapter:?}").contains("counting"));
+    }
+}
@@ -0,0 +1,1251 @@
+//! CSV-backed historical data sources.
+//!
+//! [`CsvTickSource`] streams [`MarketTick`]s and [`CsvBarSource`] streams
+//! [`OHLCVBar`]s from delimited text. Columns are mapped by header name or by
+//! position through [`TickColumns`] and [`BarColumns`], and timestamps can be
+//! Unix seconds, milliseconds, microseconds, nanoseconds or ISO 8601 text
+//! (see [`TimestampFormat`]).
+//!
+//! Rows are parsed lazily, one at a time, so files much larger than memory
+//! can be replayed. Opening a source makes one validating pass over the data
+//! to count the rows and report malformed or out-of-order rows up front, with
+//! their line number, then rewinds.
+//!
+//! Fields may be wrapped in double quotes (with `""` as an escaped quote);
+//! quoted fields spanning several lines are not supported. Blank lines are
+//! skipped.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::backtest::{
+//!     CsvConfig, CsvTickSource, HistoricalDataSource, TickColumns, TimestampFormat,
+//! };
+//! use market_maker_rs::dec;
+//! use std::io::Cursor;
+//!
+//! let data = "time;bid;bid_qty;ask;ask_qty\n\
+//!             2024-01-02T09:30:00.000Z;100.0;2;100.2;3\n\
+//!             2024-01-02T09:30:00.250Z;100.1;1;100.3;4\n";
+//!
+//! let config = CsvConfig::new()
+//!     .with_delimiter(';')
+//!     .with_timestamp_format(TimestampFormat::Iso8601);
+//! let columns = TickColumns::new()
+//!     .with_timestamp("time")
+//!     .with_bid("bid", "bid_qty")
+//!     .with_ask("ask", "ask_qty");
+//!
+//! let mut source = CsvTickSource::from_reader(Cursor::new(data), config, columns).unwrap();
+//! assert_eq!(source.len(), 2);
+//!
+//! let tick = source.next_tick().unwrap();
+//! assert_eq!(tick.timestamp, 1_704_187_800_000);
+//! assert_eq!(tick.ask_size, dec!(3));
+//! ```
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::Decimal;
+use crate::types::error::{MMError, MMResult};
+
+use super::data::{HistoricalDataSource, MarketTick, OHLCVBar};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Encoding of the timestamp column.
+///
+/// Unix formats accept fractional values (e.g. `1700000000.25` seconds).
+/// Parsed timestamps are converted to milliseconds, truncating any finer
+/// precision.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::TimestampFormat;
+///
+/// assert_eq!(TimestampFormat::UnixSeconds.parse("1700000000.25").unwrap(), 1_700_000_000_250);
+/// assert_eq!(TimestampFormat::UnixNanos.parse("1700000000250000000").unwrap(), 1_700_000_000_250);
+/// assert_eq!(
+///     TimestampFormat::Iso8601.parse("2023-11-14T22:13:20.250+00:00").unwrap(),
+///     1_700_000_000_250
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TimestampFormat {
+    /// Seconds since the Unix epoch.
+    UnixSeconds,
+    /// Milliseconds since the Unix epoch.
+    #[default]
+    UnixMillis,
+    /// Microseconds since the Unix epoch.
+    UnixMicros,
+    /// Nanoseconds since the Unix epoch.
+    UnixNanos,
+    /// ISO 8601 / RFC 3339 date-time, e.g. `2024-01-02T09:30:00.125Z`.
+    ///
+    /// The date and time may be separated by `T` or a space. Fractional
+    /// seconds and a `Z` or `±HH:MM` offset are optional; without an offset
+    /// the time is taken as UTC. A bare date means midnight.
+    Iso8601,
+}
+
+impl TimestampFormat {
+    /// Parses a timestamp into milliseconds since the Unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidTimestamp` if the text does not match the
+    /// format or lies before the epoch.
+    pub fn parse(&self, text: &str) -> MMResult<u64> {
+        let text = text.trim();
+        match self {
+            TimestampFormat::UnixSeconds => parse_unix(text, 1_000, 1),
+            TimestampFormat::UnixMillis => parse_unix(text, 1, 1),
+            TimestampFormat::UnixMicros => parse_unix(text, 1, 1_000),
+            TimestampFormat::UnixNanos => parse_unix(text, 1, 1_000_000),
+            TimestampFormat::Iso8601 => parse_iso8601(text),
+        }
+    }
+}
+
+fn parse_unix(text: &str, multiplier: u64, divisor: u64) -> MMResult<u64> {
+    if let Ok(value) = text.parse::<u64>() {
+        return value
+            .checked_mul(multiplier)
+            .map(|ms| ms / divisor)
+            .ok_or_else(|| MMError::InvalidTimestamp(format!("'{text}' is out of range")));
+    }
+
+    let value = Decimal::from_str(text)
+        .map_err(|_| MMError::InvalidTimestamp(format!("'{text}' is not a Unix timestamp")))?;
+    if value.is_sign_negative() {
+        return Err(MMError::InvalidTimestamp(format!(
+            "'{text}' is before the Unix epoch"
+        )));
+    }
+    let out_of_range = || MMError::InvalidTimestamp(format!("'{text}' is out of range"));
+    let ms = value
+        .checked_mul(Decimal::from(multiplier))
+        .and_then(|v| v.checked_div(Decimal::from(divisor)))
+        .ok_or_else(out_of_range)?
+        .trunc();
+    u64::try_from(ms).map_err(|_| out_of_range())
+}
+
+fn parse_iso8601(text: &str) -> MMResult<u64> {
+    let invalid = || MMError::InvalidTimestamp(format!("'{text}' is not an ISO 8601 date-time"));
+    let bytes = text.as_bytes();
+
+    let number = |range: std::ops::Range<usize>| -> MMResult<i64> {
+        let digits = text.get(range).ok_or_else(invalid)?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        digits.parse::<i64>().map_err(|_| invalid())
+    };
+    let expect = |index: usize, allowed: &[u8]| -> MMResult<()> {
+        match bytes.get(index) {
+            Some(b) if allowed.contains(b) => Ok(()),
+            _ => Err(invalid()),
+        }
+    };
+
+    expect(4, b"-")?;
+    expect(7, b"-")?;
+    let year = number(0..4)?;
+    let month = number(5..7)?;
+    let day = number(8..10)?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return Err(invalid());
+    }
+
+    let mut ms_of_day = 0i64;
+    let mut offset_ms = 0i64;
+    if bytes.len() > 10 {
+        expect(10, b"T ")?;
+        expect(13, b":")?;
+        expect(16, b":")?;
+        let hour = number(11..13)?;
+        let minute = number(14..16)?;
+        let second = number(17..19)?;
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(invalid());
+        }
+        ms_of_day = ((hour * 60 + minute) * 60 + second) * 1_000;
+
+        let mut pos = 19;
+        if bytes.get(pos) == Some(&b'.') {
+            let start = pos + 1;
+            pos = start;
+            while bytes.get(pos).is_some_and(u8::is_ascii_digit) {
+                pos += 1;
+            }
+            if pos == start {
+                return Err(invalid());
+            }
+            // Keep millisecond precision, padding shorter fractions.
+            let fraction = &text[start..pos.min(start + 3)];
+            let millis: i64 = fraction.parse().map_err(|_| invalid())?;
+            ms_of_day += millis * 10i64.pow(3 - fraction.len() as u32);
+        }
+
+        match bytes.get(pos) {
+            None => {}
+            Some(b'Z') | Some(b'z') if pos + 1 == bytes.len() => {}
+            Some(sign @ (b'+' | b'-')) => {
+                let hours = number(pos + 1..pos + 3)?;
+                let minutes = match bytes.len() - pos {
+                    6 => {
+                        expect(pos + 3, b":")?;
+                        number(pos + 4..pos + 6)?
+                    }
+                    5 => number(pos + 3..pos + 5)?,
+                    3 => 0,
+                    _ => return Err(invalid()),
+                };
+                if hours > 23 || minutes > 59 {
+                    return Err(invalid());
+                }
+                offset_ms = (hours * 60 + minutes) * 60_000;
+                if *sign == b'-' {
+                    offset_ms = -offset_ms;
+                }
+            }
+            _ => return Err(invalid()),
+        }
+    }
+
+    let ms = days_from_civil(year, month, day) * 86_400_000 + ms_of_day - offset_ms;
+    u64::try_from(ms)
+        .map_err(|_| MMError::InvalidTimestamp(format!("'{text}' is before the Unix epoch")))
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Reference to a CSV column.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::CsvColumn;
+///
+/// assert_eq!(CsvColumn::from("bid"), CsvColumn::Name("bid".to_string()));
+/// assert_eq!(CsvColumn::from(2), CsvColumn::Index(2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CsvColumn {
+    /// Column with this header name (requires a header row).
+    Name(String),
+    /// Zero-based column position.
+    Index(usize),
+}
+
+impl From<&str> for CsvColumn {
+    fn from(name: &str) -> Self {
+        CsvColumn::Name(name.to_string())
+    }
+}
+
+impl From<String> for CsvColumn {
+    fn from(name: String) -> Self {
+        CsvColumn::Name(name)
+    }
+}
+
+impl From<usize> for CsvColumn {
+    fn from(index: usize) -> Self {
+        CsvColumn::Index(index)
+    }
+}
+
+impl CsvColumn {
+    fn resolve(&self, header: Option<&[String]>) -> MMResult<usize> {
+        match (self, header) {
+            (CsvColumn::Index(index), _) => Ok(*index),
+            (CsvColumn::Name(name), Some(header)) => header
+                .iter()
+                .position(|column| column == name)
+                .ok_or_else(|| {
+                    MMError::InvalidConfiguration(format!("CSV header has no column '{name}'"))
+                }),
+            (CsvColumn::Name(name), None) => Err(MMError::InvalidConfiguration(format!(
+                "column '{name}' is referenced by name but the CSV has no header"
+            ))),
+        }
+    }
+}
+
+/// Layout of a CSV file.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::{CsvConfig, TimestampFormat};
+///
+/// let config = CsvConfig::new()
+///     .with_delimiter('\t')
+///     .with_header(false)
+///     .with_timestamp_format(TimestampFormat::UnixMicros);
+/// assert_eq!(config.delimiter, '\t');
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CsvConfig {
+    /// Field delimiter.
+    pub delimiter: char,
+    /// Whether the first line is a header row.
+    pub has_header: bool,
+    /// Encoding of the timestamp column.
+    pub timestamp_format: TimestampFormat,
+}
+
+impl Default for CsvConfig {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            has_header: true,
+            timestamp_format: TimestampFormat::UnixMillis,
+        }
+    }
+}
+
+impl CsvConfig {
+    /// Creates a comma-separated configuration with a header row and
+    /// millisecond timestamps.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the field delimiter.
+    #[must_use]
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether the first line is a header row.
+    #[must_use]
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    /// Sets the timestamp format.
+    #[must_use]
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self
+    }
+
+    fn validate(&self) -> MMResult<()> {
+        if self.delimiter == '"' || self.delimiter == '\n' || self.delimiter == '\r' {
+            return Err(MMError::InvalidConfiguration(format!(
+                "{:?} cannot be used as a CSV delimiter",
+                self.delimiter
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Column mapping for [`MarketTick`] rows.
+///
+/// Defaults to the header names `timestamp`, `bid_price`, `bid_size`,
+/// `ask_price` and `ask_size`, without last-trade columns.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::{CsvColumn, TickColumns};
+///
+/// // Headerless file: ts, bid, bid size, ask, ask size, last, last size
+/// let columns = TickColumns::new()
+///     .with_timestamp(0)
+///     .with_bid(1, 2)
+///     .with_ask(3, 4)
+///     .with_last_trade(5, 6);
+/// assert_eq!(columns.last_price, Some(CsvColumn::Index(5)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TickColumns {
+    /// Timestamp column.
+    pub timestamp: CsvColumn,
+    /// Best bid price column.
+    pub bid_price: CsvColumn,
+    /// Best bid size column.
+    pub bid_size: CsvColumn,
+    /// Best ask price column.
+    pub ask_price: CsvColumn,
+    /// Best ask size column.
+    pub ask_size: CsvColumn,
+    /// Last trade price column, if any. Empty fields mean no trade.
+    pub last_price: Option<CsvColumn>,
+    /// Last trade size column, if any. Empty fields mean no trade.
+    pub last_size: Option<CsvColumn>,
+}
+
+impl Default for TickColumns {
+    fn default() -> Self {
+        Self {
+            timestamp: "timestamp".into(),
+            bid_price: "bid_price".into(),
+            bid_size: "bid_size".into(),
+            ask_price: "ask_price".into(),
+            ask_size: "ask_size".into(),
+            last_price: None,
+            last_size: None,
+        }
+    }
+}
+
+impl TickColumns {
+    /// Creates the default column mapping.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the timestamp column.
+    #[must_use]
+    pub fn with_timestamp(mut self, column: impl Into<CsvColumn>) -> Self {
+        self.timestamp = column.into();
+        self
+    }
+
+    /// Sets the bid price and size columns.
+    #[must_use]
+    pub fn with_bid(mut self, price: impl Into<CsvColumn>, size: impl Into<CsvColumn>) -> Self {
+        self.bid_price = price.into();
+        self.bid_size = size.into();
+        self
+    }
+
+    /// Sets the ask price and size columns.
+    #[must_use]
+    pub fn with_ask(mut self, price: impl Into<CsvColumn>, size: impl Into<CsvColumn>) -> Self {
+        self.ask_price = price.into();
+        self.ask_size = size.into();
+        self
+    }
+
+    /// Sets the last trade price and size columns.
+    #[must_use]
+    pub fn with_last_trade(
+        mut self,
+        price: impl Into<CsvColumn>,
+        size: impl Into<CsvColumn>,
+    ) -> Self {
+        self.last_price = Some(price.into());
+        self.last_size = Some(size.into());
+        self
+    }
+}
+
+/// Column mapping for [`OHLCVBar`] rows.
+///
+/// Defaults to the header names `timestamp`, `open`, `high`, `low`, `close`
+/// and `volume`.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::{BarColumns, CsvColumn};
+///
+/// let columns = BarColumns::new().with_timestamp("time").with_volume("qty");
+/// assert_eq!(columns.open, CsvColumn::Name("open".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BarColumns {
+    /// Bar start timestamp column.
+    pub timestamp: CsvColumn,
+    /// Opening price column.
+    pub open: CsvColumn,
+    /// Highest price column.
+    pub high: CsvColumn,
+    /// Lowest price column.
+    pub low: CsvColumn,
+    /// Closing price column.
+    pub close: CsvColumn,
+    /// Volume column.
+    pub volume: CsvColumn,
+}
+
+impl Default for BarColumns {
+    fn default() -> Self {
+        Self {
+            timestamp: "timestamp".into(),
+            open: "open".into(),
+            high: "high".into(),
+            low: "low".into(),
+            close: "close".into(),
+            volume: "volume".into(),
+        }
+    }
+}
+
+impl BarColumns {
+    /// Creates the default column mapping.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the timestamp column.
+    #[must_use]
+    pub fn with_timestamp(mut self, column: impl Into<CsvColumn>) -> Self {
+        self.timestamp = column.into();
+        self
+    }
+
+    /// Sets the open, high, low and close columns.
+    #[must_use]
+    pub fn with_prices(
+        mut self,
+        open: impl Into<CsvColumn>,
+        high: impl Into<CsvColumn>,
+        low: impl Into<CsvColumn>,
+        close: impl Into<CsvColumn>,
+    ) -> Self {
+        self.open = open.into();
+        self.high = high.into();
+        self.low = low.into();
+        self.close = close.into();
+        self
+    }
+
+    /// Sets the volume column.
+    #[must_use]
+    pub fn with_volume(mut self, column: impl Into<CsvColumn>) -> Self {
+        self.volume = column.into();
+        self
+    }
+}
+
+/// Record type that can be parsed from a CSV row.
+trait CsvRecord: Sized {
+    type Columns;
+    type Layout;
+
+    fn layout(columns: &Self::Columns, header: Option<&[String]>) -> MMResult<Self::Layout>;
+
+    fn parse(layout: &Self::Layout, row: &Row<'_>) -> MMResult<Self>;
+
+    fn timestamp(&self) -> u64;
+}
+
+/// One split CSV row with its parsing context.
+struct Row<'a> {
+    fields: &'a [String],
+    format: TimestampFormat,
+}
+
+impl Row<'_> {
+    fn field(&self, index: usize) -> MMResult<&str> {
+        self.fields
+            .get(index)
+            .map(|field| field.trim())
+            .ok_or_else(|| {
+                MMError::InvalidMarketState(format!(
+                    "row has {} fields, column {index} is missing",
+                    self.fields.len()
+                ))
+            })
+    }
+
+    fn timestamp(&self, index: usize) -> MMResult<u64> {
+        self.format.parse(self.field(index)?)
+    }
+
+    fn decimal(&self, index: usize) -> MMResult<Decimal> {
+        let text = self.field(index)?;
+        Decimal::from_str(text)
+            .or_else(|_| Decimal::from_scientific(text))
+            .map_err(|_| {
+                MMError::InvalidMarketState(format!("column {index}: '{text}' is not a number"))
+            })
+    }
+
+    fn optional_decimal(&self, index: Option<usize>) -> MMResult<Option<Decimal>> {
+        match index {
+            Some(index) if !self.field(index)?.is_empty() => self.decimal(index).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+struct TickLayout {
+    timestamp: usize,
+    bid_price: usize,
+    bid_size: usize,
+    ask_price: usize,
+    ask_size: usize,
+    last_price: Option<usize>,
+    last_size: Option<usize>,
+}
+
+impl CsvRecord for MarketTick {
+    type Columns = TickColumns;
+    type Layout = TickLayout;
+
+    fn layout(columns: &TickColumns, header: Option<&[String]>) -> MMResult<TickLayout> {
+        let optional = |column: &Option<CsvColumn>| {
+            column
+                .as_ref()
+                .map(|column| column.resolve(header))
+                .transpose()
+        };
+        Ok(TickLayout {
+            timestamp: columns.timestamp.resolve(header)?,
+            bid_price: columns.bid_price.resolve(header)?,
+            bid_size: columns.bid_size.resolve(header)?,
+            ask_price: columns.ask_price.resolve(header)?,
+            ask_size: columns.ask_size.resolve(header)?,
+            last_price: optional(&columns.last_price)?,
+            last_size: optional(&columns.last_size)?,
+        })
+    }
+
+    fn parse(layout: &TickLayout, row: &Row<'_>) -> MMResult<Self> {
+        let mut tick = MarketTick::new(
+            row.timestamp(layout.timestamp)?,
+            row.decimal(layout.bid_price)?,
+            row.decimal(layout.bid_size)?,
+            row.decimal(layout.ask_price)?,
+            row.decimal(layout.ask_size)?,
+        );
+        tick.last_price = row.optional_decimal(layout.last_price)?;
+        tick.last_size = row.optional_decimal(layout.last_size)?;
+        Ok(tick)
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+struct BarLayout {
+    timestamp: usize,
+    open: usize,
+    high: usize,
+    low: usize,
+    close: usize,
+    volume: usize,
+}
+
+impl CsvRecord for OHLCVBar {
+    type Columns = BarColumns;
+    type Layout = BarLayout;
+
+    fn layout(columns: &BarColumns, header: Option<&[String]>) -> MMResult<BarLayout> {
+        Ok(BarLayout {
+            timestamp: columns.timestamp.resolve(header)?,
+            open: columns.open.resolve(header)?,
+            high: columns.high.resolve(header)?,
+            low: columns.low.resolve(header)?,
+            close: columns.close.resolve(header)?,
+            volume: columns.volume.resolve(header)?,
+        })
+    }
+
+    fn parse(layout: &BarLayout, row: &Row<'_>) -> MMResult<Self> {
+        Ok(OHLCVBar::new(
+            row.timestamp(layout.timestamp)?,
+            row.decimal(layout.open)?,
+            row.decimal(layout.high)?,
+            row.decimal(layout.low)?,
+            row.decimal(layout.close)?,
+            row.decimal(layout.volume)?,
+        ))
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+fn io_error(error: std::io::Error) -> MMError {
+    MMError::InvalidConfiguration(format!("failed to read CSV data: {error}"))
+}
+
+/// Splits one line into fields, honouring double quotes.
+fn split_fields(line: &str, delimiter: char, fields: &mut Vec<String>) -> MMResult<()> {
+    fields.clear();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut in_quotes = false;
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+        } else if c == '"' && field.trim().is_empty() {
+            field.clear();
+            in_quotes = true;
+        } else if c == delimiter {
+            fields.push(std::mem::take(&mut field));
+        } else {
+            field.push(c);
+        }
+    }
+
+    if in_quotes {
+        return Err(MMError::InvalidMarketState(
+            "unterminated quoted field".to_string(),
+        ));
+    }
+    fields.push(field);
+    Ok(())
+}
+
+/// Lazily parsed CSV records with one record of look-ahead.
+struct CsvStream<R, T: CsvRecord> {
+    reader: R,
+    delimiter: char,
+    format: TimestampFormat,
+    layout: T::Layout,
+    data_start: u64,
+    data_start_line: usize,
+    line_number: usize,
+    len: usize,
+    consumed: usize,
+    next: Option<T>,
+    error: Option<MMError>,
+    line: String,
+    fields: Vec<String>,
+}
+
+impl<R: BufRead + Seek, T: CsvRecord> CsvStream<R, T> {
+    fn new(mut reader: R, config: &CsvConfig, columns: &T::Columns) -> MMResult<Self> {
+        config.validate()?;
+        let start = reader.stream_position().map_err(io_error)?;
+
+        let mut line = String::new();
+        let mut fields = Vec::new();
+        let mut line_number = 0;
+        let header = if config.has_header {
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).map_err(io_error)? == 0 {
+                    break None;
+                }
+                line_number += 1;
+                let trimmed = line.trim_end_matches(['\n', '\r']);
+                if !trimmed.trim().is_empty() {
+                    split_fields(trimmed, config.delimiter, &mut fields)
+                        .map_err(|e| at_line(e, line_number))?;
+                    break Some(
+                        fields
+                            .iter()
+                            .map(|f| f.trim().to_string())
+                            .collect::<Vec<_>>(),
+                    );
+                }
+            }
+        } else {
+            None
+        };
+        if config.has_header && header.is_none() {
+            return Err(MMError::InvalidConfiguration(
+                "CSV data has no header row".to_string(),
+            ));
+        }
+
+        let layout = T::layout(columns, header.as_deref())?;
+        let data_start = if config.has_header {
+            reader.stream_position().map_err(io_error)?
+        } else {
+            start
+        };
+
+        let mut stream = Self {
+            reader,
+            delimiter: config.delimiter,
+            format: config.timestamp_format,
+            layout,
+            data_start,
+            data_start_line: line_number,
+            line_number,
+            len: 0,
+            consumed: 0,
+            next: None,
+            error: None,
+            line,
+            fields,
+        };
+
+        // Validate and count every row, then rewind.
+        let mut last_timestamp = 0;
+        while let Some(record) = stream.read_record()? {
+            if record.timestamp() < last_timestamp {
+                return Err(MMError::InvalidMarketState(format!(
+                    "line {}: timestamp {} is earlier than the previous row ({last_timestamp})",
+                    stream.line_number,
+                    record.timestamp()
+                )));
+            }
+            last_timestamp = record.timestamp();
+            stream.len += 1;
+        }
+        stream.rewind()?;
+        Ok(stream)
+    }
+
+    /// Reads and parses the next non-blank row.
+    fn read_record(&mut self) -> MMResult<Option<T>> {
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line).map_err(io_error)? == 0 {
+                return Ok(None);
+            }
+            self.line_number += 1;
+            let trimmed = self.line.trim_end_matches(['\n', '\r']);
+            if trimmed.trim().is_empty() {
+                continue;
+            }
+
+            let line_number = self.line_number;
+            split_fields(trimmed, self.delimiter, &mut self.fields)
+                .map_err(|e| at_line(e, line_number))?;
+            let row = Row {
+                fields: &self.fields,
+                format: self.format,
+            };
+            return T::parse(&self.layout, &row)
+                .map(Some)
+                .map_err(|e| at_line(e, line_number));
+        }
+    }
+
+    /// Moves the cursor back to the first row.
+    fn rewind(&mut self) -> MMResult<()> {
+        self.reader
+            .seek(SeekFrom::Start(self.data_start))
+            .map_err(io_error)?;
+        self.line_number = self.data_start_line;
+        self.consumed = 0;
+        self.error = None;
+        self.fill();
+        Ok(())
+    }
+
+    /// Reads the look-ahead record, recording any error.
+    fn fill(&mut self) {
+        self.next = match self.read_record() {
+            Ok(record) => record,
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        };
+    }
+
+    fn advance(&mut self) -> Option<T> {
+        let record = self.next.take()?;
+        self.consumed += 1;
+        self.fill();
+        Some(record)
+    }
+}
+
+fn at_line(error: MMError, line: usize) -> MMError {
+    match error {
+        MMError::InvalidTimestamp(msg) => MMError::InvalidTimestamp(format!("line {line}: {msg}")),
+        MMError::InvalidMarketState(msg) => {
+            MMError::InvalidMarketState(format!("line {line}: {msg}"))
+        }
+        other => other,
+    }
+}
+
+impl<R, T: CsvRecord> std::fmt::Debug for CsvStream<R, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CsvStream")
+            .field("delimiter", &self.delimiter)
+            .field("format", &self.format)
+            .field("line_number", &self.line_number)
+            .field("len", &self.len)
+            .field("consumed", &self.consumed)
+            .finish()
+    }
+}
+
+/// Market ticks streamed from CSV data.
+///
+/// Implements [`HistoricalDataSource`], so it can drive the backtest engine
+/// directly. Only the next tick is held in memory.
+#[derive(Debug)]
+pub struct CsvTickSource<R = BufReader<File>> {
+    stream: CsvStream<R, MarketTick>,
+}
+
+impl CsvTickSource {
+    /// Opens a CSV file of ticks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, a mapped column does not
+    /// exist, or a row is malformed or out of timestamp order.
+    pub fn open(path: impl AsRef<Path>, config: CsvConfig, columns: TickColumns) -> MMResult<Self> {
+        let file = File::open(path.as_ref()).map_err(|e| {
+            MMError::InvalidConfiguration(format!("cannot open {}: {e}", path.as_ref().display()))
+        })?;
+        Self::from_reader(BufReader::new(file), config, columns)
+    }
+}
+
+impl<R: BufRead + Seek> CsvTickSource<R> {
+    /// Creates a tick source over any seekable reader.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`CsvTickSource::open`].
+    pub fn from_reader(reader: R, config: CsvConfig, columns: TickColumns) -> MMResult<Self> {
+        Ok(Self {
+            stream: CsvStream::new(reader, &config, &columns)?,
+        })
+    }
+
+    /// Returns the error that stopped iteration early, if any.
+    ///
+    /// Rows are validated when the source is opened, so this is only set if
+    /// the underlying data changed or could not be read afterwards.
+    #[must_use]
+    pub fn error(&self) -> Option<&MMError> {
+        self.stream.error.as_ref()
+    }
+}
+
+impl<R: BufRead + Seek> HistoricalDataSource for CsvTickSource<R> {
+    fn next_tick(&mut self) -> Option<MarketTick> {
+        self.stream.advance()
+    }
+
+    fn peek_tick(&self) -> Option<&MarketTick> {
+        self.stream.next.as_ref()
+    }
+
+    fn reset(&mut self) {
+        if let Err(e) = self.stream.rewind() {
+            self.stream.next = None;
+            self.stream.error = Some(e);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.stream.len
+    }
+
+    fn remaining(&self) -> usize {
+        self.stream.len.saturating_sub(self.stream.consumed)
+    }
+}
+
+/// OHLCV bars streamed from CSV data.
+///
+/// Iterates lazily; only the next bar is held in memory.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::{BarColumns, CsvBarSource, CsvConfig, TimestampFormat};
+/// use market_maker_rs::dec;
+/// use std::io::Cursor;
+///
+/// let data = "timestamp,open,high,low,close,volume\n\
+///             1700000000,100,102,99,101,10\n\
+///             1700000060,101,103,100.5,102.5,12\n";
+///
+/// let config = CsvConfig::new().with_timestamp_format(TimestampFormat::UnixSeconds);
+/// let source = CsvBarSource::from_reader(Cursor::new(data), config, BarColumns::new()).unwrap();
+/// assert_eq!(source.len(), 2);
+///
+/// let bars: Vec<_> = source.collect();
+/// assert_eq!(bars[1].timestamp, 1_700_000_060_000);
+/// assert_eq!(bars[1].low, dec!(100.5));
+/// ```
+#[derive(Debug)]
+pub struct CsvBarSource<R = BufReader<File>> {
+    stream: CsvStream<R, OHLCVBar>,
+}
+
+impl CsvBarSource {
+    /// Opens a CSV file of bars.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, a mapped column does not
+    /// exist, or a row is malformed or out of timestamp order.
+    pub fn open(path: impl AsRef<Path>, config: CsvConfig, columns: BarColumns) -> MMResult<Self> {
+        let file = File::open(path.as_ref()).map_err(|e| {
+            MMError::InvalidConfiguration(format!("cannot open {}: {e}", path.as_ref().display()))
+        })?;
+        Self::from_reader(BufReader::new(file), config, columns)
+    }
+}
+
+impl<R: BufRead + Seek> CsvBarSource<R> {
+    /// Creates a bar source over any seekable reader.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`CsvBarSource::open`].
+    pub fn from_reader(reader: R, config: CsvConfig, columns: BarColumns) -> MMResult<Self> {
+        Ok(Self {
+            stream: CsvStream::new(reader, &config, &columns)?,
+        })
+    }
+
+    /// Returns the next bar without consuming it.
+    #[must_use]
+    pub fn peek(&self) -> Option<&OHLCVBar> {
+        self.stream.next.as_ref()
+    }
+
+    /// Moves back to the first bar.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader cannot seek.
+    pub fn reset(&mut self) -> MMResult<()> {
+        self.stream.rewind()
+    }
+
+    /// Returns the total number of bars.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.stream.len
+    }
+
+    /// Returns true if there are no bars.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.stream.len == 0
+    }
+
+    /// Returns the number of bars not yet consumed.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.stream.len.saturating_sub(self.stream.consumed)
+    }
+
+    /// Returns the error that stopped iteration early, if any.
+    #[must_use]
+    pub fn error(&self) -> Option<&MMError> {
+        self.stream.error.as_ref()
+    }
+}
+
+impl<R: BufRead + Seek> Iterator for CsvBarSource<R> {
+    type Item = OHLCVBar;
+
+    fn next(&mut self) -> Option<OHLCVBar> {
+        self.stream.advance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_timestamp_formats() {
+        assert_eq!(TimestampFormat::UnixMillis.parse("1000").unwrap(), 1000);
+        assert_eq!(TimestampFormat::UnixSeconds.parse("2").unwrap(), 2000);
+        assert_eq!(TimestampFormat::UnixMicros.parse("1999").unwrap(), 1);
+        assert_eq!(TimestampFormat::UnixMillis.parse("12.9").unwrap(), 12);
+        assert!(TimestampFormat::UnixMillis.parse("-5").is_err());
+        assert!(TimestampFormat::UnixMillis.parse("abc").is_err());
+
+        let iso = TimestampFormat::Iso8601;
+        assert_eq!(iso.parse("1970-01-01").unwrap(), 0);
+        assert_eq!(iso.parse("2000-02-29 12:00:00").unwrap(), 951_825_600_000);
+        assert_eq!(
+            iso.parse("2024-01-02T09:30:00.5Z").unwrap(),
+            1_704_187_800_500
+        );
+        assert_eq!(
+            iso.parse("2024-01-02T10:30:00.500123+01:00").unwrap(),
+            1_704_187_800_500
+        );
+        assert_eq!(
+            iso.parse("2024-01-02T04:30:00-0500").unwrap(),
+            1_704_187_800_000
+        );
+        assert!(iso.parse("2023-02-29").is_err());
+        assert!(iso.parse("2024-01-02T25:00:00Z").is_err());
+        assert!(iso.parse("2024-01-02T09:30:00Zjunk").is_err());
+        assert!(iso.parse("1969-12-31T23:59:59Z").is_err());
+    }
+
+    #[test]
+    fn test_out_of_range_unix_timestamps_are_errors() {
+        for text in [
+            "79228162514264337593543950335",
+            "79228162514264337593543950.335",
+            "18446744073709551615",
+            "18446744073709551616",
+        ] {
+            assert!(matches!(
+                TimestampFormat::UnixSeconds.parse(text),
+                Err(MMError::InvalidTimestamp(_))
+            ));
+        }
+        assert!(matches!(
+            TimestampFormat::UnixMillis.parse("79228162514264337593543950335"),
+            Err(MMError::InvalidTimestamp(_))
+        ));
+    }
+
+    #[test]
+    fn test_tick_source_streams_and_resets() {
+        let data = "\"ts\",bid,bq,ask,aq,last,lq\n\
+                    1000,100.0,1,100.2,2,,\n\
+                    \n\
+                    1001,\"100.1\",1,100.3,2,100.2,0.5\r\n";
+        let columns = TickColumns::new()
+            .with_timestamp("ts")
+            .with_bid("bid", "bq")
+            .with_ask("ask", "aq")
+            .with_last_trade("last", "lq");
+        let mut source =
+            CsvTickSource::from_reader(Cursor::new(data), CsvConfig::new(), columns).unwrap();
+
+        assert_eq!(source.len(), 2);
+        assert_eq!(source.peek_tick().unwrap().timestamp, 1000);
+        let first = source.next_tick().unwrap();
+        assert!(first.last_price.is_none());
+        let second = source.next_tick().unwrap();
+        assert_eq!(second.bid_price, dec!(100.1));
+        assert_eq!(second.last_size, Some(dec!(0.5)));
+        assert!(source.next_tick().is_none());
+        assert_eq!(source.remaining(), 0);
+
+        source.reset();
+        assert_eq!(source.remaining(), 2);
+        assert_eq!(source.next_tick().unwrap(), first);
+        assert!(source.error().is_none());
+    }
+
+    #[test]
+    fn test_headerless_positional_columns() {
+        let data = "100.0|1|100.2|2|5\n100.1|1|100.3|2|6\n";
+        let config = CsvConfig::new()
+            .with_delimiter('|')
+            .with_header(false)
+            .with_timestamp_format(TimestampFormat::UnixSeconds);
+        let columns = TickColumns::new()
+            .with_timestamp(4)
+            .with_bid(0, 1)
+            .with_ask(2, 3);
+        let ticks: Vec<_> = {
+            let mut source =
+                CsvTickSource::from_reader(Cursor::new(data), config, columns).unwrap();
+            std::iter::from_fn(|| source.next_tick()).collect()
+        };
+        assert_eq!(ticks.len(), 2);
+        assert_eq!(ticks[1].timestamp, 6000);
+    }
+
+    #[test]
+    fn test_errors_report_line_numbers() {
+        let columns = TickColumns::new();
+        let header = "timestamp,bid_price,bid_size,ask_price,ask_size\n";
+
+        let bad_number = format!("{header}1,100,1,100.2,1\n2,100,x,100.2,1\n");
+        let err =
+            CsvTickSource::from_reader(Cursor::new(bad_number), CsvConfig::new(), columns.clone())
+                .unwrap_err();
+        assert!(err.message().starts_with("line 3:"), "{err}");
+
+        let out_of_order = format!("{header}5,100,1,100.2,1\n4,100,1,100.2,1\n");
+        let err = CsvTickSource::from_reader(
+            Cursor::new(out_of_order),
+            CsvConfig::new(),
+            columns.clone(),
+        )
+        .unwrap_err();
+        assert!(err.is_market_state_error());
+
+        let err =
+            CsvTickSource::from_reader(Cursor::new("a,b\n"), CsvConfig::new(), columns.clone())
+                .unwrap_err();
+        assert!(err.is_configuration_error());
+
+        let err = CsvTickSource::from_reader(
+            Cursor::new("1,2,3,4,5\n"),
+            CsvConfig::new().with_header(false),
+            columns,
+        )
+        .unwrap_err();
+        assert!(err.is_configuration_error());
+
+        assert!(
+            CsvTickSource::open(
+                "/nonexistent/ticks.csv",
+                CsvConfig::new(),
+                TickColumns::new()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_bar_source() {
+        let data = "time,o,h,l,c,v\n\
+                    2024-01-02T00:00:00Z,100,102,99,101,10\n\
+                    2024-01-02T00:01:00Z,101,103,100.5,102.5,1.2e1\n";
+        let config = CsvConfig::new().with_timestamp_format(TimestampFormat::Iso8601);
+        let columns = BarColumns::new()
+            .with_timestamp("time")
+            .with_prices("o", "h", "l", "c")
+            .with_volume("v");
+        let mut source = CsvBarSource::from_reader(Cursor::new(data), config, columns).unwrap();
+
+        assert_eq!(source.len(), 2);
+        let first = source.next().unwrap();
+        assert!(first.is_bullish());
+        assert_eq!(source.remaining(), 1);
+        assert_eq!(source.peek().unwrap().volume, dec!(12));
+        assert_eq!(source.next().unwrap().timestamp - first.timestamp, 60_000);
+        assert!(source.next().is_none());
+
+        source.reset().unwrap();
+        assert_eq!(source.count(), 2);
+    }
+}
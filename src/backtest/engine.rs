@@ -55,6 +55,7 @@ use crate::position::pnl::PnL;
 use crate::strategy::quote::Quote;
 
 use super::data::{HistoricalDataSource, MarketTick};
+use super::latency::{LatencyConfig, LatencySimulator};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -203,6 +204,9 @@ pub struct BacktestConfig {
     pub record_equity_curve: bool,
     /// Record all trades.
     pub record_trades: bool,
+    /// Order-entry, cancel and market-data latency.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub latency: LatencyConfig,
 }
 
 impl Default for BacktestConfig {
@@ -216,6 +220,7 @@ impl Default for BacktestConfig {
             default_order_size: Decimal::ONE,
             record_equity_curve: true,
             record_trades: true,
+            latency: LatencyConfig::default(),
         }
     }
 }
@@ -282,6 +287,13 @@ impl BacktestConfig {
         self.record_trades = record;
         self
     }
+
+    /// Sets the latency configuration.
+    #[must_use]
+    pub fn with_latency(mut self, latency: LatencyConfig) -> Self {
+        self.latency = latency;
+        self
+    }
 }
 
 /// Backtest result containing performance metrics.
@@ -386,6 +398,12 @@ pub trait BacktestStrategy {
 
 /// Backtesting engine for simulating strategy execution.
 ///
+/// Quotes are simulated as orders at a venue. Without latency a quote is live
+/// only on the tick that produced it. With [`BacktestConfig::latency`] the
+/// strategy sees each tick late, and quotes and cancels reach the venue late,
+/// so a superseded quote can still be filled until its cancel lands. Each
+/// side of a resting quote fills at most once.
+///
 /// # Type Parameters
 ///
 /// * `S` - Strategy type implementing `BacktestStrategy`
@@ -432,33 +450,22 @@ pub struct BacktestEngine<S: BacktestStrategy, D: HistoricalDataSource> {
     config: BacktestConfig,
     strategy: S,
     data_source: D,
-    position: InventoryPosition,
-    pnl: PnL,
-    equity_curve: Vec<(u64, Decimal)>,
-    trades: Vec<SimulatedFill>,
-    total_fees: Decimal,
-    max_position: Decimal,
-    peak_equity: Decimal,
-    max_drawdown: Decimal,
+    ledger: BacktestLedger,
+    venue: LatencySimulator,
 }
 
 impl<S: BacktestStrategy, D: HistoricalDataSource> BacktestEngine<S, D> {
     /// Creates a new backtest engine.
     #[must_use]
     pub fn new(config: BacktestConfig, strategy: S, data_source: D) -> Self {
-        let initial_capital = config.initial_capital;
+        let ledger = BacktestLedger::new(&config);
+        let venue = LatencySimulator::new(config.latency.clone());
         Self {
             config,
             strategy,
             data_source,
-            position: InventoryPosition::new(),
-            pnl: PnL::new(),
-            equity_curve: Vec::new(),
-            trades: Vec::new(),
-            total_fees: Decimal::ZERO,
-            max_position: Decimal::ZERO,
-            peak_equity: initial_capital,
-            max_drawdown: Decimal::ZERO,
+            ledger,
+            venue,
         }
     }
 
@@ -472,84 +479,49 @@ impl<S: BacktestStrategy, D: HistoricalDataSource> BacktestEngine<S, D> {
     /// The callback receives (current_tick, total_ticks).
     pub fn run_with_progress<F: FnMut(usize, usize)>(&mut self, mut callback: F) -> BacktestResult {
         let total_ticks = self.data_source.len();
-        let mut num_ticks = 0u64;
-        let mut start_time = 0u64;
-        let mut end_time = 0u64;
 
         while let Some(tick) = self.data_source.next_tick() {
-            if num_ticks == 0 {
-                start_time = tick.timestamp;
-            }
-            end_time = tick.timestamp;
+            let now_us = tick.timestamp.saturating_mul(1000);
 
-            // Get strategy quote
-            if let Some(quote) = self.strategy.on_tick(&tick, &self.position) {
-                // Simulate fills
-                self.simulate_fills(&tick, &quote);
+            // Deliver market data the strategy has received by now
+            self.venue.publish(tick.clone());
+            while let Some((seen_us, seen)) = self.venue.next_delivery(now_us) {
+                let quote = self.strategy.on_tick(&seen, &self.ledger.position);
+                self.venue.submit(seen_us, quote);
             }
 
-            // Update PnL mark-to-market
-            let mid_price = tick.mid_price();
-            self.pnl.unrealized = self.position.quantity * mid_price;
-            self.pnl.total = self.pnl.realized + self.pnl.unrealized;
-
-            // Track equity
-            let equity = self.config.initial_capital + self.pnl.total - self.total_fees;
-            if self.config.record_equity_curve {
-                self.equity_curve.push((tick.timestamp, equity));
-            }
+            // Apply orders and cancels that reached the venue, then simulate fills
+            self.venue.process_arrivals(now_us);
+            self.simulate_fills(&tick);
 
-            // Track drawdown
-            if equity > self.peak_equity {
-                self.peak_equity = equity;
-            }
-            let drawdown = self.peak_equity - equity;
-            if drawdown > self.max_drawdown {
-                self.max_drawdown = drawdown;
-            }
-
-            num_ticks += 1;
-            callback(num_ticks as usize, total_ticks);
+            self.ledger.mark(tick.timestamp, tick.mid_price());
+            callback(self.ledger.num_ticks as usize, total_ticks);
         }
 
-        BacktestResult {
-            total_pnl: self.pnl.total,
-            total_fees: self.total_fees,
-            net_pnl: self.pnl.total - self.total_fees,
-            num_trades: self.trades.len() as u64,
-            num_ticks,
-            start_time,
-            end_time,
-            max_position: self.max_position,
-            final_position: self.position.quantity,
-            equity_curve: if self.config.record_equity_curve {
-                self.equity_curve.clone()
-            } else {
-                Vec::new()
-            },
-            trades: if self.config.record_trades {
-                self.trades.clone()
-            } else {
-                Vec::new()
-            },
-            max_drawdown: self.max_drawdown,
-            sharpe_ratio: self.calculate_sharpe_ratio(),
-        }
+        self.ledger.result()
     }
 
-    /// Simulates order fills based on market tick and quote.
-    fn simulate_fills(&mut self, tick: &MarketTick, quote: &Quote) {
-        // Check if bid gets filled (market sells into our bid)
-        if tick.ask_price <= quote.bid_price {
-            let fill_price = self.apply_slippage(quote.bid_price, Side::Buy);
-            let fill = self.create_fill(Side::Buy, fill_price, tick.timestamp);
-            self.process_fill(fill);
+    /// Simulates fills of the quotes resting at the venue against a tick.
+    fn simulate_fills(&mut self, tick: &MarketTick) {
+        let mut crossed = Vec::new();
+        for live in self.venue.live_mut().iter_mut() {
+            // Check if bid gets filled (market sells into our bid)
+            if live.bid_open && tick.ask_price <= live.quote.bid_price {
+                live.bid_open = false;
+                crossed.push((Side::Buy, live.quote.bid_price));
+            }
+
+            // Check if ask gets filled (market buys from our ask)
+            if live.ask_open && tick.bid_price >= live.quote.ask_price {
+                live.ask_open = false;
+                crossed.push((Side::Sell, live.quote.ask_price));
+            }
         }
+        self.venue.prune();
 
-        // Check if ask gets filled (market buys from our ask)
-        if tick.bid_price >= quote.ask_price {
-            let fill_price = self.apply_slippage(quote.ask_price, Side::Sell);
-            let fill = self.create_fill(Side::Sell, fill_price, tick.timestamp);
+        for (side, price) in crossed {
+            let fill_price = self.apply_slippage(price, side);
+            let fill = self.create_fill(side, fill_price, tick.timestamp);
             self.process_fill(fill);
         }
     }
@@ -576,6 +548,77 @@ impl<S: BacktestStrategy, D: HistoricalDataSource> BacktestEngine<S, D> {
 
     /// Processes a fill: updates position, PnL, and notifies strategy.
     fn process_fill(&mut self, fill: SimulatedFill) {
+        self.strategy.on_fill(&fill);
+        self.ledger.record_fill(fill);
+    }
+
+    /// Returns the current state (position and PnL).
+    #[must_use]
+    pub fn get_state(&self) -> (&InventoryPosition, &PnL) {
+        (&self.ledger.position, &self.ledger.pnl)
+    }
+
+    /// Returns a reference to the strategy.
+    #[must_use]
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Returns a mutable reference to the strategy.
+    pub fn strategy_mut(&mut self) -> &mut S {
+        &mut self.strategy
+    }
+
+    /// Resets the engine for another run.
+    pub fn reset(&mut self) {
+        self.data_source.reset();
+        self.strategy.reset();
+        self.ledger = BacktestLedger::new(&self.config);
+        self.venue = LatencySimulator::new(self.config.latency.clone());
+    }
+}
+
+/// Position, PnL and equity accounting shared by the backtest engines.
+#[derive(Debug, Clone)]
+pub(crate) struct BacktestLedger {
+    initial_capital: Decimal,
+    record_equity_curve: bool,
+    record_trades: bool,
+    pub(crate) position: InventoryPosition,
+    pub(crate) pnl: PnL,
+    equity_curve: Vec<(u64, Decimal)>,
+    trades: Vec<SimulatedFill>,
+    pub(crate) total_fees: Decimal,
+    max_position: Decimal,
+    peak_equity: Decimal,
+    max_drawdown: Decimal,
+    pub(crate) num_ticks: u64,
+    start_time: u64,
+    end_time: u64,
+}
+
+impl BacktestLedger {
+    pub(crate) fn new(config: &BacktestConfig) -> Self {
+        Self {
+            initial_capital: config.initial_capital,
+            record_equity_curve: config.record_equity_curve,
+            record_trades: config.record_trades,
+            position: InventoryPosition::new(),
+            pnl: PnL::new(),
+            equity_curve: Vec::new(),
+            trades: Vec::new(),
+            total_fees: Decimal::ZERO,
+            max_position: Decimal::ZERO,
+            peak_equity: config.initial_capital,
+            max_drawdown: Decimal::ZERO,
+            num_ticks: 0,
+            start_time: 0,
+            end_time: 0,
+        }
+    }
+
+    /// Updates position, cash flow and fees for a fill.
+    pub(crate) fn record_fill(&mut self, fill: SimulatedFill) {
         // Update position
         let signed_qty = match fill.side {
             Side::Buy => fill.quantity,
@@ -601,15 +644,68 @@ impl<S: BacktestStrategy, D: HistoricalDataSource> BacktestEngine<S, D> {
             self.max_position = abs_position;
         }
 
-        // Notify strategy
-        self.strategy.on_fill(&fill);
-
         // Record trade
-        if self.config.record_trades {
+        if self.record_trades {
             self.trades.push(fill);
         }
     }
 
+    /// Marks the position to `mid_price` and records equity for one tick.
+    pub(crate) fn mark(&mut self, timestamp: u64, mid_price: Decimal) {
+        if self.num_ticks == 0 {
+            self.start_time = timestamp;
+        }
+        self.end_time = timestamp;
+
+        self.revalue(mid_price);
+
+        // Track equity
+        let equity = self.equity();
+        if self.record_equity_curve {
+            self.equity_curve.push((timestamp, equity));
+        }
+
+        // Track drawdown
+        if equity > self.peak_equity {
+            self.peak_equity = equity;
+        }
+        let drawdown = self.peak_equity - equity;
+        if drawdown > self.max_drawdown {
+            self.max_drawdown = drawdown;
+        }
+
+        self.num_ticks += 1;
+    }
+
+    /// Marks the position to `mid_price` without recording a tick.
+    pub(crate) fn revalue(&mut self, mid_price: Decimal) {
+        self.pnl.unrealized = self.position.quantity * mid_price;
+        self.pnl.total = self.pnl.realized + self.pnl.unrealized;
+    }
+
+    /// Returns the equity as of the last mark.
+    pub(crate) fn equity(&self) -> Decimal {
+        self.initial_capital + self.pnl.total - self.total_fees
+    }
+
+    pub(crate) fn result(&self) -> BacktestResult {
+        BacktestResult {
+            total_pnl: self.pnl.total,
+            total_fees: self.total_fees,
+            net_pnl: self.pnl.total - self.total_fees,
+            num_trades: self.trades.len() as u64,
+            num_ticks: self.num_ticks,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            max_position: self.max_position,
+            final_position: self.position.quantity,
+            equity_curve: self.equity_curve.clone(),
+            trades: self.trades.clone(),
+            max_drawdown: self.max_drawdown,
+            sharpe_ratio: self.calculate_sharpe_ratio(),
+        }
+    }
+
     /// Calculates an approximate Sharpe ratio from the equity curve.
     fn calculate_sharpe_ratio(&self) -> Option<Decimal> {
         if self.equity_curve.len() < 2 {
@@ -659,37 +755,6 @@ impl<S: BacktestStrategy, D: HistoricalDataSource> BacktestEngine<S, D> {
             None
         }
     }
-
-    /// Returns the current state (position and PnL).
-    #[must_use]
-    pub fn get_state(&self) -> (&InventoryPosition, &PnL) {
-        (&self.position, &self.pnl)
-    }
-
-    /// Returns a reference to the strategy.
-    #[must_use]
-    pub fn strategy(&self) -> &S {
-        &self.strategy
-    }
-
-    /// Returns a mutable reference to the strategy.
-    pub fn strategy_mut(&mut self) -> &mut S {
-        &mut self.strategy
-    }
-
-    /// Resets the engine for another run.
-    pub fn reset(&mut self) {
-        self.data_source.reset();
-        self.strategy.reset();
-        self.position = InventoryPosition::new();
-        self.pnl = PnL::new();
-        self.equity_curve.clear();
-        self.trades.clear();
-        self.total_fees = Decimal::ZERO;
-        self.max_position = Decimal::ZERO;
-        self.peak_equity = self.config.initial_capital;
-        self.max_drawdown = Decimal::ZERO;
-    }
 }
 
 /// Approximate square root using Newton's method.
@@ -719,6 +784,7 @@ fn decimal_sqrt(n: Decimal) -> Option<Decimal> {
 mod tests {
     use super::*;
     use crate::backtest::VecDataSource;
+    use crate::backtest::latency::LatencyModel;
     use crate::dec;
 
     struct TestStrategy {
@@ -1101,6 +1167,21 @@ mod tests {
         assert!(config.record_trades);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_backtest_config_deserializes_without_latency() {
+        let config = BacktestConfig::default()
+            .with_fee_rate(dec!(0.001))
+            .with_latency(LatencyConfig::new().with_order_entry(LatencyModel::Fixed(500)));
+
+        let mut value = serde_json::to_value(&config).unwrap();
+        value.as_object_mut().unwrap().remove("latency");
+
+        let restored: BacktestConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.fee_rate, dec!(0.001));
+        assert!(restored.latency.is_zero());
+    }
+
     #[test]
     fn test_backtest_engine_with_actual_fills() {
         // Create ticks where market definitely crosses our quotes
@@ -1164,6 +1245,68 @@ mod tests {
         assert_eq!(result.num_ticks, 4);
     }
 
+    #[test]
+    fn test_backtest_engine_stale_quote_fills_under_latency() {
+        // The quote made at 1000 is still live when the market drops at 1010
+        let ticks = vec![
+            create_test_tick(1000, dec!(100.0), dec!(100.2)),
+            create_test_tick(1010, dec!(98.9), dec!(99.0)),
+            create_test_tick(1012, dec!(98.9), dec!(99.0)),
+        ];
+
+        let mut instant = BacktestEngine::new(
+            BacktestConfig::default(),
+            TestStrategy::new(dec!(2.0)),
+            VecDataSource::new(ticks.clone()),
+        );
+        assert_eq!(instant.run().num_trades, 0);
+
+        let latency = LatencyConfig::new()
+            .with_order_entry(LatencyModel::Fixed(5_000))
+            .with_cancel(LatencyModel::Fixed(5_000));
+        let config = BacktestConfig::default().with_latency(latency);
+        let mut engine = BacktestEngine::new(
+            config,
+            TestStrategy::new(dec!(2.0)),
+            VecDataSource::new(ticks),
+        );
+        let result = engine.run();
+
+        // Bought at the stale bid, and only once
+        assert_eq!(result.num_trades, 1);
+        assert_eq!(result.trades[0].side, Side::Buy);
+        assert_eq!(result.trades[0].price, dec!(99.1));
+        assert_eq!(result.trades[0].timestamp, 1010);
+        assert_eq!(engine.strategy().fills_received.len(), 1);
+
+        engine.reset();
+        assert_eq!(engine.run().num_trades, 1);
+    }
+
+    #[test]
+    fn test_backtest_engine_market_data_latency() {
+        let ticks = vec![
+            create_test_tick(1000, dec!(100.0), dec!(100.2)),
+            create_test_tick(1010, dec!(100.0), dec!(100.2)),
+            create_test_tick(1020, dec!(98.9), dec!(99.0)),
+        ];
+
+        // At 1020 the strategy has only seen the tick from 1000
+        let latency = LatencyConfig::new().with_market_data(LatencyModel::Fixed(15_000));
+        let config = BacktestConfig::default().with_latency(latency);
+        let mut engine = BacktestEngine::new(
+            config,
+            TestStrategy::new(dec!(2.0)),
+            VecDataSource::new(ticks),
+        );
+        let result = engine.run();
+
+        assert_eq!(result.num_ticks, 3);
+        assert_eq!(result.num_trades, 1);
+        assert_eq!(result.trades[0].price, dec!(99.1));
+        assert_eq!(result.final_position, dec!(1.0));
+    }
+
     #[test]
     fn test_backtest_result_is_profitable() {
         let profitable = BacktestResult {
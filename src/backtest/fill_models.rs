@@ -42,6 +42,7 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use super::data::MarketTick;
+use super::l2_replay::L2Book;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -366,6 +367,22 @@ impl QueuePositionFillModel {
         self.queue_depth.insert(ask_key, tick.ask_size);
     }
 
+    /// Updates queue depth estimates from every level of a replayed book.
+    ///
+    /// Unlike [`QueuePositionFillModel::update_queue`], levels behind the
+    /// touch are known too, so orders resting deeper start with their
+    /// real queue ahead. Previous estimates are discarded, so levels that
+    /// have left the book report no depth.
+    pub fn update_from_book(&mut self, book: &L2Book) {
+        self.queue_depth.clear();
+        for side in [Side::Buy, Side::Sell] {
+            for level in book.levels_on(side, book.level_count(side)) {
+                self.queue_depth
+                    .insert(level.price.to_string(), level.quantity);
+            }
+        }
+    }
+
     /// Returns the estimated queue depth at a price level.
     #[must_use]
     pub fn get_queue_depth(&self, price: Decimal) -> Decimal {
@@ -939,6 +956,31 @@ mod tests {
         assert_eq!(model.get_queue_depth(dec!(100.1)), dec!(30.0));
     }
 
+    #[test]
+    fn test_queue_position_update_from_book_drops_removed_levels() {
+        use crate::backtest::l2_replay::L2Event;
+
+        let mut book = L2Book::new();
+        book.apply(&L2Event::add(1000, Side::Buy, dec!(100.0), dec!(5.0)))
+            .unwrap();
+        book.apply(&L2Event::add(1000, Side::Buy, dec!(99.5), dec!(3.0)))
+            .unwrap();
+        book.apply(&L2Event::add(1000, Side::Sell, dec!(100.5), dec!(4.0)))
+            .unwrap();
+
+        let mut model = QueuePositionFillModel::new(dec!(0.1));
+        model.update_from_book(&book);
+        assert_eq!(model.get_queue_depth(dec!(99.5)), dec!(3.0));
+
+        book.apply(&L2Event::delete(2000, Side::Buy, dec!(99.5)))
+            .unwrap();
+        model.update_from_book(&book);
+
+        assert_eq!(model.get_queue_depth(dec!(99.5)), Decimal::ZERO);
+        assert_eq!(model.get_queue_depth(dec!(100.0)), dec!(5.0));
+        assert_eq!(model.get_queue_depth(dec!(100.5)), dec!(4.0));
+    }
+
     #[test]
     fn test_queue_position_no_fill_market_not_crossed() {
         let model = QueuePositionFillModel::new(dec!(0.1));
@@ -0,0 +1,720 @@
+//! Level-2 order book reconstruction and replay.
+//!
+//! [`L2ReplaySource`] replays incremental price-level updates (add, modify,
+//! delete), full snapshots and trades into an in-memory [`L2Book`]. It
+//! implements [`HistoricalDataSource`], so it drops into `BacktestEngine`
+//! unchanged. Each emitted [`MarketTick`] is the top of book after all
+//! events sharing one timestamp have been applied. The full book remains
+//! queryable through [`L2ReplaySource::book`], or through
+//! [`L2ReplaySource::shared_book`] from a strategy or fill model that does
+//! not own the source.
+//!
+//! Trades do not change the book. Feeds report the resulting level
+//! changes as separate updates.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::backtest::{HistoricalDataSource, L2Event, L2ReplaySource};
+//! use market_maker_rs::execution::Side;
+//! use market_maker_rs::dec;
+//!
+//! let events = vec![
+//!     L2Event::add(1000, Side::Buy, dec!(100.0), dec!(2.0)),
+//!     L2Event::add(1000, Side::Sell, dec!(100.5), dec!(1.0)),
+//!     L2Event::add(1001, Side::Buy, dec!(100.2), dec!(1.5)),
+//!     L2Event::trade(1002, dec!(100.5), dec!(0.4), Side::Buy),
+//!     L2Event::modify(1002, Side::Sell, dec!(100.5), dec!(0.6)),
+//! ];
+//!
+//! let mut source = L2ReplaySource::new(events).unwrap();
+//! assert_eq!(source.len(), 3);
+//!
+//! let tick = source.next_tick().unwrap();
+//! assert_eq!(tick.bid_price, dec!(100.0));
+//!
+//! let tick = source.next_tick().unwrap();
+//! assert_eq!(tick.bid_price, dec!(100.2));
+//! assert_eq!(source.book().quantity_at(Side::Buy, dec!(100.0)), dec!(2.0));
+//!
+//! let tick = source.next_tick().unwrap();
+//! assert_eq!(tick.ask_size, dec!(0.6));
+//! assert_eq!(tick.last_price, Some(dec!(100.5)));
+//! ```
+
+use crate::Decimal;
+use crate::execution::{BookLevel, OrderBookSnapshot, Side};
+use crate::types::error::{MMError, MMResult};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+
+use super::data::{HistoricalDataSource, MarketTick};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Book shared between an [`L2ReplaySource`] and its readers.
+pub type SharedL2Book = Arc<RwLock<L2Book>>;
+
+/// A level-2 market data event.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct L2Event {
+    /// Timestamp in milliseconds.
+    pub timestamp: u64,
+    /// What happened.
+    pub kind: L2EventKind,
+}
+
+/// Kinds of level-2 events. `Side::Buy` refers to the bid side.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum L2EventKind {
+    /// A new price level appears.
+    Add {
+        /// Book side.
+        side: Side,
+        /// Level price.
+        price: Decimal,
+        /// Level quantity.
+        quantity: Decimal,
+    },
+    /// An existing level's quantity changes; zero removes the level.
+    Modify {
+        /// Book side.
+        side: Side,
+        /// Level price.
+        price: Decimal,
+        /// New level quantity.
+        quantity: Decimal,
+    },
+    /// An existing level is removed.
+    Delete {
+        /// Book side.
+        side: Side,
+        /// Level price.
+        price: Decimal,
+    },
+    /// The whole book is replaced.
+    Snapshot {
+        /// Bid levels.
+        bids: Vec<BookLevel>,
+        /// Ask levels.
+        asks: Vec<BookLevel>,
+    },
+    /// A trade printed.
+    Trade {
+        /// Trade price.
+        price: Decimal,
+        /// Trade quantity.
+        quantity: Decimal,
+        /// Side of the aggressing order.
+        aggressor: Side,
+    },
+}
+
+impl L2Event {
+    /// Creates an add-level event.
+    #[must_use]
+    pub fn add(timestamp: u64, side: Side, price: Decimal, quantity: Decimal) -> Self {
+        Self {
+            timestamp,
+            kind: L2EventKind::Add {
+                side,
+                price,
+                quantity,
+            },
+        }
+    }
+
+    /// Creates a modify-level event.
+    #[must_use]
+    pub fn modify(timestamp: u64, side: Side, price: Decimal, quantity: Decimal) -> Self {
+        Self {
+            timestamp,
+            kind: L2EventKind::Modify {
+                side,
+                price,
+                quantity,
+            },
+        }
+    }
+
+    /// Creates a delete-level event.
+    #[must_use]
+    pub fn delete(timestamp: u64, side: Side, price: Decimal) -> Self {
+        Self {
+            timestamp,
+            kind: L2EventKind::Delete { side, price },
+        }
+    }
+
+    /// Creates a snapshot event.
+    #[must_use]
+    pub fn snapshot(timestamp: u64, bids: Vec<BookLevel>, asks: Vec<BookLevel>) -> Self {
+        Self {
+            timestamp,
+            kind: L2EventKind::Snapshot { bids, asks },
+        }
+    }
+
+    /// Creates a trade event.
+    #[must_use]
+    pub fn trade(timestamp: u64, price: Decimal, quantity: Decimal, aggressor: Side) -> Self {
+        Self {
+            timestamp,
+            kind: L2EventKind::Trade {
+                price,
+                quantity,
+                aggressor,
+            },
+        }
+    }
+}
+
+/// In-memory price-level order book.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::{L2Book, L2Event};
+/// use market_maker_rs::execution::Side;
+/// use market_maker_rs::dec;
+///
+/// let mut book = L2Book::new();
+/// book.apply(&L2Event::add(0, Side::Buy, dec!(99.0), dec!(3.0))).unwrap();
+/// book.apply(&L2Event::add(0, Side::Buy, dec!(100.0), dec!(1.0))).unwrap();
+/// book.apply(&L2Event::add(0, Side::Sell, dec!(101.0), dec!(2.0))).unwrap();
+///
+/// assert_eq!(book.best_bid().unwrap().price, dec!(100.0));
+/// assert_eq!(book.mid_price(), Some(dec!(100.5)));
+/// assert_eq!(book.depth_through(Side::Buy, dec!(99.0)), dec!(4.0));
+///
+/// // Deleting an unknown level is an error.
+/// assert!(book.apply(&L2Event::delete(0, Side::Sell, dec!(102.0))).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct L2Book {
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+    last_update: u64,
+}
+
+impl L2Book {
+    /// Creates an empty book.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies an event to the book.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if an add targets an existing
+    /// level, a modify or delete targets a missing level, or a price or
+    /// quantity is invalid. The book is unchanged on error.
+    pub fn apply(&mut self, event: &L2Event) -> MMResult<()> {
+        match &event.kind {
+            L2EventKind::Add {
+                side,
+                price,
+                quantity,
+            } => {
+                validate_level(*price, *quantity, false)?;
+                let levels = self.levels_mut(*side);
+                if levels.contains_key(price) {
+                    return Err(MMError::InvalidMarketState(format!(
+                        "add for existing {side} level {price}"
+                    )));
+                }
+                levels.insert(*price, *quantity);
+            }
+            L2EventKind::Modify {
+                side,
+                price,
+                quantity,
+            } => {
+                validate_level(*price, *quantity, true)?;
+                let levels = self.levels_mut(*side);
+                let Some(level) = levels.get_mut(price) else {
+                    return Err(MMError::InvalidMarketState(format!(
+                        "modify for missing {side} level {price}"
+                    )));
+                };
+                if quantity.is_zero() {
+                    levels.remove(price);
+                } else {
+                    *level = *quantity;
+                }
+            }
+            L2EventKind::Delete { side, price } => {
+                if self.levels_mut(*side).remove(price).is_none() {
+                    return Err(MMError::InvalidMarketState(format!(
+                        "delete for missing {side} level {price}"
+                    )));
+                }
+            }
+            L2EventKind::Snapshot { bids, asks } => {
+                for level in bids.iter().chain(asks) {
+                    validate_level(level.price, level.quantity, false)?;
+                }
+                self.bids = bids.iter().map(|l| (l.price, l.quantity)).collect();
+                self.asks = asks.iter().map(|l| (l.price, l.quantity)).collect();
+            }
+            L2EventKind::Trade {
+                price, quantity, ..
+            } => validate_level(*price, *quantity, false)?,
+        }
+        self.last_update = event.timestamp;
+        Ok(())
+    }
+
+    /// Removes all levels.
+    pub fn clear(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+        self.last_update = 0;
+    }
+
+    /// Returns the timestamp of the last applied event.
+    #[must_use]
+    pub fn last_update(&self) -> u64 {
+        self.last_update
+    }
+
+    /// Returns the best bid level.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<BookLevel> {
+        self.bids
+            .iter()
+            .next_back()
+            .map(|(p, q)| BookLevel::new(*p, *q))
+    }
+
+    /// Returns the best ask level.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<BookLevel> {
+        self.asks.iter().next().map(|(p, q)| BookLevel::new(*p, *q))
+    }
+
+    /// Returns the mid price if both sides are present.
+    #[must_use]
+    pub fn mid_price(&self) -> Option<Decimal> {
+        Some((self.best_bid()?.price + self.best_ask()?.price) / Decimal::TWO)
+    }
+
+    /// Returns the quantity resting at a price, or zero.
+    #[must_use]
+    pub fn quantity_at(&self, side: Side, price: Decimal) -> Decimal {
+        self.levels(side)
+            .get(&price)
+            .copied()
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// Returns the quantity at `price` and all better levels on `side`.
+    #[must_use]
+    pub fn depth_through(&self, side: Side, price: Decimal) -> Decimal {
+        match side {
+            Side::Buy => self.bids.range(price..).map(|(_, q)| *q).sum(),
+            Side::Sell => self.asks.range(..=price).map(|(_, q)| *q).sum(),
+        }
+    }
+
+    /// Returns up to `depth` levels on `side`, best first.
+    #[must_use]
+    pub fn levels_on(&self, side: Side, depth: usize) -> Vec<BookLevel> {
+        let to_level = |(p, q): (&Decimal, &Decimal)| BookLevel::new(*p, *q);
+        match side {
+            Side::Buy => self.bids.iter().rev().take(depth).map(to_level).collect(),
+            Side::Sell => self.asks.iter().take(depth).map(to_level).collect(),
+        }
+    }
+
+    /// Returns the number of levels on `side`.
+    #[must_use]
+    pub fn level_count(&self, side: Side) -> usize {
+        self.levels(side).len()
+    }
+
+    /// Returns true if both sides are empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+
+    /// Returns the top of book as a tick, if both sides are present.
+    #[must_use]
+    pub fn to_tick(&self) -> Option<MarketTick> {
+        let bid = self.best_bid()?;
+        let ask = self.best_ask()?;
+        Some(MarketTick::new(
+            self.last_update,
+            bid.price,
+            bid.quantity,
+            ask.price,
+            ask.quantity,
+        ))
+    }
+
+    /// Returns up to `depth` levels per side as an execution-layer snapshot.
+    #[must_use]
+    pub fn to_snapshot(&self, symbol: impl Into<String>, depth: usize) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            symbol: symbol.into(),
+            bids: self.levels_on(Side::Buy, depth),
+            asks: self.levels_on(Side::Sell, depth),
+            timestamp: self.last_update,
+        }
+    }
+
+    fn levels(&self, side: Side) -> &BTreeMap<Decimal, Decimal> {
+        match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        }
+    }
+
+    fn levels_mut(&mut self, side: Side) -> &mut BTreeMap<Decimal, Decimal> {
+        match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        }
+    }
+}
+
+fn validate_level(price: Decimal, quantity: Decimal, allow_zero: bool) -> MMResult<()> {
+    if price <= Decimal::ZERO {
+        return Err(MMError::InvalidMarketState(format!(
+            "level price must be positive, got {price}"
+        )));
+    }
+    if quantity < Decimal::ZERO || (!allow_zero && quantity.is_zero()) {
+        return Err(MMError::InvalidMarketState(format!(
+            "invalid level quantity {quantity}"
+        )));
+    }
+    Ok(())
+}
+
+/// Historical data source replaying level-2 events.
+///
+/// Events are validated when the source is created, so a replay never
+/// stops half-way on a malformed update. Batches that leave either side of
+/// the book empty produce no tick.
+#[derive(Debug)]
+pub struct L2ReplaySource {
+    events: Vec<L2Event>,
+    /// Precomputed ticks with the index one past their batch's last event.
+    ticks: Vec<(usize, MarketTick)>,
+    book: SharedL2Book,
+    event_index: usize,
+    tick_index: usize,
+    batch_trades: Vec<L2Event>,
+}
+
+impl L2ReplaySource {
+    /// Creates a replay source.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidTimestamp` if timestamps decrease, or
+    /// `MMError::InvalidMarketState` if an event does not apply cleanly to
+    /// the book built from the events before it.
+    pub fn new(events: Vec<L2Event>) -> MMResult<Self> {
+        let mut book = L2Book::new();
+        let mut ticks = Vec::new();
+        let mut last_trade = None;
+        for (i, event) in events.iter().enumerate() {
+            if event.timestamp < book.last_update() {
+                return Err(MMError::InvalidTimestamp(format!(
+                    "event {i} at {} precedes {}",
+                    event.timestamp,
+                    book.last_update()
+                )));
+            }
+            book.apply(event)
+                .map_err(|e| MMError::InvalidMarketState(format!("event {i}: {e}")))?;
+            if let L2EventKind::Trade {
+                price, quantity, ..
+            } = event.kind
+            {
+                last_trade = Some((price, quantity));
+            }
+
+            let batch_end = events
+                .get(i + 1)
+                .is_none_or(|next| next.timestamp != event.timestamp);
+            if !batch_end {
+                continue;
+            }
+            // Trades of a batch that produces no tick are not carried over.
+            let trade = last_trade.take();
+            if let Some(mut tick) = book.to_tick() {
+                if let Some((price, quantity)) = trade {
+                    tick.last_price = Some(price);
+                    tick.last_size = Some(quantity);
+                }
+                ticks.push((i + 1, tick));
+            }
+        }
+        Ok(Self {
+            events,
+            ticks,
+            book: Arc::new(RwLock::new(L2Book::new())),
+            event_index: 0,
+            tick_index: 0,
+            batch_trades: Vec::new(),
+        })
+    }
+
+    /// Returns the current book.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the book lock is poisoned.
+    pub fn book(&self) -> RwLockReadGuard<'_, L2Book> {
+        self.book.read().unwrap()
+    }
+
+    /// Returns a handle to the book that stays valid while the source is
+    /// moved into an engine.
+    #[must_use]
+    pub fn shared_book(&self) -> SharedL2Book {
+        Arc::clone(&self.book)
+    }
+
+    /// Returns the trade events of the batch that produced the last
+    /// [`next_tick`].
+    ///
+    /// [`next_tick`]: HistoricalDataSource::next_tick
+    #[must_use]
+    pub fn batch_trades(&self) -> &[L2Event] {
+        &self.batch_trades
+    }
+
+    /// Returns all events.
+    #[must_use]
+    pub fn events(&self) -> &[L2Event] {
+        &self.events
+    }
+}
+
+impl HistoricalDataSource for L2ReplaySource {
+    fn next_tick(&mut self) -> Option<MarketTick> {
+        let (end, tick) = self.ticks.get(self.tick_index)?.clone();
+        let mut book = self.book.write().unwrap();
+        self.batch_trades.clear();
+        for event in &self.events[self.event_index..end] {
+            // Validated in `new`, so this cannot fail.
+            let _ = book.apply(event);
+            if event.timestamp == tick.timestamp && matches!(event.kind, L2EventKind::Trade { .. })
+            {
+                self.batch_trades.push(event.clone());
+            }
+        }
+        self.event_index = end;
+        self.tick_index += 1;
+        Some(tick)
+    }
+
+    fn peek_tick(&self) -> Option<&MarketTick> {
+        self.ticks.get(self.tick_index).map(|(_, tick)| tick)
+    }
+
+    fn reset(&mut self) {
+        self.book.write().unwrap().clear();
+        self.batch_trades.clear();
+        self.event_index = 0;
+        self.tick_index = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    fn remaining(&self) -> usize {
+        self.ticks.len() - self.tick_index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{
+        BacktestConfig, BacktestEngine, BacktestStrategy, FillModel, QueuePositionFillModel,
+        SimulatedFill, SimulatedOrder,
+    };
+    use crate::dec;
+    use crate::position::inventory::InventoryPosition;
+    use crate::strategy::quote::Quote;
+
+    fn events() -> Vec<L2Event> {
+        vec![
+            L2Event::snapshot(
+                1000,
+                vec![
+                    BookLevel::new(dec!(100.0), dec!(2.0)),
+                    BookLevel::new(dec!(99.5), dec!(4.0)),
+                ],
+                vec![BookLevel::new(dec!(101.0), dec!(3.0))],
+            ),
+            L2Event::add(1001, Side::Sell, dec!(100.5), dec!(1.0)),
+            L2Event::trade(1002, dec!(100.0), dec!(0.5), Side::Sell),
+            L2Event::modify(1002, Side::Buy, dec!(100.0), dec!(1.5)),
+            L2Event::delete(1003, Side::Sell, dec!(100.5)),
+        ]
+    }
+
+    #[test]
+    fn test_book_updates() {
+        let mut book = L2Book::new();
+        for event in events() {
+            book.apply(&event).unwrap();
+        }
+        assert_eq!(
+            book.best_bid(),
+            Some(BookLevel::new(dec!(100.0), dec!(1.5)))
+        );
+        assert_eq!(
+            book.best_ask(),
+            Some(BookLevel::new(dec!(101.0), dec!(3.0)))
+        );
+        assert_eq!(book.depth_through(Side::Buy, dec!(99.5)), dec!(5.5));
+        assert_eq!(book.depth_through(Side::Sell, dec!(100.9)), Decimal::ZERO);
+        assert_eq!(book.level_count(Side::Buy), 2);
+        assert_eq!(book.last_update(), 1003);
+
+        let snapshot = book.to_snapshot("BTC", 1);
+        assert_eq!(snapshot.bids.len(), 1);
+        assert_eq!(snapshot.best_bid(), Some(dec!(100.0)));
+
+        book.apply(&L2Event::modify(1004, Side::Buy, dec!(100.0), dec!(0.0)))
+            .unwrap();
+        assert_eq!(book.best_bid().unwrap().price, dec!(99.5));
+
+        let before = book.clone();
+        assert!(
+            book.apply(&L2Event::add(1005, Side::Buy, dec!(99.5), dec!(1.0)))
+                .is_err()
+        );
+        assert!(
+            book.apply(&L2Event::modify(1005, Side::Sell, dec!(1.0), dec!(1.0)))
+                .is_err()
+        );
+        assert!(
+            book.apply(&L2Event::add(1005, Side::Sell, dec!(102.0), dec!(-1.0)))
+                .is_err()
+        );
+        assert_eq!(book, before);
+    }
+
+    #[test]
+    fn test_replay_batches_ticks() {
+        let mut source = L2ReplaySource::new(events()).unwrap();
+        assert_eq!(source.len(), 4);
+        assert_eq!(source.peek_tick().unwrap().timestamp, 1000);
+
+        source.next_tick().unwrap();
+        let tick = source.next_tick().unwrap();
+        assert_eq!(tick.ask_price, dec!(100.5));
+        assert!(source.batch_trades().is_empty());
+
+        // The trade and its level update share a timestamp: one tick.
+        let tick = source.next_tick().unwrap();
+        assert_eq!(tick.timestamp, 1002);
+        assert_eq!(tick.bid_size, dec!(1.5));
+        assert_eq!(tick.last_price, Some(dec!(100.0)));
+        assert_eq!(source.batch_trades().len(), 1);
+        assert_eq!(source.remaining(), 1);
+
+        let tick = source.next_tick().unwrap();
+        assert_eq!(tick.ask_price, dec!(101.0));
+        assert_eq!(tick.last_price, None);
+        assert!(source.next_tick().is_none());
+
+        source.reset();
+        assert!(source.book().is_empty());
+        assert_eq!(source.remaining(), 4);
+    }
+
+    #[test]
+    fn test_replay_validates_events() {
+        let mut bad = events();
+        bad.push(L2Event::add(999, Side::Buy, dec!(98.0), dec!(1.0)));
+        assert!(matches!(
+            L2ReplaySource::new(bad),
+            Err(MMError::InvalidTimestamp(_))
+        ));
+
+        let mut bad = events();
+        bad.push(L2Event::delete(1003, Side::Sell, dec!(100.5)));
+        assert!(matches!(
+            L2ReplaySource::new(bad),
+            Err(MMError::InvalidMarketState(_))
+        ));
+
+        // A one-sided book emits nothing until the other side appears.
+        let source = L2ReplaySource::new(vec![
+            L2Event::add(1, Side::Buy, dec!(100.0), dec!(1.0)),
+            L2Event::add(2, Side::Sell, dec!(101.0), dec!(1.0)),
+        ])
+        .unwrap();
+        assert_eq!(source.len(), 1);
+    }
+
+    #[test]
+    fn test_trades_of_tickless_batches_are_dropped() {
+        let mut source = L2ReplaySource::new(vec![
+            L2Event::add(1, Side::Buy, dec!(100.0), dec!(1.0)),
+            L2Event::trade(1, dec!(100.0), dec!(0.5), Side::Sell),
+            L2Event::add(2, Side::Sell, dec!(101.0), dec!(1.0)),
+        ])
+        .unwrap();
+        let tick = source.next_tick().unwrap();
+        assert_eq!(tick.timestamp, 2);
+        assert_eq!(tick.last_price, None);
+        assert!(source.batch_trades().is_empty());
+    }
+
+    struct BookReader {
+        book: SharedL2Book,
+        depths: Vec<usize>,
+    }
+
+    impl BacktestStrategy for BookReader {
+        fn on_tick(&mut self, _tick: &MarketTick, _position: &InventoryPosition) -> Option<Quote> {
+            self.depths
+                .push(self.book.read().unwrap().level_count(Side::Buy));
+            None
+        }
+        fn on_fill(&mut self, _fill: &SimulatedFill) {}
+        fn reset(&mut self) {}
+    }
+
+    #[test]
+    fn test_engine_and_fill_model_read_book() {
+        let source = L2ReplaySource::new(events()).unwrap();
+        let strategy = BookReader {
+            book: source.shared_book(),
+            depths: Vec::new(),
+        };
+        let mut engine = BacktestEngine::new(BacktestConfig::default(), strategy, source);
+        let result = engine.run();
+        assert_eq!(result.num_ticks, 4);
+
+        let mut source = L2ReplaySource::new(events()).unwrap();
+        source.next_tick();
+        let mut model = QueuePositionFillModel::new(dec!(0.01));
+        model.update_from_book(&source.book());
+        assert_eq!(model.get_queue_depth(dec!(99.5)), dec!(4.0));
+
+        // Two units rest ahead of a sell joining the 100.0 bid it crosses.
+        let order = SimulatedOrder::new(Side::Sell, dec!(100.0), dec!(1.0), 1000);
+        let tick = source.peek_tick().unwrap().clone();
+        assert!(!model.simulate_fill(&order, &tick, 100).is_filled());
+        assert!(model.simulate_fill(&order, &tick, 300).is_filled());
+    }
+}
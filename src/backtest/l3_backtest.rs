@@ -0,0 +1,1399 @@
+//! Level-3 (order-by-order) backtesting with exact queue position.
+//!
+//! [`L3Book`] replays market-by-order events into price-time queues and
+//! exports them as the `orderbook-rs` book that [`OrderBookConnector`]
+//! wraps. Simulated orders join the same queues as the replayed orders, so
+//! they fill only when the volume ahead of them has traded or been
+//! cancelled.
+//!
+//! An [`L3EventKind::Execute`] for a resting order is replayed as an
+//! aggressive order of the executed size, capped at the order's remaining
+//! size. Simulated orders priced through the executed one, or queued ahead
+//! of it, fill first; orders behind it never do. The replayed order keeps
+//! the volume our orders absorbed, and it is removed once the feed reports
+//! it fully executed, so traded volume is conserved. Size decreases keep queue
+//! priority and increases lose it, as on most venues.
+//!
+//! [`L3BacktestEngine`] drives a [`BacktestStrategy`] from the replayed
+//! book. Quotes that do not change keep their queue position between ticks.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::backtest::{L3Book, L3Event};
+//! use market_maker_rs::execution::{OrderBookConnectorConfig, Side};
+//! use market_maker_rs::dec;
+//!
+//! let mut book = L3Book::new("BTC-USD", OrderBookConnectorConfig::default());
+//! book.apply(&L3Event::add(1, 7, Side::Buy, dec!(100.0), dec!(2.0))).unwrap();
+//!
+//! // Our bid joins the queue behind order 7.
+//! let (id, fills) = book.place(Side::Buy, dec!(100.0), dec!(1.0), 2).unwrap();
+//! assert!(fills.is_empty());
+//! assert_eq!(book.queue_ahead(id), Some(dec!(2.0)));
+//!
+//! // Order 7 is partially executed: we move up but do not fill.
+//! let fills = book.apply(&L3Event::execute(3, 7, dec!(1.5))).unwrap();
+//! assert!(fills.is_empty());
+//! assert_eq!(book.queue_ahead(id), Some(dec!(0.5)));
+//!
+//! // The rest of order 7 is cancelled: we are now first in line.
+//! book.apply(&L3Event::cancel(4, 7)).unwrap();
+//! assert_eq!(book.queue_ahead(id), Some(dec!(0.0)));
+//! ```
+
+use crate::Decimal;
+use crate::execution::{OrderBookConnector, OrderBookConnectorConfig, Side};
+use crate::position::inventory::InventoryPosition;
+use crate::position::pnl::PnL;
+use crate::strategy::quote::Quote;
+use crate::types::error::{MMError, MMResult};
+use orderbook_rs::{
+    DefaultOrderBook, OrderBook, OrderId as OBOrderId, Side as OBSide, TimeInForce as OBTimeInForce,
+};
+use rust_decimal::prelude::ToPrimitive;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::ops::Bound;
+use std::sync::Arc;
+
+use super::data::MarketTick;
+use super::engine::{
+    BacktestConfig, BacktestLedger, BacktestResult, BacktestStrategy, SimulatedFill,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A market-by-order event.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct L3Event {
+    /// Timestamp in milliseconds.
+    pub timestamp: u64,
+    /// What happened.
+    pub kind: L3EventKind,
+}
+
+/// Kinds of market-by-order events, keyed by the venue's order ID.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum L3EventKind {
+    /// A new order rests in the book.
+    Add {
+        /// Venue order ID.
+        order_id: u64,
+        /// Order side.
+        side: Side,
+        /// Limit price.
+        price: Decimal,
+        /// Resting quantity.
+        quantity: Decimal,
+    },
+    /// A resting order's quantity changes; zero removes it.
+    Modify {
+        /// Venue order ID.
+        order_id: u64,
+        /// New resting quantity.
+        quantity: Decimal,
+    },
+    /// A resting order is cancelled.
+    Cancel {
+        /// Venue order ID.
+        order_id: u64,
+    },
+    /// A resting order trades against an incoming order.
+    Execute {
+        /// Venue order ID of the resting order.
+        order_id: u64,
+        /// Executed quantity.
+        quantity: Decimal,
+    },
+}
+
+impl L3Event {
+    /// Creates an add event.
+    #[must_use]
+    pub fn add(
+        timestamp: u64,
+        order_id: u64,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+    ) -> Self {
+        Self {
+            timestamp,
+            kind: L3EventKind::Add {
+                order_id,
+                side,
+                price,
+                quantity,
+            },
+        }
+    }
+
+    /// Creates a modify event.
+    #[must_use]
+    pub fn modify(timestamp: u64, order_id: u64, quantity: Decimal) -> Self {
+        Self {
+            timestamp,
+            kind: L3EventKind::Modify { order_id, quantity },
+        }
+    }
+
+    /// Creates a cancel event.
+    #[must_use]
+    pub fn cancel(timestamp: u64, order_id: u64) -> Self {
+        Self {
+            timestamp,
+            kind: L3EventKind::Cancel { order_id },
+        }
+    }
+
+    /// Creates an execute event.
+    #[must_use]
+    pub fn execute(timestamp: u64, order_id: u64, quantity: Decimal) -> Self {
+        Self {
+            timestamp,
+            kind: L3EventKind::Execute { order_id, quantity },
+        }
+    }
+}
+
+/// A fill of a simulated order.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct L3Fill {
+    /// Simulated order ID returned by [`L3Book::place`].
+    pub order_id: u64,
+    /// Side of the simulated order.
+    pub side: Side,
+    /// Fill price.
+    pub price: Decimal,
+    /// Filled quantity.
+    pub quantity: Decimal,
+    /// Quantity still open after this fill.
+    pub remaining: Decimal,
+    /// True if the simulated order was resting, false if it crossed.
+    pub is_maker: bool,
+    /// Timestamp in milliseconds.
+    pub timestamp: u64,
+}
+
+/// Identifies a replayed or simulated order in the queues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum OrderKey {
+    Feed(u64),
+    Sim(u64),
+}
+
+/// One order's place in a price level queue.
+#[derive(Debug, Clone, Copy)]
+struct QueueEntry {
+    key: OrderKey,
+    quantity: Decimal,
+}
+
+/// Where a resting order sits, and its ID in exported books.
+#[derive(Debug, Clone, Copy)]
+struct Location {
+    side: Side,
+    price: Decimal,
+    ob_id: OBOrderId,
+}
+
+/// A replayed venue order as the feed sees it.
+#[derive(Debug, Clone, Copy)]
+struct FeedOrder {
+    side: Side,
+    price: Decimal,
+    remaining: Decimal,
+}
+
+/// A simulated order.
+#[derive(Debug, Clone, Copy)]
+struct SimOrder {
+    side: Side,
+    remaining: Decimal,
+}
+
+type Levels = BTreeMap<Decimal, VecDeque<QueueEntry>>;
+
+/// Order-by-order book holding replayed and simulated orders.
+///
+/// Matching runs on the price-time queues kept here. `orderbook-rs` moves
+/// an order to the back of its level whenever its size changes, which
+/// would hand simulated orders priority they never had, so the queues are
+/// exported on demand through [`L3Book::order_book`] and
+/// [`L3Book::connector`] instead of being mirrored live.
+pub struct L3Book {
+    symbol: String,
+    config: OrderBookConnectorConfig,
+    bids: Levels,
+    asks: Levels,
+    locations: HashMap<OrderKey, Location>,
+    feed_orders: HashMap<u64, FeedOrder>,
+    sim_orders: HashMap<u64, SimOrder>,
+    next_sim_id: u64,
+    last_update: u64,
+}
+
+impl fmt::Debug for L3Book {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("L3Book")
+            .field("symbol", &self.symbol)
+            .field("bid_levels", &self.bids.len())
+            .field("ask_levels", &self.asks.len())
+            .field("feed_orders", &self.feed_orders.len())
+            .field("sim_orders", &self.sim_orders.len())
+            .field("last_update", &self.last_update)
+            .finish_non_exhaustive()
+    }
+}
+
+impl L3Book {
+    /// Creates an empty book.
+    ///
+    /// Exported `orderbook-rs` books use the configured price and quantity
+    /// precisions, so prices and quantities must be representable with
+    /// them.
+    #[must_use]
+    pub fn new(symbol: &str, config: OrderBookConnectorConfig) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            config,
+            bids: Levels::new(),
+            asks: Levels::new(),
+            locations: HashMap::new(),
+            feed_orders: HashMap::new(),
+            sim_orders: HashMap::new(),
+            next_sim_id: 1,
+            last_update: 0,
+        }
+    }
+
+    /// Builds an `orderbook-rs` book holding every resting order.
+    ///
+    /// Each level is added in queue order, so the book has the same
+    /// price-time priority as the replay. It is a copy: later events and
+    /// orders do not update it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if the replayed orders cross,
+    /// which `orderbook-rs` would match, or if `orderbook-rs` rejects an
+    /// order.
+    pub fn order_book(&self) -> MMResult<Arc<DefaultOrderBook>> {
+        if let (Some((bid, _)), Some((ask, _))) =
+            (self.bids.last_key_value(), self.asks.first_key_value())
+            && bid >= ask
+        {
+            return Err(MMError::InvalidMarketState(format!(
+                "replayed book is crossed: bid {bid} >= ask {ask}"
+            )));
+        }
+        let book: DefaultOrderBook = OrderBook::new(&self.symbol);
+        for (side, levels) in [(Side::Buy, &self.bids), (Side::Sell, &self.asks)] {
+            for (&price, queue) in levels {
+                let price = to_units(price, self.config.price_precision)?;
+                for entry in queue {
+                    let Some(location) = self.locations.get(&entry.key) else {
+                        continue;
+                    };
+                    book.add_limit_order(
+                        location.ob_id,
+                        price,
+                        to_units(entry.quantity, self.config.quantity_precision)?,
+                        to_ob_side(side),
+                        OBTimeInForce::Gtc,
+                        None,
+                    )
+                    .map_err(|e| MMError::InvalidMarketState(e.to_string()))?;
+                }
+            }
+        }
+        Ok(Arc::new(book))
+    }
+
+    /// Returns an [`OrderBookConnector`] over a book built by
+    /// [`L3Book::order_book`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`L3Book::order_book`].
+    pub fn connector(&self) -> MMResult<OrderBookConnector> {
+        Ok(OrderBookConnector::from_orderbook(
+            self.order_book()?,
+            self.config.clone(),
+        ))
+    }
+
+    /// Returns the timestamp of the last applied event or order.
+    #[must_use]
+    pub fn last_update(&self) -> u64 {
+        self.last_update
+    }
+
+    /// Applies a market-by-order event and returns the resulting fills of
+    /// simulated orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidTimestamp` if the event is older than the
+    /// last update, or `MMError::InvalidMarketState` if it adds a known
+    /// order, references an unknown one or has an invalid price or
+    /// quantity.
+    pub fn apply(&mut self, event: &L3Event) -> MMResult<Vec<L3Fill>> {
+        self.check_timestamp(event.timestamp)?;
+        let timestamp = event.timestamp;
+        let mut fills = Vec::new();
+        match event.kind {
+            L3EventKind::Add {
+                order_id,
+                side,
+                price,
+                quantity,
+            } => {
+                if self.feed_orders.contains_key(&order_id) {
+                    return Err(MMError::InvalidMarketState(format!(
+                        "add for existing order {order_id}"
+                    )));
+                }
+                self.validate_order(price, quantity)?;
+                self.feed_orders.insert(
+                    order_id,
+                    FeedOrder {
+                        side,
+                        price,
+                        remaining: quantity,
+                    },
+                );
+                // The venue saw no cross, so any simulated order it crosses
+                // would have been hit first.
+                let left =
+                    self.match_incoming(side, price, quantity, None, true, timestamp, &mut fills);
+                if left > Decimal::ZERO {
+                    self.rest(OrderKey::Feed(order_id), side, price, left);
+                }
+            }
+            L3EventKind::Modify { order_id, quantity } => {
+                let feed = self.feed_order(order_id)?;
+                if quantity < Decimal::ZERO {
+                    return Err(MMError::InvalidMarketState(format!(
+                        "invalid quantity {quantity} for order {order_id}"
+                    )));
+                }
+                let key = OrderKey::Feed(order_id);
+                let in_book = self.resting_quantity(key);
+                let new_in_book = in_book + quantity - feed.remaining;
+                if quantity < feed.remaining {
+                    // Size decreases keep their place in the queue.
+                    self.set_quantity(key, new_in_book.max(Decimal::ZERO));
+                } else if quantity > feed.remaining {
+                    self.set_quantity(key, Decimal::ZERO);
+                    self.rest(key, feed.side, feed.price, new_in_book);
+                }
+                if quantity.is_zero() {
+                    self.feed_orders.remove(&order_id);
+                } else if let Some(feed) = self.feed_orders.get_mut(&order_id) {
+                    feed.remaining = quantity;
+                }
+            }
+            L3EventKind::Cancel { order_id } => {
+                self.feed_order(order_id)?;
+                self.set_quantity(OrderKey::Feed(order_id), Decimal::ZERO);
+                self.feed_orders.remove(&order_id);
+            }
+            L3EventKind::Execute { order_id, quantity } => {
+                let feed = self.feed_order(order_id)?;
+                if quantity <= Decimal::ZERO {
+                    return Err(MMError::InvalidMarketState(format!(
+                        "invalid execution quantity {quantity} for order {order_id}"
+                    )));
+                }
+                let key = OrderKey::Feed(order_id);
+                let traded = quantity.min(feed.remaining);
+                // The aggressor trades through better-priced simulated
+                // orders, then through those queued ahead of this one.
+                let through: Vec<Decimal> = match feed.side {
+                    Side::Buy => self
+                        .bids
+                        .range((Bound::Excluded(feed.price), Bound::Unbounded))
+                        .rev()
+                        .map(|(p, _)| *p)
+                        .collect(),
+                    Side::Sell => self.asks.range(..feed.price).map(|(p, _)| *p).collect(),
+                };
+                let mut left = traded;
+                for price in through {
+                    left = self.fill_sims(feed.side, price, left, None, timestamp, &mut fills);
+                }
+                left = self.fill_sims(
+                    feed.side,
+                    feed.price,
+                    left,
+                    Some(key),
+                    timestamp,
+                    &mut fills,
+                );
+
+                let remaining = feed.remaining - traded;
+                if remaining.is_zero() {
+                    self.set_quantity(key, Decimal::ZERO);
+                    self.feed_orders.remove(&order_id);
+                } else {
+                    let in_book = self.resting_quantity(key);
+                    self.set_quantity(key, (in_book - left).max(Decimal::ZERO));
+                    if let Some(feed) = self.feed_orders.get_mut(&order_id) {
+                        feed.remaining = remaining;
+                    }
+                }
+            }
+        }
+        Ok(fills)
+    }
+
+    /// Places a simulated limit order at the back of its price level.
+    ///
+    /// Any part that crosses the book fills immediately as taker. Returns
+    /// the simulated order ID and those fills.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidTimestamp` if `timestamp` is older than the
+    /// last update, `MMError::InvalidMarketState` if the price or quantity
+    /// is invalid, or `MMError::InvalidQuoteGeneration` if the order would
+    /// trade with another simulated order.
+    pub fn place(
+        &mut self,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+        timestamp: u64,
+    ) -> MMResult<(u64, Vec<L3Fill>)> {
+        self.validate_order(price, quantity)?;
+        if let Some(own) = self.crossed_sim(side, price) {
+            return Err(MMError::InvalidQuoteGeneration(format!(
+                "{side:?} at {price} crosses own order at {own}"
+            )));
+        }
+        self.check_timestamp(timestamp)?;
+        let id = self.next_sim_id;
+        self.next_sim_id += 1;
+        self.sim_orders.insert(
+            id,
+            SimOrder {
+                side,
+                remaining: quantity,
+            },
+        );
+        let mut fills = Vec::new();
+        let left = self.match_incoming(
+            side,
+            price,
+            quantity,
+            Some(id),
+            false,
+            timestamp,
+            &mut fills,
+        );
+        if left > Decimal::ZERO {
+            self.rest(OrderKey::Sim(id), side, price, left);
+        }
+        Ok((id, fills))
+    }
+
+    /// Cancels a simulated order. Returns false if it is no longer open.
+    pub fn cancel(&mut self, order_id: u64) -> bool {
+        if self.sim_orders.remove(&order_id).is_none() {
+            return false;
+        }
+        self.set_quantity(OrderKey::Sim(order_id), Decimal::ZERO);
+        true
+    }
+
+    /// Returns the open quantity of a simulated order.
+    #[must_use]
+    pub fn open_quantity(&self, order_id: u64) -> Option<Decimal> {
+        self.sim_orders.get(&order_id).map(|o| o.remaining)
+    }
+
+    /// Returns the quantity queued ahead of a simulated order at its price.
+    #[must_use]
+    pub fn queue_ahead(&self, order_id: u64) -> Option<Decimal> {
+        let key = OrderKey::Sim(order_id);
+        let location = self.locations.get(&key)?;
+        let queue = self.levels(location.side).get(&location.price)?;
+        let mut ahead = Decimal::ZERO;
+        for entry in queue {
+            if entry.key == key {
+                return Some(ahead);
+            }
+            ahead += entry.quantity;
+        }
+        None
+    }
+
+    /// Returns the total quantity resting at a price.
+    #[must_use]
+    pub fn quantity_at(&self, side: Side, price: Decimal) -> Decimal {
+        self.levels(side)
+            .get(&price)
+            .map_or(Decimal::ZERO, |queue| {
+                queue.iter().map(|e| e.quantity).sum()
+            })
+    }
+
+    /// Returns the top of the replayed book as a tick, if both sides are
+    /// present.
+    ///
+    /// Simulated orders are left out, so a strategy never sees its own
+    /// quotes as the market.
+    #[must_use]
+    pub fn to_tick(&self) -> Option<MarketTick> {
+        let (bid, bid_size) = feed_top(self.bids.iter().rev())?;
+        let (ask, ask_size) = feed_top(self.asks.iter())?;
+        Some(MarketTick::new(
+            self.last_update,
+            bid,
+            bid_size,
+            ask,
+            ask_size,
+        ))
+    }
+
+    /// Removes all orders.
+    pub fn clear(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+        self.locations.clear();
+        self.feed_orders.clear();
+        self.sim_orders.clear();
+        self.last_update = 0;
+    }
+
+    fn check_timestamp(&mut self, timestamp: u64) -> MMResult<()> {
+        if timestamp < self.last_update {
+            return Err(MMError::InvalidTimestamp(format!(
+                "{timestamp} precedes last update {}",
+                self.last_update
+            )));
+        }
+        self.last_update = timestamp;
+        Ok(())
+    }
+
+    fn validate_order(&self, price: Decimal, quantity: Decimal) -> MMResult<()> {
+        if price <= Decimal::ZERO || quantity <= Decimal::ZERO {
+            return Err(MMError::InvalidMarketState(format!(
+                "invalid order {quantity} @ {price}"
+            )));
+        }
+        to_units(price, self.config.price_precision)?;
+        to_units(quantity, self.config.quantity_precision)?;
+        Ok(())
+    }
+
+    /// Returns the best price of a simulated order that an order on `side`
+    /// limited at `price` would trade with.
+    fn crossed_sim(&self, side: Side, price: Decimal) -> Option<Decimal> {
+        let has_sim =
+            |queue: &VecDeque<QueueEntry>| queue.iter().any(|e| matches!(e.key, OrderKey::Sim(_)));
+        match side {
+            Side::Buy => self
+                .asks
+                .range(..=price)
+                .find(|(_, queue)| has_sim(queue))
+                .map(|(p, _)| *p),
+            Side::Sell => self
+                .bids
+                .range(price..)
+                .rev()
+                .find(|(_, queue)| has_sim(queue))
+                .map(|(p, _)| *p),
+        }
+    }
+
+    fn feed_order(&self, order_id: u64) -> MMResult<FeedOrder> {
+        self.feed_orders
+            .get(&order_id)
+            .copied()
+            .ok_or_else(|| MMError::InvalidMarketState(format!("unknown order {order_id}")))
+    }
+
+    fn levels(&self, side: Side) -> &Levels {
+        match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        }
+    }
+
+    fn levels_mut(&mut self, side: Side) -> &mut Levels {
+        match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        }
+    }
+
+    /// Appends an order to the back of its level.
+    fn rest(&mut self, key: OrderKey, side: Side, price: Decimal, quantity: Decimal) {
+        let ob_id = match key {
+            OrderKey::Feed(id) => OBOrderId::from_u64(id),
+            OrderKey::Sim(_) => OBOrderId::new(),
+        };
+        self.levels_mut(side)
+            .entry(price)
+            .or_default()
+            .push_back(QueueEntry { key, quantity });
+        self.locations.insert(key, Location { side, price, ob_id });
+    }
+
+    fn resting_quantity(&self, key: OrderKey) -> Decimal {
+        self.locations
+            .get(&key)
+            .and_then(|l| self.levels(l.side).get(&l.price))
+            .and_then(|queue| queue.iter().find(|e| e.key == key))
+            .map_or(Decimal::ZERO, |e| e.quantity)
+    }
+
+    /// Changes a resting order's quantity in place; zero removes it.
+    fn set_quantity(&mut self, key: OrderKey, quantity: Decimal) {
+        let Some(location) = self.locations.get(&key).copied() else {
+            return;
+        };
+        let levels = self.levels_mut(location.side);
+        if let Some(queue) = levels.get_mut(&location.price) {
+            if quantity.is_zero() {
+                queue.retain(|e| e.key != key);
+                if queue.is_empty() {
+                    levels.remove(&location.price);
+                }
+            } else if let Some(entry) = queue.iter_mut().find(|e| e.key == key) {
+                entry.quantity = quantity;
+            }
+        }
+        if quantity.is_zero() {
+            self.locations.remove(&key);
+        }
+    }
+
+    /// Matches an incoming order against the opposite side in price-time
+    /// priority and returns the unfilled quantity.
+    ///
+    /// With `sims_only`, replayed orders are skipped: the venue did not see
+    /// them cross.
+    #[allow(clippy::too_many_arguments)]
+    fn match_incoming(
+        &mut self,
+        side: Side,
+        limit: Decimal,
+        quantity: Decimal,
+        taker: Option<u64>,
+        sims_only: bool,
+        timestamp: u64,
+        fills: &mut Vec<L3Fill>,
+    ) -> Decimal {
+        let crossing: Vec<Decimal> = match side {
+            Side::Buy => self.asks.range(..=limit).map(|(p, _)| *p).collect(),
+            Side::Sell => self.bids.range(limit..).rev().map(|(p, _)| *p).collect(),
+        };
+        let mut left = quantity;
+        let mut touched = Vec::new();
+        for price in crossing {
+            if left <= Decimal::ZERO {
+                break;
+            }
+            let Some(queue) = self.levels_mut(side.opposite()).get_mut(&price) else {
+                continue;
+            };
+            for entry in queue.iter_mut() {
+                if left <= Decimal::ZERO {
+                    break;
+                }
+                if sims_only && matches!(entry.key, OrderKey::Feed(_)) {
+                    continue;
+                }
+                let traded = entry.quantity.min(left);
+                entry.quantity -= traded;
+                left -= traded;
+                touched.push((entry.key, price, traded));
+            }
+            queue.retain(|e| e.quantity > Decimal::ZERO);
+        }
+
+        for (key, price, traded) in touched {
+            if let OrderKey::Sim(id) = key {
+                self.record_fill(id, price, traded, true, timestamp, fills);
+            }
+            if let Some(id) = taker {
+                self.record_fill(id, price, traded, false, timestamp, fills);
+            }
+            let remaining = self.resting_quantity(key);
+            self.set_quantity(key, remaining);
+        }
+        left
+    }
+
+    /// Fills simulated orders resting at `price` on `side` in queue order,
+    /// up to `until` if given, and returns the unfilled quantity.
+    ///
+    /// Replayed orders are skipped: the feed reports their executions.
+    fn fill_sims(
+        &mut self,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+        until: Option<OrderKey>,
+        timestamp: u64,
+        fills: &mut Vec<L3Fill>,
+    ) -> Decimal {
+        let Some(queue) = self.levels(side).get(&price) else {
+            return quantity;
+        };
+        // Without the stopping order in the book we cannot tell who was
+        // ahead of it, so nothing fills.
+        let end = match until {
+            Some(key) => match queue.iter().position(|e| e.key == key) {
+                Some(end) => end,
+                None => return quantity,
+            },
+            None => queue.len(),
+        };
+        let mut left = quantity;
+        let mut touched = Vec::new();
+        for entry in queue.iter().take(end) {
+            if left <= Decimal::ZERO {
+                break;
+            }
+            if let OrderKey::Sim(id) = entry.key {
+                let traded = entry.quantity.min(left);
+                left -= traded;
+                touched.push((entry.key, id, entry.quantity - traded, traded));
+            }
+        }
+        for (key, id, remaining, traded) in touched {
+            self.record_fill(id, price, traded, true, timestamp, fills);
+            self.set_quantity(key, remaining);
+        }
+        left
+    }
+
+    fn record_fill(
+        &mut self,
+        id: u64,
+        price: Decimal,
+        quantity: Decimal,
+        is_maker: bool,
+        timestamp: u64,
+        fills: &mut Vec<L3Fill>,
+    ) {
+        let Some(order) = self.sim_orders.get_mut(&id) else {
+            return;
+        };
+        order.remaining -= quantity;
+        let (side, remaining) = (order.side, order.remaining);
+        if remaining <= Decimal::ZERO {
+            self.sim_orders.remove(&id);
+        }
+        fills.push(L3Fill {
+            order_id: id,
+            side,
+            price,
+            quantity,
+            remaining: remaining.max(Decimal::ZERO),
+            is_maker,
+            timestamp,
+        });
+    }
+}
+
+/// Returns the best level holding replayed volume, and that volume.
+fn feed_top<'a>(
+    mut levels: impl Iterator<Item = (&'a Decimal, &'a VecDeque<QueueEntry>)>,
+) -> Option<(Decimal, Decimal)> {
+    levels.find_map(|(&price, queue)| {
+        let size: Decimal = queue
+            .iter()
+            .filter(|e| matches!(e.key, OrderKey::Feed(_)))
+            .map(|e| e.quantity)
+            .sum();
+        (size > Decimal::ZERO).then_some((price, size))
+    })
+}
+
+fn to_units(value: Decimal, precision: u32) -> MMResult<u64> {
+    (value * Decimal::from(10u64.pow(precision)))
+        .trunc()
+        .to_u64()
+        .ok_or_else(|| MMError::NumericalError(format!("{value} is out of range")))
+}
+
+fn to_ob_side(side: Side) -> OBSide {
+    match side {
+        Side::Buy => OBSide::Buy,
+        Side::Sell => OBSide::Sell,
+    }
+}
+
+/// A simulated quote resting in the book.
+#[derive(Debug, Clone, Copy)]
+struct RestingQuote {
+    id: u64,
+    price: Decimal,
+    size: Decimal,
+}
+
+/// Backtest engine replaying market-by-order events through an [`L3Book`].
+///
+/// After each batch of events sharing a timestamp the strategy sees the top
+/// of book and may quote. A side whose price and size are unchanged keeps
+/// its order and queue position; otherwise the order is cancelled and
+/// replaced at the back of the new level. Resting fills are charged
+/// `BacktestConfig::fee_rate` and quotes that cross the book are charged the
+/// taker fee rate, which defaults to the same; the tick-based slippage model
+/// does not apply.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::{
+///     BacktestConfig, BacktestStrategy, L3BacktestEngine, L3Book, L3Event, MarketTick,
+///     SimulatedFill,
+/// };
+/// use market_maker_rs::execution::{OrderBookConnectorConfig, Side};
+/// use market_maker_rs::position::inventory::InventoryPosition;
+/// use market_maker_rs::strategy::quote::Quote;
+/// use market_maker_rs::dec;
+///
+/// struct JoinTheBid;
+///
+/// impl BacktestStrategy for JoinTheBid {
+///     fn on_tick(&mut self, tick: &MarketTick, _position: &InventoryPosition) -> Option<Quote> {
+///         Some(Quote {
+///             bid_price: tick.bid_price,
+///             bid_size: dec!(1.0),
+///             ask_price: tick.ask_price + dec!(1.0),
+///             ask_size: dec!(1.0),
+///             timestamp: tick.timestamp,
+///         })
+///     }
+///     fn on_fill(&mut self, _fill: &SimulatedFill) {}
+///     fn reset(&mut self) {}
+/// }
+///
+/// let events = vec![
+///     L3Event::add(1, 1, Side::Buy, dec!(100.0), dec!(2.0)),
+///     L3Event::add(1, 2, Side::Sell, dec!(101.0), dec!(1.0)),
+///     // Sellers take order 1: nothing reaches our bid behind it.
+///     L3Event::execute(2, 1, dec!(2.0)),
+/// ];
+///
+/// let book = L3Book::new("BTC-USD", OrderBookConnectorConfig::default());
+/// let mut engine = L3BacktestEngine::new(BacktestConfig::default(), JoinTheBid, book, events);
+/// let result = engine.run().unwrap();
+/// assert_eq!(result.num_trades, 0);
+/// ```
+#[derive(Debug)]
+pub struct L3BacktestEngine<S: BacktestStrategy> {
+    config: BacktestConfig,
+    strategy: S,
+    book: L3Book,
+    events: Vec<L3Event>,
+    ledger: BacktestLedger,
+    taker_fee_rate: Decimal,
+    bid: Option<RestingQuote>,
+    ask: Option<RestingQuote>,
+}
+
+impl<S: BacktestStrategy> L3BacktestEngine<S> {
+    /// Creates an engine replaying `events` into `book`.
+    #[must_use]
+    pub fn new(config: BacktestConfig, strategy: S, book: L3Book, events: Vec<L3Event>) -> Self {
+        let ledger = BacktestLedger::new(&config);
+        let taker_fee_rate = config.fee_rate;
+        Self {
+            config,
+            strategy,
+            book,
+            events,
+            ledger,
+            taker_fee_rate,
+            bid: None,
+            ask: None,
+        }
+    }
+
+    /// Sets the fee rate charged on fills of quotes that cross the book.
+    #[must_use]
+    pub fn with_taker_fee_rate(mut self, rate: Decimal) -> Self {
+        self.taker_fee_rate = rate;
+        self
+    }
+
+    /// Runs the backtest and returns the result.
+    ///
+    /// The events are replayed into the current book, so call
+    /// [`L3BacktestEngine::reset`] before running again.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from replaying an event or placing a quote,
+    /// including `MMError::InvalidQuoteGeneration` for a quote whose bid
+    /// and ask cross.
+    pub fn run(&mut self) -> MMResult<BacktestResult> {
+        let events = std::mem::take(&mut self.events);
+        let outcome = self.replay(&events);
+        self.events = events;
+        outcome?;
+        Ok(self.ledger.result())
+    }
+
+    fn replay(&mut self, events: &[L3Event]) -> MMResult<()> {
+        for (i, event) in events.iter().enumerate() {
+            let fills = self.book.apply(event)?;
+            self.process_fills(fills);
+
+            let batch_end = events
+                .get(i + 1)
+                .is_none_or(|next| next.timestamp != event.timestamp);
+            if !batch_end {
+                continue;
+            }
+            let Some(tick) = self.book.to_tick() else {
+                continue;
+            };
+            let quote = self.strategy.on_tick(&tick, &self.ledger.position);
+            self.update_quotes(quote.as_ref(), tick.timestamp)?;
+            self.ledger.mark(tick.timestamp, tick.mid_price());
+        }
+        Ok(())
+    }
+
+    fn update_quotes(&mut self, quote: Option<&Quote>, timestamp: u64) -> MMResult<()> {
+        let mut replace = Vec::new();
+        for side in [Side::Buy, Side::Sell] {
+            let target = quote.and_then(|q| {
+                let (price, size) = match side {
+                    Side::Buy => (q.bid_price, q.bid_size),
+                    Side::Sell => (q.ask_price, q.ask_size),
+                };
+                (price > Decimal::ZERO && size > Decimal::ZERO).then_some((price, size))
+            });
+            let resting = match side {
+                Side::Buy => self.bid,
+                Side::Sell => self.ask,
+            };
+            if let Some(r) = resting
+                && self.book.open_quantity(r.id).is_some()
+                && target == Some((r.price, r.size))
+            {
+                continue;
+            }
+            if let Some(r) = resting {
+                self.book.cancel(r.id);
+            }
+            replace.push((side, target));
+        }
+        // Stale orders on both sides are gone before either side is placed,
+        // so only a quote crossing itself is rejected by the book.
+        for (side, target) in replace {
+            let placed = match target {
+                Some((price, size)) => {
+                    let (id, fills) = self.book.place(side, price, size, timestamp)?;
+                    self.process_fills(fills);
+                    Some(RestingQuote { id, price, size })
+                }
+                None => None,
+            };
+            match side {
+                Side::Buy => self.bid = placed,
+                Side::Sell => self.ask = placed,
+            }
+        }
+        Ok(())
+    }
+
+    fn process_fills(&mut self, fills: Vec<L3Fill>) {
+        for fill in fills {
+            let rate = if fill.is_maker {
+                self.config.fee_rate
+            } else {
+                self.taker_fee_rate
+            };
+            let fee = fill.price * fill.quantity * rate;
+            let fill =
+                SimulatedFill::with_fee(fill.side, fill.price, fill.quantity, fill.timestamp, fee);
+            self.strategy.on_fill(&fill);
+            self.ledger.record_fill(fill);
+        }
+    }
+
+    /// Returns the book.
+    #[must_use]
+    pub fn book(&self) -> &L3Book {
+        &self.book
+    }
+
+    /// Returns the current state (position and PnL).
+    #[must_use]
+    pub fn get_state(&self) -> (&InventoryPosition, &PnL) {
+        (&self.ledger.position, &self.ledger.pnl)
+    }
+
+    /// Returns a reference to the strategy.
+    #[must_use]
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Returns a mutable reference to the strategy.
+    pub fn strategy_mut(&mut self) -> &mut S {
+        &mut self.strategy
+    }
+
+    /// Resets the engine for another run.
+    pub fn reset(&mut self) {
+        self.book.clear();
+        self.strategy.reset();
+        self.ledger = BacktestLedger::new(&self.config);
+        self.bid = None;
+        self.ask = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+
+    fn book() -> L3Book {
+        let mut book = L3Book::new("TEST", OrderBookConnectorConfig::default());
+        book.apply(&L3Event::add(1, 1, Side::Buy, dec!(100.0), dec!(2.0)))
+            .unwrap();
+        book.apply(&L3Event::add(1, 2, Side::Sell, dec!(101.0), dec!(3.0)))
+            .unwrap();
+        book
+    }
+
+    #[test]
+    fn test_fills_only_after_queue_ahead_trades() {
+        let mut book = book();
+        let (id, _) = book.place(Side::Buy, dec!(100.0), dec!(1.0), 2).unwrap();
+        book.apply(&L3Event::add(3, 3, Side::Buy, dec!(100.0), dec!(5.0)))
+            .unwrap();
+        assert_eq!(book.queue_ahead(id), Some(dec!(2.0)));
+        // The exported book holds every resting order.
+        let snapshot = book.order_book().unwrap().create_snapshot(1);
+        assert_eq!(snapshot.bids[0].price, 10_000);
+        assert_eq!(snapshot.bids[0].visible_quantity, 800_000_000);
+
+        assert!(
+            book.apply(&L3Event::execute(4, 1, dec!(2.0)))
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(book.queue_ahead(id), Some(Decimal::ZERO));
+
+        // Order 3 trades, but we are ahead of it and fill first.
+        let fills = book.apply(&L3Event::execute(5, 3, dec!(1.5))).unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].order_id, id);
+        assert_eq!(fills[0].quantity, dec!(1.0));
+        assert!(fills[0].is_maker);
+        assert_eq!(book.open_quantity(id), None);
+
+        // Order 3 keeps the volume we absorbed until the feed retires it.
+        let tick = book.to_tick().unwrap();
+        assert_eq!(tick.bid_size, dec!(4.5));
+        book.apply(&L3Event::execute(6, 3, dec!(3.5))).unwrap();
+        assert!(book.to_tick().is_none());
+    }
+
+    #[test]
+    fn test_execute_fills_only_ahead_of_executed_order() {
+        let mut book = book();
+        let (ahead, _) = book.place(Side::Buy, dec!(100.0), dec!(1.0), 2).unwrap();
+        book.apply(&L3Event::add(3, 3, Side::Buy, dec!(100.0), dec!(5.0)))
+            .unwrap();
+        let (behind, _) = book.place(Side::Buy, dec!(100.0), dec!(1.0), 4).unwrap();
+
+        // Order 3 trades while order 1 is still at the head: order 1 keeps
+        // its size, we fill ahead of order 3 and not behind it.
+        let fills = book.apply(&L3Event::execute(5, 3, dec!(1.5))).unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].order_id, ahead);
+        assert_eq!(fills[0].quantity, dec!(1.0));
+        assert_eq!(book.open_quantity(behind), Some(dec!(1.0)));
+        assert_eq!(book.queue_ahead(behind), Some(dec!(6.5)));
+
+        // An execution beyond order 3's remaining size does not spill over.
+        let fills = book.apply(&L3Event::execute(6, 3, dec!(10.0))).unwrap();
+        assert!(fills.is_empty());
+        assert_eq!(book.open_quantity(behind), Some(dec!(1.0)));
+        assert_eq!(book.queue_ahead(behind), Some(dec!(2.0)));
+        assert!(matches!(
+            book.apply(&L3Event::execute(7, 3, dec!(1.0))),
+            Err(MMError::InvalidMarketState(_))
+        ));
+    }
+
+    #[test]
+    fn test_trade_through_and_crossing() {
+        let mut book = book();
+        let (bid, _) = book.place(Side::Buy, dec!(100.5), dec!(1.0), 2).unwrap();
+
+        // The venue had no bid at 100.5, so this sell rested; we would have
+        // been hit.
+        let fills = book
+            .apply(&L3Event::add(3, 3, Side::Sell, dec!(100.5), dec!(0.4)))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert!(fills[0].is_maker);
+        assert_eq!(book.open_quantity(bid), Some(dec!(0.6)));
+        assert_eq!(book.quantity_at(Side::Sell, dec!(100.5)), Decimal::ZERO);
+        book.apply(&L3Event::cancel(4, 3)).unwrap();
+        assert!(book.cancel(bid));
+        assert!(!book.cancel(bid));
+
+        // Our sell crossing the bids takes liquidity and rests the rest.
+        let (ask, fills) = book.place(Side::Sell, dec!(100.0), dec!(3.0), 5).unwrap();
+        assert_eq!(fills.len(), 1);
+        assert!(!fills[0].is_maker);
+        assert_eq!(fills[0].quantity, dec!(2.0));
+        assert_eq!(book.open_quantity(ask), Some(dec!(1.0)));
+
+        // A buyer executing the 101 ask trades through our 100 ask first.
+        let fills = book.apply(&L3Event::execute(6, 2, dec!(1.5))).unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, dec!(100.0));
+        assert_eq!(book.open_quantity(ask), None);
+        assert_eq!(book.quantity_at(Side::Sell, dec!(101.0)), dec!(2.5));
+    }
+
+    #[test]
+    fn test_modify_priority_and_validation() {
+        let mut book = book();
+        let (id, _) = book.place(Side::Buy, dec!(100.0), dec!(1.0), 2).unwrap();
+        book.apply(&L3Event::modify(3, 1, dec!(1.5))).unwrap();
+        assert_eq!(book.queue_ahead(id), Some(dec!(1.5)));
+        book.apply(&L3Event::modify(3, 1, dec!(2.5))).unwrap();
+        assert_eq!(book.queue_ahead(id), Some(Decimal::ZERO));
+        assert_eq!(book.quantity_at(Side::Buy, dec!(100.0)), dec!(3.5));
+        book.apply(&L3Event::modify(4, 1, dec!(0.0))).unwrap();
+
+        assert!(matches!(
+            book.apply(&L3Event::cancel(5, 1)),
+            Err(MMError::InvalidMarketState(_))
+        ));
+        assert!(matches!(
+            book.apply(&L3Event::add(5, 2, Side::Sell, dec!(102.0), dec!(1.0))),
+            Err(MMError::InvalidMarketState(_))
+        ));
+        assert!(matches!(
+            book.apply(&L3Event::add(1, 9, Side::Sell, dec!(102.0), dec!(1.0))),
+            Err(MMError::InvalidTimestamp(_))
+        ));
+        assert!(book.place(Side::Buy, dec!(100.0), dec!(0.0), 6).is_err());
+
+        book.clear();
+        assert!(book.to_tick().is_none());
+        assert_eq!(book.open_quantity(id), None);
+    }
+
+    struct FixedQuote {
+        bid_price: Decimal,
+        fills: Vec<SimulatedFill>,
+    }
+
+    impl FixedQuote {
+        fn new(bid_price: Decimal) -> Self {
+            Self {
+                bid_price,
+                fills: Vec::new(),
+            }
+        }
+    }
+
+    impl BacktestStrategy for FixedQuote {
+        fn on_tick(&mut self, tick: &MarketTick, _position: &InventoryPosition) -> Option<Quote> {
+            Some(Quote {
+                bid_price: self.bid_price,
+                bid_size: dec!(1.0),
+                ask_price: dec!(105.0),
+                ask_size: dec!(1.0),
+                timestamp: tick.timestamp,
+            })
+        }
+        fn on_fill(&mut self, fill: &SimulatedFill) {
+            self.fills.push(fill.clone());
+        }
+        fn reset(&mut self) {
+            self.fills.clear();
+        }
+    }
+
+    #[test]
+    fn test_engine_keeps_queue_priority() {
+        let events = vec![
+            L3Event::add(1, 1, Side::Buy, dec!(100.0), dec!(2.0)),
+            L3Event::add(1, 2, Side::Sell, dec!(101.0), dec!(3.0)),
+            L3Event::add(2, 3, Side::Buy, dec!(100.0), dec!(5.0)),
+            L3Event::execute(3, 1, dec!(2.0)),
+            L3Event::execute(4, 3, dec!(1.0)),
+            L3Event::add(5, 4, Side::Buy, dec!(99.0), dec!(1.0)),
+        ];
+        let config = BacktestConfig::default().with_fee_rate(dec!(0.001));
+        let mut engine = L3BacktestEngine::new(
+            config,
+            FixedQuote::new(dec!(100.0)),
+            L3Book::new("TEST", OrderBookConnectorConfig::default()),
+            events,
+        );
+        let result = engine.run().unwrap();
+
+        assert_eq!(result.num_ticks, 5);
+        assert_eq!(result.num_trades, 1);
+        assert_eq!(result.trades[0].timestamp, 4);
+        assert_eq!(result.trades[0].fee, dec!(0.1));
+        assert_eq!(result.final_position, dec!(1.0));
+        assert_eq!(engine.strategy().fills.len(), 1);
+
+        engine.reset();
+        assert!(engine.strategy().fills.is_empty());
+        assert_eq!(engine.run().unwrap().num_trades, 1);
+    }
+
+    #[test]
+    fn test_engine_charges_taker_fee_on_crossing_quotes() {
+        let events = vec![
+            L3Event::add(1, 1, Side::Buy, dec!(100.0), dec!(2.0)),
+            L3Event::add(1, 2, Side::Sell, dec!(101.0), dec!(1.0)),
+        ];
+        let config = BacktestConfig::default().with_fee_rate(dec!(0.001));
+        let mut engine = L3BacktestEngine::new(
+            config,
+            FixedQuote::new(dec!(101.0)),
+            L3Book::new("TEST", OrderBookConnectorConfig::default()),
+            events,
+        )
+        .with_taker_fee_rate(dec!(0.002));
+        let result = engine.run().unwrap();
+
+        assert_eq!(result.num_trades, 1);
+        assert_eq!(result.trades[0].price, dec!(101.0));
+        assert_eq!(result.trades[0].fee, dec!(0.202));
+    }
+
+    #[test]
+    fn test_exported_book_keeps_queue_order() {
+        let mut book = book();
+        book.place(Side::Buy, dec!(100.0), dec!(1.0), 2).unwrap();
+        book.apply(&L3Event::add(3, 3, Side::Buy, dec!(100.0), dec!(5.0)))
+            .unwrap();
+        // A size decrease keeps order 1 at the head here and in the export.
+        book.apply(&L3Event::modify(4, 1, dec!(1.5))).unwrap();
+
+        // A sell sweeping the exported level trades in replay order.
+        let connector = book.connector().unwrap();
+        let result = connector
+            .order_book()
+            .match_market_order(OBOrderId::new(), 750_000_000, OBSide::Sell)
+            .unwrap();
+        let makers: Vec<OBOrderId> = result
+            .transactions
+            .transactions
+            .iter()
+            .map(|t| t.maker_order_id)
+            .collect();
+        assert_eq!(makers.len(), 3);
+        assert_eq!(makers[0], OBOrderId::from_u64(1));
+        assert_eq!(makers[2], OBOrderId::from_u64(3));
+        // The export is a copy.
+        assert_eq!(book.quantity_at(Side::Buy, dec!(100.0)), dec!(7.5));
+
+        // The venue saw no cross: this sell fills our bid and rests through
+        // the replayed bids, which the export refuses to let orderbook-rs
+        // match.
+        book.apply(&L3Event::add(5, 4, Side::Sell, dec!(99.0), dec!(2.0)))
+            .unwrap();
+        assert!(matches!(
+            book.order_book(),
+            Err(MMError::InvalidMarketState(_))
+        ));
+    }
+
+    struct ImproveTouch {
+        ticks: Vec<MarketTick>,
+    }
+
+    impl BacktestStrategy for ImproveTouch {
+        fn on_tick(&mut self, tick: &MarketTick, _position: &InventoryPosition) -> Option<Quote> {
+            self.ticks.push(tick.clone());
+            Some(Quote {
+                bid_price: tick.bid_price + dec!(0.01),
+                bid_size: dec!(1.0),
+                ask_price: tick.ask_price + dec!(1.0),
+                ask_size: dec!(1.0),
+                timestamp: tick.timestamp,
+            })
+        }
+        fn on_fill(&mut self, _fill: &SimulatedFill) {}
+        fn reset(&mut self) {
+            self.ticks.clear();
+        }
+    }
+
+    #[test]
+    fn test_engine_ticks_exclude_own_quotes() {
+        let events = vec![
+            L3Event::add(1, 1, Side::Buy, dec!(100.0), dec!(2.0)),
+            L3Event::add(1, 2, Side::Sell, dec!(101.0), dec!(3.0)),
+            L3Event::add(2, 3, Side::Buy, dec!(99.0), dec!(1.0)),
+            L3Event::add(3, 4, Side::Buy, dec!(98.0), dec!(1.0)),
+        ];
+        let mut engine = L3BacktestEngine::new(
+            BacktestConfig::default(),
+            ImproveTouch { ticks: Vec::new() },
+            L3Book::new("TEST", OrderBookConnectorConfig::default()),
+            events,
+        );
+        let result = engine.run().unwrap();
+
+        assert_eq!(result.num_trades, 0);
+        let ticks = &engine.strategy().ticks;
+        assert_eq!(ticks.len(), 3);
+        for tick in ticks {
+            assert_eq!(tick.bid_price, dec!(100.0));
+            assert_eq!(tick.bid_size, dec!(2.0));
+            assert_eq!(tick.ask_price, dec!(101.0));
+        }
+        // Our bid improves the touch once and stays there.
+        assert_eq!(
+            engine.book().quantity_at(Side::Buy, dec!(100.01)),
+            dec!(1.0)
+        );
+        assert_eq!(
+            engine.book().quantity_at(Side::Buy, dec!(100.02)),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_engine_rejects_self_crossing_quote() {
+        let events = vec![
+            L3Event::add(1, 1, Side::Buy, dec!(100.0), dec!(2.0)),
+            L3Event::add(1, 2, Side::Sell, dec!(107.0), dec!(3.0)),
+        ];
+        let mut engine = L3BacktestEngine::new(
+            BacktestConfig::default(),
+            FixedQuote::new(dec!(106.0)),
+            L3Book::new("TEST", OrderBookConnectorConfig::default()),
+            events,
+        );
+        assert!(matches!(
+            engine.run(),
+            Err(MMError::InvalidQuoteGeneration(_))
+        ));
+        assert!(engine.strategy().fills.is_empty());
+    }
+}
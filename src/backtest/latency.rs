@@ -0,0 +1,499 @@
+//! Order and market-data latency simulation for backtesting.
+//!
+//! Without latency the backtest engine applies a quote on the same tick that
+//! produced it. With a [`LatencyConfig`] three delays are modelled:
+//!
+//! - **Market data**: a tick reaches the strategy only after the feed latency
+//! - **Order entry**: a new quote becomes live at the venue after the entry latency
+//! - **Cancel**: a replaced or withdrawn quote stays live until its cancel arrives
+//!
+//! Until a cancel lands the previous quote can still be filled, so stale-quote
+//! adverse selection shows up in the backtest results.
+//!
+//! Each delay follows a [`LatencyModel`]: fixed, uniform, normal, or drawn
+//! from an empirical [`Histogram`] such as one recorded by a live
+//! `LatencyTracker`. Latencies are in microseconds, like the rest of the
+//! execution latency tooling, and sampling is seeded for reproducibility.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::backtest::{BacktestConfig, LatencyConfig, LatencyModel};
+//! use market_maker_rs::execution::Histogram;
+//!
+//! let mut recorded = Histogram::new(100, 50);
+//! for value in [800, 950, 1_200, 4_000] {
+//!     recorded.record(value);
+//! }
+//!
+//! let latency = LatencyConfig::new()
+//!     .with_market_data(LatencyModel::Fixed(200))
+//!     .with_order_entry(LatencyModel::Empirical(recorded))
+//!     .with_cancel(LatencyModel::Normal {
+//!         mean_us: 1_500,
+//!         std_dev_us: 300,
+//!     })
+//!     .with_seed(7);
+//!
+//! let config = BacktestConfig::default().with_latency(latency);
+//! assert!(!config.latency.is_zero());
+//! ```
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::execution::Histogram;
+use crate::strategy::quote::Quote;
+use crate::types::numerics::Lcg;
+
+use super::data::MarketTick;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Distribution of a single latency, in microseconds.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::LatencyModel;
+///
+/// let model = LatencyModel::Uniform {
+///     min_us: 500,
+///     max_us: 1_500,
+/// };
+/// assert_eq!(model.mean_us(), 1_000);
+/// assert!(LatencyModel::None.is_zero());
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LatencyModel {
+    /// No latency.
+    #[default]
+    None,
+    /// Constant latency.
+    Fixed(u64),
+    /// Uniformly distributed latency in `[min_us, max_us]`.
+    Uniform {
+        /// Lower bound.
+        min_us: u64,
+        /// Upper bound.
+        max_us: u64,
+    },
+    /// Normally distributed latency, truncated at zero.
+    Normal {
+        /// Mean latency.
+        mean_us: u64,
+        /// Standard deviation.
+        std_dev_us: u64,
+    },
+    /// Latency drawn from an empirical histogram.
+    ///
+    /// A bucket is chosen in proportion to its count and the value is
+    /// uniform within it. Overflow samples map to the histogram's maximum
+    /// trackable value. An empty histogram yields zero latency.
+    Empirical(Histogram),
+}
+
+impl LatencyModel {
+    /// Returns true if the model never produces a delay.
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        match self {
+            LatencyModel::None => true,
+            LatencyModel::Fixed(latency_us) => *latency_us == 0,
+            LatencyModel::Uniform { min_us, max_us } => *min_us == 0 && *max_us == 0,
+            LatencyModel::Normal {
+                mean_us,
+                std_dev_us,
+            } => *mean_us == 0 && *std_dev_us == 0,
+            LatencyModel::Empirical(histogram) => histogram.total_count() == 0,
+        }
+    }
+
+    /// Returns the mean latency of the model.
+    ///
+    /// For the normal model this ignores the truncation at zero; for the
+    /// empirical model buckets are taken at their midpoints.
+    #[must_use]
+    pub fn mean_us(&self) -> u64 {
+        match self {
+            LatencyModel::None => 0,
+            LatencyModel::Fixed(latency_us) => *latency_us,
+            LatencyModel::Uniform { min_us, max_us } => {
+                let (low, high) = ordered(*min_us, *max_us);
+                low + (high - low) / 2
+            }
+            LatencyModel::Normal { mean_us, .. } => *mean_us,
+            LatencyModel::Empirical(histogram) => {
+                if histogram.total_count() == 0 {
+                    return 0;
+                }
+                let bucket = histogram.bucket_size_us();
+                let in_buckets: u128 = histogram
+                    .get_buckets()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &count)| u128::from(count) * (i as u128 * 2 + 1) * bucket as u128)
+                    .sum::<u128>()
+                    / 2;
+                let overflow =
+                    u128::from(histogram.overflow_count()) * histogram.max_trackable_us() as u128;
+                ((in_buckets + overflow) / u128::from(histogram.total_count())) as u64
+            }
+        }
+    }
+
+    /// Draws one latency sample.
+    pub(crate) fn sample(&self, rng: &mut Lcg) -> u64 {
+        match self {
+            LatencyModel::None => 0,
+            LatencyModel::Fixed(latency_us) => *latency_us,
+            LatencyModel::Uniform { min_us, max_us } => {
+                let (low, high) = ordered(*min_us, *max_us);
+                low + (rng.uniform() * (high - low + 1) as f64) as u64
+            }
+            LatencyModel::Normal {
+                mean_us,
+                std_dev_us,
+            } => {
+                let value = *mean_us as f64 + rng.gaussian() * *std_dev_us as f64;
+                value.max(0.0).round() as u64
+            }
+            LatencyModel::Empirical(histogram) => sample_histogram(histogram, rng),
+        }
+    }
+}
+
+fn ordered(a: u64, b: u64) -> (u64, u64) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+fn sample_histogram(histogram: &Histogram, rng: &mut Lcg) -> u64 {
+    let total = histogram.total_count();
+    if total == 0 {
+        return 0;
+    }
+
+    let target = ((rng.uniform() * total as f64) as u64).min(total - 1);
+    let bucket_size = histogram.bucket_size_us();
+    let mut cumulative = 0u64;
+    for (i, &count) in histogram.get_buckets().iter().enumerate() {
+        cumulative += count;
+        if target < cumulative {
+            let offset = (rng.uniform() * bucket_size as f64) as u64;
+            return i as u64 * bucket_size + offset.min(bucket_size.saturating_sub(1));
+        }
+    }
+
+    histogram.max_trackable_us()
+}
+
+/// Latency settings for a backtest.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::{LatencyConfig, LatencyModel};
+///
+/// let config = LatencyConfig::new().with_order_entry(LatencyModel::Fixed(1_000));
+/// assert!(!config.is_zero());
+/// assert!(LatencyConfig::default().is_zero());
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LatencyConfig {
+    /// Delay between sending a quote and it resting at the venue.
+    pub order_entry: LatencyModel,
+    /// Delay between sending a cancel and it taking effect at the venue.
+    pub cancel: LatencyModel,
+    /// Delay between a tick happening and the strategy seeing it.
+    pub market_data: LatencyModel,
+    /// Seed for latency sampling.
+    pub seed: u64,
+}
+
+impl LatencyConfig {
+    /// Creates a configuration without latency.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the order-entry latency model.
+    #[must_use]
+    pub fn with_order_entry(mut self, model: LatencyModel) -> Self {
+        self.order_entry = model;
+        self
+    }
+
+    /// Sets the cancel latency model.
+    #[must_use]
+    pub fn with_cancel(mut self, model: LatencyModel) -> Self {
+        self.cancel = model;
+        self
+    }
+
+    /// Sets the market-data latency model.
+    #[must_use]
+    pub fn with_market_data(mut self, model: LatencyModel) -> Self {
+        self.market_data = model;
+        self
+    }
+
+    /// Sets the sampling seed.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns true if none of the models produce a delay.
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.order_entry.is_zero() && self.cancel.is_zero() && self.market_data.is_zero()
+    }
+}
+
+/// Quote resting at the simulated venue.
+#[derive(Debug, Clone)]
+pub(crate) struct LiveQuote {
+    pub(crate) quote: Quote,
+    /// Whether the bid side can still be filled.
+    pub(crate) bid_open: bool,
+    /// Whether the ask side can still be filled.
+    pub(crate) ask_open: bool,
+    id: u64,
+}
+
+#[derive(Debug, Clone)]
+enum VenueAction {
+    Place(u64, Quote),
+    Cancel(u64),
+}
+
+/// Delays market data towards the strategy and orders towards the venue.
+///
+/// Times are in microseconds. Each quote is its own order: a new decision
+/// cancels every order sent before it and, if it carries a quote, places a
+/// new one. Orders stay live until their cancel arrives, and a cancel that
+/// overtakes its order stops the order from ever resting.
+#[derive(Debug, Clone)]
+pub(crate) struct LatencySimulator {
+    config: LatencyConfig,
+    rng: Lcg,
+    feed: VecDeque<(u64, MarketTick)>,
+    last_delivery_us: u64,
+    in_flight: BTreeMap<(u64, u64), VenueAction>,
+    sequence: u64,
+    working: Vec<u64>,
+    live: Vec<LiveQuote>,
+    next_order_id: u64,
+}
+
+impl LatencySimulator {
+    pub(crate) fn new(config: LatencyConfig) -> Self {
+        let rng = Lcg::new(config.seed);
+        Self {
+            config,
+            rng,
+            feed: VecDeque::new(),
+            last_delivery_us: 0,
+            in_flight: BTreeMap::new(),
+            sequence: 0,
+            working: Vec::new(),
+            live: Vec::new(),
+            next_order_id: 0,
+        }
+    }
+
+    /// Queues a tick observed at the venue for delivery to the strategy.
+    ///
+    /// The feed is FIFO: a tick is never delivered before an earlier one.
+    pub(crate) fn publish(&mut self, tick: MarketTick) {
+        let happened_us = tick.timestamp.saturating_mul(1000);
+        let delivery_us = happened_us
+            .saturating_add(self.config.market_data.sample(&mut self.rng))
+            .max(self.last_delivery_us);
+        self.last_delivery_us = delivery_us;
+        self.feed.push_back((delivery_us, tick));
+    }
+
+    /// Returns when the oldest undelivered tick reaches the strategy.
+    pub(crate) fn next_delivery_us(&self) -> Option<u64> {
+        self.feed.front().map(|(delivery_us, _)| *delivery_us)
+    }
+
+    /// Pops the next tick the strategy has received by `now_us`.
+    pub(crate) fn next_delivery(&mut self, now_us: u64) -> Option<(u64, MarketTick)> {
+        match self.feed.front() {
+            Some((delivery_us, _)) if *delivery_us <= now_us => self.feed.pop_front(),
+            _ => None,
+        }
+    }
+
+    /// Sends the strategy's decision taken at `decided_us` towards the venue.
+    pub(crate) fn submit(&mut self, decided_us: u64, quote: Option<Quote>) {
+        for id in std::mem::take(&mut self.working) {
+            let arrival_us = decided_us.saturating_add(self.config.cancel.sample(&mut self.rng));
+            self.send(arrival_us, VenueAction::Cancel(id));
+        }
+
+        if let Some(quote) = quote {
+            let id = self.next_order_id;
+            self.next_order_id += 1;
+            let arrival_us =
+                decided_us.saturating_add(self.config.order_entry.sample(&mut self.rng));
+            self.send(arrival_us, VenueAction::Place(id, quote));
+            self.working.push(id);
+        }
+    }
+
+    /// Samples the order-entry latency for an order sent outside `submit`.
+    pub(crate) fn order_entry_delay(&mut self) -> u64 {
+        self.config.order_entry.sample(&mut self.rng)
+    }
+
+    fn send(&mut self, arrival_us: u64, action: VenueAction) {
+        self.in_flight.insert((arrival_us, self.sequence), action);
+        self.sequence += 1;
+    }
+
+    /// Applies every order and cancel that has reached the venue by `now_us`.
+    pub(crate) fn process_arrivals(&mut self, now_us: u64) {
+        while let Some(entry) = self.in_flight.first_entry() {
+            if entry.key().0 > now_us {
+                break;
+            }
+            match entry.remove() {
+                VenueAction::Place(id, quote) => self.live.push(LiveQuote {
+                    quote,
+                    bid_open: true,
+                    ask_open: true,
+                    id,
+                }),
+                VenueAction::Cancel(id) => {
+                    let before = self.live.len();
+                    self.live.retain(|live| live.id != id);
+                    if self.live.len() == before {
+                        self.in_flight.retain(
+                            |_, action| !matches!(action, VenueAction::Place(placed, _) if *placed == id),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the quotes currently resting at the venue.
+    pub(crate) fn live_mut(&mut self) -> &mut Vec<LiveQuote> {
+        &mut self.live
+    }
+
+    /// Drops quotes with no open side left.
+    pub(crate) fn prune(&mut self) {
+        self.live.retain(|live| live.bid_open || live.ask_open);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+
+    fn quote(bid: rust_decimal::Decimal, ask: rust_decimal::Decimal) -> Quote {
+        Quote {
+            bid_price: bid,
+            bid_size: dec!(1.0),
+            ask_price: ask,
+            ask_size: dec!(1.0),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_model_samples_stay_in_range() {
+        let mut rng = Lcg::new(3);
+        let uniform = LatencyModel::Uniform {
+            min_us: 1_500,
+            max_us: 500,
+        };
+        let normal = LatencyModel::Normal {
+            mean_us: 100,
+            std_dev_us: 1_000,
+        };
+        for _ in 0..1_000 {
+            let value = uniform.sample(&mut rng);
+            assert!((500..=1_500).contains(&value));
+            // Truncated at zero rather than wrapping.
+            assert!(normal.sample(&mut rng) < 100_000);
+        }
+        assert_eq!(LatencyModel::Fixed(42).sample(&mut rng), 42);
+        assert!(LatencyModel::Fixed(0).is_zero());
+    }
+
+    #[test]
+    fn test_empirical_model_follows_histogram() {
+        let mut histogram = Histogram::new(100, 10);
+        for _ in 0..3 {
+            histogram.record(250);
+        }
+        histogram.record(5_000);
+        let model = LatencyModel::Empirical(histogram);
+        assert_eq!(model.mean_us(), (3 * 250 + 1_000) / 4);
+
+        let mut rng = Lcg::new(11);
+        let mut in_bucket = 0;
+        for _ in 0..4_000 {
+            match model.sample(&mut rng) {
+                200..=299 => in_bucket += 1,
+                1_000 => {}
+                other => panic!("unexpected sample {other}"),
+            }
+        }
+        assert!((2_800..3_200).contains(&in_bucket));
+
+        assert_eq!(
+            LatencyModel::Empirical(Histogram::new(100, 10)).sample(&mut rng),
+            0
+        );
+    }
+
+    #[test]
+    fn test_simulator_delays_orders_and_cancels() {
+        let config = LatencyConfig::new()
+            .with_order_entry(LatencyModel::Fixed(3_000))
+            .with_cancel(LatencyModel::Fixed(1_000))
+            .with_market_data(LatencyModel::Fixed(500));
+        let mut sim = LatencySimulator::new(config);
+
+        sim.publish(MarketTick::new(
+            1,
+            dec!(99.0),
+            dec!(1.0),
+            dec!(101.0),
+            dec!(1.0),
+        ));
+        assert!(sim.next_delivery(1_000).is_none());
+        let (seen_us, _) = sim.next_delivery(1_500).unwrap();
+        assert_eq!(seen_us, 1_500);
+
+        sim.submit(seen_us, Some(quote(dec!(99.0), dec!(101.0))));
+        sim.process_arrivals(4_000);
+        assert!(sim.live_mut().is_empty());
+        sim.process_arrivals(4_500);
+        assert_eq!(sim.live_mut().len(), 1);
+
+        // The replacement arrives after the cancel of the old quote.
+        sim.submit(5_000, Some(quote(dec!(98.0), dec!(102.0))));
+        sim.process_arrivals(6_000);
+        assert!(sim.live_mut().is_empty());
+        sim.process_arrivals(8_000);
+        assert_eq!(sim.live_mut()[0].quote.bid_price, dec!(98.0));
+
+        // A cancel overtaking its order keeps the order from resting.
+        sim.submit(9_000, Some(quote(dec!(97.0), dec!(103.0))));
+        sim.submit(9_100, None);
+        sim.process_arrivals(20_000);
+        assert!(sim.live_mut().is_empty());
+    }
+}
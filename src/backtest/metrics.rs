@@ -44,7 +44,10 @@
 //! println!("Sharpe ratio: {}", metrics.sharpe_ratio);
 //! ```
 
+use std::collections::VecDeque;
+
 use crate::Decimal;
+use crate::backtest::engine::{BacktestResult, SimulatedFill};
 use crate::execution::Side;
 use crate::types::error::{MMError, MMResult};
 
@@ -186,6 +189,99 @@ impl TradeRecord {
         }
         (self.net_pnl() / notional) * Decimal::ONE_HUNDRED
     }
+
+    /// Pairs simulated fills into round-trip trades, first in first out.
+    ///
+    /// Each fill first closes open lots on the opposite side, oldest first;
+    /// any remaining quantity opens a new lot. Fees are apportioned to each
+    /// round trip pro rata by quantity. Lots still open at the end of the
+    /// sequence produce no record.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use market_maker_rs::backtest::{SimulatedFill, TradeRecord};
+    /// use market_maker_rs::execution::Side;
+    /// use market_maker_rs::dec;
+    ///
+    /// let fills = vec![
+    ///     SimulatedFill::new(Side::Buy, dec!(100.0), dec!(2.0), 1000),
+    ///     SimulatedFill::new(Side::Sell, dec!(101.0), dec!(1.0), 2000),
+    /// ];
+    ///
+    /// let trades = TradeRecord::from_fills(&fills);
+    /// assert_eq!(trades.len(), 1);
+    /// assert_eq!(trades[0].pnl, dec!(1.0));
+    /// ```
+    #[must_use]
+    pub fn from_fills(fills: &[SimulatedFill]) -> Vec<TradeRecord> {
+        struct Lot {
+            side: Side,
+            price: Decimal,
+            quantity: Decimal,
+            timestamp: u64,
+            fee_per_unit: Decimal,
+        }
+
+        let fee_per_unit = |fill: &SimulatedFill| {
+            if fill.quantity > Decimal::ZERO {
+                fill.fee / fill.quantity
+            } else {
+                Decimal::ZERO
+            }
+        };
+
+        let mut open: VecDeque<Lot> = VecDeque::new();
+        let mut trades = Vec::new();
+
+        for fill in fills {
+            let exit_fee = fee_per_unit(fill);
+            let mut remaining = fill.quantity;
+
+            while remaining > Decimal::ZERO {
+                let Some(lot) = open.front_mut() else {
+                    break;
+                };
+                if lot.side == fill.side {
+                    break;
+                }
+
+                let matched = remaining.min(lot.quantity);
+                let pnl = match lot.side {
+                    Side::Buy => (fill.price - lot.price) * matched,
+                    Side::Sell => (lot.price - fill.price) * matched,
+                };
+                trades.push(TradeRecord::new(
+                    lot.timestamp,
+                    fill.timestamp,
+                    lot.side,
+                    lot.price,
+                    fill.price,
+                    matched,
+                    pnl,
+                    (lot.fee_per_unit + exit_fee) * matched,
+                ));
+
+                lot.quantity -= matched;
+                remaining -= matched;
+                if lot.quantity <= Decimal::ZERO {
+                    open.pop_front();
+                }
+            }
+
+            if remaining > Decimal::ZERO {
+                open.push_back(Lot {
+                    side: fill.side,
+                    price: fill.price,
+                    quantity: remaining,
+                    timestamp: fill.timestamp,
+                    fee_per_unit: exit_fee,
+                });
+            }
+        }
+
+        trades
+    }
 }
 
 /// Configuration for metrics calculation.
@@ -489,6 +585,29 @@ impl MetricsCalculator {
         })
     }
 
+    /// Calculates all performance metrics from a [`BacktestResult`].
+    ///
+    /// The engine's equity curve becomes the metrics equity curve and its
+    /// fills are paired into round trips with [`TradeRecord::from_fills`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the result has no equity curve, e.g. because the
+    /// backtest ran with `record_equity_curve` disabled or on no data.
+    pub fn calculate_from_backtest(
+        &self,
+        result: &BacktestResult,
+        initial_capital: Decimal,
+    ) -> MMResult<PerformanceMetrics> {
+        let equity_curve: Vec<EquityPoint> = result
+            .equity_curve
+            .iter()
+            .map(|&(timestamp, equity)| EquityPoint::new(timestamp, equity))
+            .collect();
+        let trades = TradeRecord::from_fills(&result.trades);
+        self.calculate(&equity_curve, &trades, initial_capital)
+    }
+
     /// Calculates period returns from equity curve.
     ///
     /// Returns are calculated as: (equity\[i\] - equity\[i-1\]) / equity\[i-1\]
@@ -1331,6 +1450,79 @@ mod tests {
         assert!((result - dec!(2.718281828)).abs() < dec!(0.01));
     }
 
+    #[test]
+    fn test_trade_record_from_fills_fifo() {
+        let fills = vec![
+            SimulatedFill::with_fee(Side::Buy, dec!(100.0), dec!(1.0), 1000, dec!(0.2)),
+            SimulatedFill::with_fee(Side::Buy, dec!(102.0), dec!(1.0), 2000, dec!(0.2)),
+            SimulatedFill::with_fee(Side::Sell, dec!(103.0), dec!(1.5), 3000, dec!(0.3)),
+            SimulatedFill::new(Side::Sell, dec!(99.0), dec!(1.0), 4000),
+        ];
+
+        let trades = TradeRecord::from_fills(&fills);
+        assert_eq!(trades.len(), 3);
+
+        // Oldest long lot closes first
+        assert_eq!(trades[0].side, Side::Buy);
+        assert_eq!(trades[0].entry_price, dec!(100.0));
+        assert_eq!(trades[0].quantity, dec!(1.0));
+        assert_eq!(trades[0].pnl, dec!(3.0));
+        assert_eq!(trades[0].fees, dec!(0.4));
+
+        // Half of the second lot closes at 103, the other half at 99
+        assert_eq!(trades[1].quantity, dec!(0.5));
+        assert_eq!(trades[1].pnl, dec!(0.5));
+        assert_eq!(trades[1].fees, dec!(0.2));
+        assert_eq!(trades[2].quantity, dec!(0.5));
+        assert_eq!(trades[2].pnl, dec!(-1.5));
+        assert_eq!(trades[2].entry_time, 2000);
+        assert_eq!(trades[2].exit_time, 4000);
+    }
+
+    #[test]
+    fn test_trade_record_from_fills_short_and_flip() {
+        let fills = vec![
+            SimulatedFill::new(Side::Sell, dec!(100.0), dec!(1.0), 1000),
+            SimulatedFill::new(Side::Buy, dec!(98.0), dec!(2.0), 2000),
+            SimulatedFill::new(Side::Sell, dec!(99.0), dec!(1.0), 3000),
+        ];
+
+        let trades = TradeRecord::from_fills(&fills);
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].side, Side::Sell);
+        assert_eq!(trades[0].pnl, dec!(2.0));
+        // The buy flipped the position long; the last sell closes it
+        assert_eq!(trades[1].side, Side::Buy);
+        assert_eq!(trades[1].pnl, dec!(1.0));
+    }
+
+    #[test]
+    fn test_calculate_from_backtest() {
+        let result = BacktestResult {
+            equity_curve: vec![
+                (0, dec!(10000.0)),
+                (86400000, dec!(10010.0)),
+                (172800000, dec!(10030.0)),
+            ],
+            trades: vec![
+                SimulatedFill::new(Side::Buy, dec!(100.0), dec!(1.0), 0),
+                SimulatedFill::new(Side::Sell, dec!(130.0), dec!(1.0), 172800000),
+            ],
+            ..Default::default()
+        };
+
+        let calc = MetricsCalculator::with_defaults();
+        let metrics = calc
+            .calculate_from_backtest(&result, dec!(10000.0))
+            .unwrap();
+        assert_eq!(metrics.total_return, dec!(30.0));
+        assert_eq!(metrics.total_trades, 1);
+        assert_eq!(metrics.win_rate, dec!(1));
+
+        let empty = BacktestResult::default();
+        assert!(calc.calculate_from_backtest(&empty, dec!(10000.0)).is_err());
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_equity_point_serialization() {
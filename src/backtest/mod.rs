@@ -9,11 +9,19 @@
 //!
 //! - **Data types**: `MarketTick`, `OHLCVBar` for market data
 //! - **Data sources**: `HistoricalDataSource` trait and `VecDataSource` implementation
+//! - **Loaders**: `CsvTickSource`, `CsvBarSource` and binary `TickFileSource` streaming from disk
+//! - **L2 replay**: `L2ReplaySource` rebuilding a full depth book from incremental updates
+//! - **L3 backtest**: `L3BacktestEngine` queueing simulated orders in an order-by-order book
+//! - **Latency**: `LatencyConfig` delaying market data, quotes and cancels in the engine
 //! - **Strategy trait**: `BacktestStrategy` for strategy integration
+//! - **Adapter**: `QuotingStrategyAdapter` to backtest any `QuotingStrategy`
 //! - **Engine**: `BacktestEngine` for running simulations
+//! - **Multi-asset**: `MultiAssetEngine` trading several symbols with per-symbol breakdowns
 //! - **Results**: `BacktestResult` with comprehensive metrics
 //! - **Fill models**: Realistic fill simulation with queue position and market impact
 //! - **Performance metrics**: Comprehensive metrics calculation (Sharpe, Sortino, etc.)
+//! - **Optimization**: Grid, random and Bayesian parameter search with `BacktestOptimizer`
+//! - **Walk-forward**: Rolling in-sample calibration with stitched out-of-sample validation
 //!
 //! # Example
 //!
@@ -37,6 +45,12 @@
 //! // let result = engine.run();
 //! ```
 
+/// Adapter running any `QuotingStrategy` in the backtest engine.
+pub mod adapter;
+
+/// CSV loaders for ticks and OHLCV bars.
+pub mod csv_source;
+
 /// Data types for market data.
 pub mod data;
 
@@ -46,9 +60,34 @@ pub mod engine;
 /// Realistic fill models for backtesting.
 pub mod fill_models;
 
+/// Level-2 order book reconstruction and replay.
+pub mod l2_replay;
+
+/// Order-by-order backtesting with exact queue position.
+pub mod l3_backtest;
+
+/// Order and market-data latency simulation.
+pub mod latency;
+
 /// Performance metrics calculator.
 pub mod metrics;
 
+/// Multi-symbol backtesting with portfolio reporting.
+pub mod multi_asset;
+
+/// Parameter optimization over the backtest engine.
+pub mod optimization;
+
+/// Compact binary tick files for fast replay.
+pub mod tick_file;
+
+/// Walk-forward analysis and out-of-sample validation.
+pub mod walk_forward;
+
+pub use adapter::QuotingStrategyAdapter;
+pub use csv_source::{
+    BarColumns, CsvBarSource, CsvColumn, CsvConfig, CsvTickSource, TickColumns, TimestampFormat,
+};
 pub use data::{HistoricalDataSource, MarketTick, OHLCVBar, VecDataSource};
 pub use engine::{
     BacktestConfig, BacktestEngine, BacktestResult, BacktestStrategy, SimulatedFill, SlippageModel,
@@ -57,4 +96,19 @@ pub use fill_models::{
     FillModel, FillResult, ImmediateFillModel, MarketImpactFillModel, ProbabilisticFillModel,
     QueuePositionFillModel, SimulatedOrder,
 };
+pub use l2_replay::{L2Book, L2Event, L2EventKind, L2ReplaySource, SharedL2Book};
+pub use l3_backtest::{L3BacktestEngine, L3Book, L3Event, L3EventKind, L3Fill};
+pub use latency::{LatencyConfig, LatencyModel};
 pub use metrics::{EquityPoint, MetricsCalculator, MetricsConfig, PerformanceMetrics, TradeRecord};
+pub use multi_asset::{
+    MultiAssetAction, MultiAssetEngine, MultiAssetResult, MultiAssetStrategy, PortfolioView,
+    SymbolBreakdown,
+};
+pub use optimization::{
+    BacktestOptimizer, FailedTrial, OptimizationObjective, OptimizationReport, OptimizerConfig,
+    ParameterRange, ParameterSet, ParameterSpace, SearchMethod, TrialResult,
+};
+pub use tick_file::{TickFileSource, TickFileWriter};
+pub use walk_forward::{
+    WalkForwardAnalyzer, WalkForwardConfig, WalkForwardReport, WalkForwardWindow, WindowResult,
+};
@@ -0,0 +1,880 @@
+//! Multi-symbol backtesting.
+//!
+//! [`MultiAssetEngine`] replays several [`HistoricalDataSource`]s, one per
+//! symbol, merged into a single stream by timestamp. Ticks with equal
+//! timestamps are delivered in the order the sources were added.
+//!
+//! On every tick a [`MultiAssetStrategy`] may return [`MultiAssetAction`]s for
+//! any symbol: standing two-sided quotes, quote withdrawals, and market
+//! orders, e.g. to hedge a fill on one book in a correlated one.
+//!
+//! # Execution model
+//!
+//! - A quote rests on its symbol until replaced or withdrawn, and is checked
+//!   against that symbol's ticks. Each side fills once, at the quoted price,
+//!   for `BacktestConfig::default_order_size` as in
+//!   [`BacktestEngine`](super::BacktestEngine); quoted sizes are ignored.
+//! - A market order fills in full at the opposite side of the symbol's
+//!   latest tick.
+//! - Fees, slippage and latency come from the [`BacktestConfig`]; each symbol
+//!   has its own latency stream. Market orders use the order-entry latency.
+//!
+//! # Reporting
+//!
+//! Positions, PnL and fees are tracked per symbol, and each symbol gets its
+//! own [`BacktestResult`] and [`PerformanceMetrics`] measured against the full
+//! initial capital. The portfolio equity curve, recorded on every tick when
+//! `record_equity_curve` is set, is the initial capital plus the net PnL of
+//! all symbols, and yields the portfolio-level metrics.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::backtest::{
+//!     BacktestConfig, MarketTick, MultiAssetAction, MultiAssetEngine, MultiAssetStrategy,
+//!     PortfolioView, SimulatedFill, VecDataSource,
+//! };
+//! use market_maker_rs::strategy::quote::Quote;
+//! use market_maker_rs::dec;
+//!
+//! // Quotes fixed levels on BTC only
+//! struct Quoter;
+//!
+//! impl MultiAssetStrategy for Quoter {
+//!     fn on_tick(
+//!         &mut self,
+//!         symbol: &str,
+//!         tick: &MarketTick,
+//!         _portfolio: &PortfolioView<'_>,
+//!     ) -> Vec<MultiAssetAction> {
+//!         if symbol != "BTC" {
+//!             return Vec::new();
+//!         }
+//!         vec![MultiAssetAction::quote(
+//!             symbol,
+//!             Quote {
+//!                 bid_price: dec!(99.5),
+//!                 bid_size: dec!(1.0),
+//!                 ask_price: dec!(100.7),
+//!                 ask_size: dec!(1.0),
+//!                 timestamp: tick.timestamp,
+//!             },
+//!         )]
+//!     }
+//!
+//!     fn on_fill(&mut self, _symbol: &str, _fill: &SimulatedFill) {}
+//!     fn reset(&mut self) {}
+//! }
+//!
+//! let btc = vec![
+//!     MarketTick::new(1000, dec!(100.0), dec!(1.0), dec!(100.2), dec!(1.0)),
+//!     MarketTick::new(3000, dec!(99.0), dec!(1.0), dec!(99.2), dec!(1.0)),
+//! ];
+//! let eth = vec![MarketTick::new(2000, dec!(50.0), dec!(1.0), dec!(50.1), dec!(1.0))];
+//!
+//! let mut engine = MultiAssetEngine::new(BacktestConfig::default(), Quoter);
+//! engine.add_source("BTC", VecDataSource::new(btc)).unwrap();
+//! engine.add_source("ETH", VecDataSource::new(eth)).unwrap();
+//!
+//! let result = engine.run().unwrap();
+//! assert_eq!(result.num_ticks, 3);
+//! assert_eq!(result.symbols["BTC"].result.final_position, dec!(1.0));
+//! assert_eq!(result.symbols["ETH"].result.num_trades, 0);
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::Decimal;
+use crate::execution::Side;
+use crate::position::inventory::InventoryPosition;
+use crate::position::pnl::PnL;
+use crate::strategy::quote::Quote;
+use crate::types::error::{MMError, MMResult};
+
+use super::data::{HistoricalDataSource, MarketTick};
+use super::engine::{BacktestConfig, BacktestLedger, BacktestResult, SimulatedFill};
+use super::latency::LatencySimulator;
+use super::metrics::{
+    EquityPoint, MetricsCalculator, MetricsConfig, PerformanceMetrics, TradeRecord,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Order instruction returned by a [`MultiAssetStrategy`].
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::MultiAssetAction;
+/// use market_maker_rs::execution::Side;
+/// use market_maker_rs::dec;
+///
+/// let hedge = MultiAssetAction::market("ETH", Side::Sell, dec!(2.0));
+/// assert_eq!(hedge.symbol(), "ETH");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MultiAssetAction {
+    /// Replaces the symbol's standing quote.
+    Quote {
+        /// Symbol to quote.
+        symbol: String,
+        /// New two-sided quote.
+        quote: Quote,
+    },
+    /// Withdraws the symbol's standing quote.
+    CancelQuote {
+        /// Symbol to stop quoting.
+        symbol: String,
+    },
+    /// Takes liquidity at the symbol's top of book.
+    Market {
+        /// Symbol to trade.
+        symbol: String,
+        /// Order side.
+        side: Side,
+        /// Order quantity.
+        quantity: Decimal,
+    },
+}
+
+impl MultiAssetAction {
+    /// Creates a quote action.
+    #[must_use]
+    pub fn quote(symbol: impl Into<String>, quote: Quote) -> Self {
+        Self::Quote {
+            symbol: symbol.into(),
+            quote,
+        }
+    }
+
+    /// Creates a quote withdrawal.
+    #[must_use]
+    pub fn cancel(symbol: impl Into<String>) -> Self {
+        Self::CancelQuote {
+            symbol: symbol.into(),
+        }
+    }
+
+    /// Creates a market order.
+    #[must_use]
+    pub fn market(symbol: impl Into<String>, side: Side, quantity: Decimal) -> Self {
+        Self::Market {
+            symbol: symbol.into(),
+            side,
+            quantity,
+        }
+    }
+
+    /// Returns the symbol the action applies to.
+    #[must_use]
+    pub fn symbol(&self) -> &str {
+        match self {
+            Self::Quote { symbol, .. }
+            | Self::CancelQuote { symbol }
+            | Self::Market { symbol, .. } => symbol,
+        }
+    }
+}
+
+/// Strategy trait for multi-symbol backtesting.
+pub trait MultiAssetStrategy {
+    /// Called on each tick of any symbol.
+    ///
+    /// Returns the actions to send, for this or any other symbol.
+    fn on_tick(
+        &mut self,
+        symbol: &str,
+        tick: &MarketTick,
+        portfolio: &PortfolioView<'_>,
+    ) -> Vec<MultiAssetAction>;
+
+    /// Called when an order on `symbol` is filled.
+    fn on_fill(&mut self, symbol: &str, fill: &SimulatedFill);
+
+    /// Resets the strategy state.
+    fn reset(&mut self);
+}
+
+/// Read-only view of the portfolio passed to the strategy.
+#[derive(Debug)]
+pub struct PortfolioView<'a> {
+    slots: &'a [SymbolSlot],
+    initial_capital: Decimal,
+}
+
+impl PortfolioView<'_> {
+    /// Returns the symbols in the order their sources were added.
+    pub fn symbols(&self) -> impl Iterator<Item = &str> {
+        self.slots.iter().map(|slot| slot.symbol.as_str())
+    }
+
+    /// Returns the position in `symbol`.
+    #[must_use]
+    pub fn position(&self, symbol: &str) -> Option<&InventoryPosition> {
+        self.slot(symbol).map(|slot| &slot.ledger.position)
+    }
+
+    /// Returns the PnL of `symbol`, before fees.
+    #[must_use]
+    pub fn pnl(&self, symbol: &str) -> Option<&PnL> {
+        self.slot(symbol).map(|slot| &slot.ledger.pnl)
+    }
+
+    /// Returns the portfolio equity: initial capital plus net PnL of all symbols.
+    #[must_use]
+    pub fn equity(&self) -> Decimal {
+        portfolio_equity(self.slots, self.initial_capital)
+    }
+
+    fn slot(&self, symbol: &str) -> Option<&SymbolSlot> {
+        self.slots.iter().find(|slot| slot.symbol == symbol)
+    }
+}
+
+/// Results of one symbol in a multi-symbol backtest.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SymbolBreakdown {
+    /// Backtest result of the symbol on its own ticks.
+    pub result: BacktestResult,
+    /// Metrics of the symbol, if it has an equity curve.
+    pub metrics: Option<PerformanceMetrics>,
+}
+
+/// Portfolio-level and per-symbol results of a multi-symbol backtest.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiAssetResult {
+    /// Per-symbol results.
+    pub symbols: BTreeMap<String, SymbolBreakdown>,
+    /// Portfolio equity after every tick.
+    pub equity_curve: Vec<EquityPoint>,
+    /// Round trips of all symbols, ordered by exit time.
+    pub trades: Vec<TradeRecord>,
+    /// Portfolio metrics, if the equity curve was recorded.
+    pub metrics: Option<PerformanceMetrics>,
+    /// Total fees paid across symbols.
+    pub total_fees: Decimal,
+    /// Portfolio PnL after fees.
+    pub net_pnl: Decimal,
+    /// Number of ticks processed across symbols.
+    pub num_ticks: u64,
+}
+
+/// Market order waiting for its order-entry latency.
+#[derive(Debug, Clone)]
+struct PendingOrder {
+    arrival_us: u64,
+    slot: usize,
+    side: Side,
+    quantity: Decimal,
+}
+
+/// Per-symbol data, venue and accounting.
+struct SymbolSlot {
+    symbol: String,
+    source: Box<dyn HistoricalDataSource>,
+    ledger: BacktestLedger,
+    venue: LatencySimulator,
+    last_tick: Option<MarketTick>,
+}
+
+impl std::fmt::Debug for SymbolSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SymbolSlot")
+            .field("symbol", &self.symbol)
+            .field("ticks", &self.source.len())
+            .field("remaining", &self.source.remaining())
+            .field("ledger", &self.ledger)
+            .field("last_tick", &self.last_tick)
+            .finish()
+    }
+}
+
+fn portfolio_equity(slots: &[SymbolSlot], initial_capital: Decimal) -> Decimal {
+    initial_capital
+        + slots
+            .iter()
+            .map(|slot| slot.ledger.pnl.total - slot.ledger.total_fees)
+            .sum::<Decimal>()
+}
+
+/// Backtesting engine for strategies trading several symbols.
+///
+/// See the [module documentation](self) for the execution model.
+#[derive(Debug)]
+pub struct MultiAssetEngine<S: MultiAssetStrategy> {
+    config: BacktestConfig,
+    metrics_config: MetricsConfig,
+    strategy: S,
+    slots: Vec<SymbolSlot>,
+    pending: Vec<PendingOrder>,
+    equity_curve: Vec<EquityPoint>,
+    num_ticks: u64,
+}
+
+impl<S: MultiAssetStrategy> MultiAssetEngine<S> {
+    /// Creates an engine without data sources.
+    #[must_use]
+    pub fn new(config: BacktestConfig, strategy: S) -> Self {
+        Self {
+            config,
+            metrics_config: MetricsConfig::default(),
+            strategy,
+            slots: Vec::new(),
+            pending: Vec::new(),
+            equity_curve: Vec::new(),
+            num_ticks: 0,
+        }
+    }
+
+    /// Sets the configuration used for the performance metrics.
+    #[must_use]
+    pub fn with_metrics_config(mut self, config: MetricsConfig) -> Self {
+        self.metrics_config = config;
+        self
+    }
+
+    /// Adds the data source of a symbol.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the symbol already has a
+    /// source.
+    pub fn add_source(
+        &mut self,
+        symbol: impl Into<String>,
+        source: impl HistoricalDataSource + 'static,
+    ) -> MMResult<()> {
+        let symbol = symbol.into();
+        if self.slots.iter().any(|slot| slot.symbol == symbol) {
+            return Err(MMError::InvalidConfiguration(format!(
+                "symbol {symbol} already has a data source"
+            )));
+        }
+        let venue = self.new_venue(self.slots.len());
+        self.slots.push(SymbolSlot {
+            symbol,
+            source: Box::new(source),
+            ledger: BacktestLedger::new(&self.config),
+            venue,
+            last_tick: None,
+        });
+        Ok(())
+    }
+
+    /// Gives each symbol its own latency stream.
+    fn new_venue(&self, index: usize) -> LatencySimulator {
+        let seed = self.config.latency.seed.wrapping_add(index as u64);
+        LatencySimulator::new(self.config.latency.clone().with_seed(seed))
+    }
+
+    /// Runs the backtest over all sources.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no source has data, or if the strategy sends an
+    /// action for an unknown symbol or a market order without a positive
+    /// quantity.
+    pub fn run(&mut self) -> MMResult<MultiAssetResult> {
+        if self.slots.is_empty() {
+            return Err(MMError::InvalidConfiguration(
+                "multi-asset backtest needs at least one data source".to_string(),
+            ));
+        }
+
+        while let Some(index) = self.next_source() {
+            let Some(tick) = self.slots[index].source.next_tick() else {
+                break;
+            };
+            let now_us = tick.timestamp.saturating_mul(1000);
+
+            let slot = &mut self.slots[index];
+            slot.last_tick = Some(tick.clone());
+            slot.venue.publish(tick.clone());
+
+            self.deliver(now_us)?;
+            for slot in &mut self.slots {
+                slot.venue.process_arrivals(now_us);
+            }
+            self.execute_market_orders(now_us);
+            self.simulate_fills(index, &tick);
+
+            // Value every book at its latest mid, then record the tick
+            for slot in &mut self.slots {
+                if let Some(last) = &slot.last_tick {
+                    slot.ledger.revalue(last.mid_price());
+                }
+            }
+            self.slots[index]
+                .ledger
+                .mark(tick.timestamp, tick.mid_price());
+            self.num_ticks += 1;
+            if self.config.record_equity_curve {
+                let equity = portfolio_equity(&self.slots, self.config.initial_capital);
+                self.equity_curve
+                    .push(EquityPoint::new(tick.timestamp, equity));
+            }
+        }
+
+        self.result()
+    }
+
+    /// Returns the source holding the earliest next tick.
+    fn next_source(&self) -> Option<usize> {
+        let mut best: Option<(u64, usize)> = None;
+        for (index, slot) in self.slots.iter().enumerate() {
+            if let Some(tick) = slot.source.peek_tick()
+                && best.is_none_or(|(timestamp, _)| tick.timestamp < timestamp)
+            {
+                best = Some((tick.timestamp, index));
+            }
+        }
+        best.map(|(_, index)| index)
+    }
+
+    /// Hands the strategy every tick it has received by `now_us`, in
+    /// delivery order, and dispatches its actions.
+    fn deliver(&mut self, now_us: u64) -> MMResult<()> {
+        loop {
+            let next = self
+                .slots
+                .iter()
+                .enumerate()
+                .filter_map(|(index, slot)| {
+                    slot.venue
+                        .next_delivery_us()
+                        .filter(|delivery_us| *delivery_us <= now_us)
+                        .map(|delivery_us| (delivery_us, index))
+                })
+                .min();
+            let Some((_, index)) = next else {
+                return Ok(());
+            };
+            let Some((seen_us, seen)) = self.slots[index].venue.next_delivery(now_us) else {
+                return Ok(());
+            };
+
+            let portfolio = PortfolioView {
+                slots: &self.slots,
+                initial_capital: self.config.initial_capital,
+            };
+            let actions = self
+                .strategy
+                .on_tick(&self.slots[index].symbol, &seen, &portfolio);
+            for action in actions {
+                self.dispatch(seen_us, action)?;
+            }
+        }
+    }
+
+    /// Sends one strategy action towards its symbol's venue.
+    fn dispatch(&mut self, sent_us: u64, action: MultiAssetAction) -> MMResult<()> {
+        let slot = self
+            .slots
+            .iter()
+            .position(|slot| slot.symbol == action.symbol())
+            .ok_or_else(|| {
+                MMError::InvalidConfiguration(format!(
+                    "strategy sent an order for unknown symbol {}",
+                    action.symbol()
+                ))
+            })?;
+
+        match action {
+            MultiAssetAction::Quote { quote, .. } => {
+                self.slots[slot].venue.submit(sent_us, Some(quote));
+            }
+            MultiAssetAction::CancelQuote { .. } => {
+                self.slots[slot].venue.submit(sent_us, None);
+            }
+            MultiAssetAction::Market { side, quantity, .. } => {
+                if quantity <= Decimal::ZERO {
+                    return Err(MMError::InvalidPositionUpdate(format!(
+                        "market order quantity must be positive, got {quantity}"
+                    )));
+                }
+                let delay = self.slots[slot].venue.order_entry_delay();
+                self.pending.push(PendingOrder {
+                    arrival_us: sent_us.saturating_add(delay),
+                    slot,
+                    side,
+                    quantity,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Fills market orders that reached a venue with a book by `now_us`.
+    fn execute_market_orders(&mut self, now_us: u64) {
+        let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|order| {
+                order.arrival_us <= now_us && self.slots[order.slot].last_tick.is_some()
+            });
+        self.pending = waiting;
+
+        for order in ready {
+            let Some(tick) = self.slots[order.slot].last_tick.clone() else {
+                continue;
+            };
+            let price = match order.side {
+                Side::Buy => tick.ask_price,
+                Side::Sell => tick.bid_price,
+            };
+            let fill = self.create_fill(order.side, price, order.quantity, tick.timestamp);
+            self.process_fill(order.slot, fill);
+        }
+    }
+
+    /// Simulates fills of the quotes resting on one symbol against its tick.
+    fn simulate_fills(&mut self, index: usize, tick: &MarketTick) {
+        let mut crossed = Vec::new();
+        let venue = &mut self.slots[index].venue;
+        for live in venue.live_mut().iter_mut() {
+            if live.bid_open && tick.ask_price <= live.quote.bid_price {
+                live.bid_open = false;
+                crossed.push((Side::Buy, live.quote.bid_price));
+            }
+            if live.ask_open && tick.bid_price >= live.quote.ask_price {
+                live.ask_open = false;
+                crossed.push((Side::Sell, live.quote.ask_price));
+            }
+        }
+        venue.prune();
+
+        let quantity = self.config.default_order_size;
+        for (side, price) in crossed {
+            let fill = self.create_fill(side, price, quantity, tick.timestamp);
+            self.process_fill(index, fill);
+        }
+    }
+
+    /// Creates a fill with slippage and fee.
+    fn create_fill(
+        &self,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+        timestamp: u64,
+    ) -> SimulatedFill {
+        let slippage = self
+            .config
+            .slippage
+            .calculate_slippage(price, Decimal::ZERO);
+        let price = match side {
+            Side::Buy => price + slippage,
+            Side::Sell => price - slippage,
+        };
+        let fee = price * quantity * self.config.fee_rate;
+        SimulatedFill::with_fee(side, price, quantity, timestamp, fee)
+    }
+
+    fn process_fill(&mut self, index: usize, fill: SimulatedFill) {
+        let slot = &mut self.slots[index];
+        self.strategy.on_fill(&slot.symbol, &fill);
+        slot.ledger.record_fill(fill);
+    }
+
+    fn result(&self) -> MMResult<MultiAssetResult> {
+        let initial_capital = self.config.initial_capital;
+        let calculator = MetricsCalculator::new(self.metrics_config.clone());
+
+        let mut symbols = BTreeMap::new();
+        let mut trades = Vec::new();
+        let mut total_fees = Decimal::ZERO;
+        for slot in &self.slots {
+            let result = slot.ledger.result();
+            let equity_curve: Vec<EquityPoint> = result
+                .equity_curve
+                .iter()
+                .map(|&(timestamp, equity)| EquityPoint::new(timestamp, equity))
+                .collect();
+            let symbol_trades = TradeRecord::from_fills(&result.trades);
+            let metrics = if equity_curve.is_empty() {
+                None
+            } else {
+                Some(calculator.calculate(&equity_curve, &symbol_trades, initial_capital)?)
+            };
+
+            total_fees += result.total_fees;
+            trades.extend(symbol_trades);
+            symbols.insert(slot.symbol.clone(), SymbolBreakdown { result, metrics });
+        }
+        trades.sort_by_key(|trade| trade.exit_time);
+
+        let metrics = if self.equity_curve.is_empty() {
+            None
+        } else {
+            Some(calculator.calculate(&self.equity_curve, &trades, initial_capital)?)
+        };
+        Ok(MultiAssetResult {
+            symbols,
+            equity_curve: self.equity_curve.clone(),
+            trades,
+            metrics,
+            total_fees,
+            net_pnl: portfolio_equity(&self.slots, initial_capital) - initial_capital,
+            num_ticks: self.num_ticks,
+        })
+    }
+
+    /// Returns the symbols in the order their sources were added.
+    pub fn symbols(&self) -> impl Iterator<Item = &str> {
+        self.slots.iter().map(|slot| slot.symbol.as_str())
+    }
+
+    /// Returns the current position and PnL of `symbol`.
+    #[must_use]
+    pub fn get_state(&self, symbol: &str) -> Option<(&InventoryPosition, &PnL)> {
+        self.slots
+            .iter()
+            .find(|slot| slot.symbol == symbol)
+            .map(|slot| (&slot.ledger.position, &slot.ledger.pnl))
+    }
+
+    /// Returns a reference to the strategy.
+    #[must_use]
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Returns a mutable reference to the strategy.
+    pub fn strategy_mut(&mut self) -> &mut S {
+        &mut self.strategy
+    }
+
+    /// Resets the engine for another run.
+    pub fn reset(&mut self) {
+        self.strategy.reset();
+        for index in 0..self.slots.len() {
+            let venue = self.new_venue(index);
+            let slot = &mut self.slots[index];
+            slot.source.reset();
+            slot.ledger = BacktestLedger::new(&self.config);
+            slot.venue = venue;
+            slot.last_tick = None;
+        }
+        self.pending.clear();
+        self.equity_curve.clear();
+        self.num_ticks = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::VecDataSource;
+    use crate::backtest::latency::{LatencyConfig, LatencyModel};
+    use crate::dec;
+
+    fn tick(timestamp: u64, bid: Decimal, ask: Decimal) -> MarketTick {
+        MarketTick::new(timestamp, bid, dec!(1.0), ask, dec!(1.0))
+    }
+
+    /// Places one standing quote on `quoted` and hedges its fills in `hedge`.
+    struct HedgedQuoter {
+        quoted: &'static str,
+        hedge: &'static str,
+        quoting: bool,
+        unhedged: Decimal,
+        seen: Vec<(String, u64)>,
+        fills: Vec<(String, Side)>,
+    }
+
+    impl HedgedQuoter {
+        fn new(quoted: &'static str, hedge: &'static str) -> Self {
+            Self {
+                quoted,
+                hedge,
+                quoting: false,
+                unhedged: Decimal::ZERO,
+                seen: Vec::new(),
+                fills: Vec::new(),
+            }
+        }
+    }
+
+    impl MultiAssetStrategy for HedgedQuoter {
+        fn on_tick(
+            &mut self,
+            symbol: &str,
+            tick: &MarketTick,
+            _portfolio: &PortfolioView<'_>,
+        ) -> Vec<MultiAssetAction> {
+            self.seen.push((symbol.to_string(), tick.timestamp));
+            let mut actions = Vec::new();
+            if self.unhedged != Decimal::ZERO {
+                let side = if self.unhedged > Decimal::ZERO {
+                    Side::Sell
+                } else {
+                    Side::Buy
+                };
+                actions.push(MultiAssetAction::market(
+                    self.hedge,
+                    side,
+                    self.unhedged.abs(),
+                ));
+                self.unhedged = Decimal::ZERO;
+            }
+            if symbol == self.quoted && !self.quoting {
+                self.quoting = true;
+                let mid = tick.mid_price();
+                actions.push(MultiAssetAction::quote(
+                    symbol,
+                    Quote {
+                        bid_price: mid - dec!(1.0),
+                        bid_size: dec!(2.0),
+                        ask_price: mid + dec!(1.0),
+                        ask_size: dec!(2.0),
+                        timestamp: tick.timestamp,
+                    },
+                ));
+            }
+            actions
+        }
+
+        fn on_fill(&mut self, symbol: &str, fill: &SimulatedFill) {
+            if symbol == self.quoted {
+                self.unhedged += match fill.side {
+                    Side::Buy => fill.quantity,
+                    Side::Sell => -fill.quantity,
+                };
+            }
+            self.fills.push((symbol.to_string(), fill.side));
+        }
+
+        fn reset(&mut self) {
+            self.quoting = false;
+            self.unhedged = Decimal::ZERO;
+            self.seen.clear();
+            self.fills.clear();
+        }
+    }
+
+    fn sources() -> (VecDataSource, VecDataSource) {
+        let a = VecDataSource::new(vec![
+            tick(1000, dec!(100.0), dec!(100.2)),
+            tick(2000, dec!(98.8), dec!(99.0)),
+            tick(4000, dec!(98.8), dec!(99.0)),
+        ]);
+        let b = VecDataSource::new(vec![
+            tick(1000, dec!(50.0), dec!(50.1)),
+            tick(3000, dec!(49.5), dec!(49.6)),
+        ]);
+        (a, b)
+    }
+
+    #[test]
+    fn test_merges_sources_by_timestamp() {
+        let (a, b) = sources();
+        let mut engine =
+            MultiAssetEngine::new(BacktestConfig::default(), HedgedQuoter::new("A", "B"));
+        engine.add_source("A", a).unwrap();
+        engine.add_source("B", b).unwrap();
+        assert!(
+            engine
+                .add_source("A", VecDataSource::empty())
+                .unwrap_err()
+                .is_configuration_error()
+        );
+
+        let result = engine.run().unwrap();
+        assert_eq!(result.num_ticks, 5);
+        let seen: Vec<_> = engine
+            .strategy()
+            .seen
+            .iter()
+            .map(|(symbol, ts)| format!("{symbol}{ts}"))
+            .collect();
+        assert_eq!(seen, ["A1000", "B1000", "A2000", "B3000", "A4000"]);
+        assert_eq!(result.equity_curve.len(), 5);
+        assert_eq!(result.symbols["A"].result.num_ticks, 3);
+        assert_eq!(result.symbols["B"].result.num_ticks, 2);
+    }
+
+    #[test]
+    fn test_hedges_across_symbols_with_breakdown() {
+        let (a, b) = sources();
+        let config = BacktestConfig::default()
+            .with_fee_rate(dec!(0.001))
+            .with_default_order_size(dec!(2.0));
+        let mut engine = MultiAssetEngine::new(config, HedgedQuoter::new("A", "B"));
+        engine.add_source("A", a).unwrap();
+        engine.add_source("B", b).unwrap();
+        let result = engine.run().unwrap();
+
+        // A's bid at 99.1 is hit at 2000, and the hedge sells B at 3000
+        let a = &result.symbols["A"].result;
+        let b = &result.symbols["B"].result;
+        assert_eq!(a.final_position, dec!(2.0));
+        assert_eq!(a.trades[0].price, dec!(99.1));
+        assert_eq!(b.final_position, dec!(-2.0));
+        assert_eq!(b.trades[0].price, dec!(49.5));
+        assert_eq!(b.trades[0].timestamp, 3000);
+        assert_eq!(engine.get_state("B").unwrap().0.quantity, dec!(-2.0));
+
+        // The portfolio adds up the symbols
+        assert_eq!(result.net_pnl, a.net_pnl + b.net_pnl);
+        assert_eq!(result.total_fees, a.total_fees + b.total_fees);
+        assert_eq!(result.metrics.unwrap().total_return, result.net_pnl);
+        assert!(result.symbols["A"].metrics.is_some());
+
+        engine.reset();
+        let again = engine.run().unwrap();
+        assert_eq!(again.net_pnl, result.net_pnl);
+    }
+
+    #[test]
+    fn test_sizes_and_equity_curve_follow_config() {
+        let (a, b) = sources();
+        let config = BacktestConfig::default().with_record_equity_curve(false);
+        let mut engine = MultiAssetEngine::new(config, HedgedQuoter::new("A", "B"));
+        engine.add_source("A", a).unwrap();
+        engine.add_source("B", b).unwrap();
+        let result = engine.run().unwrap();
+
+        // The quote asks for 2.0, but fills use the default order size
+        assert_eq!(result.symbols["A"].result.final_position, dec!(1.0));
+        assert_eq!(result.symbols["B"].result.final_position, dec!(-1.0));
+
+        assert!(result.equity_curve.is_empty());
+        assert!(result.metrics.is_none());
+        assert!(result.symbols["A"].metrics.is_none());
+        assert_eq!(result.num_ticks, 5);
+    }
+
+    #[test]
+    fn test_market_orders_wait_for_order_entry_latency() {
+        let (a, b) = sources();
+        let latency = LatencyConfig::new().with_order_entry(LatencyModel::Fixed(1_500_000));
+        let config = BacktestConfig::default().with_latency(latency);
+        let mut engine = MultiAssetEngine::new(config, HedgedQuoter::new("A", "B"));
+        engine.add_source("A", a).unwrap();
+        engine.add_source("B", b).unwrap();
+        let result = engine.run().unwrap();
+
+        // The quote from 1000 rests at 2500, so A fills at 4000; its hedge
+        // sent then never reaches B before the data ends.
+        assert_eq!(result.symbols["A"].result.num_trades, 1);
+        assert_eq!(result.symbols["A"].result.trades[0].timestamp, 4000);
+        assert_eq!(result.symbols["B"].result.num_trades, 0);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut empty =
+            MultiAssetEngine::new(BacktestConfig::default(), HedgedQuoter::new("A", "B"));
+        assert!(empty.run().is_err());
+
+        let (a, _) = sources();
+        let mut unknown =
+            MultiAssetEngine::new(BacktestConfig::default(), HedgedQuoter::new("A", "B"));
+        unknown.add_source("A", a).unwrap();
+        let err = unknown.run().unwrap_err();
+        assert!(err.message().contains("unknown symbol B"));
+    }
+}
@@ -0,0 +1,1316 @@
+//! Parameter search over the backtest engine.
+//!
+//! A [`BacktestOptimizer`] evaluates strategy parameter sets by running each
+//! one through a fresh [`BacktestEngine`] on the same historical data, scoring
+//! the resulting [`PerformanceMetrics`] with an [`OptimizationObjective`], and
+//! ranking the trials in an [`OptimizationReport`].
+//!
+//! # Search methods
+//!
+//! - **Grid**: every combination of the grid points of each parameter
+//! - **Random**: uniform samples from the parameter space, seeded
+//! - **Bayesian**: a tree-structured Parzen estimator that, after a random
+//!   warm-up, proposes points where the density of the best trials is high
+//!   relative to the density of the rest
+//!
+//! Trials are independent, so each batch is spread across worker threads.
+//! Strategies are built inside the workers by a factory closure, so they do
+//! not need to be `Send`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::backtest::{
+//!     BacktestConfig, MarketTick, OptimizationObjective, OptimizerConfig, ParameterRange,
+//!     ParameterSpace, QuotingStrategyAdapter, SearchMethod, VecDataSource,
+//!     BacktestOptimizer,
+//! };
+//! use market_maker_rs::strategy::config::StrategyConfig;
+//! use market_maker_rs::strategy::quoting::QuotingStrategyConfig;
+//! use market_maker_rs::dec;
+//!
+//! let ticks: Vec<MarketTick> = (0..50u64)
+//!     .map(|i| {
+//!         let mid = if i % 2 == 0 { dec!(100.0) } else { dec!(100.5) };
+//!         MarketTick::new(i * 1000, mid - dec!(0.01), dec!(1.0), mid + dec!(0.01), dec!(1.0))
+//!     })
+//!     .collect();
+//!
+//! let base = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+//! let space = ParameterSpace::new()
+//!     .with_parameter("risk_aversion", ParameterRange::linear(dec!(0.05), dec!(0.5), 3).unwrap());
+//!
+//! let optimizer = BacktestOptimizer::new(
+//!     space,
+//!     OptimizerConfig::new(SearchMethod::Grid, OptimizationObjective::TotalReturn),
+//!     BacktestConfig::default(),
+//!     VecDataSource::new(ticks),
+//!     |params| {
+//!         let config = params.apply_to_strategy_config(&base)?;
+//!         let strategy = QuotingStrategyConfig::AvellanedaStoikov {
+//!             config,
+//!             order_size: dec!(1.0),
+//!         }
+//!         .build()?;
+//!         Ok(QuotingStrategyAdapter::new(strategy, dec!(0.2)))
+//!     },
+//! )
+//! .unwrap();
+//!
+//! let report = optimizer.run();
+//! assert_eq!(report.len(), 3);
+//! println!("{report}");
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+use crate::Decimal;
+use crate::backtest::data::HistoricalDataSource;
+use crate::backtest::engine::{BacktestConfig, BacktestEngine, BacktestStrategy};
+use crate::backtest::metrics::{MetricsCalculator, MetricsConfig, PerformanceMetrics};
+use crate::strategy::config::StrategyConfig;
+use crate::types::error::{MMError, MMResult};
+use crate::types::numerics::Lcg;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Decimal places kept when sampling continuous ranges.
+const SAMPLE_PRECISION: u32 = 6;
+
+/// Fraction of trials treated as "good" by the Bayesian search.
+const GOOD_FRACTION: f64 = 0.25;
+
+/// Values explored for a single parameter.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::ParameterRange;
+/// use market_maker_rs::dec;
+///
+/// let range = ParameterRange::linear(dec!(0.1), dec!(0.5), 5).unwrap();
+/// assert_eq!(
+///     range.grid_values(),
+///     vec![dec!(0.1), dec!(0.2), dec!(0.3), dec!(0.4), dec!(0.5)]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ParameterRange {
+    /// Explicit list of candidate values.
+    Values(Vec<Decimal>),
+    /// Interval from `min` to `max` inclusive.
+    ///
+    /// Grid search uses `steps` evenly spaced points; random and Bayesian
+    /// search sample the interval continuously.
+    Linear {
+        /// Lower bound.
+        min: Decimal,
+        /// Upper bound.
+        max: Decimal,
+        /// Number of grid points.
+        steps: usize,
+    },
+}
+
+impl ParameterRange {
+    /// Creates a range from an explicit list of values.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `values` is empty.
+    pub fn values(values: Vec<Decimal>) -> MMResult<Self> {
+        if values.is_empty() {
+            return Err(MMError::InvalidConfiguration(
+                "parameter range needs at least one value".to_string(),
+            ));
+        }
+        Ok(Self::Values(values))
+    }
+
+    /// Creates an interval range.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `min > max` or `steps` is zero.
+    pub fn linear(min: Decimal, max: Decimal, steps: usize) -> MMResult<Self> {
+        if min > max {
+            return Err(MMError::InvalidConfiguration(
+                "parameter range min must not exceed max".to_string(),
+            ));
+        }
+        if steps == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "parameter range needs at least one step".to_string(),
+            ));
+        }
+        Ok(Self::Linear { min, max, steps })
+    }
+
+    /// Returns the points visited by grid search.
+    #[must_use]
+    pub fn grid_values(&self) -> Vec<Decimal> {
+        match self {
+            Self::Values(values) => values.clone(),
+            Self::Linear { min, max, steps } => {
+                if *steps == 1 || min == max {
+                    return vec![*min];
+                }
+                let step = (*max - *min) / Decimal::from(*steps - 1);
+                (0..*steps)
+                    .map(|i| {
+                        if i == *steps - 1 {
+                            *max
+                        } else {
+                            *min + step * Decimal::from(i)
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns the number of grid points.
+    #[must_use]
+    pub fn num_points(&self) -> usize {
+        match self {
+            Self::Values(values) => values.len(),
+            Self::Linear { min, max, steps } => {
+                if min == max {
+                    1
+                } else {
+                    *steps
+                }
+            }
+        }
+    }
+
+    /// Maps a coordinate in `[0, 1]` to a value in the range.
+    fn sample(&self, u: f64) -> Decimal {
+        let u = u.clamp(0.0, 1.0);
+        match self {
+            Self::Values(values) => {
+                let index = ((u * values.len() as f64) as usize).min(values.len() - 1);
+                values[index]
+            }
+            Self::Linear { min, max, .. } => {
+                let fraction = Decimal::from_f64(u)
+                    .unwrap_or(Decimal::ZERO)
+                    .round_dp(SAMPLE_PRECISION);
+                *min + (*max - *min) * fraction
+            }
+        }
+    }
+
+    /// Maps a value in the range back to a coordinate in `[0, 1]`.
+    fn normalize(&self, value: Decimal) -> f64 {
+        match self {
+            Self::Values(values) => {
+                let index = values.iter().position(|v| *v == value).unwrap_or(0);
+                (index as f64 + 0.5) / values.len() as f64
+            }
+            Self::Linear { min, max, .. } => {
+                if max == min {
+                    return 0.5;
+                }
+                ((value - *min) / (*max - *min))
+                    .to_f64()
+                    .unwrap_or(0.5)
+                    .clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+/// Named parameters to search over.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::{ParameterRange, ParameterSpace};
+/// use market_maker_rs::dec;
+///
+/// let space = ParameterSpace::new()
+///     .with_parameter("risk_aversion", ParameterRange::linear(dec!(0.1), dec!(0.5), 3).unwrap())
+///     .with_parameter("min_spread", ParameterRange::values(vec![dec!(0.01), dec!(0.02)]).unwrap());
+///
+/// assert_eq!(space.grid_size(), 6);
+/// assert_eq!(space.grid().len(), 6);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParameterSpace {
+    parameters: Vec<(String, ParameterRange)>,
+}
+
+impl ParameterSpace {
+    /// Creates an empty parameter space.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a parameter, replacing any existing range with the same name.
+    #[must_use]
+    pub fn with_parameter(mut self, name: impl Into<String>, range: ParameterRange) -> Self {
+        let name = name.into();
+        match self.parameters.iter_mut().find(|(n, _)| *n == name) {
+            Some(entry) => entry.1 = range,
+            None => self.parameters.push((name, range)),
+        }
+        self
+    }
+
+    /// Returns the range of a parameter.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&ParameterRange> {
+        self.parameters
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, range)| range)
+    }
+
+    /// Returns the parameter names in insertion order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.parameters.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the number of parameters.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parameters.len()
+    }
+
+    /// Returns true if the space has no parameters.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    /// Returns the number of combinations visited by grid search.
+    #[must_use]
+    pub fn grid_size(&self) -> usize {
+        self.parameters.iter().fold(1usize, |acc, (_, range)| {
+            acc.saturating_mul(range.num_points())
+        })
+    }
+
+    /// Returns every combination of grid points.
+    ///
+    /// The last parameter varies fastest.
+    #[must_use]
+    pub fn grid(&self) -> Vec<ParameterSet> {
+        let mut sets = vec![ParameterSet::new()];
+        for (name, range) in &self.parameters {
+            let values = range.grid_values();
+            sets = sets
+                .iter()
+                .flat_map(|set| {
+                    values
+                        .iter()
+                        .map(|value| set.clone().with_value(name.clone(), *value))
+                })
+                .collect();
+        }
+        sets
+    }
+
+    /// Maps a point of the unit hypercube to a parameter set.
+    fn sample(&self, point: &[f64]) -> ParameterSet {
+        let mut set = ParameterSet::new();
+        for ((name, range), u) in self.parameters.iter().zip(point) {
+            set.insert(name.clone(), range.sample(*u));
+        }
+        set
+    }
+
+    /// Maps a parameter set to a point of the unit hypercube.
+    fn normalize(&self, set: &ParameterSet) -> Vec<f64> {
+        self.parameters
+            .iter()
+            .map(|(name, range)| set.get(name).map_or(0.5, |value| range.normalize(value)))
+            .collect()
+    }
+}
+
+/// One assignment of values to named parameters.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::ParameterSet;
+/// use market_maker_rs::dec;
+///
+/// let set = ParameterSet::new().with_value("risk_aversion", dec!(0.2));
+/// assert_eq!(set.get("risk_aversion"), Some(dec!(0.2)));
+/// assert!(set.require("order_intensity").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParameterSet {
+    values: BTreeMap<String, Decimal>,
+}
+
+impl ParameterSet {
+    /// Creates an empty parameter set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a value.
+    #[must_use]
+    pub fn with_value(mut self, name: impl Into<String>, value: Decimal) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    /// Sets a value in place.
+    pub fn insert(&mut self, name: impl Into<String>, value: Decimal) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// Returns a value, if set.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Decimal> {
+        self.values.get(name).copied()
+    }
+
+    /// Returns a value that the strategy factory cannot do without.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the value is not set.
+    pub fn require(&self, name: &str) -> MMResult<Decimal> {
+        self.get(name)
+            .ok_or_else(|| MMError::InvalidConfiguration(format!("parameter '{name}' is not set")))
+    }
+
+    /// Returns the values in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Decimal)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+
+    /// Returns the number of values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if no values are set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Overrides the matching fields of a [`StrategyConfig`].
+    ///
+    /// Recognized names are `risk_aversion`, `order_intensity` and
+    /// `min_spread`; other values are left for the caller to interpret.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the resulting configuration
+    /// is invalid.
+    pub fn apply_to_strategy_config(&self, base: &StrategyConfig) -> MMResult<StrategyConfig> {
+        let config = StrategyConfig::new(
+            self.get("risk_aversion").unwrap_or(base.risk_aversion),
+            self.get("order_intensity").unwrap_or(base.order_intensity),
+            base.terminal_time,
+            self.get("min_spread").unwrap_or(base.min_spread),
+        )?;
+        Ok(config.with_horizon(base.horizon))
+    }
+}
+
+impl fmt::Display for ParameterSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Performance measure maximized by the optimizer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OptimizationObjective {
+    /// Absolute total return.
+    TotalReturn,
+    /// Annualized Sharpe ratio.
+    #[default]
+    SharpeRatio,
+    /// Annualized Sortino ratio.
+    SortinoRatio,
+    /// Calmar ratio.
+    CalmarRatio,
+    /// Gross profit over gross loss.
+    ProfitFactor,
+    /// Fraction of winning round trips.
+    WinRate,
+    /// Maximum drawdown, minimized.
+    MaxDrawdown,
+}
+
+impl OptimizationObjective {
+    /// Returns the score of a trial; higher is better.
+    #[must_use]
+    pub fn score(&self, metrics: &PerformanceMetrics) -> Decimal {
+        match self {
+            Self::TotalReturn => metrics.total_return,
+            Self::SharpeRatio => metrics.sharpe_ratio,
+            Self::SortinoRatio => metrics.sortino_ratio,
+            Self::CalmarRatio => metrics.calmar_ratio,
+            Self::ProfitFactor => metrics.profit_factor,
+            Self::WinRate => metrics.win_rate,
+            Self::MaxDrawdown => -metrics.max_drawdown,
+        }
+    }
+
+    /// Returns the objective name.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TotalReturn => "total_return",
+            Self::SharpeRatio => "sharpe_ratio",
+            Self::SortinoRatio => "sortino_ratio",
+            Self::CalmarRatio => "calmar_ratio",
+            Self::ProfitFactor => "profit_factor",
+            Self::WinRate => "win_rate",
+            Self::MaxDrawdown => "max_drawdown",
+        }
+    }
+}
+
+/// How candidate parameter sets are chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SearchMethod {
+    /// Every combination of grid points.
+    #[default]
+    Grid,
+    /// Uniform random samples.
+    Random {
+        /// Number of trials.
+        samples: usize,
+        /// Random seed for reproducibility.
+        seed: u64,
+    },
+    /// Tree-structured Parzen estimator.
+    Bayesian {
+        /// Random trials before the model is used.
+        initial_samples: usize,
+        /// Model-guided trials after the warm-up.
+        iterations: usize,
+        /// Random candidates scored by the model per proposal.
+        candidates: usize,
+        /// Random seed for reproducibility.
+        seed: u64,
+    },
+}
+
+/// Configuration for a [`BacktestOptimizer`].
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::{OptimizationObjective, OptimizerConfig, SearchMethod};
+///
+/// let config = OptimizerConfig::new(
+///     SearchMethod::Random { samples: 50, seed: 7 },
+///     OptimizationObjective::SortinoRatio,
+/// )
+/// .with_threads(4);
+/// assert_eq!(config.threads, 4);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OptimizerConfig {
+    /// Search method.
+    pub method: SearchMethod,
+    /// Objective to maximize.
+    pub objective: OptimizationObjective,
+    /// Number of worker threads.
+    pub threads: usize,
+    /// Configuration of the metrics computed for each trial.
+    pub metrics: MetricsConfig,
+}
+
+impl OptimizerConfig {
+    /// Creates a configuration using all available cores.
+    #[must_use]
+    pub fn new(method: SearchMethod, objective: OptimizationObjective) -> Self {
+        Self {
+            method,
+            objective,
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            metrics: MetricsConfig::default(),
+        }
+    }
+
+    /// Sets the number of worker threads, at least one.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Sets the metrics configuration.
+    #[must_use]
+    pub fn with_metrics_config(mut self, metrics: MetricsConfig) -> Self {
+        self.metrics = metrics;
+        self
+    }
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> Self {
+        Self::new(SearchMethod::default(), OptimizationObjective::default())
+    }
+}
+
+/// Outcome of a single parameter set.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrialResult {
+    /// Parameters evaluated.
+    pub parameters: ParameterSet,
+    /// Objective score; higher is better.
+    pub score: Decimal,
+    /// Performance metrics of the backtest.
+    pub metrics: PerformanceMetrics,
+    /// Net PnL reported by the engine.
+    pub net_pnl: Decimal,
+    /// Number of fills.
+    pub num_trades: u64,
+}
+
+/// Parameter set that could not be evaluated.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FailedTrial {
+    /// Parameters attempted.
+    pub parameters: ParameterSet,
+    /// Reason for the failure.
+    pub error: String,
+}
+
+/// Ranked results of an optimization run.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OptimizationReport {
+    /// Objective the trials were ranked by.
+    pub objective: OptimizationObjective,
+    /// Successful trials, best first.
+    pub trials: Vec<TrialResult>,
+    /// Trials whose strategy or metrics could not be built.
+    pub failures: Vec<FailedTrial>,
+}
+
+impl OptimizationReport {
+    fn new(
+        objective: OptimizationObjective,
+        mut trials: Vec<TrialResult>,
+        failures: Vec<FailedTrial>,
+    ) -> Self {
+        // Stable sort keeps evaluation order among ties
+        trials.sort_by_key(|t| std::cmp::Reverse(t.score));
+        Self {
+            objective,
+            trials,
+            failures,
+        }
+    }
+
+    /// Returns the best trial.
+    #[must_use]
+    pub fn best(&self) -> Option<&TrialResult> {
+        self.trials.first()
+    }
+
+    /// Returns the `n` best trials.
+    #[must_use]
+    pub fn top(&self, n: usize) -> &[TrialResult] {
+        &self.trials[..n.min(self.trials.len())]
+    }
+
+    /// Returns the number of successful trials.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.trials.len()
+    }
+
+    /// Returns true if no trial succeeded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.trials.is_empty()
+    }
+}
+
+impl fmt::Display for OptimizationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "objective: {} ({} trials, {} failed)",
+            self.objective.name(),
+            self.trials.len(),
+            self.failures.len()
+        )?;
+        writeln!(
+            f,
+            "{:>4}  {:>14}  {:>14}  {:>7}  parameters",
+            "rank", "score", "net_pnl", "trades"
+        )?;
+        for (i, trial) in self.trials.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:>14}  {:>14}  {:>7}  {}",
+                i + 1,
+                trial.score.round_dp(6).to_string(),
+                trial.net_pnl.round_dp(4).to_string(),
+                trial.num_trades,
+                trial.parameters
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Searches strategy parameters by backtesting each candidate.
+///
+/// `factory` turns a [`ParameterSet`] into a fresh strategy; it runs on the
+/// worker threads, and its errors are recorded as failed trials.
+pub struct BacktestOptimizer<F, D> {
+    space: ParameterSpace,
+    config: OptimizerConfig,
+    backtest_config: BacktestConfig,
+    data_source: D,
+    factory: F,
+}
+
+impl<S, F, D> BacktestOptimizer<F, D>
+where
+    S: BacktestStrategy,
+    F: Fn(&ParameterSet) -> MMResult<S> + Sync,
+    D: HistoricalDataSource + Clone + Sync,
+{
+    /// Creates a new optimizer.
+    ///
+    /// The backtest configuration always records the equity curve and
+    /// trades, since the metrics are computed from them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the space is empty or the
+    /// search method asks for no trials.
+    pub fn new(
+        space: ParameterSpace,
+        config: OptimizerConfig,
+        backtest_config: BacktestConfig,
+        data_source: D,
+        factory: F,
+    ) -> MMResult<Self> {
+        if space.is_empty() {
+            return Err(MMError::InvalidConfiguration(
+                "parameter space must not be empty".to_string(),
+            ));
+        }
+
+        match config.method {
+            SearchMethod::Grid => {}
+            SearchMethod::Random { samples, .. } => {
+                if samples == 0 {
+                    return Err(MMError::InvalidConfiguration(
+                        "random search needs at least one sample".to_string(),
+                    ));
+                }
+            }
+            SearchMethod::Bayesian {
+                initial_samples,
+                candidates,
+                ..
+            } => {
+                if initial_samples < 2 {
+                    return Err(MMError::InvalidConfiguration(
+                        "bayesian search needs at least two initial samples".to_string(),
+                    ));
+                }
+                if candidates == 0 {
+                    return Err(MMError::InvalidConfiguration(
+                        "bayesian search needs at least one candidate".to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            space,
+            config,
+            backtest_config: backtest_config
+                .with_record_equity_curve(true)
+                .with_record_trades(true),
+            data_source,
+            factory,
+        })
+    }
+
+    /// Returns the parameter space.
+    #[must_use]
+    pub fn space(&self) -> &ParameterSpace {
+        &self.space
+    }
+
+    /// Returns the optimizer configuration.
+    #[must_use]
+    pub fn config(&self) -> &OptimizerConfig {
+        &self.config
+    }
+
+    /// Runs the search and returns the ranked trials.
+    #[must_use]
+    pub fn run(&self) -> OptimizationReport {
+        let mut trials = Vec::new();
+        let mut failures = Vec::new();
+
+        match self.config.method {
+            SearchMethod::Grid => {
+                self.evaluate_into(self.space.grid(), &mut trials, &mut failures);
+            }
+            SearchMethod::Random { samples, seed } => {
+                let mut rng = Lcg::new(seed);
+                let sets = (0..samples)
+                    .map(|_| self.space.sample(&rng.point(self.space.len())))
+                    .collect();
+                self.evaluate_into(sets, &mut trials, &mut failures);
+            }
+            SearchMethod::Bayesian {
+                initial_samples,
+                iterations,
+                candidates,
+                seed,
+            } => {
+                let mut rng = Lcg::new(seed);
+                let sets = (0..initial_samples)
+                    .map(|_| self.space.sample(&rng.point(self.space.len())))
+                    .collect();
+                self.evaluate_into(sets, &mut trials, &mut failures);
+
+                let mut remaining = iterations;
+                while remaining > 0 {
+                    let batch = remaining.min(self.config.threads);
+                    let evaluated: Vec<&ParameterSet> = trials
+                        .iter()
+                        .map(|t| &t.parameters)
+                        .chain(failures.iter().map(|f| &f.parameters))
+                        .collect();
+                    let sets = self.propose(&trials, &evaluated, batch, candidates, &mut rng);
+                    if sets.is_empty() {
+                        break;
+                    }
+                    remaining -= sets.len();
+                    self.evaluate_into(sets, &mut trials, &mut failures);
+                }
+            }
+        }
+
+        OptimizationReport::new(self.config.objective, trials, failures)
+    }
+
+    /// Backtests a single parameter set.
+    ///
+    /// # Errors
+    ///
+    /// Returns the factory's error, or an error if the backtest produced no
+    /// equity curve.
+    pub fn evaluate(&self, parameters: &ParameterSet) -> MMResult<TrialResult> {
+        let strategy = (self.factory)(parameters)?;
+        let mut engine = BacktestEngine::new(
+            self.backtest_config.clone(),
+            strategy,
+            self.data_source.clone(),
+        );
+        let result = engine.run();
+
+        let metrics = MetricsCalculator::new(self.config.metrics.clone())
+            .calculate_from_backtest(&result, self.backtest_config.initial_capital)?;
+
+        Ok(TrialResult {
+            parameters: parameters.clone(),
+            score: self.config.objective.score(&metrics),
+            metrics,
+            net_pnl: result.net_pnl,
+            num_trades: result.num_trades,
+        })
+    }
+
+    /// Evaluates a batch across the worker threads, preserving order.
+    fn evaluate_batch(&self, sets: &[ParameterSet]) -> Vec<MMResult<TrialResult>> {
+        let threads = self.config.threads.min(sets.len()).max(1);
+        if threads == 1 {
+            return sets.iter().map(|set| self.evaluate(set)).collect();
+        }
+
+        let mut results: Vec<Option<MMResult<TrialResult>>> =
+            std::iter::repeat_with(|| None).take(sets.len()).collect();
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|worker| {
+                    scope.spawn(move || {
+                        sets.iter()
+                            .enumerate()
+                            .skip(worker)
+                            .step_by(threads)
+                            .map(|(i, set)| (i, self.evaluate(set)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            for handle in handles {
+                match handle.join() {
+                    Ok(worker_results) => {
+                        for (i, result) in worker_results {
+                            results[i] = Some(result);
+                        }
+                    }
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            }
+        });
+
+        results.into_iter().flatten().collect()
+    }
+
+    fn evaluate_into(
+        &self,
+        sets: Vec<ParameterSet>,
+        trials: &mut Vec<TrialResult>,
+        failures: &mut Vec<FailedTrial>,
+    ) {
+        let results = self.evaluate_batch(&sets);
+        for (parameters, result) in sets.into_iter().zip(results) {
+            match result {
+                Ok(trial) => trials.push(trial),
+                Err(e) => failures.push(FailedTrial {
+                    parameters,
+                    error: e.to_string(),
+                }),
+            }
+        }
+    }
+
+    /// Proposes up to `batch` unevaluated parameter sets.
+    ///
+    /// Trials are split into the best quarter and the rest, a Gaussian
+    /// kernel density is fitted to each in the unit hypercube, and the
+    /// candidates with the highest good-to-bad density ratio are kept.
+    fn propose(
+        &self,
+        trials: &[TrialResult],
+        evaluated: &[&ParameterSet],
+        batch: usize,
+        candidates: usize,
+        rng: &mut Lcg,
+    ) -> Vec<ParameterSet> {
+        let dims = self.space.len();
+
+        let mut ranked: Vec<&TrialResult> = trials.iter().collect();
+        ranked.sort_by_key(|t| std::cmp::Reverse(t.score));
+        let points: Vec<Vec<f64>> = ranked
+            .iter()
+            .map(|t| self.space.normalize(&t.parameters))
+            .collect();
+
+        let num_good = ((points.len() as f64 * GOOD_FRACTION).ceil() as usize).max(1);
+        let (good, bad) = points.split_at(num_good.min(points.len()));
+        let bandwidth =
+            (0.25 * (points.len().max(1) as f64).powf(-1.0 / (dims as f64 + 4.0))).max(0.02);
+
+        let mut scored: Vec<(f64, ParameterSet)> = Vec::with_capacity(candidates);
+        for i in 0..candidates {
+            // Half the candidates perturb a good point, half explore uniformly
+            let point = if i % 2 == 0 && !good.is_empty() {
+                let center = &good[rng.index(good.len())];
+                center
+                    .iter()
+                    .map(|c| (c + bandwidth * rng.gaussian()).clamp(0.0, 1.0))
+                    .collect()
+            } else {
+                rng.point(dims)
+            };
+
+            let set = self.space.sample(&point);
+            if evaluated.contains(&&set) || scored.iter().any(|(_, s)| *s == set) {
+                continue;
+            }
+
+            let point = self.space.normalize(&set);
+            let good_density = kernel_density(&point, good, bandwidth);
+            let bad_density = if bad.is_empty() {
+                1.0
+            } else {
+                kernel_density(&point, bad, bandwidth)
+            };
+            scored.push((good_density / (bad_density + f64::EPSILON), set));
+        }
+
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.into_iter().take(batch).map(|(_, set)| set).collect()
+    }
+}
+
+impl<F, D> fmt::Debug for BacktestOptimizer<F, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BacktestOptimizer")
+            .field("space", &self.space)
+            .field("config", &self.config)
+            .field("backtest_config", &self.backtest_config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Mean Gaussian kernel over `samples`, up to a constant factor.
+fn kernel_density(point: &[f64], samples: &[Vec<f64>], bandwidth: f64) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let denom = 2.0 * bandwidth * bandwidth;
+    let total: f64 = samples
+        .iter()
+        .map(|sample| {
+            let dist2: f64 = point
+                .iter()
+                .zip(sample)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum();
+            (-dist2 / denom).exp()
+        })
+        .sum();
+    total / samples.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::data::{MarketTick, VecDataSource};
+    use crate::backtest::engine::SimulatedFill;
+    use crate::dec;
+    use crate::position::inventory::InventoryPosition;
+    use crate::strategy::quote::Quote;
+
+    /// Quotes symmetrically around the previous tick's mid.
+    struct LaggedQuoter {
+        half_spread: Decimal,
+        last_mid: Option<Decimal>,
+    }
+
+    impl BacktestStrategy for LaggedQuoter {
+        fn on_tick(&mut self, tick: &MarketTick, _position: &InventoryPosition) -> Option<Quote> {
+            let quote = self.last_mid.map(|mid| Quote {
+                bid_price: mid - self.half_spread,
+                bid_size: Decimal::ONE,
+                ask_price: mid + self.half_spread,
+                ask_size: Decimal::ONE,
+                timestamp: tick.timestamp,
+            });
+            self.last_mid = Some(tick.mid_price());
+            quote
+        }
+
+        fn on_fill(&mut self, _fill: &SimulatedFill) {}
+
+        fn reset(&mut self) {
+            self.last_mid = None;
+        }
+    }
+
+    /// Mid alternates between 100 and 101, so round trips earn
+    /// `2 * half_spread - 1` for half spreads up to 0.99 and nothing above.
+    fn oscillating_ticks() -> VecDataSource {
+        let ticks = (0..40u64)
+            .map(|i| {
+                let mid = if i % 2 == 0 { dec!(100.0) } else { dec!(101.0) };
+                MarketTick::new(
+                    i * 3_600_000,
+                    mid - dec!(0.01),
+                    dec!(1.0),
+                    mid + dec!(0.01),
+                    dec!(1.0),
+                )
+            })
+            .collect();
+        VecDataSource::new(ticks)
+    }
+
+    fn optimizer(
+        space: ParameterSpace,
+        config: OptimizerConfig,
+    ) -> BacktestOptimizer<impl Fn(&ParameterSet) -> MMResult<LaggedQuoter> + Sync, VecDataSource>
+    {
+        BacktestOptimizer::new(
+            space,
+            config,
+            BacktestConfig::default(),
+            oscillating_ticks(),
+            |params: &ParameterSet| {
+                Ok(LaggedQuoter {
+                    half_spread: params.require("half_spread")?,
+                    last_mid: None,
+                })
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parameter_range() {
+        assert!(ParameterRange::values(vec![]).is_err());
+        assert!(ParameterRange::linear(dec!(2.0), dec!(1.0), 3).is_err());
+        assert!(ParameterRange::linear(dec!(1.0), dec!(2.0), 0).is_err());
+
+        let range = ParameterRange::linear(dec!(1.0), dec!(2.0), 3).unwrap();
+        assert_eq!(range.grid_values(), vec![dec!(1.0), dec!(1.5), dec!(2.0)]);
+        assert_eq!(range.sample(0.0), dec!(1.0));
+        assert_eq!(range.sample(1.0), dec!(2.0));
+        assert!((range.normalize(dec!(1.25)) - 0.25).abs() < 1e-12);
+
+        let single = ParameterRange::linear(dec!(1.0), dec!(1.0), 5).unwrap();
+        assert_eq!(single.num_points(), 1);
+        assert_eq!(single.grid_values(), vec![dec!(1.0)]);
+
+        let values = ParameterRange::values(vec![dec!(1), dec!(5), dec!(9)]).unwrap();
+        assert_eq!(values.sample(0.5), dec!(5));
+        assert_eq!(values.sample(0.999), dec!(9));
+        assert_eq!(values.sample(values.normalize(dec!(9))), dec!(9));
+    }
+
+    #[test]
+    fn test_parameter_space_grid() {
+        let space = ParameterSpace::new()
+            .with_parameter("a", ParameterRange::values(vec![dec!(1), dec!(2)]).unwrap())
+            .with_parameter("b", ParameterRange::linear(dec!(0), dec!(1), 3).unwrap())
+            .with_parameter("a", ParameterRange::values(vec![dec!(7), dec!(8)]).unwrap());
+
+        assert_eq!(space.len(), 2);
+        assert_eq!(space.names().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(space.grid_size(), 6);
+
+        let grid = space.grid();
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid[0].get("a"), Some(dec!(7)));
+        assert_eq!(grid[0].get("b"), Some(dec!(0)));
+        assert_eq!(grid[1].get("b"), Some(dec!(0.5)));
+        assert_eq!(grid[5].get("a"), Some(dec!(8)));
+        assert_eq!(grid[5].get("b"), Some(dec!(1)));
+        assert_eq!(grid[0].to_string(), "a=7, b=0");
+    }
+
+    #[test]
+    fn test_apply_to_strategy_config() {
+        let base = StrategyConfig::new(dec!(0.1), dec!(1.5), 1000, dec!(0.01)).unwrap();
+        let set = ParameterSet::new()
+            .with_value("risk_aversion", dec!(0.3))
+            .with_value("unrelated", dec!(42));
+
+        let config = set.apply_to_strategy_config(&base).unwrap();
+        assert_eq!(config.risk_aversion, dec!(0.3));
+        assert_eq!(config.order_intensity, dec!(1.5));
+        assert_eq!(config.min_spread, dec!(0.01));
+
+        let invalid = ParameterSet::new().with_value("order_intensity", dec!(-1));
+        assert!(invalid.apply_to_strategy_config(&base).is_err());
+    }
+
+    #[test]
+    fn test_grid_search_ranks_by_objective() {
+        let space = ParameterSpace::new().with_parameter(
+            "half_spread",
+            ParameterRange::values(vec![dec!(0.2), dec!(0.6), dec!(0.9), dec!(1.2)]).unwrap(),
+        );
+        let config = OptimizerConfig::new(SearchMethod::Grid, OptimizationObjective::TotalReturn)
+            .with_threads(1);
+
+        let report = optimizer(space, config).run();
+        assert_eq!(report.len(), 4);
+        assert!(report.failures.is_empty());
+
+        let order: Vec<Decimal> = report
+            .trials
+            .iter()
+            .map(|t| t.parameters.get("half_spread").unwrap())
+            .collect();
+        assert_eq!(order, vec![dec!(0.9), dec!(0.6), dec!(1.2), dec!(0.2)]);
+
+        let best = report.best().unwrap();
+        assert!(best.score > Decimal::ZERO);
+        assert!(best.num_trades > 0);
+        assert_eq!(report.top(2).len(), 2);
+        assert_eq!(report.top(10).len(), 4);
+
+        // The wide quote never trades
+        assert_eq!(report.trials[2].num_trades, 0);
+        assert!(report.trials[3].score < Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let space = ParameterSpace::new().with_parameter(
+            "half_spread",
+            ParameterRange::linear(dec!(0.1), dec!(1.5), 15).unwrap(),
+        );
+        let method = SearchMethod::Random {
+            samples: 20,
+            seed: 11,
+        };
+
+        let sequential = optimizer(
+            space.clone(),
+            OptimizerConfig::new(method, OptimizationObjective::TotalReturn).with_threads(1),
+        )
+        .run();
+        let parallel = optimizer(
+            space,
+            OptimizerConfig::new(method, OptimizationObjective::TotalReturn).with_threads(4),
+        )
+        .run();
+
+        assert_eq!(sequential.len(), 20);
+        assert_eq!(parallel.len(), 20);
+        for (a, b) in sequential.trials.iter().zip(&parallel.trials) {
+            assert_eq!(a.parameters, b.parameters);
+            assert_eq!(a.score, b.score);
+        }
+
+        for trial in &sequential.trials {
+            let h = trial.parameters.get("half_spread").unwrap();
+            assert!(h >= dec!(0.1) && h <= dec!(1.5));
+        }
+    }
+
+    #[test]
+    fn test_bayesian_search_finds_profitable_region() {
+        let space = ParameterSpace::new().with_parameter(
+            "half_spread",
+            ParameterRange::linear(dec!(0.1), dec!(1.5), 2).unwrap(),
+        );
+        let config = OptimizerConfig::new(
+            SearchMethod::Bayesian {
+                initial_samples: 6,
+                iterations: 18,
+                candidates: 64,
+                seed: 3,
+            },
+            OptimizationObjective::TotalReturn,
+        )
+        .with_threads(2);
+
+        let report = optimizer(space, config).run();
+        assert_eq!(report.len(), 24);
+
+        let best = report.best().unwrap();
+        let h = best.parameters.get("half_spread").unwrap();
+        assert!(h > dec!(0.8) && h <= dec!(0.99), "best half spread {h}");
+
+        // Model-guided trials concentrate in the profitable region
+        let guided_profitable = report
+            .trials
+            .iter()
+            .filter(|t| t.score > Decimal::ZERO)
+            .count();
+        assert!(guided_profitable >= 10);
+    }
+
+    #[test]
+    fn test_failures_and_validation() {
+        let space = ParameterSpace::new().with_parameter(
+            "other",
+            ParameterRange::values(vec![dec!(1), dec!(2)]).unwrap(),
+        );
+        let report = optimizer(space, OptimizerConfig::default().with_threads(2)).run();
+        assert!(report.is_empty());
+        assert_eq!(report.failures.len(), 2);
+        assert!(report.failures[0].error.contains("half_spread"));
+
+        let factory = |_: &ParameterSet| -> MMResult<LaggedQuoter> { unreachable!() };
+        assert!(
+            BacktestOptimizer::new(
+                ParameterSpace::new(),
+                OptimizerConfig::default(),
+                BacktestConfig::default(),
+                oscillating_ticks(),
+                factory,
+            )
+            .is_err()
+        );
+
+        let space = ParameterSpace::new()
+            .with_parameter("x", ParameterRange::values(vec![dec!(1)]).unwrap());
+        let random = OptimizerConfig::new(
+            SearchMethod::Random {
+                samples: 0,
+                seed: 1,
+            },
+            OptimizationObjective::SharpeRatio,
+        );
+        assert!(
+            BacktestOptimizer::new(
+                space,
+                random,
+                BacktestConfig::default(),
+                oscillating_ticks(),
+                factory,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_objective_scores_and_report_display() {
+        let metrics = PerformanceMetrics {
+            total_return: dec!(12.0),
+            sharpe_ratio: dec!(1.5),
+            max_drawdown: dec!(0.2),
+            ..Default::default()
+        };
+        assert_eq!(
+            OptimizationObjective::TotalReturn.score(&metrics),
+            dec!(12.0)
+        );
+        assert_eq!(
+            OptimizationObjective::SharpeRatio.score(&metrics),
+            dec!(1.5)
+        );
+        assert_eq!(
+            OptimizationObjective::MaxDrawdown.score(&metrics),
+            dec!(-0.2)
+        );
+
+        let space = ParameterSpace::new().with_parameter(
+            "half_spread",
+            ParameterRange::values(vec![dec!(0.6), dec!(0.9)]).unwrap(),
+        );
+        let report = optimizer(
+            space,
+            OptimizerConfig::new(SearchMethod::Grid, OptimizationObjective::TotalReturn),
+        )
+        .run();
+
+        let text = report.to_string();
+        assert!(text.starts_with("objective: total_return (2 trials, 0 failed)"));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[2].trim_start().starts_with('1'));
+        assert!(lines[2].ends_with("half_spread=0.9"));
+    }
+}
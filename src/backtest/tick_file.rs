@@ -0,0 +1,589 @@
+//! Compact binary tick files.
+//!
+//! A tick file is a 16-byte header followed by fixed-size 56-byte records, all
+//! little-endian:
+//!
+//! | Offset | Size | Field                                              |
+//! |--------|------|----------------------------------------------------|
+//! | 0      | 8    | Magic `MMTICKS\0`                                  |
+//! | 8      | 2    | Format version (currently 1)                       |
+//! | 10     | 1    | Price decimal places                               |
+//! | 11     | 1    | Size decimal places                                |
+//! | 12     | 4    | Reserved, zero                                     |
+//!
+//! Each record holds the timestamp in milliseconds as a `u64` followed by
+//! bid price, bid size, ask price, ask size, last price and last size as
+//! `i64` fixed-point values scaled by the header's decimal places. A missing
+//! last trade is stored as `i64::MIN`.
+//!
+//! [`TickFileWriter`] appends ticks as they arrive, e.g. from a live feed,
+//! and never rewrites earlier bytes, so a file cut short by a crash stays
+//! readable up to its last complete record. [`TickFileSource`] replays a file
+//! lazily through any `Read + Seek`, one record at a time. Because records
+//! have a fixed size, a file can also be memory-mapped by the caller and the
+//! map wrapped in `std::io::Cursor` for replay straight from the page cache.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::backtest::{HistoricalDataSource, MarketTick, TickFileSource, TickFileWriter};
+//! use market_maker_rs::dec;
+//! use std::io::Cursor;
+//!
+//! let mut writer = TickFileWriter::new(Vec::new(), 2, 4).unwrap();
+//! writer
+//!     .write_tick(&MarketTick::new(1000, dec!(100.05), dec!(1.5), dec!(100.10), dec!(2)))
+//!     .unwrap();
+//! let bytes = writer.into_inner().unwrap();
+//! assert_eq!(bytes.len(), 16 + 56);
+//!
+//! let mut source = TickFileSource::from_reader(Cursor::new(bytes)).unwrap();
+//! assert_eq!(source.len(), 1);
+//! assert_eq!(source.next_tick().unwrap().bid_price, dec!(100.05));
+//! ```
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::Decimal;
+use crate::types::error::{MMError, MMResult};
+
+use super::data::{HistoricalDataSource, MarketTick};
+
+/// Magic bytes at the start of every tick file.
+pub const TICK_FILE_MAGIC: [u8; 8] = *b"MMTICKS\0";
+
+/// Current tick file format version.
+pub const TICK_FILE_VERSION: u16 = 1;
+
+/// Size of the file header in bytes.
+pub const TICK_FILE_HEADER_SIZE: usize = 16;
+
+/// Size of one tick record in bytes.
+pub const TICK_RECORD_SIZE: usize = 56;
+
+/// Largest supported number of decimal places.
+const MAX_DECIMALS: u8 = 18;
+
+/// Sentinel for a missing last trade.
+const NO_VALUE: i64 = i64::MIN;
+
+fn io_error(error: std::io::Error) -> MMError {
+    MMError::InvalidConfiguration(format!("tick file I/O failed: {error}"))
+}
+
+fn open_error(path: &Path, error: std::io::Error) -> MMError {
+    MMError::InvalidConfiguration(format!("cannot open {}: {error}", path.display()))
+}
+
+/// Converts a decimal to a fixed-point integer without losing precision.
+fn encode(value: Decimal, decimals: u8, field: &str) -> MMResult<i64> {
+    let mut scaled = value;
+    scaled.rescale(u32::from(decimals));
+    if scaled != value {
+        return Err(MMError::InvalidMarketState(format!(
+            "{field} {value} has more than {decimals} decimal places"
+        )));
+    }
+    i64::try_from(scaled.mantissa())
+        .ok()
+        .filter(|mantissa| *mantissa != NO_VALUE)
+        .ok_or_else(|| {
+            MMError::InvalidMarketState(format!(
+                "{field} {value} does not fit the tick file format"
+            ))
+        })
+}
+
+fn decode(value: i64, decimals: u8) -> Decimal {
+    Decimal::new(value, u32::from(decimals))
+}
+
+/// Streams ticks into the binary tick file format.
+///
+/// Ticks must be written in timestamp order, and prices and sizes must fit
+/// the decimal places chosen when the file was created.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::{MarketTick, TickFileWriter};
+/// use market_maker_rs::dec;
+///
+/// let mut writer = TickFileWriter::new(Vec::new(), 2, 0).unwrap();
+/// assert!(writer.write_tick(&MarketTick::new(1, dec!(1.001), dec!(1), dec!(1.01), dec!(1))).is_err());
+/// assert_eq!(writer.ticks_written(), 0);
+/// ```
+#[derive(Debug)]
+pub struct TickFileWriter<W: Write = BufWriter<File>> {
+    writer: W,
+    price_decimals: u8,
+    size_decimals: u8,
+    ticks_written: u64,
+    last_timestamp: u64,
+}
+
+impl TickFileWriter {
+    /// Creates a tick file at `path`, truncating any existing file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or the decimal places
+    /// exceed 18.
+    pub fn create(path: impl AsRef<Path>, price_decimals: u8, size_decimals: u8) -> MMResult<Self> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|e| open_error(path, e))?;
+        Self::new(BufWriter::new(file), price_decimals, size_decimals)
+    }
+}
+
+impl<W: Write> TickFileWriter<W> {
+    /// Creates a writer and writes the file header.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Destination of the file bytes
+    /// * `price_decimals` - Decimal places stored for prices
+    /// * `size_decimals` - Decimal places stored for sizes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the decimal places exceed 18 or the header cannot
+    /// be written.
+    pub fn new(mut writer: W, price_decimals: u8, size_decimals: u8) -> MMResult<Self> {
+        if price_decimals > MAX_DECIMALS || size_decimals > MAX_DECIMALS {
+            return Err(MMError::InvalidConfiguration(format!(
+                "tick files support at most {MAX_DECIMALS} decimal places"
+            )));
+        }
+
+        let mut header = [0u8; TICK_FILE_HEADER_SIZE];
+        header[..8].copy_from_slice(&TICK_FILE_MAGIC);
+        header[8..10].copy_from_slice(&TICK_FILE_VERSION.to_le_bytes());
+        header[10] = price_decimals;
+        header[11] = size_decimals;
+        writer.write_all(&header).map_err(io_error)?;
+
+        Ok(Self {
+            writer,
+            price_decimals,
+            size_decimals,
+            ticks_written: 0,
+            last_timestamp: 0,
+        })
+    }
+
+    /// Appends one tick.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidTimestamp` for a tick older than the previous
+    /// one, `MMError::InvalidMarketState` for a value that cannot be stored
+    /// exactly, or an I/O error. Nothing is written on error.
+    pub fn write_tick(&mut self, tick: &MarketTick) -> MMResult<()> {
+        if tick.timestamp < self.last_timestamp {
+            return Err(MMError::InvalidTimestamp(format!(
+                "tick at {} is older than the previous tick at {}",
+                tick.timestamp, self.last_timestamp
+            )));
+        }
+
+        let price = |value: Decimal, field: &str| encode(value, self.price_decimals, field);
+        let size = |value: Decimal, field: &str| encode(value, self.size_decimals, field);
+        let fields = [
+            price(tick.bid_price, "bid price")?,
+            size(tick.bid_size, "bid size")?,
+            price(tick.ask_price, "ask price")?,
+            size(tick.ask_size, "ask size")?,
+            tick.last_price
+                .map_or(Ok(NO_VALUE), |value| price(value, "last price"))?,
+            tick.last_size
+                .map_or(Ok(NO_VALUE), |value| size(value, "last size"))?,
+        ];
+
+        let mut record = [0u8; TICK_RECORD_SIZE];
+        record[..8].copy_from_slice(&tick.timestamp.to_le_bytes());
+        for (i, value) in fields.iter().enumerate() {
+            let start = 8 + i * 8;
+            record[start..start + 8].copy_from_slice(&value.to_le_bytes());
+        }
+        self.writer.write_all(&record).map_err(io_error)?;
+
+        self.ticks_written += 1;
+        self.last_timestamp = tick.timestamp;
+        Ok(())
+    }
+
+    /// Returns the number of ticks written so far.
+    #[must_use]
+    pub fn ticks_written(&self) -> u64 {
+        self.ticks_written
+    }
+
+    /// Flushes buffered records to the destination.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the destination cannot be flushed.
+    pub fn flush(&mut self) -> MMResult<()> {
+        self.writer.flush().map_err(io_error)
+    }
+
+    /// Flushes and returns the destination.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the destination cannot be flushed.
+    pub fn into_inner(mut self) -> MMResult<W> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Lazily replays a binary tick file.
+///
+/// Implements [`HistoricalDataSource`]; only the next tick is held in memory.
+/// The tick count comes from the data length, and a trailing partial record
+/// is ignored.
+///
+/// To replay from a memory map, wrap it in a cursor:
+///
+/// ```rust,ignore
+/// let map = unsafe { memmap2::Mmap::map(&File::open("ticks.bin")?)? };
+/// let source = TickFileSource::from_reader(std::io::Cursor::new(map))?;
+/// ```
+#[derive(Debug)]
+pub struct TickFileSource<R = BufReader<File>> {
+    reader: R,
+    price_decimals: u8,
+    size_decimals: u8,
+    len: usize,
+    consumed: usize,
+    next: Option<MarketTick>,
+    error: Option<MMError>,
+}
+
+impl TickFileSource {
+    /// Opens a tick file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or has no valid header.
+    pub fn open(path: impl AsRef<Path>) -> MMResult<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| open_error(path, e))?;
+        Self::from_reader(BufReader::new(file))
+    }
+}
+
+impl<R: Read + Seek> TickFileSource<R> {
+    /// Creates a source over any seekable reader positioned anywhere.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data has no valid header or an unsupported
+    /// version.
+    pub fn from_reader(mut reader: R) -> MMResult<Self> {
+        let total = reader.seek(SeekFrom::End(0)).map_err(io_error)?;
+        reader.seek(SeekFrom::Start(0)).map_err(io_error)?;
+
+        let mut header = [0u8; TICK_FILE_HEADER_SIZE];
+        reader.read_exact(&mut header).map_err(|e| {
+            if e.kind() == ErrorKind::UnexpectedEof {
+                MMError::InvalidConfiguration("tick file is shorter than its header".to_string())
+            } else {
+                io_error(e)
+            }
+        })?;
+        if header[..8] != TICK_FILE_MAGIC {
+            return Err(MMError::InvalidConfiguration(
+                "data is not a tick file".to_string(),
+            ));
+        }
+        let version = u16::from_le_bytes([header[8], header[9]]);
+        if version != TICK_FILE_VERSION {
+            return Err(MMError::InvalidConfiguration(format!(
+                "unsupported tick file version {version}"
+            )));
+        }
+        let (price_decimals, size_decimals) = (header[10], header[11]);
+        if price_decimals > MAX_DECIMALS || size_decimals > MAX_DECIMALS {
+            return Err(MMError::InvalidConfiguration(
+                "tick file header has invalid decimal places".to_string(),
+            ));
+        }
+
+        let records = (total - TICK_FILE_HEADER_SIZE as u64) / TICK_RECORD_SIZE as u64;
+        let mut source = Self {
+            reader,
+            price_decimals,
+            size_decimals,
+            len: records as usize,
+            consumed: 0,
+            next: None,
+            error: None,
+        };
+        source.fill();
+        Ok(source)
+    }
+
+    /// Returns the error that stopped iteration early, if any.
+    #[must_use]
+    pub fn error(&self) -> Option<&MMError> {
+        self.error.as_ref()
+    }
+
+    /// Positions the source at the first tick at or after `timestamp`.
+    ///
+    /// Records are fixed-size and in timestamp order, so this is a binary
+    /// search reading O(log n) records.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reader fails.
+    pub fn seek_to_timestamp(&mut self, timestamp: u64) -> MMResult<()> {
+        let (mut low, mut high) = (0, self.len);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            self.seek_record(mid)?;
+            let tick = self.read_record()?.ok_or_else(|| {
+                MMError::InvalidConfiguration("tick file changed while reading".to_string())
+            })?;
+            if tick.timestamp < timestamp {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        self.seek_record(low)?;
+        self.consumed = low;
+        self.error = None;
+        self.fill();
+        Ok(())
+    }
+
+    fn seek_record(&mut self, index: usize) -> MMResult<()> {
+        let offset = TICK_FILE_HEADER_SIZE as u64 + (index * TICK_RECORD_SIZE) as u64;
+        self.reader
+            .seek(SeekFrom::Start(offset))
+            .map_err(io_error)?;
+        Ok(())
+    }
+
+    fn read_record(&mut self) -> MMResult<Option<MarketTick>> {
+        let mut record = [0u8; TICK_RECORD_SIZE];
+        match self.reader.read_exact(&mut record) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(io_error(e)),
+        }
+
+        let word = |i: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&record[i * 8..i * 8 + 8]);
+            bytes
+        };
+        let value = |i: usize| i64::from_le_bytes(word(i));
+        let optional = |i: usize, decimals: u8| {
+            let raw = value(i);
+            (raw != NO_VALUE).then(|| decode(raw, decimals))
+        };
+
+        let mut tick = MarketTick::new(
+            u64::from_le_bytes(word(0)),
+            decode(value(1), self.price_decimals),
+            decode(value(2), self.size_decimals),
+            decode(value(3), self.price_decimals),
+            decode(value(4), self.size_decimals),
+        );
+        tick.last_price = optional(5, self.price_decimals);
+        tick.last_size = optional(6, self.size_decimals);
+        Ok(Some(tick))
+    }
+
+    /// Reads the look-ahead tick, recording any error.
+    fn fill(&mut self) {
+        self.next = if self.consumed < self.len {
+            match self.read_record() {
+                Ok(tick) => tick,
+                Err(e) => {
+                    self.error = Some(e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+    }
+}
+
+impl<R: Read + Seek> HistoricalDataSource for TickFileSource<R> {
+    fn next_tick(&mut self) -> Option<MarketTick> {
+        let tick = self.next.take()?;
+        self.consumed += 1;
+        self.fill();
+        Some(tick)
+    }
+
+    fn peek_tick(&self) -> Option<&MarketTick> {
+        self.next.as_ref()
+    }
+
+    fn reset(&mut self) {
+        if let Err(e) = self.seek_to_timestamp(0) {
+            self.next = None;
+            self.error = Some(e);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn remaining(&self) -> usize {
+        self.len.saturating_sub(self.consumed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+    use std::io::Cursor;
+
+    fn sample_ticks() -> Vec<MarketTick> {
+        (0..10u64)
+            .map(|i| {
+                let bid = dec!(100.00) + Decimal::from(i) * dec!(0.25);
+                if i % 3 == 0 {
+                    MarketTick::with_last_trade(
+                        1_000 + i * 10,
+                        bid,
+                        dec!(1.5),
+                        bid + dec!(0.05),
+                        dec!(0.001),
+                        bid,
+                        dec!(0.25),
+                    )
+                } else {
+                    MarketTick::new(1_000 + i * 10, bid, dec!(2), bid + dec!(0.05), dec!(3))
+                }
+            })
+            .collect()
+    }
+
+    fn encode_all(ticks: &[MarketTick]) -> Vec<u8> {
+        let mut writer = TickFileWriter::new(Vec::new(), 2, 3).unwrap();
+        for tick in ticks {
+            writer.write_tick(tick).unwrap();
+        }
+        assert_eq!(writer.ticks_written(), ticks.len() as u64);
+        writer.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let ticks = sample_ticks();
+        let bytes = encode_all(&ticks);
+        assert_eq!(
+            bytes.len(),
+            TICK_FILE_HEADER_SIZE + ticks.len() * TICK_RECORD_SIZE
+        );
+
+        let mut source = TickFileSource::from_reader(Cursor::new(bytes)).unwrap();
+        assert_eq!(source.len(), ticks.len());
+        let replayed: Vec<_> = std::iter::from_fn(|| source.next_tick()).collect();
+        assert_eq!(replayed, ticks);
+        assert_eq!(source.remaining(), 0);
+
+        source.reset();
+        assert_eq!(source.peek_tick(), Some(&ticks[0]));
+        assert!(source.error().is_none());
+    }
+
+    #[test]
+    fn test_seek_and_truncated_file() {
+        let ticks = sample_ticks();
+        let mut bytes = encode_all(&ticks);
+        // A crash mid-write leaves a partial record behind.
+        bytes.truncate(bytes.len() - 20);
+
+        let mut source = TickFileSource::from_reader(Cursor::new(bytes)).unwrap();
+        assert_eq!(source.len(), ticks.len() - 1);
+
+        source.seek_to_timestamp(1_045).unwrap();
+        assert_eq!(source.remaining(), 4);
+        assert_eq!(source.next_tick().unwrap(), ticks[5]);
+
+        source.seek_to_timestamp(u64::MAX).unwrap();
+        assert!(source.next_tick().is_none());
+    }
+
+    #[test]
+    fn test_writer_rejects_unrepresentable_ticks() {
+        let mut writer = TickFileWriter::new(Vec::new(), 2, 3).unwrap();
+        writer
+            .write_tick(&MarketTick::new(10, dec!(1), dec!(1), dec!(2), dec!(1)))
+            .unwrap();
+
+        let err = writer
+            .write_tick(&MarketTick::new(9, dec!(1), dec!(1), dec!(2), dec!(1)))
+            .unwrap_err();
+        assert!(matches!(err, MMError::InvalidTimestamp(_)));
+        let err = writer
+            .write_tick(&MarketTick::new(
+                11,
+                dec!(1),
+                dec!(0.0001),
+                dec!(2),
+                dec!(1),
+            ))
+            .unwrap_err();
+        assert!(err.is_market_state_error());
+        let err = writer
+            .write_tick(&MarketTick::new(
+                11,
+                dec!(100000000000000000),
+                dec!(1),
+                dec!(2),
+                dec!(1),
+            ))
+            .unwrap_err();
+        assert!(err.is_market_state_error());
+        assert_eq!(writer.ticks_written(), 1);
+
+        assert!(TickFileWriter::new(Vec::new(), 19, 0).is_err());
+    }
+
+    #[test]
+    fn test_reader_rejects_bad_headers() {
+        assert!(TickFileSource::from_reader(Cursor::new(vec![0u8; 4])).is_err());
+        assert!(TickFileSource::from_reader(Cursor::new(vec![0u8; 16])).is_err());
+
+        let mut bytes = encode_all(&[]);
+        bytes[8] = 2;
+        assert!(TickFileSource::from_reader(Cursor::new(bytes)).is_err());
+
+        let empty = TickFileSource::from_reader(Cursor::new(encode_all(&[]))).unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.peek_tick().is_none());
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let path = std::env::temp_dir().join(format!("mm-ticks-{}.bin", std::process::id()));
+        let ticks = sample_ticks();
+        let mut writer = TickFileWriter::create(&path, 2, 3).unwrap();
+        for tick in &ticks {
+            writer.write_tick(tick).unwrap();
+        }
+        writer.flush().unwrap();
+        drop(writer);
+
+        let mut source = TickFileSource::open(&path).unwrap();
+        assert_eq!(source.len(), ticks.len());
+        assert_eq!(source.next_tick().unwrap(), ticks[0]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
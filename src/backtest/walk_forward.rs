@@ -0,0 +1,836 @@
+//! Walk-forward analysis and out-of-sample validation.
+//!
+//! History is split into consecutive in-sample / out-of-sample windows. On
+//! each in-sample window the strategy parameters are calibrated, either by a
+//! caller-supplied procedure or by a [`BacktestOptimizer`] search, and the
+//! chosen parameters are then backtested on the following out-of-sample
+//! window, which the calibration never saw.
+//!
+//! The out-of-sample runs are stitched into one equity curve whose
+//! [`PerformanceMetrics`] describe how the calibrated strategy would have
+//! performed live. Comparing them with the in-sample metrics, e.g. through
+//! [`WalkForwardReport::efficiency`], shows how much of the in-sample
+//! performance was overfitting.
+//!
+//! Each out-of-sample run starts flat with a fresh strategy; any inventory
+//! left at the end of a window is valued at the last mid, as if liquidated
+//! there.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::backtest::{
+//!     BacktestConfig, MarketTick, OptimizationObjective, OptimizerConfig, ParameterRange,
+//!     ParameterSpace, QuotingStrategyAdapter, SearchMethod, VecDataSource, WalkForwardAnalyzer,
+//!     WalkForwardConfig,
+//! };
+//! use market_maker_rs::strategy::config::StrategyConfig;
+//! use market_maker_rs::strategy::quoting::QuotingStrategyConfig;
+//! use market_maker_rs::dec;
+//!
+//! let ticks: Vec<MarketTick> = (0..120u64)
+//!     .map(|i| {
+//!         let mid = if i % 2 == 0 { dec!(100.0) } else { dec!(100.5) };
+//!         MarketTick::new(i * 1000, mid - dec!(0.01), dec!(1.0), mid + dec!(0.01), dec!(1.0))
+//!     })
+//!     .collect();
+//!
+//! let base = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+//! let analyzer = WalkForwardAnalyzer::new(
+//!     WalkForwardConfig::new(60_000, 20_000).unwrap(),
+//!     BacktestConfig::default(),
+//!     VecDataSource::new(ticks),
+//!     |params| {
+//!         let config = params.apply_to_strategy_config(&base)?;
+//!         let strategy = QuotingStrategyConfig::AvellanedaStoikov {
+//!             config,
+//!             order_size: dec!(1.0),
+//!         }
+//!         .build()?;
+//!         Ok(QuotingStrategyAdapter::new(strategy, dec!(0.2)))
+//!     },
+//! );
+//!
+//! let space = ParameterSpace::new()
+//!     .with_parameter("risk_aversion", ParameterRange::linear(dec!(0.05), dec!(0.5), 3).unwrap());
+//! let report = analyzer
+//!     .optimize(
+//!         &space,
+//!         &OptimizerConfig::new(SearchMethod::Grid, OptimizationObjective::TotalReturn),
+//!     )
+//!     .unwrap();
+//!
+//! assert_eq!(report.windows.len(), 3);
+//! println!("Out-of-sample return: {}", report.metrics.total_return);
+//! ```
+
+use crate::Decimal;
+use crate::backtest::data::{MarketTick, VecDataSource};
+use crate::backtest::engine::{BacktestConfig, BacktestEngine, BacktestResult, BacktestStrategy};
+use crate::backtest::metrics::{
+    EquityPoint, MetricsCalculator, MetricsConfig, PerformanceMetrics, TradeRecord,
+};
+use crate::backtest::optimization::{
+    BacktestOptimizer, OptimizerConfig, ParameterSet, ParameterSpace,
+};
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Window layout for walk-forward analysis.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::WalkForwardConfig;
+///
+/// // One day in sample, one hour out of sample, expanding in-sample window
+/// let config = WalkForwardConfig::new(86_400_000, 3_600_000)
+///     .unwrap()
+///     .with_anchored(true);
+///
+/// let windows = config.windows(0, 86_400_000 + 3 * 3_600_000 - 1).unwrap();
+/// assert_eq!(windows.len(), 3);
+/// assert_eq!(windows[2].in_sample_start, 0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WalkForwardConfig {
+    /// In-sample window length in milliseconds.
+    pub in_sample_ms: u64,
+    /// Out-of-sample window length in milliseconds.
+    pub out_of_sample_ms: u64,
+    /// Distance between consecutive windows in milliseconds.
+    ///
+    /// Defaults to the out-of-sample length, so the out-of-sample windows
+    /// tile the history. It must not be shorter than the out-of-sample
+    /// length: overlapping windows would count the same PnL twice.
+    pub step_ms: u64,
+    /// Keep the in-sample start fixed at the beginning of the history.
+    pub anchored: bool,
+    /// Minimum number of in-sample ticks; shorter windows are skipped.
+    pub min_in_sample_ticks: usize,
+}
+
+impl WalkForwardConfig {
+    /// Creates a rolling walk-forward configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if either length is zero.
+    pub fn new(in_sample_ms: u64, out_of_sample_ms: u64) -> MMResult<Self> {
+        if in_sample_ms == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "in_sample_ms must be positive".to_string(),
+            ));
+        }
+        if out_of_sample_ms == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "out_of_sample_ms must be positive".to_string(),
+            ));
+        }
+        Ok(Self {
+            in_sample_ms,
+            out_of_sample_ms,
+            step_ms: out_of_sample_ms,
+            anchored: false,
+            min_in_sample_ticks: 2,
+        })
+    }
+
+    /// Sets the distance between windows; zero is ignored.
+    ///
+    /// A step shorter than the out-of-sample length is rejected when the
+    /// windows are laid out.
+    #[must_use]
+    pub fn with_step(mut self, step_ms: u64) -> Self {
+        if step_ms > 0 {
+            self.step_ms = step_ms;
+        }
+        self
+    }
+
+    /// Switches between an expanding (anchored) and a rolling in-sample window.
+    #[must_use]
+    pub fn with_anchored(mut self, anchored: bool) -> Self {
+        self.anchored = anchored;
+        self
+    }
+
+    /// Sets the minimum number of in-sample ticks.
+    #[must_use]
+    pub fn with_min_in_sample_ticks(mut self, ticks: usize) -> Self {
+        self.min_in_sample_ticks = ticks;
+        self
+    }
+
+    /// Lays out the windows covering `[start, end]`.
+    ///
+    /// The last out-of-sample window is truncated at `end`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if a window length is zero
+    /// or the step is shorter than the out-of-sample length.
+    pub fn windows(&self, start: u64, end: u64) -> MMResult<Vec<WalkForwardWindow>> {
+        self.validate()?;
+        let mut windows = Vec::new();
+        let mut offset = 0u64;
+
+        loop {
+            let out_of_sample_start = start
+                .saturating_add(self.in_sample_ms)
+                .saturating_add(offset);
+            if out_of_sample_start > end {
+                break;
+            }
+            let in_sample_start = if self.anchored {
+                start
+            } else {
+                start.saturating_add(offset)
+            };
+
+            windows.push(WalkForwardWindow {
+                index: windows.len(),
+                in_sample_start,
+                in_sample_end: out_of_sample_start,
+                out_of_sample_start,
+                out_of_sample_end: out_of_sample_start
+                    .saturating_add(self.out_of_sample_ms)
+                    .min(end.saturating_add(1)),
+            });
+            offset = offset.saturating_add(self.step_ms);
+        }
+
+        Ok(windows)
+    }
+
+    fn validate(&self) -> MMResult<()> {
+        if self.in_sample_ms == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "in_sample_ms must be positive".to_string(),
+            ));
+        }
+        if self.out_of_sample_ms == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "out_of_sample_ms must be positive".to_string(),
+            ));
+        }
+        if self.step_ms < self.out_of_sample_ms {
+            return Err(MMError::InvalidConfiguration(
+                "step_ms must be at least out_of_sample_ms".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// One in-sample / out-of-sample split.
+///
+/// Both ranges are half-open: `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WalkForwardWindow {
+    /// Window number, starting at zero.
+    pub index: usize,
+    /// In-sample start timestamp in milliseconds.
+    pub in_sample_start: u64,
+    /// In-sample end timestamp in milliseconds.
+    pub in_sample_end: u64,
+    /// Out-of-sample start timestamp in milliseconds.
+    pub out_of_sample_start: u64,
+    /// Out-of-sample end timestamp in milliseconds.
+    pub out_of_sample_end: u64,
+}
+
+impl WalkForwardWindow {
+    /// Returns the in-sample ticks of a time-ordered series.
+    #[must_use]
+    pub fn in_sample<'a>(&self, ticks: &'a [MarketTick]) -> &'a [MarketTick] {
+        slice_between(ticks, self.in_sample_start, self.in_sample_end)
+    }
+
+    /// Returns the out-of-sample ticks of a time-ordered series.
+    #[must_use]
+    pub fn out_of_sample<'a>(&self, ticks: &'a [MarketTick]) -> &'a [MarketTick] {
+        slice_between(ticks, self.out_of_sample_start, self.out_of_sample_end)
+    }
+}
+
+/// Calibration and validation outcome of one window.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindowResult {
+    /// Window layout.
+    pub window: WalkForwardWindow,
+    /// Parameters calibrated on the in-sample data.
+    pub parameters: ParameterSet,
+    /// Metrics of the calibrated parameters on the in-sample data.
+    pub in_sample_metrics: PerformanceMetrics,
+    /// Metrics of the calibrated parameters on the out-of-sample data.
+    pub out_of_sample_metrics: PerformanceMetrics,
+    /// Out-of-sample backtest result.
+    pub out_of_sample: BacktestResult,
+}
+
+/// Stitched out-of-sample results of a walk-forward run.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WalkForwardReport {
+    /// Per-window results in time order.
+    pub windows: Vec<WindowResult>,
+    /// Out-of-sample equity curve, chained across windows.
+    pub equity_curve: Vec<EquityPoint>,
+    /// Round trips of all out-of-sample windows.
+    pub trades: Vec<TradeRecord>,
+    /// Metrics of the stitched out-of-sample run.
+    pub metrics: PerformanceMetrics,
+}
+
+impl WalkForwardReport {
+    /// Returns the walk-forward efficiency.
+    ///
+    /// Mean out-of-sample annualized return divided by mean in-sample
+    /// annualized return. Values near one mean the in-sample performance
+    /// carried over; values near zero or negative point to overfitting.
+    /// Returns `None` if the in-sample return is not positive.
+    #[must_use]
+    pub fn efficiency(&self) -> Option<Decimal> {
+        if self.windows.is_empty() {
+            return None;
+        }
+        let n = Decimal::from(self.windows.len());
+        let in_sample = self
+            .windows
+            .iter()
+            .map(|w| w.in_sample_metrics.annualized_return)
+            .sum::<Decimal>()
+            / n;
+        if in_sample <= Decimal::ZERO {
+            return None;
+        }
+        let out_of_sample = self
+            .windows
+            .iter()
+            .map(|w| w.out_of_sample_metrics.annualized_return)
+            .sum::<Decimal>()
+            / n;
+        Some(out_of_sample / in_sample)
+    }
+
+    /// Returns the fraction of windows with a positive out-of-sample return.
+    #[must_use]
+    pub fn profitable_window_ratio(&self) -> Decimal {
+        if self.windows.is_empty() {
+            return Decimal::ZERO;
+        }
+        let profitable = self
+            .windows
+            .iter()
+            .filter(|w| w.out_of_sample_metrics.total_return > Decimal::ZERO)
+            .count();
+        Decimal::from(profitable) / Decimal::from(self.windows.len())
+    }
+}
+
+/// Runs walk-forward analysis over an in-memory tick history.
+///
+/// `factory` builds a fresh strategy from calibrated parameters for every
+/// backtest, in sample and out of sample alike.
+pub struct WalkForwardAnalyzer<F> {
+    config: WalkForwardConfig,
+    backtest_config: BacktestConfig,
+    metrics_config: MetricsConfig,
+    data_source: VecDataSource,
+    factory: F,
+}
+
+impl<S, F> WalkForwardAnalyzer<F>
+where
+    S: BacktestStrategy,
+    F: Fn(&ParameterSet) -> MMResult<S>,
+{
+    /// Creates a new analyzer.
+    ///
+    /// The ticks must be in time order. The backtest configuration always
+    /// records the equity curve and trades, since the metrics are computed
+    /// from them.
+    #[must_use]
+    pub fn new(
+        config: WalkForwardConfig,
+        backtest_config: BacktestConfig,
+        data_source: VecDataSource,
+        factory: F,
+    ) -> Self {
+        Self {
+            config,
+            backtest_config: backtest_config
+                .with_record_equity_curve(true)
+                .with_record_trades(true),
+            metrics_config: MetricsConfig::default(),
+            data_source,
+            factory,
+        }
+    }
+
+    /// Sets the configuration of the computed metrics.
+    #[must_use]
+    pub fn with_metrics_config(mut self, metrics_config: MetricsConfig) -> Self {
+        self.metrics_config = metrics_config;
+        self
+    }
+
+    /// Returns the window configuration.
+    #[must_use]
+    pub fn config(&self) -> &WalkForwardConfig {
+        &self.config
+    }
+
+    /// Returns the windows that will be evaluated.
+    ///
+    /// Windows with too few in-sample ticks or no out-of-sample ticks are
+    /// left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the window configuration
+    /// is invalid.
+    pub fn windows(&self) -> MMResult<Vec<WalkForwardWindow>> {
+        let ticks = self.data_source.ticks();
+        let Some((start, end)) = self.data_source.time_range() else {
+            return Ok(Vec::new());
+        };
+
+        Ok(self
+            .config
+            .windows(start, end)?
+            .into_iter()
+            .filter(|w| {
+                w.in_sample(ticks).len() >= self.config.min_in_sample_ticks
+                    && !w.out_of_sample(ticks).is_empty()
+            })
+            .enumerate()
+            .map(|(index, w)| WalkForwardWindow { index, ..w })
+            .collect())
+    }
+
+    /// Runs the analysis with a caller-supplied calibration.
+    ///
+    /// `calibrate` receives each window and its in-sample ticks and returns
+    /// the parameters to validate out of sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the window configuration is invalid, there is no
+    /// complete window, or if calibration, strategy construction or metrics
+    /// calculation fails for any window.
+    pub fn run<C>(&self, mut calibrate: C) -> MMResult<WalkForwardReport>
+    where
+        C: FnMut(&WalkForwardWindow, &[MarketTick]) -> MMResult<ParameterSet>,
+    {
+        let windows = self.windows()?;
+        if windows.is_empty() {
+            return Err(MMError::InvalidConfiguration(
+                "history is too short for a single walk-forward window".to_string(),
+            ));
+        }
+
+        let ticks = self.data_source.ticks();
+        let initial_capital = self.backtest_config.initial_capital;
+        let calculator = MetricsCalculator::new(self.metrics_config.clone());
+
+        let mut results = Vec::with_capacity(windows.len());
+        let mut equity_curve = Vec::new();
+        let mut trades = Vec::new();
+        let mut equity = initial_capital;
+
+        for window in windows {
+            let in_sample_ticks = window.in_sample(ticks);
+            let parameters = calibrate(&window, in_sample_ticks)?;
+
+            let in_sample = self.backtest(&parameters, in_sample_ticks)?;
+            let in_sample_metrics =
+                calculator.calculate_from_backtest(&in_sample, initial_capital)?;
+
+            let out_of_sample = self.backtest(&parameters, window.out_of_sample(ticks))?;
+            let out_of_sample_metrics =
+                calculator.calculate_from_backtest(&out_of_sample, initial_capital)?;
+
+            // Chain the window onto the equity reached so far
+            let offset = equity - initial_capital;
+            equity_curve.extend(
+                out_of_sample
+                    .equity_curve
+                    .iter()
+                    .map(|&(timestamp, value)| EquityPoint::new(timestamp, value + offset)),
+            );
+            if let Some(last) = equity_curve.last() {
+                equity = last.equity;
+            }
+            trades.extend(TradeRecord::from_fills(&out_of_sample.trades));
+
+            results.push(WindowResult {
+                window,
+                parameters,
+                in_sample_metrics,
+                out_of_sample_metrics,
+                out_of_sample,
+            });
+        }
+
+        let metrics = calculator.calculate(&equity_curve, &trades, initial_capital)?;
+
+        Ok(WalkForwardReport {
+            windows: results,
+            equity_curve,
+            trades,
+            metrics,
+        })
+    }
+
+    /// Backtests one parameter set on a slice of the history.
+    fn backtest(
+        &self,
+        parameters: &ParameterSet,
+        ticks: &[MarketTick],
+    ) -> MMResult<BacktestResult> {
+        let strategy = (self.factory)(parameters)?;
+        let mut engine = BacktestEngine::new(
+            self.backtest_config.clone(),
+            strategy,
+            VecDataSource::new(ticks.to_vec()),
+        );
+        Ok(engine.run())
+    }
+}
+
+impl<S, F> WalkForwardAnalyzer<F>
+where
+    S: BacktestStrategy,
+    F: Fn(&ParameterSet) -> MMResult<S> + Sync,
+{
+    /// Runs the analysis, optimizing each in-sample window with a
+    /// [`BacktestOptimizer`] and validating its best trial.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no complete window, the optimizer
+    /// configuration is invalid, or no trial succeeds on some window.
+    pub fn optimize(
+        &self,
+        space: &ParameterSpace,
+        optimizer_config: &OptimizerConfig,
+    ) -> MMResult<WalkForwardReport> {
+        let optimizer_config = optimizer_config
+            .clone()
+            .with_metrics_config(self.metrics_config.clone());
+
+        self.run(|window, in_sample| {
+            let optimizer = BacktestOptimizer::new(
+                space.clone(),
+                optimizer_config.clone(),
+                self.backtest_config.clone(),
+                VecDataSource::new(in_sample.to_vec()),
+                &self.factory,
+            )?;
+            let report = optimizer.run();
+            report
+                .best()
+                .map(|trial| trial.parameters.clone())
+                .ok_or_else(|| {
+                    MMError::InvalidConfiguration(format!(
+                        "no successful trial in walk-forward window {}",
+                        window.index
+                    ))
+                })
+        })
+    }
+}
+
+impl<F> std::fmt::Debug for WalkForwardAnalyzer<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalkForwardAnalyzer")
+            .field("config", &self.config)
+            .field("backtest_config", &self.backtest_config)
+            .field("ticks", &self.data_source.ticks().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the ticks with `start <= timestamp < end`.
+fn slice_between(ticks: &[MarketTick], start: u64, end: u64) -> &[MarketTick] {
+    let from = ticks.partition_point(|t| t.timestamp < start);
+    let to = ticks.partition_point(|t| t.timestamp < end);
+    &ticks[from..to.max(from)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::engine::SimulatedFill;
+    use crate::backtest::optimization::{OptimizationObjective, ParameterRange, SearchMethod};
+    use crate::dec;
+    use crate::position::inventory::InventoryPosition;
+    use crate::strategy::quote::Quote;
+
+    /// Quotes symmetrically around the previous tick's mid.
+    struct LaggedQuoter {
+        half_spread: Decimal,
+        last_mid: Option<Decimal>,
+    }
+
+    impl BacktestStrategy for LaggedQuoter {
+        fn on_tick(&mut self, tick: &MarketTick, _position: &InventoryPosition) -> Option<Quote> {
+            let quote = self.last_mid.map(|mid| Quote {
+                bid_price: mid - self.half_spread,
+                bid_size: Decimal::ONE,
+                ask_price: mid + self.half_spread,
+                ask_size: Decimal::ONE,
+                timestamp: tick.timestamp,
+            });
+            self.last_mid = Some(tick.mid_price());
+            quote
+        }
+
+        fn on_fill(&mut self, _fill: &SimulatedFill) {}
+
+        fn reset(&mut self) {
+            self.last_mid = None;
+        }
+    }
+
+    const HOUR: u64 = 3_600_000;
+
+    /// Hourly ticks whose mid alternates with the given swings, one per block.
+    fn ticks(swings: &[(Decimal, u64)]) -> VecDataSource {
+        let mut ticks = Vec::new();
+        let mut i = 0u64;
+        for (swing, count) in swings {
+            for _ in 0..*count {
+                let mid = if i.is_multiple_of(2) {
+                    dec!(100.0)
+                } else {
+                    dec!(100.0) + *swing
+                };
+                ticks.push(MarketTick::new(
+                    i * HOUR,
+                    mid - dec!(0.01),
+                    dec!(1.0),
+                    mid + dec!(0.01),
+                    dec!(1.0),
+                ));
+                i += 1;
+            }
+        }
+        VecDataSource::new(ticks)
+    }
+
+    fn analyzer(
+        config: WalkForwardConfig,
+        data: VecDataSource,
+    ) -> WalkForwardAnalyzer<impl Fn(&ParameterSet) -> MMResult<LaggedQuoter> + Sync> {
+        WalkForwardAnalyzer::new(
+            config,
+            BacktestConfig::default(),
+            data,
+            |params: &ParameterSet| {
+                Ok(LaggedQuoter {
+                    half_spread: params.require("half_spread")?,
+                    last_mid: None,
+                })
+            },
+        )
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(WalkForwardConfig::new(0, HOUR).is_err());
+        assert!(WalkForwardConfig::new(HOUR, 0).is_err());
+
+        let config = WalkForwardConfig::new(HOUR, HOUR).unwrap().with_step(0);
+        assert_eq!(config.step_ms, HOUR);
+
+        // Fields set directly bypass the builders and are checked on use
+        for config in [
+            WalkForwardConfig {
+                step_ms: 0,
+                ..config.clone()
+            },
+            WalkForwardConfig {
+                in_sample_ms: 0,
+                ..config.clone()
+            },
+            WalkForwardConfig {
+                out_of_sample_ms: 0,
+                ..config
+            },
+        ] {
+            assert!(matches!(
+                config.windows(0, 10 * HOUR),
+                Err(MMError::InvalidConfiguration(_))
+            ));
+            let analyzer = analyzer(config, ticks(&[(dec!(1.0), 20)]));
+            assert!(matches!(
+                analyzer.run(|_, _| Ok(ParameterSet::new())),
+                Err(MMError::InvalidConfiguration(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_rolling_and_anchored_windows() {
+        let rolling = WalkForwardConfig::new(10 * HOUR, 5 * HOUR).unwrap();
+        let windows = rolling.windows(0, 22 * HOUR).unwrap();
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[1].in_sample_start, 5 * HOUR);
+        assert_eq!(windows[1].in_sample_end, 15 * HOUR);
+        assert_eq!(windows[1].out_of_sample_end, 20 * HOUR);
+        // The last window is truncated at the end of the data
+        assert_eq!(windows[2].out_of_sample_start, 20 * HOUR);
+        assert_eq!(windows[2].out_of_sample_end, 22 * HOUR + 1);
+
+        let anchored = rolling.clone().with_anchored(true);
+        let windows = anchored.windows(0, 22 * HOUR).unwrap();
+        assert_eq!(windows.len(), 3);
+        assert!(windows.iter().all(|w| w.in_sample_start == 0));
+        assert_eq!(windows[2].in_sample_end, 20 * HOUR);
+
+        let stepped = rolling.with_step(7 * HOUR);
+        let windows = stepped.windows(0, 22 * HOUR).unwrap();
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[1].out_of_sample_start, 17 * HOUR);
+        assert_eq!(windows[1].out_of_sample_end, 22 * HOUR);
+    }
+
+    #[test]
+    fn test_overlapping_out_of_sample_windows_are_rejected() {
+        let config = WalkForwardConfig::new(10 * HOUR, 5 * HOUR)
+            .unwrap()
+            .with_step(2 * HOUR);
+        assert!(matches!(
+            config.windows(0, 22 * HOUR),
+            Err(MMError::InvalidConfiguration(_))
+        ));
+        let analyzer = analyzer(config, ticks(&[(dec!(1.0), 20)]));
+        assert!(matches!(
+            analyzer.run(|_, _| Ok(ParameterSet::new())),
+            Err(MMError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_window_slicing() {
+        let data = ticks(&[(dec!(1.0), 10)]);
+        let window = WalkForwardWindow {
+            index: 0,
+            in_sample_start: 2 * HOUR,
+            in_sample_end: 5 * HOUR,
+            out_of_sample_start: 5 * HOUR,
+            out_of_sample_end: 7 * HOUR,
+        };
+        let in_sample = window.in_sample(data.ticks());
+        assert_eq!(in_sample.len(), 3);
+        assert_eq!(in_sample[0].timestamp, 2 * HOUR);
+        assert_eq!(window.out_of_sample(data.ticks()).len(), 2);
+    }
+
+    #[test]
+    fn test_run_stitches_out_of_sample_equity() {
+        let analyzer = analyzer(
+            WalkForwardConfig::new(10 * HOUR, 10 * HOUR).unwrap(),
+            ticks(&[(dec!(1.0), 40)]),
+        );
+
+        let mut seen = Vec::new();
+        let report = analyzer
+            .run(|window, in_sample| {
+                seen.push((window.index, in_sample.len()));
+                Ok(ParameterSet::new().with_value("half_spread", dec!(0.9)))
+            })
+            .unwrap();
+
+        assert_eq!(report.windows.len(), 3);
+        assert_eq!(seen, vec![(0, 10), (1, 10), (2, 10)]);
+
+        // Out-of-sample windows tile the history after the first in-sample block
+        assert_eq!(report.equity_curve.len(), 30);
+        assert_eq!(report.equity_curve[0].timestamp, 10 * HOUR);
+
+        let total_oos: Decimal = report.windows.iter().map(|w| w.out_of_sample.net_pnl).sum();
+        assert!(total_oos > Decimal::ZERO);
+        assert_eq!(report.metrics.total_return, total_oos);
+        assert_eq!(
+            report.equity_curve.last().unwrap().equity,
+            BacktestConfig::default().initial_capital + total_oos
+        );
+
+        let window_trades: u64 = report
+            .windows
+            .iter()
+            .map(|w| w.out_of_sample_metrics.total_trades)
+            .sum();
+        assert_eq!(report.metrics.total_trades, window_trades);
+        assert_eq!(report.profitable_window_ratio(), Decimal::ONE);
+    }
+
+    #[test]
+    fn test_optimize_detects_regime_change() {
+        // Calm swings of 1.0 followed by wide swings of 3.0
+        let analyzer = analyzer(
+            WalkForwardConfig::new(20 * HOUR, 10 * HOUR).unwrap(),
+            ticks(&[(dec!(1.0), 40), (dec!(3.0), 40)]),
+        );
+        let space = ParameterSpace::new().with_parameter(
+            "half_spread",
+            ParameterRange::values(vec![dec!(0.4), dec!(0.9), dec!(2.9)]).unwrap(),
+        );
+        let optimizer =
+            OptimizerConfig::new(SearchMethod::Grid, OptimizationObjective::TotalReturn)
+                .with_threads(2);
+
+        let report = analyzer.optimize(&space, &optimizer).unwrap();
+        assert_eq!(report.windows.len(), 6);
+
+        let chosen: Vec<Decimal> = report
+            .windows
+            .iter()
+            .map(|w| w.parameters.get("half_spread").unwrap())
+            .collect();
+        assert_eq!(chosen[0], dec!(0.9));
+        assert_eq!(*chosen.last().unwrap(), dec!(2.9));
+
+        // The window straddling the regime change was calibrated on calm data
+        let straddling = &report.windows[2];
+        assert_eq!(straddling.parameters.get("half_spread"), Some(dec!(0.9)));
+        assert!(
+            straddling.out_of_sample_metrics.total_return
+                < straddling.in_sample_metrics.total_return
+        );
+
+        let efficiency = report.efficiency().unwrap();
+        assert!(efficiency > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_errors() {
+        let short = analyzer(
+            WalkForwardConfig::new(100 * HOUR, HOUR).unwrap(),
+            ticks(&[(dec!(1.0), 10)]),
+        );
+        assert!(short.windows().unwrap().is_empty());
+        assert!(short.run(|_, _| Ok(ParameterSet::new())).is_err());
+
+        let data = analyzer(
+            WalkForwardConfig::new(5 * HOUR, 5 * HOUR).unwrap(),
+            ticks(&[(dec!(1.0), 20)]),
+        );
+        // Missing parameter surfaces as the factory error
+        assert!(data.run(|_, _| Ok(ParameterSet::new())).is_err());
+
+        let space = ParameterSpace::new()
+            .with_parameter("other", ParameterRange::values(vec![dec!(1)]).unwrap());
+        let result = data.optimize(&space, &OptimizerConfig::default().with_threads(1));
+        assert!(matches!(result, Err(MMError::InvalidConfiguration(_))));
+    }
+}
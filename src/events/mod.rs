@@ -40,5 +40,5 @@ pub use broadcaster::{
 };
 pub use types::{
     AlertCategory, AlertLevel, CancelReason, CircuitBreakerState, EventFilter, EventType,
-    HedgeReason, MarketMakerEvent, OptionStyle, Side, SystemStatus,
+    HedgeReason, MarketMakerEvent, OptionStyle, Side, SystemStatus, VolatilityRegimeLevel,
 };
@@ -207,6 +207,20 @@ pub enum MarketMakerEvent {
         timestamp: u64,
     },
 
+    /// Volatility regime changed after hysteresis and dwell checks.
+    VolatilityRegimeChanged {
+        /// Symbol (None if not symbol-specific).
+        symbol: Option<String>,
+        /// Previous regime.
+        previous_regime: VolatilityRegimeLevel,
+        /// New regime.
+        new_regime: VolatilityRegimeLevel,
+        /// Ratio of current to baseline volatility at the transition.
+        volatility_ratio: f64,
+        /// Event timestamp in milliseconds since epoch.
+        timestamp: u64,
+    },
+
     /// Heartbeat for connection keep-alive.
     Heartbeat {
         /// Event timestamp in milliseconds since epoch.
@@ -233,6 +247,7 @@ impl MarketMakerEvent {
             Self::UnderlyingPriceUpdated { .. } => EventType::UnderlyingPriceUpdated,
             Self::HedgeExecuted { .. } => EventType::HedgeExecuted,
             Self::SystemStatusChanged { .. } => EventType::SystemStatusChanged,
+            Self::VolatilityRegimeChanged { .. } => EventType::VolatilityRegimeChanged,
             Self::Heartbeat { .. } => EventType::Heartbeat,
         }
     }
@@ -247,9 +262,9 @@ impl MarketMakerEvent {
             | Self::PositionChanged { symbol, .. }
             | Self::UnderlyingPriceUpdated { symbol, .. }
             | Self::HedgeExecuted { symbol, .. } => Some(symbol),
-            Self::GreeksUpdated { symbol, .. } | Self::PnLUpdated { symbol, .. } => {
-                symbol.as_deref()
-            }
+            Self::GreeksUpdated { symbol, .. }
+            | Self::PnLUpdated { symbol, .. }
+            | Self::VolatilityRegimeChanged { symbol, .. } => symbol.as_deref(),
             Self::AlertTriggered { .. }
             | Self::CircuitBreakerChanged { .. }
             | Self::ConfigChanged { .. }
@@ -274,6 +289,7 @@ impl MarketMakerEvent {
             | Self::UnderlyingPriceUpdated { timestamp, .. }
             | Self::HedgeExecuted { timestamp, .. }
             | Self::SystemStatusChanged { timestamp, .. }
+            | Self::VolatilityRegimeChanged { timestamp, .. }
             | Self::Heartbeat { timestamp, .. } => *timestamp,
         }
     }
@@ -394,6 +410,20 @@ pub enum SystemStatus {
     Stopped,
 }
 
+/// Volatility regime for regime change events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VolatilityRegimeLevel {
+    /// Volatility well below its baseline.
+    Low,
+    /// Volatility near its baseline.
+    Normal,
+    /// Volatility above its baseline.
+    High,
+    /// Volatility far above its baseline.
+    Extreme,
+}
+
 /// Circuit breaker state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -436,6 +466,8 @@ pub enum EventType {
     HedgeExecuted,
     /// System status changed event.
     SystemStatusChanged,
+    /// Volatility regime changed event.
+    VolatilityRegimeChanged,
     /// Heartbeat event.
     Heartbeat,
 }
@@ -887,6 +919,13 @@ mod tests {
                 message: Some("Quoter started successfully".to_string()),
                 timestamp: 1000,
             },
+            MarketMakerEvent::VolatilityRegimeChanged {
+                symbol: Some("BTC".to_string()),
+                previous_regime: VolatilityRegimeLevel::Normal,
+                new_regime: VolatilityRegimeLevel::High,
+                volatility_ratio: 1.8,
+                timestamp: 1000,
+            },
             MarketMakerEvent::Heartbeat {
                 timestamp: 1000,
                 sequence: 1,
@@ -407,8 +407,11 @@ pub use rust_decimal_macros::dec;
 /// Market state module containing market data representations.
 ///
 /// Provides:
-/// - Market snapshots with bid/ask prices
-/// - Volatility estimation (simple, EWMA, Parkinson)
+/// - Market snapshots with book levels, recent trades and derived features
+/// - Incremental state tracking from ticks and data feed updates
+/// - Volatility estimation (simple, EWMA, Parkinson, Garman-Klass, Rogers-Satchell, Yang-Zhang)
+/// - GARCH(1,1) forecasting and noise-robust realized variance
+/// - Streaming volatility estimation with O(1) updates
 pub mod market_state;
 
 /// Position tracking module for inventory and PnL management.
@@ -424,9 +427,12 @@ pub mod position;
 /// - **Avellaneda-Stoikov**: Optimal quotes using stochastic control
 /// - **GLFT**: Extension with terminal inventory penalties
 /// - **Grid Trading**: Multi-level orders with configurable spacing
+/// - **Grid Engine**: Fill-driven take-profits with recentering and trailing grids
 /// - **Adaptive Spread**: Dynamic adjustment based on order book imbalance
 /// - **Depth-Based**: Size adjustment based on market depth
 /// - **Parameter Calibration**: Tools for γ and k estimation
+/// - **Volatility Regimes**: Hysteresis-based regime tracking with transition events
+/// - **Cross-Venue**: Quotes priced off a hedge venue book with residual tracking
 pub mod strategy;
 
 /// Risk management module for comprehensive risk control.
@@ -472,8 +478,12 @@ pub mod execution;
 ///
 /// Provides:
 /// - **Event-Driven Engine**: Tick-by-tick simulation
-/// - **Data Sources**: Ticks and OHLCV bars
+/// - **Multi-Asset**: Merged multi-symbol replay with portfolio and per-symbol metrics
+/// - **Data Sources**: Ticks, OHLCV bars and level-2 book replay
+/// - **Loaders**: Streaming CSV ticks and bars, compact binary tick files
 /// - **Fill Models**: Immediate, queue position, probabilistic, market impact
+/// - **L3 Backtests**: Order-by-order replay with exact queue position
+/// - **Latency**: Order-entry, cancel and market-data delays with stale-quote fills
 /// - **Performance Metrics**: Sharpe, Sortino, Calmar, profit factor
 /// - **Slippage Models**: Fixed, percentage, volatility-based
 pub mod backtest;
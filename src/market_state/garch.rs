@@ -0,0 +1,629 @@
+//! GARCH(1,1) conditional volatility model.
+//!
+//! The conditional variance of the next return follows
+//!
+//! σ²_{t+1} = ω + α * r²_t + β * σ²_t
+//!
+//! so a large return raises the variance estimate immediately by `α * r²`,
+//! and the excess decays back to the long-run variance `ω / (1 - α - β)` at
+//! rate `α + β`. Unlike EWMA, which has no mean reversion and a single
+//! smoothing constant, the reaction to news and the speed of reversion are
+//! fitted separately from the data.
+//!
+//! Parameters are estimated by Gaussian maximum likelihood. Multi-step
+//! forecasts use the closed form
+//!
+//! E[σ²_{T+h}] = V_L + (α + β)^{h-1} * (σ²_{T+1} - V_L)
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::market_state::garch::{GarchConfig, GarchModel};
+//! use market_maker_rs::dec;
+//! use market_maker_rs::Decimal;
+//!
+//! // Returns with a volatility burst in the middle
+//! let returns: Vec<Decimal> = (0..200)
+//!     .map(|i| {
+//!         let size = if (80..100).contains(&i) { dec!(0.03) } else { dec!(0.01) };
+//!         if i % 2 == 0 { size } else { -size }
+//!     })
+//!     .collect();
+//!
+//! let mut model = GarchModel::fit(&returns, GarchConfig::default()).unwrap();
+//! let params = model.parameters();
+//! assert!(params.alpha + params.beta < dec!(1.0));
+//!
+//! // React to a shock, then forecast the next ten periods
+//! model.update(dec!(0.05));
+//! let forecast = model.forecast_variance(10);
+//! assert_eq!(forecast.len(), 10);
+//! let annualized = model.forecast_volatility(10).unwrap();
+//! assert!(annualized > dec!(0.0));
+//! ```
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+use crate::Decimal;
+use crate::types::decimal::{decimal_ln, decimal_powi, decimal_sqrt};
+use crate::types::error::{MMError, MMResult};
+use crate::types::numerics::nelder_mead;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Upper bound on α + β during fitting, keeping the model stationary.
+const MAX_PERSISTENCE: f64 = 0.9999;
+
+/// GARCH(1,1) parameters.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::market_state::garch::GarchParameters;
+/// use market_maker_rs::dec;
+///
+/// let params = GarchParameters::new(dec!(0.000002), dec!(0.08), dec!(0.9)).unwrap();
+/// assert_eq!(params.persistence(), dec!(0.98));
+/// assert_eq!(params.long_run_variance(), dec!(0.0001));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GarchParameters {
+    /// Constant term (ω).
+    pub omega: Decimal,
+    /// Reaction to the last squared return (α).
+    pub alpha: Decimal,
+    /// Weight of the last conditional variance (β).
+    pub beta: Decimal,
+}
+
+impl GarchParameters {
+    /// Creates validated parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` unless `omega > 0`,
+    /// `alpha >= 0`, `beta >= 0` and `alpha + beta < 1`.
+    pub fn new(omega: Decimal, alpha: Decimal, beta: Decimal) -> MMResult<Self> {
+        if omega <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "omega must be positive".to_string(),
+            ));
+        }
+        if alpha < Decimal::ZERO || beta < Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "alpha and beta must be non-negative".to_string(),
+            ));
+        }
+        if alpha + beta >= Decimal::ONE {
+            return Err(MMError::InvalidConfiguration(
+                "alpha + beta must be less than 1 for a stationary model".to_string(),
+            ));
+        }
+        Ok(Self { omega, alpha, beta })
+    }
+
+    /// Returns α + β, the rate at which variance shocks persist.
+    #[must_use]
+    pub fn persistence(&self) -> Decimal {
+        self.alpha + self.beta
+    }
+
+    /// Returns the unconditional variance per period, ω / (1 - α - β).
+    #[must_use]
+    pub fn long_run_variance(&self) -> Decimal {
+        self.omega / (Decimal::ONE - self.persistence())
+    }
+
+    /// Returns the number of periods for a variance shock to halve.
+    ///
+    /// Returns `None` if shocks do not persist at all.
+    #[must_use]
+    pub fn half_life(&self) -> Option<Decimal> {
+        let persistence = self.persistence();
+        if persistence <= Decimal::ZERO {
+            return None;
+        }
+        let ln_half = decimal_ln(Decimal::new(5, 1)).ok()?;
+        let ln_persistence = decimal_ln(persistence).ok()?;
+        Some(ln_half / ln_persistence)
+    }
+}
+
+/// Configuration for GARCH fitting.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::market_state::garch::GarchConfig;
+/// use market_maker_rs::dec;
+///
+/// // Hourly returns on a 24/7 market
+/// let config = GarchConfig::new(100)
+///     .unwrap()
+///     .with_annualization_factor(dec!(93.6));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GarchConfig {
+    /// Minimum number of returns required to fit.
+    pub min_observations: usize,
+    /// Maximum optimizer iterations per starting point.
+    pub max_iterations: usize,
+    /// Convergence tolerance on the scaled negative log-likelihood.
+    pub tolerance: f64,
+    /// Factor converting a per-period volatility to an annualized one,
+    /// e.g. sqrt(252) for daily returns.
+    pub annualization_factor: Decimal,
+}
+
+impl GarchConfig {
+    /// Creates a configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `min_observations` is below 10.
+    pub fn new(min_observations: usize) -> MMResult<Self> {
+        if min_observations < 10 {
+            return Err(MMError::InvalidConfiguration(
+                "min_observations must be at least 10".to_string(),
+            ));
+        }
+        Ok(Self {
+            min_observations,
+            max_iterations: 1000,
+            tolerance: 1e-9,
+            annualization_factor: decimal_sqrt(Decimal::from(252))?,
+        })
+    }
+
+    /// Sets the maximum optimizer iterations.
+    #[must_use]
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the convergence tolerance.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the annualization factor.
+    #[must_use]
+    pub fn with_annualization_factor(mut self, factor: Decimal) -> Self {
+        self.annualization_factor = factor;
+        self
+    }
+}
+
+impl Default for GarchConfig {
+    fn default() -> Self {
+        Self::new(50).expect("default GARCH configuration is valid")
+    }
+}
+
+/// Fitted GARCH(1,1) model tracking the next-period conditional variance.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GarchModel {
+    config: GarchConfig,
+    parameters: GarchParameters,
+    variance: Decimal,
+    log_likelihood: Option<Decimal>,
+    observations: usize,
+}
+
+impl GarchModel {
+    /// Creates a model from known parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - Model parameters
+    /// * `initial_variance` - Conditional variance of the next return
+    /// * `config` - Model configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `initial_variance` is not positive.
+    pub fn from_parameters(
+        parameters: GarchParameters,
+        initial_variance: Decimal,
+        config: GarchConfig,
+    ) -> MMResult<Self> {
+        if initial_variance <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "initial_variance must be positive".to_string(),
+            ));
+        }
+        Ok(Self {
+            config,
+            parameters,
+            variance: initial_variance,
+            log_likelihood: None,
+            observations: 0,
+        })
+    }
+
+    /// Fits the model to a series of returns by maximum likelihood.
+    ///
+    /// Returns are assumed to have zero mean, which holds closely for the
+    /// short horizons used in market making. The recursion is started at
+    /// the sample variance.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if there are fewer than
+    /// `min_observations` returns or they have no variance, and
+    /// `MMError::NumericalError` if the optimization fails.
+    pub fn fit(returns: &[Decimal], config: GarchConfig) -> MMResult<Self> {
+        if returns.len() < config.min_observations {
+            return Err(MMError::InvalidMarketState(format!(
+                "insufficient returns: {} < {}",
+                returns.len(),
+                config.min_observations
+            )));
+        }
+
+        let raw: Vec<f64> = returns.iter().map(|r| r.to_f64().unwrap_or(0.0)).collect();
+        let sample_variance = raw.iter().map(|r| r * r).sum::<f64>() / raw.len() as f64;
+        if !sample_variance.is_finite() || sample_variance <= 0.0 {
+            return Err(MMError::InvalidMarketState(
+                "returns must have positive variance".to_string(),
+            ));
+        }
+
+        // Fit on unit-variance returns so ω is of order one
+        let scale = sample_variance.sqrt();
+        let scaled: Vec<f64> = raw.iter().map(|r| r / scale).collect();
+
+        let objective = |x: &[f64; 3]| {
+            let (omega, alpha, beta) = unpack(x);
+            -log_likelihood(&scaled, omega, alpha, beta)
+        };
+
+        let mut best: Option<([f64; 3], f64)> = None;
+        for (persistence, share) in [(0.9f64, 0.1), (0.97, 0.05), (0.6, 0.3)] {
+            let start = [
+                (1.0 - persistence).ln(),
+                logit(persistence / MAX_PERSISTENCE),
+                logit(share),
+            ];
+            let (point, value) =
+                nelder_mead(objective, start, config.max_iterations, config.tolerance);
+            if value.is_finite() && best.is_none_or(|(_, v)| value < v) {
+                best = Some((point, value));
+            }
+        }
+
+        let Some((point, value)) = best else {
+            return Err(MMError::NumericalError(
+                "GARCH likelihood optimization failed".to_string(),
+            ));
+        };
+        let (omega, alpha, beta) = unpack(&point);
+
+        let mut next_variance = 1.0;
+        for r in &scaled {
+            next_variance = omega + alpha * r * r + beta * next_variance;
+        }
+
+        // Undo the scaling: variances scale by σ², the likelihood shifts by -n/2 ln σ²
+        let n = scaled.len() as f64;
+        let log_likelihood = -value - 0.5 * n * sample_variance.ln();
+
+        let parameters = GarchParameters {
+            omega: from_f64(omega * sample_variance)?,
+            alpha: from_f64(alpha)?,
+            beta: from_f64(beta)?,
+        };
+
+        Ok(Self {
+            config,
+            parameters,
+            variance: from_f64(next_variance * sample_variance)?,
+            log_likelihood: Some(from_f64(log_likelihood)?),
+            observations: returns.len(),
+        })
+    }
+
+    /// Fits the model to the log returns of a price series.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if any price is not positive,
+    /// plus the errors of [`GarchModel::fit`].
+    pub fn fit_prices(prices: &[Decimal], config: GarchConfig) -> MMResult<Self> {
+        let mut returns = Vec::with_capacity(prices.len().saturating_sub(1));
+        for pair in prices.windows(2) {
+            if pair[0] <= Decimal::ZERO || pair[1] <= Decimal::ZERO {
+                return Err(MMError::InvalidMarketState(
+                    "prices must be positive".to_string(),
+                ));
+            }
+            returns.push(decimal_ln(pair[1] / pair[0])?);
+        }
+        Self::fit(&returns, config)
+    }
+
+    /// Returns the model parameters.
+    #[must_use]
+    pub fn parameters(&self) -> GarchParameters {
+        self.parameters
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn config(&self) -> &GarchConfig {
+        &self.config
+    }
+
+    /// Returns the maximized log-likelihood, if the model was fitted.
+    #[must_use]
+    pub fn log_likelihood(&self) -> Option<Decimal> {
+        self.log_likelihood
+    }
+
+    /// Returns the number of returns seen, in the fit and since.
+    #[must_use]
+    pub fn observations(&self) -> usize {
+        self.observations
+    }
+
+    /// Returns the conditional variance of the next return.
+    #[must_use]
+    pub fn conditional_variance(&self) -> Decimal {
+        self.variance
+    }
+
+    /// Returns the annualized conditional volatility of the next return.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the square root fails.
+    pub fn volatility(&self) -> MMResult<Decimal> {
+        Ok(decimal_sqrt(self.variance)? * self.config.annualization_factor)
+    }
+
+    /// Feeds a new return through the variance recursion.
+    pub fn update(&mut self, log_return: Decimal) {
+        let p = &self.parameters;
+        self.variance = p.omega + p.alpha * log_return * log_return + p.beta * self.variance;
+        self.observations += 1;
+    }
+
+    /// Returns the expected conditional variance for each of the next
+    /// `horizon` periods.
+    #[must_use]
+    pub fn forecast_variance(&self, horizon: usize) -> Vec<Decimal> {
+        let long_run = self.parameters.long_run_variance();
+        let persistence = self.parameters.persistence();
+        let mut excess = self.variance - long_run;
+
+        (0..horizon)
+            .map(|_| {
+                let forecast = long_run + excess;
+                excess *= persistence;
+                forecast
+            })
+            .collect()
+    }
+
+    /// Returns the annualized volatility over the next `horizon` periods.
+    ///
+    /// This is the root of the mean forecast variance, i.e. the constant
+    /// volatility with the same expected total variance over the horizon.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `horizon` is zero.
+    pub fn forecast_volatility(&self, horizon: usize) -> MMResult<Decimal> {
+        if horizon == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "horizon must be positive".to_string(),
+            ));
+        }
+        let total: Decimal = self.forecast_variance(horizon).into_iter().sum();
+        let mean = total / Decimal::from(horizon);
+        Ok(decimal_sqrt(mean.max(Decimal::ZERO))? * self.config.annualization_factor)
+    }
+
+    /// Returns the expected conditional variance `horizon` periods ahead.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `horizon` is zero.
+    pub fn variance_at(&self, horizon: u32) -> MMResult<Decimal> {
+        if horizon == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "horizon must be positive".to_string(),
+            ));
+        }
+        let long_run = self.parameters.long_run_variance();
+        let exponent = i32::try_from(horizon - 1).unwrap_or(i32::MAX);
+        let decay = decimal_powi(self.parameters.persistence(), exponent)?;
+        Ok(long_run + decay * (self.variance - long_run))
+    }
+}
+
+fn from_f64(value: f64) -> MMResult<Decimal> {
+    Decimal::from_f64(value)
+        .ok_or_else(|| MMError::NumericalError(format!("cannot represent {value} as a decimal")))
+}
+
+fn logit(p: f64) -> f64 {
+    (p / (1.0 - p)).ln()
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Maps unconstrained optimizer coordinates to `(ω, α, β)`.
+fn unpack(x: &[f64; 3]) -> (f64, f64, f64) {
+    let omega = x[0].exp();
+    let persistence = MAX_PERSISTENCE * sigmoid(x[1]);
+    let share = sigmoid(x[2]);
+    (omega, persistence * share, persistence * (1.0 - share))
+}
+
+/// Gaussian GARCH(1,1) log-likelihood, started at unit variance.
+fn log_likelihood(returns: &[f64], omega: f64, alpha: f64, beta: f64) -> f64 {
+    let mut variance: f64 = 1.0;
+    let mut total = 0.0;
+    for r in returns {
+        total -= 0.5 * ((2.0 * std::f64::consts::PI).ln() + variance.ln() + r * r / variance);
+        variance = omega + alpha * r * r + beta * variance;
+    }
+    if total.is_finite() {
+        total
+    } else {
+        f64::NEG_INFINITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+
+    /// Simulates GARCH(1,1) returns with Gaussian shocks from a fixed LCG seed.
+    fn simulate(omega: f64, alpha: f64, beta: f64, n: usize, seed: u64) -> Vec<Decimal> {
+        let mut state = seed;
+        let mut uniform = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        };
+
+        let mut variance = omega / (1.0 - alpha - beta);
+        (0..n)
+            .map(|_| {
+                let z =
+                    (-2.0 * uniform().ln()).sqrt() * (2.0 * std::f64::consts::PI * uniform()).cos();
+                let r = variance.sqrt() * z;
+                variance = omega + alpha * r * r + beta * variance;
+                Decimal::from_f64(r).unwrap().round_dp(12)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parameters_validation() {
+        assert!(GarchParameters::new(Decimal::ZERO, dec!(0.1), dec!(0.8)).is_err());
+        assert!(GarchParameters::new(dec!(0.1), dec!(-0.1), dec!(0.8)).is_err());
+        assert!(GarchParameters::new(dec!(0.1), dec!(0.3), dec!(0.7)).is_err());
+
+        let params = GarchParameters::new(dec!(0.1), dec!(0.1), dec!(0.8)).unwrap();
+        assert_eq!(params.long_run_variance(), dec!(1));
+        let half_life = params.half_life().unwrap();
+        assert!((half_life - dec!(6.5788)).abs() < dec!(0.001));
+
+        let memoryless = GarchParameters::new(dec!(0.1), Decimal::ZERO, Decimal::ZERO).unwrap();
+        assert!(memoryless.half_life().is_none());
+
+        assert!(GarchConfig::new(5).is_err());
+    }
+
+    #[test]
+    fn test_fit_recovers_simulated_parameters() {
+        let returns = simulate(0.000002, 0.1, 0.85, 4000, 42);
+        let model = GarchModel::fit(&returns, GarchConfig::default()).unwrap();
+        let params = model.parameters();
+
+        assert!(
+            params.alpha > dec!(0.05) && params.alpha < dec!(0.16),
+            "{params:?}"
+        );
+        assert!(
+            params.beta > dec!(0.75) && params.beta < dec!(0.93),
+            "{params:?}"
+        );
+        let long_run = params.long_run_variance();
+        assert!(
+            long_run > dec!(0.00003) && long_run < dec!(0.00006),
+            "{long_run}"
+        );
+        assert_eq!(model.observations(), 4000);
+        assert!(model.log_likelihood().is_some());
+    }
+
+    #[test]
+    fn test_fit_on_iid_returns_has_low_reaction() {
+        // Constant variance: news should not move the estimate much
+        let returns = simulate(0.0001, 0.0, 0.0, 3000, 7);
+        let model = GarchModel::fit(&returns, GarchConfig::default()).unwrap();
+        assert!(model.parameters().alpha < dec!(0.05));
+    }
+
+    #[test]
+    fn test_update_reacts_to_shock() {
+        let params = GarchParameters::new(dec!(0.000002), dec!(0.1), dec!(0.88)).unwrap();
+        let mut model =
+            GarchModel::from_parameters(params, dec!(0.0001), GarchConfig::default()).unwrap();
+
+        let before = model.conditional_variance();
+        model.update(dec!(0.05));
+        let after = model.conditional_variance();
+        // 0.000002 + 0.1 * 0.0025 + 0.88 * 0.0001
+        assert_eq!(after, dec!(0.00034));
+        assert!(after > before * dec!(3));
+        assert_eq!(model.observations(), 1);
+    }
+
+    #[test]
+    fn test_forecast_reverts_to_long_run() {
+        let params = GarchParameters::new(dec!(0.000002), dec!(0.1), dec!(0.88)).unwrap();
+        let long_run = params.long_run_variance();
+        let model = GarchModel::from_parameters(params, long_run * dec!(4), GarchConfig::default())
+            .unwrap();
+
+        let forecast = model.forecast_variance(500);
+        assert_eq!(forecast[0], model.conditional_variance());
+        assert!(forecast.windows(2).all(|w| w[1] < w[0]));
+        assert!((forecast[499] - long_run).abs() < long_run * dec!(0.001));
+
+        let tenth = model.variance_at(10).unwrap();
+        assert!((tenth - forecast[9]).abs() < dec!(0.0000000001));
+        assert!(model.variance_at(0).is_err());
+
+        let short = model.forecast_volatility(1).unwrap();
+        let long = model.forecast_volatility(500).unwrap();
+        assert!(short > long);
+        assert!(model.forecast_volatility(0).is_err());
+    }
+
+    #[test]
+    fn test_fit_errors() {
+        let few = vec![dec!(0.01); 10];
+        assert!(matches!(
+            GarchModel::fit(&few, GarchConfig::default()),
+            Err(MMError::InvalidMarketState(_))
+        ));
+
+        let flat = vec![Decimal::ZERO; 100];
+        assert!(GarchModel::fit(&flat, GarchConfig::default()).is_err());
+
+        let prices = vec![dec!(100.0), dec!(-1.0)];
+        assert!(GarchModel::fit_prices(&prices, GarchConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_fit_prices() {
+        let returns = simulate(0.000002, 0.1, 0.85, 500, 3);
+        let mut price = dec!(100.0);
+        let mut prices = vec![price];
+        for r in &returns {
+            price *= Decimal::ONE + r;
+            prices.push(price);
+        }
+
+        let model = GarchModel::fit_prices(&prices, GarchConfig::default()).unwrap();
+        assert_eq!(model.observations(), 500);
+        assert!(model.volatility().unwrap() > Decimal::ZERO);
+    }
+}
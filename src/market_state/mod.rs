@@ -1,12 +1,28 @@
 //! Market state module for representing observable market data.
 //!
 //! This module provides:
-//! - Market state snapshots at a point in time
+//! - Market state snapshots with book, trades and derived features
+//! - Incremental market state tracking from ticks and feed updates
 //! - Volatility estimation utilities
+//! - GARCH(1,1) conditional volatility forecasting
+//! - Noise-robust realized variance for tick data
+//! - Streaming volatility with O(1) updates
 //! - Price tracking and updates
 
+/// GARCH(1,1) conditional volatility model.
+pub mod garch;
+
+/// Noise-robust realized variance estimators.
+pub mod realized;
+
 /// Market state snapshot representation.
 pub mod snapshot;
 
+/// Streaming volatility estimation with O(1) updates.
+pub mod streaming;
+
+/// Incremental market state construction.
+pub mod tracker;
+
 /// Volatility estimation methods.
 pub mod volatility;
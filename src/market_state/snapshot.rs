@@ -1,11 +1,42 @@
 //! Market state snapshot representation.
+//!
+//! [`MarketState`] is the single market context passed to strategies: the
+//! reference mid and volatility, plus the top of the order book, the most
+//! recent trades, a realized volatility estimate and a staleness flag when
+//! they are known. Use [`MarketStateTracker`](super::tracker::MarketStateTracker)
+//! to maintain one incrementally from ticks and data feed updates.
+
+use std::collections::VecDeque;
 
 use crate::Decimal;
+use crate::analytics::order_flow::Trade;
+use crate::execution::OrderBookSnapshot;
 
 #[cfg(feature = "serde")]
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 
 /// Represents the observable state of the market at a point in time.
+///
+/// Only the mid price, volatility and timestamp are required. The remaining
+/// fields are filled in when the data is available, and the derived
+/// features (spread, microprice, imbalance) return `None` without a book.
+///
+/// # Examples
+///
+/// ```
+/// use market_maker_rs::execution::{BookLevel, OrderBookSnapshot};
+/// use market_maker_rs::market_state::snapshot::MarketState;
+/// use market_maker_rs::dec;
+///
+/// let mut book = OrderBookSnapshot::new("BTC-USD", 1_000);
+/// book.bids.push(BookLevel::new(dec!(99.0), dec!(3.0)));
+/// book.asks.push(BookLevel::new(dec!(101.0), dec!(1.0)));
+///
+/// let state = MarketState::new(dec!(100.0), dec!(0.2), 1_000).with_orderbook(book);
+/// assert_eq!(state.spread(), Some(dec!(2.0)));
+/// assert_eq!(state.microprice(), Some(dec!(100.5)));
+/// assert_eq!(state.imbalance(), Some(dec!(0.5)));
+/// ```
 #[derive(Clone, PartialEq)]
 #[cfg_attr(not(feature = "serde"), derive(Debug))]
 #[cfg_attr(
@@ -21,6 +52,41 @@ pub struct MarketState {
 
     /// Current timestamp in milliseconds since Unix epoch.
     pub timestamp: u64,
+
+    /// Top levels of the order book, if known.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub orderbook: Option<OrderBookSnapshot>,
+
+    /// Most recent trades, oldest first.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub recent_trades: VecDeque<Trade>,
+
+    /// Volatility measured from recent mid prices (annualized), once enough
+    /// data has been observed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub realized_volatility: Option<Decimal>,
+
+    /// True when no market data has arrived for longer than the configured
+    /// staleness threshold.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stale: bool,
+}
+
+/// Weights each side's price by the opposite side's size.
+///
+/// Falls back to the arithmetic mid when both sizes are zero.
+pub(crate) fn cross_weighted(
+    bid: Decimal,
+    bid_size: Decimal,
+    ask: Decimal,
+    ask_size: Decimal,
+) -> Decimal {
+    let total = bid_size + ask_size;
+    if total > Decimal::ZERO {
+        (bid * ask_size + ask * bid_size) / total
+    } else {
+        (bid + ask) / Decimal::TWO
+    }
 }
 
 impl MarketState {
@@ -37,14 +103,99 @@ impl MarketState {
             mid_price,
             volatility,
             timestamp,
+            orderbook: None,
+            recent_trades: VecDeque::new(),
+            realized_volatility: None,
+            stale: false,
         }
     }
+
+    /// Attaches an order book snapshot.
+    #[must_use]
+    pub fn with_orderbook(mut self, orderbook: OrderBookSnapshot) -> Self {
+        self.orderbook = Some(orderbook);
+        self
+    }
+
+    /// Sets the recent trades, oldest first.
+    #[must_use]
+    pub fn with_trades(mut self, trades: impl IntoIterator<Item = Trade>) -> Self {
+        self.recent_trades = trades.into_iter().collect();
+        self
+    }
+
+    /// Sets the realized volatility estimate (annualized).
+    #[must_use]
+    pub fn with_realized_volatility(mut self, volatility: Decimal) -> Self {
+        self.realized_volatility = Some(volatility);
+        self
+    }
+
+    /// Sets the staleness flag.
+    #[must_use]
+    pub fn with_stale(mut self, stale: bool) -> Self {
+        self.stale = stale;
+        self
+    }
+
+    /// Returns the best bid price, if a book is attached.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<Decimal> {
+        self.orderbook.as_ref()?.best_bid()
+    }
+
+    /// Returns the best ask price, if a book is attached.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<Decimal> {
+        self.orderbook.as_ref()?.best_ask()
+    }
+
+    /// Returns the bid-ask spread, if a book is attached.
+    #[must_use]
+    pub fn spread(&self) -> Option<Decimal> {
+        self.orderbook.as_ref()?.spread()
+    }
+
+    /// Returns the size-weighted microprice of the top of book.
+    ///
+    /// Falls back to the book mid when both top sizes are zero.
+    #[must_use]
+    pub fn microprice(&self) -> Option<Decimal> {
+        let book = self.orderbook.as_ref()?;
+        let (bid, ask) = (book.bids.first()?, book.asks.first()?);
+        Some(cross_weighted(
+            bid.price,
+            bid.quantity,
+            ask.price,
+            ask.quantity,
+        ))
+    }
+
+    /// Returns the depth imbalance over the attached levels, in [-1, 1].
+    #[must_use]
+    pub fn imbalance(&self) -> Option<Decimal> {
+        self.orderbook.as_ref().map(OrderBookSnapshot::imbalance)
+    }
+
+    /// Returns the most recent trade.
+    #[must_use]
+    pub fn last_trade(&self) -> Option<&Trade> {
+        self.recent_trades.back()
+    }
+
+    /// Returns true if the state is flagged as stale.
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analytics::order_flow::TradeSide;
     use crate::dec;
+    use crate::execution::BookLevel;
 
     #[test]
     fn test_market_state_new() {
@@ -52,6 +203,9 @@ mod tests {
         assert_eq!(state.mid_price, dec!(100.0));
         assert_eq!(state.volatility, dec!(0.2));
         assert_eq!(state.timestamp, 1234567890);
+        assert!(state.orderbook.is_none());
+        assert!(state.recent_trades.is_empty());
+        assert!(!state.is_stale());
     }
 
     #[test]
@@ -60,9 +214,60 @@ mod tests {
             mid_price: dec!(99.5),
             volatility: dec!(0.15),
             timestamp: 9876543210,
+            ..MarketState::new(Decimal::ZERO, Decimal::ZERO, 0)
         };
         assert_eq!(state.mid_price, dec!(99.5));
         assert_eq!(state.volatility, dec!(0.15));
         assert_eq!(state.timestamp, 9876543210);
     }
+
+    #[test]
+    fn test_market_state_derived_features() {
+        let state = MarketState::new(dec!(100.0), dec!(0.2), 0);
+        assert_eq!(state.spread(), None);
+        assert_eq!(state.microprice(), None);
+        assert_eq!(state.imbalance(), None);
+
+        let mut book = OrderBookSnapshot::new("TEST", 0);
+        book.bids = vec![
+            BookLevel::new(dec!(99.0), dec!(1.0)),
+            BookLevel::new(dec!(98.0), dec!(5.0)),
+        ];
+        book.asks = vec![BookLevel::new(dec!(101.0), dec!(2.0))];
+        let state = state.with_orderbook(book).with_trades([
+            Trade::new(dec!(101.0), dec!(1.0), TradeSide::Buy, 1),
+            Trade::new(dec!(99.0), dec!(2.0), TradeSide::Sell, 2),
+        ]);
+
+        assert_eq!(state.best_bid(), Some(dec!(99.0)));
+        assert_eq!(state.best_ask(), Some(dec!(101.0)));
+        assert_eq!(state.spread(), Some(dec!(2.0)));
+        // (99 * 2 + 101 * 1) / 3
+        assert_eq!(state.microprice().unwrap().round_dp(6), dec!(99.666667));
+        assert_eq!(state.imbalance(), Some(dec!(0.5)));
+        assert_eq!(state.last_trade().unwrap().side, TradeSide::Sell);
+    }
+
+    #[test]
+    fn test_market_state_builders() {
+        let state = MarketState::new(dec!(100.0), dec!(0.2), 0)
+            .with_realized_volatility(dec!(0.25))
+            .with_stale(true);
+        assert_eq!(state.realized_volatility, Some(dec!(0.25)));
+        assert!(state.is_stale());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_market_state_deserializes_core_fields_only() {
+        let state = MarketState::new(dec!(100.0), dec!(0.2), 1_000).with_stale(true);
+        let mut value = serde_json::to_value(&state).unwrap();
+        let fields = value.as_object_mut().unwrap();
+        for field in ["orderbook", "recent_trades", "realized_volatility", "stale"] {
+            fields.remove(field);
+        }
+
+        let restored: MarketState = serde_json::from_value(value).unwrap();
+        assert_eq!(restored, MarketState::new(dec!(100.0), dec!(0.2), 1_000));
+    }
 }
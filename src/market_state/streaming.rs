@@ -0,0 +1,738 @@
+//! Streaming volatility estimation.
+//!
+//! [`VolatilityEstimator`](super::volatility::VolatilityEstimator) recomputes
+//! from a full price history on every call. [`StreamingVolatilityEstimator`]
+//! keeps running state instead, so each new price costs one logarithm and a
+//! handful of additions regardless of the lookback. This is the estimator to
+//! use inside a quote loop or a market data subscription.
+//!
+//! # Modes
+//!
+//! - **EWMA**: σ²_t = λ * σ²_{t-1} + (1-λ) * r²_t, seeded the same way as
+//!   [`calculate_ewma`](super::volatility::VolatilityEstimator::calculate_ewma)
+//! - **Rolling**: sample standard deviation of the last `window` log returns,
+//!   maintained with running sums
+//! - **Parkinson**: range-based estimator over the last `window` bars, where
+//!   bars are either supplied directly or built from prices on a fixed clock
+//!
+//! # Examples
+//!
+//! ```
+//! use market_maker_rs::market_state::streaming::{
+//!     StreamingVolatilityEstimator, StreamingVolatilityMode,
+//! };
+//! use market_maker_rs::dec;
+//!
+//! let mut estimator =
+//!     StreamingVolatilityEstimator::new(StreamingVolatilityMode::Rolling { window: 20 }).unwrap();
+//!
+//! for (i, price) in [dec!(100.0), dec!(101.0), dec!(99.5), dec!(100.5)].into_iter().enumerate() {
+//!     estimator.update_price(i as u64 * 1000, price).unwrap();
+//! }
+//!
+//! assert!(estimator.is_ready());
+//! assert!(estimator.volatility().unwrap() > dec!(0.0));
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::Decimal;
+use crate::types::decimal::{decimal_ln, decimal_sqrt};
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "data-feeds")]
+use crate::data_feeds::PriceUpdate;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Number of returns used to seed the EWMA variance, matching the batch
+/// estimator.
+const EWMA_SEED_RETURNS: usize = 5;
+
+/// Update rule of a [`StreamingVolatilityEstimator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StreamingVolatilityMode {
+    /// Exponentially weighted moving average of squared log returns.
+    Ewma {
+        /// Decay factor in (0, 1); 0.94 is the RiskMetrics daily value.
+        lambda: Decimal,
+    },
+    /// Sample volatility of the most recent log returns.
+    Rolling {
+        /// Number of returns in the window (at least 2).
+        window: usize,
+    },
+    /// Parkinson high-low estimator over the most recent bars.
+    Parkinson {
+        /// Number of completed bars in the window (at least 1).
+        window: usize,
+        /// Bar length in milliseconds used when building bars from prices.
+        bar_ms: u64,
+    },
+}
+
+impl StreamingVolatilityMode {
+    fn validate(self) -> MMResult<()> {
+        match self {
+            StreamingVolatilityMode::Ewma { lambda } => {
+                if lambda <= Decimal::ZERO || lambda >= Decimal::ONE {
+                    return Err(MMError::InvalidConfiguration(
+                        "lambda must be between 0 and 1".to_string(),
+                    ));
+                }
+            }
+            StreamingVolatilityMode::Rolling { window } => {
+                if window < 2 {
+                    return Err(MMError::InvalidConfiguration(
+                        "rolling window must contain at least 2 returns".to_string(),
+                    ));
+                }
+            }
+            StreamingVolatilityMode::Parkinson { window, bar_ms } => {
+                if window == 0 {
+                    return Err(MMError::InvalidConfiguration(
+                        "Parkinson window must contain at least 1 bar".to_string(),
+                    ));
+                }
+                if bar_ms == 0 {
+                    return Err(MMError::InvalidConfiguration(
+                        "bar length must be positive".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// High and low of the bar currently being built from prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct OpenBar {
+    bucket: u64,
+    high: Decimal,
+    low: Decimal,
+}
+
+/// Online volatility estimator with O(1) updates.
+///
+/// Volatility is annualized with sqrt(252) unless a custom factor is set,
+/// consistent with the batch estimator. Running sums in the rolling modes are
+/// recomputed from the window once per `window` updates, which bounds
+/// rounding drift while keeping the amortized cost constant.
+///
+/// # Examples
+///
+/// ```
+/// use market_maker_rs::market_state::streaming::{
+///     StreamingVolatilityEstimator, StreamingVolatilityMode,
+/// };
+/// use market_maker_rs::dec;
+///
+/// let mut estimator = StreamingVolatilityEstimator::new(StreamingVolatilityMode::Ewma {
+///     lambda: dec!(0.94),
+/// })
+/// .unwrap();
+///
+/// estimator.update_return(dec!(0.01)).unwrap();
+/// estimator.update_return(dec!(-0.02)).unwrap();
+/// estimator.update_return(dec!(0.015)).unwrap();
+///
+/// let volatility = estimator.volatility().unwrap();
+/// assert!(volatility > dec!(0.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "StreamingVolatilityEstimatorData")
+)]
+pub struct StreamingVolatilityEstimator {
+    mode: StreamingVolatilityMode,
+    annualization_factor: Decimal,
+    four_ln_2: Decimal,
+    observations: usize,
+    last_price: Option<Decimal>,
+    last_timestamp: Option<u64>,
+    ewma_seed: Vec<Decimal>,
+    ewma_variance: Option<Decimal>,
+    window_values: VecDeque<Decimal>,
+    sum: Decimal,
+    sum_squares: Decimal,
+    updates_since_resync: usize,
+    open_bar: Option<OpenBar>,
+}
+
+/// Unchecked fields of a deserialized [`StreamingVolatilityEstimator`].
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StreamingVolatilityEstimatorData {
+    mode: StreamingVolatilityMode,
+    annualization_factor: Decimal,
+    four_ln_2: Decimal,
+    observations: usize,
+    last_price: Option<Decimal>,
+    last_timestamp: Option<u64>,
+    ewma_seed: Vec<Decimal>,
+    ewma_variance: Option<Decimal>,
+    window_values: VecDeque<Decimal>,
+    sum: Decimal,
+    sum_squares: Decimal,
+    updates_since_resync: usize,
+    open_bar: Option<OpenBar>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<StreamingVolatilityEstimatorData> for StreamingVolatilityEstimator {
+    type Error = MMError;
+
+    fn try_from(data: StreamingVolatilityEstimatorData) -> MMResult<Self> {
+        data.mode.validate()?;
+        Ok(Self {
+            mode: data.mode,
+            annualization_factor: data.annualization_factor,
+            four_ln_2: data.four_ln_2,
+            observations: data.observations,
+            last_price: data.last_price,
+            last_timestamp: data.last_timestamp,
+            ewma_seed: data.ewma_seed,
+            ewma_variance: data.ewma_variance,
+            window_values: data.window_values,
+            sum: data.sum,
+            sum_squares: data.sum_squares,
+            updates_since_resync: data.updates_since_resync,
+            open_bar: data.open_bar,
+        })
+    }
+}
+
+impl StreamingVolatilityEstimator {
+    /// Creates a streaming estimator for the given mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if lambda is outside (0, 1),
+    /// the rolling window is shorter than 2, the Parkinson window is empty,
+    /// or the bar length is zero.
+    pub fn new(mode: StreamingVolatilityMode) -> MMResult<Self> {
+        mode.validate()?;
+
+        let capacity = match mode {
+            StreamingVolatilityMode::Ewma { .. } => 0,
+            StreamingVolatilityMode::Rolling { window }
+            | StreamingVolatilityMode::Parkinson { window, .. } => window,
+        };
+
+        Ok(Self {
+            mode,
+            annualization_factor: decimal_sqrt(Decimal::from(252))?,
+            four_ln_2: Decimal::from(4) * decimal_ln(Decimal::from(2))?,
+            observations: 0,
+            last_price: None,
+            last_timestamp: None,
+            ewma_seed: Vec::with_capacity(EWMA_SEED_RETURNS),
+            ewma_variance: None,
+            window_values: VecDeque::with_capacity(capacity),
+            sum: Decimal::ZERO,
+            sum_squares: Decimal::ZERO,
+            updates_since_resync: 0,
+            open_bar: None,
+        })
+    }
+
+    /// Sets a custom annualization factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - Multiplier applied to the per-period volatility
+    ///   (e.g., sqrt(365*24) for hourly returns)
+    #[must_use]
+    pub fn with_annualization_factor(mut self, factor: Decimal) -> Self {
+        self.annualization_factor = factor;
+        self
+    }
+
+    /// Returns the update mode.
+    #[must_use]
+    pub fn mode(&self) -> StreamingVolatilityMode {
+        self.mode
+    }
+
+    /// Returns the annualization factor.
+    #[must_use]
+    pub fn annualization_factor(&self) -> Decimal {
+        self.annualization_factor
+    }
+
+    /// Returns the number of returns (or completed bars) absorbed so far.
+    #[must_use]
+    pub fn observations(&self) -> usize {
+        self.observations
+    }
+
+    /// Returns the last price passed to [`update_price`](Self::update_price).
+    #[must_use]
+    pub fn last_price(&self) -> Option<Decimal> {
+        self.last_price
+    }
+
+    /// Returns true once enough data has arrived to produce an estimate.
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        match self.mode {
+            StreamingVolatilityMode::Ewma { .. } => {
+                self.ewma_variance.is_some() || self.ewma_seed.len() >= 2
+            }
+            StreamingVolatilityMode::Rolling { .. } => self.window_values.len() >= 2,
+            StreamingVolatilityMode::Parkinson { .. } => !self.window_values.is_empty(),
+        }
+    }
+
+    /// Feeds a new price observation.
+    ///
+    /// In the EWMA and rolling modes the log return from the previous price
+    /// is absorbed. In Parkinson mode the price extends the bar for
+    /// `timestamp / bar_ms`; the previous bar is completed and enters the
+    /// window when a price arrives in a later bar. The bar still being built
+    /// is not part of the estimate.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` for a non-positive price and
+    /// `MMError::InvalidTimestamp` if the timestamp is earlier than the
+    /// previous update. The estimator is unchanged on error.
+    pub fn update_price(&mut self, timestamp: u64, price: Decimal) -> MMResult<()> {
+        if price <= Decimal::ZERO {
+            return Err(MMError::InvalidMarketState(
+                "prices must be positive".to_string(),
+            ));
+        }
+        if let Some(last) = self.last_timestamp
+            && timestamp < last
+        {
+            return Err(MMError::InvalidTimestamp(format!(
+                "timestamp {timestamp} is earlier than previous update {last}"
+            )));
+        }
+
+        match self.mode {
+            StreamingVolatilityMode::Parkinson { bar_ms, .. } => {
+                let bucket = timestamp / bar_ms;
+                match self.open_bar {
+                    Some(ref mut bar) if bar.bucket == bucket => {
+                        bar.high = bar.high.max(price);
+                        bar.low = bar.low.min(price);
+                    }
+                    Some(bar) => {
+                        self.push_range(bar.high, bar.low)?;
+                        self.open_bar = Some(OpenBar {
+                            bucket,
+                            high: price,
+                            low: price,
+                        });
+                    }
+                    None => {
+                        self.open_bar = Some(OpenBar {
+                            bucket,
+                            high: price,
+                            low: price,
+                        });
+                    }
+                }
+            }
+            StreamingVolatilityMode::Ewma { .. } | StreamingVolatilityMode::Rolling { .. } => {
+                if let Some(previous) = self.last_price {
+                    let log_return = decimal_ln(price / previous)?;
+                    self.push_return(log_return);
+                }
+            }
+        }
+
+        self.last_price = Some(price);
+        self.last_timestamp = Some(timestamp);
+        Ok(())
+    }
+
+    /// Feeds a log return directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` in Parkinson mode, which needs
+    /// price ranges rather than returns.
+    pub fn update_return(&mut self, log_return: Decimal) -> MMResult<()> {
+        if matches!(self.mode, StreamingVolatilityMode::Parkinson { .. }) {
+            return Err(MMError::InvalidConfiguration(
+                "Parkinson mode is driven by prices or ranges, not returns".to_string(),
+            ));
+        }
+        self.push_return(log_return);
+        Ok(())
+    }
+
+    /// Feeds the high and low of a completed bar.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` outside Parkinson mode, and
+    /// `MMError::InvalidMarketState` for non-positive prices or a high below
+    /// the low.
+    pub fn update_range(&mut self, high: Decimal, low: Decimal) -> MMResult<()> {
+        if !matches!(self.mode, StreamingVolatilityMode::Parkinson { .. }) {
+            return Err(MMError::InvalidConfiguration(
+                "ranges can only drive Parkinson mode".to_string(),
+            ));
+        }
+        if high <= Decimal::ZERO || low <= Decimal::ZERO {
+            return Err(MMError::InvalidMarketState(
+                "prices must be positive".to_string(),
+            ));
+        }
+        if high < low {
+            return Err(MMError::InvalidMarketState(
+                "high price must be >= low price".to_string(),
+            ));
+        }
+        self.push_range(high, low)
+    }
+
+    /// Feeds a price update received from a data feed subscription.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`update_price`](Self::update_price).
+    #[cfg(feature = "data-feeds")]
+    pub fn on_price_update(&mut self, update: &PriceUpdate) -> MMResult<()> {
+        self.update_price(update.timestamp, update.price)
+    }
+
+    /// Returns the current per-period variance, if enough data has arrived.
+    #[must_use]
+    pub fn variance(&self) -> Option<Decimal> {
+        match self.mode {
+            StreamingVolatilityMode::Ewma { lambda } => match self.ewma_variance {
+                Some(variance) => Some(variance),
+                None if self.ewma_seed.len() >= 2 => Some(seed_ewma(&self.ewma_seed, lambda)),
+                None => None,
+            },
+            StreamingVolatilityMode::Rolling { .. } => {
+                let n = self.window_values.len();
+                if n < 2 {
+                    return None;
+                }
+                let count = Decimal::from(n);
+                let variance =
+                    (self.sum_squares - self.sum * self.sum / count) / Decimal::from(n - 1);
+                Some(variance.max(Decimal::ZERO))
+            }
+            StreamingVolatilityMode::Parkinson { .. } => {
+                if self.window_values.is_empty() {
+                    return None;
+                }
+                let count = Decimal::from(self.window_values.len());
+                Some((self.sum_squares / (self.four_ln_2 * count)).max(Decimal::ZERO))
+            }
+        }
+    }
+
+    /// Returns the current annualized volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if not enough data has arrived,
+    /// or `MMError::NumericalError` if the square root fails.
+    pub fn volatility(&self) -> MMResult<Decimal> {
+        let variance = self.variance().ok_or_else(|| {
+            MMError::InvalidMarketState("not enough data to estimate volatility".to_string())
+        })?;
+        Ok(decimal_sqrt(variance)? * self.annualization_factor)
+    }
+
+    /// Clears all accumulated state, keeping the mode and annualization.
+    pub fn reset(&mut self) {
+        self.observations = 0;
+        self.last_price = None;
+        self.last_timestamp = None;
+        self.ewma_seed.clear();
+        self.ewma_variance = None;
+        self.window_values.clear();
+        self.sum = Decimal::ZERO;
+        self.sum_squares = Decimal::ZERO;
+        self.updates_since_resync = 0;
+        self.open_bar = None;
+    }
+
+    fn push_return(&mut self, log_return: Decimal) {
+        self.observations += 1;
+        match self.mode {
+            StreamingVolatilityMode::Ewma { lambda } => match self.ewma_variance {
+                Some(variance) => {
+                    self.ewma_variance =
+                        Some(lambda * variance + (Decimal::ONE - lambda) * log_return * log_return);
+                }
+                None => {
+                    self.ewma_seed.push(log_return);
+                    if self.ewma_seed.len() == EWMA_SEED_RETURNS {
+                        self.ewma_variance = Some(seed_ewma(&self.ewma_seed, lambda));
+                        self.ewma_seed.clear();
+                    }
+                }
+            },
+            StreamingVolatilityMode::Rolling { window } => {
+                self.push_window_value(window, log_return, true);
+            }
+            StreamingVolatilityMode::Parkinson { .. } => {}
+        }
+    }
+
+    fn push_range(&mut self, high: Decimal, low: Decimal) -> MMResult<()> {
+        if let StreamingVolatilityMode::Parkinson { window, .. } = self.mode {
+            let log_range = decimal_ln(high / low)?;
+            self.observations += 1;
+            self.push_window_value(window, log_range * log_range, false);
+        }
+        Ok(())
+    }
+
+    /// Appends to the window and updates the running sums.
+    ///
+    /// With `squared` set the value is a return whose square feeds
+    /// `sum_squares`; otherwise the value is already a squared term.
+    fn push_window_value(&mut self, window: usize, value: Decimal, squared: bool) {
+        if self.window_values.len() == window
+            && let Some(evicted) = self.window_values.pop_front()
+        {
+            if squared {
+                self.sum -= evicted;
+                self.sum_squares -= evicted * evicted;
+            } else {
+                self.sum_squares -= evicted;
+            }
+        }
+
+        self.window_values.push_back(value);
+        if squared {
+            self.sum += value;
+            self.sum_squares += value * value;
+        } else {
+            self.sum_squares += value;
+        }
+
+        self.updates_since_resync += 1;
+        if self.updates_since_resync >= window {
+            self.updates_since_resync = 0;
+            if squared {
+                self.sum = self.window_values.iter().sum();
+                self.sum_squares = self.window_values.iter().map(|v| v * v).sum();
+            } else {
+                self.sum_squares = self.window_values.iter().sum();
+            }
+        }
+    }
+}
+
+/// EWMA variance over seed returns, initialized with their sample variance.
+fn seed_ewma(returns: &[Decimal], lambda: Decimal) -> Decimal {
+    let n = Decimal::from(returns.len());
+    let mean = returns.iter().sum::<Decimal>() / n;
+    let squared_devs: Decimal = returns
+        .iter()
+        .map(|&r| {
+            let dev = r - mean;
+            dev * dev
+        })
+        .sum();
+    let mut variance = squared_devs / (n - Decimal::ONE);
+
+    let one_minus_lambda = Decimal::ONE - lambda;
+    for &r in returns {
+        variance = lambda * variance + one_minus_lambda * r * r;
+    }
+    variance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+    use crate::market_state::volatility::VolatilityEstimator;
+    use crate::types::numerics::Lcg;
+
+    fn prices() -> Vec<Decimal> {
+        let mut rng = Lcg::new(7);
+        let mut price = dec!(100);
+        let mut prices = vec![price];
+        for _ in 0..60 {
+            let step = Decimal::from(rng.index(201)) - dec!(100);
+            price += step / dec!(100);
+            prices.push(price);
+        }
+        prices
+    }
+
+    fn assert_close(a: Decimal, b: Decimal) {
+        assert!((a - b).abs() < dec!(0.0000001), "{a} vs {b}");
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_modes() {
+        let invalid = [
+            StreamingVolatilityMode::Ewma { lambda: dec!(1) },
+            StreamingVolatilityMode::Ewma { lambda: dec!(0) },
+            StreamingVolatilityMode::Rolling { window: 1 },
+            StreamingVolatilityMode::Parkinson {
+                window: 0,
+                bar_ms: 1000,
+            },
+            StreamingVolatilityMode::Parkinson {
+                window: 5,
+                bar_ms: 0,
+            },
+        ];
+        for mode in invalid {
+            assert!(matches!(
+                StreamingVolatilityEstimator::new(mode),
+                Err(MMError::InvalidConfiguration(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_ewma_matches_batch_for_every_prefix() {
+        let prices = prices();
+        let batch = VolatilityEstimator::new();
+        let mut streaming =
+            StreamingVolatilityEstimator::new(StreamingVolatilityMode::Ewma { lambda: dec!(0.94) })
+                .unwrap();
+
+        for (i, &price) in prices.iter().enumerate() {
+            streaming.update_price(i as u64, price).unwrap();
+            if i >= 2 {
+                let expected = batch.calculate_ewma(&prices[..=i], dec!(0.94)).unwrap();
+                assert_close(streaming.volatility().unwrap(), expected);
+            } else {
+                assert!(!streaming.is_ready());
+            }
+        }
+        assert_eq!(streaming.observations(), prices.len() - 1);
+    }
+
+    #[test]
+    fn test_rolling_matches_batch_over_window() {
+        let prices = prices();
+        let window = 10;
+        let batch = VolatilityEstimator::new();
+        let mut streaming =
+            StreamingVolatilityEstimator::new(StreamingVolatilityMode::Rolling { window }).unwrap();
+
+        for (i, &price) in prices.iter().enumerate() {
+            streaming.update_price(i as u64, price).unwrap();
+            if i >= 2 {
+                let start = i.saturating_sub(window);
+                let expected = batch.calculate_simple(&prices[start..=i]).unwrap();
+                assert_close(streaming.volatility().unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parkinson_ranges_match_batch() {
+        let highs = [dec!(102.0), dec!(103.5), dec!(101.0), dec!(104.0)];
+        let lows = [dec!(99.0), dec!(100.5), dec!(98.0), dec!(101.5)];
+        let batch = VolatilityEstimator::new();
+        let mut streaming = StreamingVolatilityEstimator::new(StreamingVolatilityMode::Parkinson {
+            window: 3,
+            bar_ms: 1000,
+        })
+        .unwrap();
+
+        for (&high, &low) in highs.iter().zip(&lows) {
+            streaming.update_range(high, low).unwrap();
+        }
+
+        let expected = batch.calculate_parkinson(&highs[1..], &lows[1..]).unwrap();
+        assert_close(streaming.volatility().unwrap(), expected);
+        assert!(streaming.update_return(dec!(0.01)).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialization_validates_mode() {
+        let mut streaming = StreamingVolatilityEstimator::new(StreamingVolatilityMode::Parkinson {
+            window: 10,
+            bar_ms: 1000,
+        })
+        .unwrap();
+        streaming.update_price(0, dec!(100)).unwrap();
+        streaming.update_price(1500, dec!(101)).unwrap();
+
+        let value = serde_json::to_value(&streaming).unwrap();
+        let restored: StreamingVolatilityEstimator = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(restored.mode(), streaming.mode());
+        assert_eq!(restored.observations(), streaming.observations());
+
+        let mut zero_bar = value;
+        zero_bar["mode"]["Parkinson"]["bar_ms"] = serde_json::json!(0);
+        assert!(serde_json::from_value::<StreamingVolatilityEstimator>(zero_bar).is_err());
+    }
+
+    #[test]
+    fn test_parkinson_builds_bars_from_prices() {
+        let mut streaming = StreamingVolatilityEstimator::new(StreamingVolatilityMode::Parkinson {
+            window: 10,
+            bar_ms: 1000,
+        })
+        .unwrap();
+
+        streaming.update_price(0, dec!(100)).unwrap();
+        streaming.update_price(400, dec!(102)).unwrap();
+        streaming.update_price(900, dec!(99)).unwrap();
+        // Open bar is excluded until the next bar starts
+        assert!(!streaming.is_ready());
+
+        streaming.update_price(1_000, dec!(101)).unwrap();
+        let expected = VolatilityEstimator::new()
+            .calculate_parkinson(&[dec!(102)], &[dec!(99)])
+            .unwrap();
+        assert_close(streaming.volatility().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_update_price_rejects_bad_input() {
+        let mut streaming =
+            StreamingVolatilityEstimator::new(StreamingVolatilityMode::Rolling { window: 5 })
+                .unwrap();
+        streaming.update_price(1_000, dec!(100)).unwrap();
+
+        assert!(matches!(
+            streaming.update_price(500, dec!(101)),
+            Err(MMError::InvalidTimestamp(_))
+        ));
+        assert!(matches!(
+            streaming.update_price(2_000, dec!(0)),
+            Err(MMError::InvalidMarketState(_))
+        ));
+        assert!(streaming.update_range(dec!(101), dec!(99)).is_err());
+        assert_eq!(streaming.last_price(), Some(dec!(100)));
+    }
+
+    #[test]
+    fn test_reset_clears_state() {
+        let mut streaming =
+            StreamingVolatilityEstimator::new(StreamingVolatilityMode::Rolling { window: 5 })
+                .unwrap()
+                .with_annualization_factor(dec!(10));
+        for (i, price) in prices().into_iter().take(8).enumerate() {
+            streaming.update_price(i as u64, price).unwrap();
+        }
+        assert!(streaming.is_ready());
+
+        streaming.reset();
+        assert!(!streaming.is_ready());
+        assert_eq!(streaming.observations(), 0);
+        assert_eq!(streaming.annualization_factor(), dec!(10));
+        assert!(streaming.volatility().is_err());
+        streaming.update_price(0, dec!(100)).unwrap();
+    }
+}
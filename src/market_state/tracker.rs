@@ -0,0 +1,556 @@
+//! Incremental construction of [`MarketState`].
+//!
+//! [`MarketStateTracker`] folds market ticks, order book snapshots, trades
+//! and (with the `data-feeds` feature) [`MarketDataFeed`] updates into a
+//! single [`MarketState`]. Each update is O(book depth): the realized
+//! volatility comes from a [`StreamingVolatilityEstimator`] and the trade
+//! history is a bounded ring.
+//!
+//! [`MarketDataFeed`]: crate::data_feeds::MarketDataFeed
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::backtest::MarketTick;
+//! use market_maker_rs::market_state::tracker::{MarketStateConfig, MarketStateTracker};
+//! use market_maker_rs::dec;
+//!
+//! let config = MarketStateConfig::new(dec!(0.2)).unwrap().with_stale_after(1_000);
+//! let mut tracker = MarketStateTracker::new(config).unwrap();
+//!
+//! tracker
+//!     .on_tick(&MarketTick::new(0, dec!(99.9), dec!(4.0), dec!(100.1), dec!(1.0)))
+//!     .unwrap();
+//!
+//! let state = tracker.state();
+//! assert_eq!(state.mid_price, dec!(100.0));
+//! assert_eq!(state.microprice(), Some(dec!(100.06)));
+//!
+//! // No update for longer than the threshold
+//! assert!(tracker.refresh(5_000).is_stale());
+//! ```
+
+use crate::Decimal;
+use crate::analytics::order_flow::{Trade, TradeSide};
+use crate::backtest::data::MarketTick;
+use crate::execution::{BookLevel, OrderBookSnapshot};
+use crate::market_state::snapshot::MarketState;
+use crate::market_state::streaming::{StreamingVolatilityEstimator, StreamingVolatilityMode};
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "data-feeds")]
+use crate::data_feeds::{self, MarketSnapshot, PriceUpdate};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Configuration for a [`MarketStateTracker`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MarketStateConfig {
+    /// Number of book levels kept per side.
+    pub depth: usize,
+
+    /// Number of recent trades kept.
+    pub max_trades: usize,
+
+    /// Milliseconds without an update after which the state is stale.
+    pub stale_after_ms: u64,
+
+    /// Volatility used until the realized estimate is available (annualized).
+    pub default_volatility: Decimal,
+
+    /// Update rule of the realized volatility estimator.
+    pub volatility_mode: StreamingVolatilityMode,
+
+    /// Annualization factor of the realized volatility; sqrt(252) if unset.
+    pub annualization_factor: Option<Decimal>,
+}
+
+impl MarketStateConfig {
+    /// Creates a configuration keeping 5 levels and 100 trades, flagging the
+    /// state stale after 5 seconds, and estimating realized volatility with
+    /// an EWMA (λ = 0.94) of mid returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the default volatility is
+    /// not positive.
+    pub fn new(default_volatility: Decimal) -> MMResult<Self> {
+        if default_volatility <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "default_volatility must be positive".to_string(),
+            ));
+        }
+        Ok(Self {
+            depth: 5,
+            max_trades: 100,
+            stale_after_ms: 5_000,
+            default_volatility,
+            volatility_mode: StreamingVolatilityMode::Ewma {
+                lambda: Decimal::new(94, 2),
+            },
+            annualization_factor: None,
+        })
+    }
+
+    /// Sets the number of book levels kept per side (at least 1).
+    #[must_use]
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// Sets the number of recent trades kept.
+    #[must_use]
+    pub fn with_max_trades(mut self, max_trades: usize) -> Self {
+        self.max_trades = max_trades;
+        self
+    }
+
+    /// Sets the staleness threshold in milliseconds.
+    #[must_use]
+    pub fn with_stale_after(mut self, stale_after_ms: u64) -> Self {
+        self.stale_after_ms = stale_after_ms;
+        self
+    }
+
+    /// Sets the update rule of the realized volatility estimator.
+    #[must_use]
+    pub fn with_volatility_mode(mut self, mode: StreamingVolatilityMode) -> Self {
+        self.volatility_mode = mode;
+        self
+    }
+
+    /// Sets the annualization factor of the realized volatility.
+    #[must_use]
+    pub fn with_annualization_factor(mut self, factor: Decimal) -> Self {
+        self.annualization_factor = Some(factor);
+        self
+    }
+}
+
+/// Maintains a [`MarketState`] from incremental market data.
+///
+/// Book, tick and price updates move the mid and feed the realized
+/// volatility estimator; the state's `volatility` follows the realized
+/// estimate once it is available and positive, and the configured default
+/// before that. Trades only extend the trade history.
+#[derive(Debug, Clone)]
+pub struct MarketStateTracker {
+    config: MarketStateConfig,
+    state: MarketState,
+    estimator: StreamingVolatilityEstimator,
+    last_update: Option<u64>,
+    last_price_update: Option<u64>,
+}
+
+impl MarketStateTracker {
+    /// Creates a tracker with no market data yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the volatility mode is
+    /// invalid.
+    pub fn new(config: MarketStateConfig) -> MMResult<Self> {
+        let mut estimator = StreamingVolatilityEstimator::new(config.volatility_mode)?;
+        if let Some(factor) = config.annualization_factor {
+            estimator = estimator.with_annualization_factor(factor);
+        }
+        Ok(Self {
+            state: MarketState::new(Decimal::ZERO, config.default_volatility, 0),
+            config,
+            estimator,
+            last_update: None,
+            last_price_update: None,
+        })
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn config(&self) -> &MarketStateConfig {
+        &self.config
+    }
+
+    /// Returns the current market state.
+    #[must_use]
+    pub fn state(&self) -> &MarketState {
+        &self.state
+    }
+
+    /// Returns true once a price has been observed.
+    #[must_use]
+    pub fn has_price(&self) -> bool {
+        self.state.mid_price > Decimal::ZERO
+    }
+
+    /// Returns the timestamp of the last update, if any.
+    #[must_use]
+    pub fn last_update(&self) -> Option<u64> {
+        self.last_update
+    }
+
+    /// Re-evaluates staleness at `now` and returns the state.
+    ///
+    /// A tracker that has never received data is always stale.
+    pub fn refresh(&mut self, now: u64) -> &MarketState {
+        self.state.stale = match self.last_update {
+            Some(last) => now.saturating_sub(last) > self.config.stale_after_ms,
+            None => true,
+        };
+        &self.state
+    }
+
+    /// Applies a top-of-book tick, including its last trade if present.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` for non-positive prices and
+    /// `MMError::InvalidTimestamp` if the tick is older than the last price update.
+    pub fn on_tick(&mut self, tick: &MarketTick) -> MMResult<()> {
+        let mut book = OrderBookSnapshot::new(self.symbol(), tick.timestamp);
+        book.bids
+            .push(BookLevel::new(tick.bid_price, tick.bid_size));
+        book.asks
+            .push(BookLevel::new(tick.ask_price, tick.ask_size));
+        self.on_orderbook(&book)?;
+
+        if let (Some(price), Some(size)) = (tick.last_price, tick.last_size) {
+            let side = self.classify(price);
+            self.on_trade(Trade::new(price, size, side, tick.timestamp));
+        }
+        Ok(())
+    }
+
+    /// Applies an order book snapshot, keeping the configured depth.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if either side is empty or the
+    /// best prices are not positive, and `MMError::InvalidTimestamp` if the
+    /// snapshot is older than the last price update.
+    pub fn on_orderbook(&mut self, book: &OrderBookSnapshot) -> MMResult<()> {
+        let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) else {
+            return Err(MMError::InvalidMarketState(
+                "order book needs both a bid and an ask".to_string(),
+            ));
+        };
+        if bid <= Decimal::ZERO || ask <= Decimal::ZERO {
+            return Err(MMError::InvalidMarketState(
+                "prices must be positive".to_string(),
+            ));
+        }
+        self.check_timestamp(book.timestamp)?;
+
+        let mut book = book.clone();
+        book.bids.truncate(self.config.depth);
+        book.asks.truncate(self.config.depth);
+        let timestamp = book.timestamp;
+        self.state.orderbook = Some(book);
+        self.apply_mid((bid + ask) / Decimal::TWO, timestamp)
+    }
+
+    /// Applies a price-only update, such as a last price or index level.
+    ///
+    /// The book, if any, is dropped: it no longer matches the new mid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` for a non-positive price and
+    /// `MMError::InvalidTimestamp` if the update is older than the last price update.
+    pub fn on_price(&mut self, timestamp: u64, price: Decimal) -> MMResult<()> {
+        if price <= Decimal::ZERO {
+            return Err(MMError::InvalidMarketState(
+                "prices must be positive".to_string(),
+            ));
+        }
+        self.check_timestamp(timestamp)?;
+        self.state.orderbook = None;
+        self.apply_mid(price, timestamp)
+    }
+
+    /// Records a trade, dropping the oldest beyond `max_trades`.
+    pub fn on_trade(&mut self, trade: Trade) {
+        self.touch(trade.timestamp);
+        if self.config.max_trades == 0 {
+            return;
+        }
+        while self.state.recent_trades.len() >= self.config.max_trades {
+            self.state.recent_trades.pop_front();
+        }
+        self.state.recent_trades.push_back(trade);
+    }
+
+    /// Applies a price update from a [`MarketDataFeed`](data_feeds::MarketDataFeed).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`on_price`](Self::on_price).
+    #[cfg(feature = "data-feeds")]
+    pub fn on_price_update(&mut self, update: &PriceUpdate) -> MMResult<()> {
+        self.on_price(update.timestamp, update.price)
+    }
+
+    /// Records a trade from a [`MarketDataFeed`](data_feeds::MarketDataFeed).
+    ///
+    /// Trades without a side are classified against the current book.
+    #[cfg(feature = "data-feeds")]
+    pub fn on_feed_trade(&mut self, trade: &data_feeds::Trade) {
+        let side = match trade.side {
+            data_feeds::TradeSide::Buy => TradeSide::Buy,
+            data_feeds::TradeSide::Sell => TradeSide::Sell,
+            data_feeds::TradeSide::Unknown => self.classify(trade.price),
+        };
+        self.on_trade(Trade::new(
+            trade.price,
+            trade.quantity,
+            side,
+            trade.timestamp,
+        ));
+    }
+
+    /// Applies a market snapshot from a [`MarketDataFeed`](data_feeds::MarketDataFeed).
+    ///
+    /// Snapshots carry no sizes, so the book holds a single level per side
+    /// with zero quantity.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`on_orderbook`](Self::on_orderbook).
+    #[cfg(feature = "data-feeds")]
+    pub fn on_snapshot(&mut self, snapshot: &MarketSnapshot) -> MMResult<()> {
+        let mut book = OrderBookSnapshot::new(snapshot.symbol.clone(), snapshot.timestamp);
+        book.bids.push(BookLevel::new(snapshot.bid, Decimal::ZERO));
+        book.asks.push(BookLevel::new(snapshot.ask, Decimal::ZERO));
+        self.on_orderbook(&book)
+    }
+
+    /// Clears all market data, keeping the configuration.
+    pub fn reset(&mut self) {
+        self.state = MarketState::new(Decimal::ZERO, self.config.default_volatility, 0);
+        self.estimator.reset();
+        self.last_update = None;
+        self.last_price_update = None;
+    }
+
+    fn symbol(&self) -> String {
+        self.state
+            .orderbook
+            .as_ref()
+            .map(|book| book.symbol.clone())
+            .unwrap_or_default()
+    }
+
+    fn check_timestamp(&self, timestamp: u64) -> MMResult<()> {
+        if let Some(last) = self.last_price_update
+            && timestamp < last
+        {
+            return Err(MMError::InvalidTimestamp(format!(
+                "update at {timestamp} is older than the last price update at {last}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn touch(&mut self, timestamp: u64) {
+        self.last_update = Some(
+            self.last_update
+                .map_or(timestamp, |last| last.max(timestamp)),
+        );
+        self.state.timestamp = self.state.timestamp.max(timestamp);
+        self.state.stale = false;
+    }
+
+    fn apply_mid(&mut self, mid: Decimal, timestamp: u64) -> MMResult<()> {
+        self.estimator.update_price(timestamp, mid)?;
+        self.last_price_update = Some(timestamp);
+        self.touch(timestamp);
+        self.state.mid_price = mid;
+
+        let realized = if self.estimator.is_ready() {
+            Some(self.estimator.volatility()?)
+        } else {
+            None
+        };
+        self.state.realized_volatility = realized;
+        self.state.volatility = realized
+            .filter(|v| *v > Decimal::ZERO)
+            .unwrap_or(self.config.default_volatility);
+        Ok(())
+    }
+
+    /// Infers the aggressor side: trades at or through the touch take that
+    /// side, otherwise the position against the mid decides, then the tick
+    /// test against the previous trade.
+    fn classify(&self, price: Decimal) -> TradeSide {
+        if let Some(ask) = self.state.best_ask()
+            && price >= ask
+        {
+            return TradeSide::Buy;
+        }
+        if let Some(bid) = self.state.best_bid()
+            && price <= bid
+        {
+            return TradeSide::Sell;
+        }
+        if self.has_price() && price != self.state.mid_price {
+            return if price > self.state.mid_price {
+                TradeSide::Buy
+            } else {
+                TradeSide::Sell
+            };
+        }
+        match self.state.last_trade() {
+            Some(last) if price > last.price => TradeSide::Buy,
+            Some(last) if price < last.price => TradeSide::Sell,
+            Some(last) => last.side,
+            None => TradeSide::Buy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+
+    fn tracker() -> MarketStateTracker {
+        let config = MarketStateConfig::new(dec!(0.3))
+            .unwrap()
+            .with_depth(2)
+            .with_max_trades(3)
+            .with_stale_after(1_000)
+            .with_volatility_mode(StreamingVolatilityMode::Rolling { window: 5 });
+        MarketStateTracker::new(config).unwrap()
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(MarketStateConfig::new(dec!(0)).is_err());
+        let config = MarketStateConfig::new(dec!(0.2))
+            .unwrap()
+            .with_depth(0)
+            .with_volatility_mode(StreamingVolatilityMode::Rolling { window: 1 });
+        assert_eq!(config.depth, 1);
+        assert!(MarketStateTracker::new(config).is_err());
+    }
+
+    #[test]
+    fn test_orderbook_updates_truncate_depth() {
+        let mut tracker = tracker();
+        let mut book = OrderBookSnapshot::new("BTC-USD", 100);
+        for i in 0..4 {
+            book.bids
+                .push(BookLevel::new(dec!(99) - Decimal::from(i), dec!(1)));
+            book.asks
+                .push(BookLevel::new(dec!(101) + Decimal::from(i), dec!(2)));
+        }
+        tracker.on_orderbook(&book).unwrap();
+
+        let state = tracker.state();
+        assert_eq!(state.mid_price, dec!(100));
+        assert_eq!(state.timestamp, 100);
+        let kept = state.orderbook.as_ref().unwrap();
+        assert_eq!(kept.bids.len(), 2);
+        assert_eq!(kept.asks.len(), 2);
+        assert_eq!(state.imbalance(), Some(dec!(-2) / dec!(6)));
+
+        let empty = OrderBookSnapshot::new("BTC-USD", 200);
+        assert!(tracker.on_orderbook(&empty).is_err());
+        let mut old = book.clone();
+        old.timestamp = 50;
+        assert!(matches!(
+            tracker.on_orderbook(&old),
+            Err(MMError::InvalidTimestamp(_))
+        ));
+    }
+
+    #[test]
+    fn test_ticks_build_trades_and_volatility() {
+        let mut tracker = tracker();
+        let prices = [
+            dec!(100.0),
+            dec!(100.4),
+            dec!(99.8),
+            dec!(100.6),
+            dec!(100.1),
+        ];
+        for (i, &mid) in prices.iter().enumerate() {
+            let tick = MarketTick::with_last_trade(
+                i as u64 * 100,
+                mid - dec!(0.1),
+                dec!(1),
+                mid + dec!(0.1),
+                dec!(1),
+                mid + dec!(0.1),
+                dec!(0.5),
+            );
+            tracker.on_tick(&tick).unwrap();
+            if i < 2 {
+                assert_eq!(tracker.state().volatility, dec!(0.3));
+                assert!(tracker.state().realized_volatility.is_none());
+            }
+        }
+
+        let state = tracker.state();
+        assert_eq!(state.recent_trades.len(), 3);
+        assert!(state.recent_trades.iter().all(Trade::is_buy));
+        assert_eq!(state.last_trade().unwrap().timestamp, 400);
+        let realized = state.realized_volatility.unwrap();
+        assert!(realized > Decimal::ZERO);
+        assert_eq!(state.volatility, realized);
+    }
+
+    #[test]
+    fn test_trade_classification() {
+        let mut tracker = tracker();
+        tracker
+            .on_tick(&MarketTick::new(0, dec!(99), dec!(1), dec!(101), dec!(1)))
+            .unwrap();
+
+        for (price, expected) in [
+            (dec!(98.5), TradeSide::Sell),
+            (dec!(100.5), TradeSide::Buy),
+            (dec!(99.5), TradeSide::Sell),
+            (dec!(100), TradeSide::Buy),
+        ] {
+            assert_eq!(tracker.classify(price), expected, "{price}");
+            tracker.on_trade(Trade::new(price, dec!(1), expected, 1));
+        }
+    }
+
+    #[test]
+    fn test_price_update_drops_book() {
+        let mut tracker = tracker();
+        tracker
+            .on_tick(&MarketTick::new(0, dec!(99), dec!(1), dec!(101), dec!(1)))
+            .unwrap();
+        assert_eq!(tracker.state().spread(), Some(dec!(2)));
+
+        tracker.on_price(1_000, dec!(105)).unwrap();
+        let state = tracker.state();
+        assert_eq!(state.mid_price, dec!(105));
+        assert!(state.orderbook.is_none());
+        assert_eq!(state.microprice(), None);
+        assert_eq!(tracker.classify(dec!(104)), TradeSide::Sell);
+    }
+
+    #[test]
+    fn test_staleness_and_reset() {
+        let mut tracker = tracker();
+        assert!(tracker.refresh(0).is_stale());
+
+        tracker.on_price(1_000, dec!(50)).unwrap();
+        assert!(!tracker.refresh(1_500).is_stale());
+        assert!(tracker.refresh(2_500).is_stale());
+
+        tracker.on_trade(Trade::new(dec!(50), dec!(1), TradeSide::Buy, 2_600));
+        assert!(!tracker.state().is_stale());
+        assert_eq!(tracker.last_update(), Some(2_600));
+
+        tracker.reset();
+        assert!(!tracker.has_price());
+        assert!(tracker.state().recent_trades.is_empty());
+        assert!(tracker.refresh(3_000).is_stale());
+    }
+}
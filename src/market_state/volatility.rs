@@ -9,6 +9,11 @@
 //! - **Simple Standard Deviation**: Traditional statistical volatility
 //! - **Exponentially Weighted Moving Average (EWMA)**: Gives more weight to recent observations
 //! - **Parkinson's Range-Based**: Uses high-low price range (more efficient)
+//! - **Garman-Klass, Rogers-Satchell, Yang-Zhang**: Range-based estimators over
+//!   [`OHLCVBar`]s that also use the open and close
+//!
+//! GARCH(1,1) forecasting lives in [`garch`](super::garch) and noise-robust
+//! realized variance for tick data in [`realized`](super::realized).
 //!
 //! # Examples
 //!
@@ -24,6 +29,7 @@
 //! ```
 
 use crate::Decimal;
+use crate::backtest::data::OHLCVBar;
 use crate::types::decimal::{decimal_ln, decimal_sqrt};
 use crate::types::error::{MMError, MMResult};
 
@@ -362,6 +368,241 @@ impl VolatilityEstimator {
         let annualization_factor = self.get_annualization_factor()?;
         Ok(std_dev * annualization_factor)
     }
+
+    /// Calculates volatility using the Garman-Klass estimator.
+    ///
+    /// Adds the open-to-close move to Parkinson's range term, which makes it
+    /// roughly seven times as efficient as close-to-close volatility for a
+    /// driftless price: σ² = mean(0.5 * ln(H/L)² - (2*ln(2) - 1) * ln(C/O)²)
+    ///
+    /// # Arguments
+    ///
+    /// * `bars` - Bars of equal duration (chronologically ordered)
+    ///
+    /// # Returns
+    ///
+    /// Annualized volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if no bars are provided or any bar is inconsistent
+    /// (non-positive prices, or open/close outside the high-low range).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use market_maker_rs::backtest::OHLCVBar;
+    /// use market_maker_rs::market_state::volatility::VolatilityEstimator;
+    /// use market_maker_rs::dec;
+    ///
+    /// let bars = vec![
+    ///     OHLCVBar::new(0, dec!(100.0), dec!(102.0), dec!(99.0), dec!(101.0), dec!(10.0)),
+    ///     OHLCVBar::new(1, dec!(101.0), dec!(103.0), dec!(100.5), dec!(102.5), dec!(12.0)),
+    /// ];
+    ///
+    /// let estimator = VolatilityEstimator::new();
+    /// let volatility = estimator.calculate_garman_klass(&bars).unwrap();
+    /// assert!(volatility > dec!(0.0));
+    /// ```
+    pub fn calculate_garman_klass(&self, bars: &[OHLCVBar]) -> MMResult<Decimal> {
+        if bars.is_empty() {
+            return Err(MMError::InvalidMarketState(
+                "need at least 1 bar for Garman-Klass estimator".to_string(),
+            ));
+        }
+
+        let ln_2 = decimal_ln(Decimal::from(2))?;
+        let close_weight = Decimal::TWO * ln_2 - Decimal::ONE;
+        let half = Decimal::new(5, 1);
+
+        let mut sum = Decimal::ZERO;
+        for bar in bars {
+            validate_bar(bar)?;
+            let range = decimal_ln(bar.high / bar.low)?;
+            let body = decimal_ln(bar.close / bar.open)?;
+            sum += half * range * range - close_weight * body * body;
+        }
+
+        let variance = (sum / Decimal::from(bars.len())).max(Decimal::ZERO);
+        let std_dev = decimal_sqrt(variance)?;
+
+        let annualization_factor = self.get_annualization_factor()?;
+        Ok(std_dev * annualization_factor)
+    }
+
+    /// Calculates volatility using the Rogers-Satchell estimator.
+    ///
+    /// Unlike Parkinson and Garman-Klass it stays unbiased when the price
+    /// drifts: σ² = mean(ln(H/C) * ln(H/O) + ln(L/C) * ln(L/O))
+    ///
+    /// # Arguments
+    ///
+    /// * `bars` - Bars of equal duration (chronologically ordered)
+    ///
+    /// # Returns
+    ///
+    /// Annualized volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if no bars are provided or any bar is inconsistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use market_maker_rs::backtest::OHLCVBar;
+    /// use market_maker_rs::market_state::volatility::VolatilityEstimator;
+    /// use market_maker_rs::dec;
+    ///
+    /// let bars = vec![
+    ///     OHLCVBar::new(0, dec!(100.0), dec!(102.0), dec!(99.0), dec!(101.0), dec!(10.0)),
+    ///     OHLCVBar::new(1, dec!(101.0), dec!(103.0), dec!(100.5), dec!(102.5), dec!(12.0)),
+    /// ];
+    ///
+    /// let estimator = VolatilityEstimator::new();
+    /// let volatility = estimator.calculate_rogers_satchell(&bars).unwrap();
+    /// assert!(volatility > dec!(0.0));
+    /// ```
+    pub fn calculate_rogers_satchell(&self, bars: &[OHLCVBar]) -> MMResult<Decimal> {
+        if bars.is_empty() {
+            return Err(MMError::InvalidMarketState(
+                "need at least 1 bar for Rogers-Satchell estimator".to_string(),
+            ));
+        }
+
+        let variance = rogers_satchell_variance(bars)?;
+        let std_dev = decimal_sqrt(variance)?;
+
+        let annualization_factor = self.get_annualization_factor()?;
+        Ok(std_dev * annualization_factor)
+    }
+
+    /// Calculates volatility using the Yang-Zhang estimator.
+    ///
+    /// Combines the overnight (previous close to open) variance, the
+    /// open-to-close variance and the Rogers-Satchell variance, so it handles
+    /// both drift and opening jumps:
+    /// σ² = σ²_overnight + k * σ²_open_close + (1 - k) * σ²_rs,
+    /// with k = 0.34 / (1.34 + (n + 1) / (n - 1)).
+    ///
+    /// The first bar only supplies the previous close, so `n` is one less
+    /// than the number of bars.
+    ///
+    /// # Arguments
+    ///
+    /// * `bars` - Consecutive bars of equal duration (chronologically ordered)
+    ///
+    /// # Returns
+    ///
+    /// Annualized volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if fewer than 3 bars are provided or any bar is
+    /// inconsistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use market_maker_rs::backtest::OHLCVBar;
+    /// use market_maker_rs::market_state::volatility::VolatilityEstimator;
+    /// use market_maker_rs::dec;
+    ///
+    /// let bars = vec![
+    ///     OHLCVBar::new(0, dec!(100.0), dec!(102.0), dec!(99.0), dec!(101.0), dec!(10.0)),
+    ///     OHLCVBar::new(1, dec!(101.5), dec!(103.0), dec!(100.5), dec!(102.5), dec!(12.0)),
+    ///     OHLCVBar::new(2, dec!(102.0), dec!(104.0), dec!(101.0), dec!(101.5), dec!(9.0)),
+    /// ];
+    ///
+    /// let estimator = VolatilityEstimator::new();
+    /// let volatility = estimator.calculate_yang_zhang(&bars).unwrap();
+    /// assert!(volatility > dec!(0.0));
+    /// ```
+    pub fn calculate_yang_zhang(&self, bars: &[OHLCVBar]) -> MMResult<Decimal> {
+        if bars.len() < 3 {
+            return Err(MMError::InvalidMarketState(
+                "need at least 3 bars for Yang-Zhang estimator".to_string(),
+            ));
+        }
+
+        let mut overnight = Vec::with_capacity(bars.len() - 1);
+        let mut open_close = Vec::with_capacity(bars.len() - 1);
+        for pair in bars.windows(2) {
+            let (previous, bar) = (&pair[0], &pair[1]);
+            validate_bar(previous)?;
+            validate_bar(bar)?;
+            overnight.push(decimal_ln(bar.open / previous.close)?);
+            open_close.push(decimal_ln(bar.close / bar.open)?);
+        }
+
+        let n = Decimal::from(overnight.len());
+        let k =
+            Decimal::new(34, 2) / (Decimal::new(134, 2) + (n + Decimal::ONE) / (n - Decimal::ONE));
+
+        let variance = sample_variance(&overnight)
+            + k * sample_variance(&open_close)
+            + (Decimal::ONE - k) * rogers_satchell_variance(&bars[1..])?;
+        let std_dev = decimal_sqrt(variance.max(Decimal::ZERO))?;
+
+        let annualization_factor = self.get_annualization_factor()?;
+        Ok(std_dev * annualization_factor)
+    }
+}
+
+/// Checks that a bar has positive prices and a consistent range.
+fn validate_bar(bar: &OHLCVBar) -> MMResult<()> {
+    if bar.open <= Decimal::ZERO
+        || bar.high <= Decimal::ZERO
+        || bar.low <= Decimal::ZERO
+        || bar.close <= Decimal::ZERO
+    {
+        return Err(MMError::InvalidMarketState(
+            "prices must be positive".to_string(),
+        ));
+    }
+
+    if bar.high < bar.low
+        || bar.open > bar.high
+        || bar.open < bar.low
+        || bar.close > bar.high
+        || bar.close < bar.low
+    {
+        return Err(MMError::InvalidMarketState(
+            "open and close must lie within the high-low range".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Mean Rogers-Satchell variance per bar.
+fn rogers_satchell_variance(bars: &[OHLCVBar]) -> MMResult<Decimal> {
+    let mut sum = Decimal::ZERO;
+    for bar in bars {
+        validate_bar(bar)?;
+        let high_close = decimal_ln(bar.high / bar.close)?;
+        let high_open = decimal_ln(bar.high / bar.open)?;
+        let low_close = decimal_ln(bar.low / bar.close)?;
+        let low_open = decimal_ln(bar.low / bar.open)?;
+        sum += high_close * high_open + low_close * low_open;
+    }
+    Ok((sum / Decimal::from(bars.len())).max(Decimal::ZERO))
+}
+
+/// Unbiased sample variance; zero for fewer than two values.
+fn sample_variance(values: &[Decimal]) -> Decimal {
+    if values.len() < 2 {
+        return Decimal::ZERO;
+    }
+    let mean = values.iter().sum::<Decimal>() / Decimal::from(values.len());
+    let squared_deviations: Decimal = values
+        .iter()
+        .map(|&v| {
+            let dev = v - mean;
+            dev * dev
+        })
+        .sum();
+    squared_deviations / Decimal::from(values.len() - 1)
 }
 
 #[cfg(test)]
@@ -577,6 +818,106 @@ mod tests {
         ));
     }
 
+    fn bar(open: Decimal, high: Decimal, low: Decimal, close: Decimal) -> OHLCVBar {
+        OHLCVBar::new(0, open, high, low, close, dec!(1.0))
+    }
+
+    #[test]
+    fn test_range_estimators_on_flat_bodies() {
+        // With open == close, Garman-Klass reduces to 0.5 * ln(H/L)^2 and
+        // Rogers-Satchell to ln(H/O)^2 + ln(L/O)^2
+        let estimator = VolatilityEstimator::with_annualization_factor(Decimal::ONE);
+        let bars = vec![
+            bar(dec!(100.0), dec!(101.0), dec!(99.0), dec!(100.0)),
+            bar(dec!(100.0), dec!(101.0), dec!(99.0), dec!(100.0)),
+        ];
+
+        let range = decimal_ln(dec!(101.0) / dec!(99.0)).unwrap();
+        let gk = estimator.calculate_garman_klass(&bars).unwrap();
+        let expected = decimal_sqrt(dec!(0.5) * range * range).unwrap();
+        assert!((gk - expected).abs() < dec!(0.000001));
+
+        let up = decimal_ln(dec!(1.01)).unwrap();
+        let down = decimal_ln(dec!(0.99)).unwrap();
+        let rs = estimator.calculate_rogers_satchell(&bars).unwrap();
+        let expected = decimal_sqrt(up * up + down * down).unwrap();
+        assert!((rs - expected).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn test_range_estimators_agree_on_consistent_bars() {
+        let estimator = VolatilityEstimator::new();
+        let bars = vec![
+            bar(dec!(100.0), dec!(101.2), dec!(99.4), dec!(100.6)),
+            bar(dec!(100.6), dec!(101.0), dec!(99.8), dec!(100.2)),
+            bar(dec!(100.2), dec!(100.9), dec!(99.1), dec!(99.5)),
+            bar(dec!(99.5), dec!(100.4), dec!(98.9), dec!(100.1)),
+            bar(dec!(100.1), dec!(101.5), dec!(99.9), dec!(101.3)),
+        ];
+        let highs: Vec<Decimal> = bars.iter().map(|b| b.high).collect();
+        let lows: Vec<Decimal> = bars.iter().map(|b| b.low).collect();
+
+        let parkinson = estimator.calculate_parkinson(&highs, &lows).unwrap();
+        let gk = estimator.calculate_garman_klass(&bars).unwrap();
+        let rs = estimator.calculate_rogers_satchell(&bars).unwrap();
+        let yz = estimator.calculate_yang_zhang(&bars).unwrap();
+
+        for vol in [gk, rs, yz] {
+            assert!(vol > parkinson / Decimal::TWO);
+            assert!(vol < parkinson * Decimal::TWO);
+        }
+    }
+
+    #[test]
+    fn test_yang_zhang_captures_opening_gaps() {
+        let estimator = VolatilityEstimator::new();
+        // Identical intraday ranges, but the second series gaps at each open
+        let calm = vec![
+            bar(dec!(100.0), dec!(100.5), dec!(99.5), dec!(100.0)),
+            bar(dec!(100.0), dec!(100.5), dec!(99.5), dec!(100.0)),
+            bar(dec!(100.0), dec!(100.5), dec!(99.5), dec!(100.0)),
+            bar(dec!(100.0), dec!(100.5), dec!(99.5), dec!(100.0)),
+        ];
+        let gappy = vec![
+            bar(dec!(100.0), dec!(100.5), dec!(99.5), dec!(100.0)),
+            bar(dec!(102.0), dec!(102.5), dec!(101.5), dec!(102.0)),
+            bar(dec!(99.0), dec!(99.5), dec!(98.5), dec!(99.0)),
+            bar(dec!(101.0), dec!(101.5), dec!(100.5), dec!(101.0)),
+        ];
+
+        let calm_rs = estimator.calculate_rogers_satchell(&calm).unwrap();
+        let gappy_rs = estimator.calculate_rogers_satchell(&gappy).unwrap();
+        assert!((calm_rs - gappy_rs).abs() < dec!(0.001));
+
+        let calm_yz = estimator.calculate_yang_zhang(&calm).unwrap();
+        let gappy_yz = estimator.calculate_yang_zhang(&gappy).unwrap();
+        assert!(gappy_yz > calm_yz * Decimal::TWO);
+    }
+
+    #[test]
+    fn test_ohlc_estimators_validation() {
+        let estimator = VolatilityEstimator::new();
+        assert!(estimator.calculate_garman_klass(&[]).is_err());
+        assert!(estimator.calculate_rogers_satchell(&[]).is_err());
+
+        let two = vec![
+            bar(dec!(100.0), dec!(101.0), dec!(99.0), dec!(100.5)),
+            bar(dec!(100.5), dec!(101.0), dec!(99.0), dec!(100.0)),
+        ];
+        assert!(matches!(
+            estimator.calculate_yang_zhang(&two),
+            Err(MMError::InvalidMarketState(_))
+        ));
+
+        // Close above the high
+        let broken = vec![bar(dec!(100.0), dec!(101.0), dec!(99.0), dec!(102.0))];
+        assert!(estimator.calculate_garman_klass(&broken).is_err());
+        assert!(estimator.calculate_rogers_satchell(&broken).is_err());
+
+        let negative = vec![bar(dec!(-1.0), dec!(101.0), dec!(99.0), dec!(100.0))];
+        assert!(estimator.calculate_garman_klass(&negative).is_err());
+    }
+
     #[test]
     fn test_calculate_parkinson_zero_price() {
         let estimator = VolatilityEstimator::new();
@@ -8,7 +8,7 @@
 //! ```
 
 // Re-export Decimal and helper functions
-pub use crate::types::decimal::{decimal_ln, decimal_powi, decimal_sqrt};
+pub use crate::types::decimal::{decimal_exp, decimal_ln, decimal_powi, decimal_sqrt};
 pub use crate::{Decimal, dec};
 
 // Re-export types module
@@ -24,20 +24,68 @@ pub use crate::strategy::adaptive_spread::{
 };
 pub use crate::strategy::calibration::{
     CalibrationConfig, CalibrationResult, FillObservation as CalibrationFillObservation,
-    OptimizedParameters, OrderIntensityCalibrator, ParameterOptimizer, RegimeAdjustments,
-    RiskAversionCalibrator, VolatilityRegime, VolatilityRegimeDetector,
+    IntensityCalibration, OptimizedParameters, OrderIntensityCalibrator, ParameterOptimizer,
+    QuoteLifetime, RegimeAdjustments, RiskAversionCalibrator, VolatilityRegime,
+    VolatilityRegimeDetector,
+};
+pub use crate::strategy::cartea_jaimungal::{
+    AlphaSignal, BookImbalanceSignal, CarteaJaimungalConfig, CarteaJaimungalStrategy,
+    ExternalSignal, OrderFlowSignal,
+};
+pub use crate::strategy::config::{StrategyConfig, TimeHorizon};
+pub use crate::strategy::cross_venue::{
+    CrossVenueConfig, CrossVenueMarketMaker, CrossVenueQuote, CrossVenueStrategy, HedgedQuote,
+};
+pub use crate::strategy::fair_value::{
+    DepthWeightedMid, FairValueEstimator, FairValueQuoter, Microprice, MidPrice, StoikovConfig,
+    StoikovMicroprice,
+};
+pub use crate::strategy::glft::{
+    GLFTConfig, GLFTSolution, GLFTStrategy, InventoryBounds, PenaltyFunction,
 };
-pub use crate::strategy::config::StrategyConfig;
-pub use crate::strategy::glft::{GLFTConfig, GLFTStrategy, PenaltyFunction};
 pub use crate::strategy::grid::{GridConfig, GridOrder, GridStrategy, OrderSide};
+pub use crate::strategy::grid_engine::{
+    GridAction, GridEngine, GridLevelStats, GridMode, GridOrderKind, LiveGridOrder,
+};
+pub use crate::strategy::ladder::{
+    LadderConfig, LadderDiff, LadderLevel, LadderModel, LadderQuoter, LadderSizing, LadderSpacing,
+    QuoteLadder, QuoteLadderGenerator,
+};
+pub use crate::strategy::pipeline::{
+    ImbalanceWideningOverlay, InventorySkewOverlay, QuoteExplanation, QuoteOverlay, QuotePipeline,
+    RiskLimitOverlay, StageContribution, ToxicityOverlay, VolatilityRegimeOverlay,
+};
+pub use crate::strategy::queue_placement::{
+    PlacementAction, PlacementDecision, QueuePlacementConfig, QueuePlacer, RestingOrder,
+};
 pub use crate::strategy::quote::Quote;
+pub use crate::strategy::quoting::{
+    AdaptiveSpreadQuoter, AvellanedaStoikovQuoter, DepthBasedQuoter, GLFTQuoter, QuoteContext,
+    QuoteLevel, QuoteSet, QuotingStrategy, QuotingStrategyConfig,
+};
+pub use crate::strategy::recalibration::{
+    CalibratedParameter, OnlineRecalibrator, ParameterUpdate, RecalibrationConfig,
+};
+pub use crate::strategy::regime::{
+    HmmRegimeClassifier, RegimeClassifier, RegimeThresholds, RegimeTrackerConfig, RegimeTransition,
+    VolatilityRegimeTracker,
+};
+pub use crate::strategy::toxicity_guard::{
+    ToxicityCurve, ToxicityGuard, ToxicityGuardConfig, ToxicityState, ToxicityTransition,
+};
 
 // Re-export position types
 pub use crate::position::inventory::InventoryPosition;
 pub use crate::position::pnl::PnL;
 
 // Re-export market state types
+pub use crate::market_state::garch::{GarchConfig, GarchModel, GarchParameters};
+pub use crate::market_state::realized::{
+    RealizedEstimate, RealizedMeasure, RealizedVarianceEstimator,
+};
 pub use crate::market_state::snapshot::MarketState;
+pub use crate::market_state::streaming::{StreamingVolatilityEstimator, StreamingVolatilityMode};
+pub use crate::market_state::tracker::{MarketStateConfig, MarketStateTracker};
 
 // Re-export risk types
 pub use crate::risk::{
@@ -48,6 +96,9 @@ pub use crate::risk::{
 };
 
 // Re-export analytics types
+pub use crate::analytics::hawkes::{
+    HawkesConfig, HawkesEstimate, HawkesEstimator, HawkesParameters,
+};
 pub use crate::analytics::intensity::{
     FillObservation, FillSide, IntensityEstimate, ObservationStats, OrderIntensityConfig,
     OrderIntensityEstimator,
@@ -75,11 +126,18 @@ pub use crate::execution::{
 
 // Re-export backtest types
 pub use crate::backtest::{
-    BacktestConfig, BacktestEngine, BacktestResult, BacktestStrategy, EquityPoint, FillModel,
-    FillResult, HistoricalDataSource, ImmediateFillModel, MarketImpactFillModel, MarketTick,
-    MetricsCalculator, MetricsConfig, OHLCVBar, PerformanceMetrics, ProbabilisticFillModel,
-    QueuePositionFillModel, SimulatedFill, SimulatedOrder, SlippageModel, TradeRecord,
-    VecDataSource,
+    BacktestConfig, BacktestEngine, BacktestOptimizer, BacktestResult, BacktestStrategy,
+    BarColumns, CsvBarSource, CsvColumn, CsvConfig, CsvTickSource, EquityPoint, FailedTrial,
+    FillModel, FillResult, HistoricalDataSource, ImmediateFillModel, L2Book, L2Event, L2EventKind,
+    L2ReplaySource, L3BacktestEngine, L3Book, L3Event, L3EventKind, L3Fill, LatencyConfig,
+    LatencyModel, MarketImpactFillModel, MarketTick, MetricsCalculator, MetricsConfig,
+    MultiAssetAction, MultiAssetEngine, MultiAssetResult, MultiAssetStrategy, OHLCVBar,
+    OptimizationObjective, OptimizationReport, OptimizerConfig, ParameterRange, ParameterSet,
+    ParameterSpace, PerformanceMetrics, PortfolioView, ProbabilisticFillModel,
+    QueuePositionFillModel, QuotingStrategyAdapter, SearchMethod, SimulatedFill, SimulatedOrder,
+    SlippageModel, TickColumns, TickFileSource, TickFileWriter, TimestampFormat, TradeRecord,
+    TrialResult, VecDataSource, WalkForwardAnalyzer, WalkForwardConfig, WalkForwardReport,
+    WalkForwardWindow, WindowResult,
 };
 
 // Re-export options types (when feature is enabled)
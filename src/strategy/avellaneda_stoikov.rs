@@ -24,6 +24,18 @@
 //! ```
 //! Where:
 //! - `k`: order intensity parameter
+//!
+//! ### Infinite Horizon
+//!
+//! For perpetual markets the terminal time is replaced by a discount rate `ω`
+//! and the indifference prices become stationary:
+//! ```text
+//! r_a = s + (1/γ) * ln(1 + (1 - 2q) * γ²σ² / (2ω - γ²σ²q²))
+//! r_b = s + (1/γ) * ln(1 + (-1 - 2q) * γ²σ² / (2ω - γ²σ²q²))
+//! ```
+//! The reservation price is `(r_a + r_b) / 2` and the spread is
+//! `(r_a - r_b) + (2/γ) * ln(1 + γ/k)`. Prices are bounded only while
+//! `ω > γ²σ²(|q| + 1)² / 2`.
 
 use crate::Decimal;
 use crate::types::decimal::{decimal_ln, decimal_powi};
@@ -288,6 +300,283 @@ pub fn calculate_optimal_quotes(
     Ok((bid_price, ask_price))
 }
 
+/// Computes the infinite-horizon indifference offsets `(r_a - s, r_b - s)`.
+fn infinite_horizon_offsets(
+    inventory: Decimal,
+    risk_aversion: Decimal,
+    volatility: Decimal,
+    discount_rate: Decimal,
+) -> MMResult<(Decimal, Decimal)> {
+    if volatility <= Decimal::ZERO {
+        return Err(MMError::InvalidMarketState(
+            "volatility must be positive".to_string(),
+        ));
+    }
+
+    if risk_aversion <= Decimal::ZERO {
+        return Err(MMError::InvalidConfiguration(
+            "risk_aversion must be positive".to_string(),
+        ));
+    }
+
+    if discount_rate <= Decimal::ZERO {
+        return Err(MMError::InvalidConfiguration(
+            "discount_rate must be positive".to_string(),
+        ));
+    }
+
+    if discount_rate <= minimum_discount_rate(risk_aversion, volatility, inventory.abs())? {
+        return Err(MMError::InvalidMarketState(
+            "inventory exceeds the admissible range for discount_rate".to_string(),
+        ));
+    }
+
+    // Formula: r_a/b = s + (1/γ) * ln(1 + (±1 - 2q) * γ²σ² / (2ω - γ²σ²q²))
+    let gamma_sigma_squared = decimal_powi(risk_aversion * volatility, 2)?;
+    let denominator =
+        Decimal::TWO * discount_rate - gamma_sigma_squared * decimal_powi(inventory, 2)?;
+    let two_q = Decimal::TWO * inventory;
+
+    let ask_inner = Decimal::ONE + (Decimal::ONE - two_q) * gamma_sigma_squared / denominator;
+    let bid_inner = Decimal::ONE + (-Decimal::ONE - two_q) * gamma_sigma_squared / denominator;
+
+    let ask_offset = decimal_ln(ask_inner)? / risk_aversion;
+    let bid_offset = decimal_ln(bid_inner)? / risk_aversion;
+
+    Ok((ask_offset, bid_offset))
+}
+
+/// Returns the smallest discount rate that keeps infinite-horizon prices
+/// bounded for inventories up to `max_inventory`.
+///
+/// Any admissible discount rate must be strictly greater than
+/// `γ²σ²(q_max + 1)² / 2`.
+///
+/// # Errors
+///
+/// Returns `MMError::InvalidConfiguration` if `max_inventory` is negative.
+///
+/// # Examples
+///
+/// ```
+/// use market_maker_rs::strategy::avellaneda_stoikov::minimum_discount_rate;
+/// use market_maker_rs::dec;
+///
+/// let omega = minimum_discount_rate(dec!(0.1), dec!(0.2), dec!(9.0)).unwrap();
+/// assert_eq!(omega, dec!(0.02));
+/// ```
+pub fn minimum_discount_rate(
+    risk_aversion: Decimal,
+    volatility: Decimal,
+    max_inventory: Decimal,
+) -> MMResult<Decimal> {
+    if max_inventory < Decimal::ZERO {
+        return Err(MMError::InvalidConfiguration(
+            "max_inventory must be non-negative".to_string(),
+        ));
+    }
+
+    let gamma_sigma_squared = decimal_powi(risk_aversion * volatility, 2)?;
+    let inventory_term = decimal_powi(max_inventory + Decimal::ONE, 2)?;
+    Ok(gamma_sigma_squared * inventory_term / Decimal::TWO)
+}
+
+/// Calculates the stationary reservation price for an infinite horizon.
+///
+/// This is the midpoint of the reservation ask and bid prices of the
+/// discounted Avellaneda-Stoikov problem, so it plays the role of
+/// [`calculate_reservation_price`] when there is no terminal time.
+///
+/// # Arguments
+///
+/// * `mid_price` - Current mid-price of the asset
+/// * `inventory` - Current inventory position (positive = long, negative = short)
+/// * `risk_aversion` - Risk aversion parameter (gamma), must be positive
+/// * `volatility` - Volatility estimate (annualized), must be positive
+/// * `discount_rate` - Discount rate (omega), annualized, must be positive
+///
+/// # Errors
+///
+/// Returns `MMError::InvalidMarketState` if mid_price or volatility are not
+/// positive, or if the inventory is outside the range allowed by
+/// `discount_rate` (see [`minimum_discount_rate`]).
+///
+/// Returns `MMError::InvalidConfiguration` if risk_aversion or discount_rate
+/// are not positive.
+///
+/// # Examples
+///
+/// ```
+/// use market_maker_rs::strategy::avellaneda_stoikov::calculate_infinite_horizon_reservation_price;
+/// use market_maker_rs::dec;
+///
+/// let reservation = calculate_infinite_horizon_reservation_price(
+///     dec!(100.0),  // mid_price
+///     dec!(10.0),   // inventory (long)
+///     dec!(0.1),    // risk_aversion
+///     dec!(0.2),    // volatility (20%)
+///     dec!(0.05),   // discount_rate
+/// ).unwrap();
+///
+/// assert!(reservation < dec!(100.0));
+/// ```
+pub fn calculate_infinite_horizon_reservation_price(
+    mid_price: Decimal,
+    inventory: Decimal,
+    risk_aversion: Decimal,
+    volatility: Decimal,
+    discount_rate: Decimal,
+) -> MMResult<Decimal> {
+    if mid_price <= Decimal::ZERO {
+        return Err(MMError::InvalidMarketState(
+            "mid_price must be positive".to_string(),
+        ));
+    }
+
+    let (ask_offset, bid_offset) =
+        infinite_horizon_offsets(inventory, risk_aversion, volatility, discount_rate)?;
+
+    Ok(mid_price + (ask_offset + bid_offset) / Decimal::TWO)
+}
+
+/// Calculates the stationary optimal spread for an infinite horizon.
+///
+/// Unlike the finite-horizon spread, the inventory-risk term depends on the
+/// current inventory and widens as it approaches the admissible bound.
+///
+/// # Arguments
+///
+/// * `inventory` - Current inventory position
+/// * `risk_aversion` - Risk aversion parameter (gamma), must be positive
+/// * `volatility` - Volatility estimate (annualized), must be positive
+/// * `discount_rate` - Discount rate (omega), annualized, must be positive
+/// * `order_intensity` - Order intensity parameter (k), must be positive
+///
+/// # Errors
+///
+/// Returns `MMError::InvalidConfiguration` if any parameter is not positive,
+/// and `MMError::InvalidMarketState` if the inventory is outside the range
+/// allowed by `discount_rate`.
+///
+/// # Examples
+///
+/// ```
+/// use market_maker_rs::strategy::avellaneda_stoikov::calculate_infinite_horizon_spread;
+/// use market_maker_rs::dec;
+///
+/// let spread = calculate_infinite_horizon_spread(
+///     dec!(0.0),    // inventory
+///     dec!(0.1),    // risk_aversion
+///     dec!(0.2),    // volatility
+///     dec!(0.05),   // discount_rate
+///     dec!(1.5),    // order_intensity
+/// ).unwrap();
+///
+/// assert!(spread > dec!(0.0));
+/// ```
+pub fn calculate_infinite_horizon_spread(
+    inventory: Decimal,
+    risk_aversion: Decimal,
+    volatility: Decimal,
+    discount_rate: Decimal,
+    order_intensity: Decimal,
+) -> MMResult<Decimal> {
+    if order_intensity <= Decimal::ZERO {
+        return Err(MMError::InvalidConfiguration(
+            "order_intensity must be positive".to_string(),
+        ));
+    }
+
+    let (ask_offset, bid_offset) =
+        infinite_horizon_offsets(inventory, risk_aversion, volatility, discount_rate)?;
+
+    // Formula: δ = (r_a - r_b) + (2/γ) * ln(1 + γ/k)
+    let adverse_selection_ln = decimal_ln(Decimal::ONE + risk_aversion / order_intensity)?;
+    let adverse_selection_term = (Decimal::TWO / risk_aversion) * adverse_selection_ln;
+
+    Ok((ask_offset - bid_offset) + adverse_selection_term)
+}
+
+/// Calculates optimal bid and ask prices for an infinite horizon.
+///
+/// # Arguments
+///
+/// * `mid_price` - Current mid-price
+/// * `inventory` - Current inventory position
+/// * `risk_aversion` - Risk aversion parameter (gamma)
+/// * `volatility` - Volatility estimate (annualized)
+/// * `discount_rate` - Discount rate (omega), annualized
+/// * `order_intensity` - Order intensity parameter (k)
+///
+/// # Returns
+///
+/// A tuple `(bid_price, ask_price)`.
+///
+/// # Errors
+///
+/// Returns errors from underlying calculations if inputs are invalid.
+///
+/// # Examples
+///
+/// ```
+/// use market_maker_rs::strategy::avellaneda_stoikov::calculate_infinite_horizon_quotes;
+/// use market_maker_rs::dec;
+///
+/// let (bid, ask) = calculate_infinite_horizon_quotes(
+///     dec!(100.0),  // mid_price
+///     dec!(0.0),    // flat inventory
+///     dec!(0.1),    // risk_aversion
+///     dec!(0.2),    // volatility
+///     dec!(0.05),   // discount_rate
+///     dec!(1.5),    // order_intensity
+/// ).unwrap();
+///
+/// assert!(bid < dec!(100.0));
+/// assert!(ask > dec!(100.0));
+/// ```
+pub fn calculate_infinite_horizon_quotes(
+    mid_price: Decimal,
+    inventory: Decimal,
+    risk_aversion: Decimal,
+    volatility: Decimal,
+    discount_rate: Decimal,
+    order_intensity: Decimal,
+) -> MMResult<(Decimal, Decimal)> {
+    let reservation_price = calculate_infinite_horizon_reservation_price(
+        mid_price,
+        inventory,
+        risk_aversion,
+        volatility,
+        discount_rate,
+    )?;
+
+    let spread = calculate_infinite_horizon_spread(
+        inventory,
+        risk_aversion,
+        volatility,
+        discount_rate,
+        order_intensity,
+    )?;
+
+    let half_spread = spread / Decimal::TWO;
+    let bid_price = reservation_price - half_spread;
+    let ask_price = reservation_price + half_spread;
+
+    if bid_price >= ask_price {
+        return Err(MMError::InvalidQuoteGeneration(
+            "bid price must be less than ask price".to_string(),
+        ));
+    }
+
+    if bid_price <= Decimal::ZERO {
+        return Err(MMError::InvalidQuoteGeneration(
+            "bid price must be positive".to_string(),
+        ));
+    }
+
+    Ok((bid_price, ask_price))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -584,4 +873,121 @@ mod tests {
             ));
         }
     }
+
+    #[test]
+    fn test_minimum_discount_rate() {
+        let omega = minimum_discount_rate(dec!(0.1), dec!(0.2), dec!(4.0)).unwrap();
+        // 0.0004 * 25 / 2
+        assert_eq!(omega, dec!(0.005));
+        assert!(minimum_discount_rate(dec!(0.1), dec!(0.2), dec!(-1.0)).is_err());
+    }
+
+    #[test]
+    fn test_infinite_horizon_reservation_price_direction() {
+        let flat = calculate_infinite_horizon_reservation_price(
+            dec!(100.0),
+            Decimal::ZERO,
+            dec!(0.1),
+            dec!(0.2),
+            dec!(0.05),
+        )
+        .unwrap();
+        let long = calculate_infinite_horizon_reservation_price(
+            dec!(100.0),
+            dec!(5.0),
+            dec!(0.1),
+            dec!(0.2),
+            dec!(0.05),
+        )
+        .unwrap();
+        let short = calculate_infinite_horizon_reservation_price(
+            dec!(100.0),
+            dec!(-5.0),
+            dec!(0.1),
+            dec!(0.2),
+            dec!(0.05),
+        )
+        .unwrap();
+
+        assert!((flat - dec!(100.0)).abs() < dec!(0.001));
+        assert!(long < flat);
+        assert!(short > flat);
+    }
+
+    #[test]
+    fn test_infinite_horizon_offsets_match_closed_form() {
+        // γ²σ² = 0.0004, ω = 0.05, q = 0: r_a - s = 10 * ln(1.004)
+        let (ask_offset, bid_offset) =
+            infinite_horizon_offsets(Decimal::ZERO, dec!(0.1), dec!(0.2), dec!(0.05)).unwrap();
+        let expected_ask = decimal_ln(dec!(1.004)).unwrap() * dec!(10);
+        let expected_bid = decimal_ln(dec!(0.996)).unwrap() * dec!(10);
+        assert!((ask_offset - expected_ask).abs() < dec!(0.000001));
+        assert!((bid_offset - expected_bid).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn test_infinite_horizon_spread_widens_with_inventory() {
+        let flat = calculate_infinite_horizon_spread(
+            Decimal::ZERO,
+            dec!(0.1),
+            dec!(0.2),
+            dec!(0.05),
+            dec!(1.5),
+        )
+        .unwrap();
+        let loaded = calculate_infinite_horizon_spread(
+            dec!(10.0),
+            dec!(0.1),
+            dec!(0.2),
+            dec!(0.05),
+            dec!(1.5),
+        )
+        .unwrap();
+        assert!(flat > Decimal::ZERO);
+        assert!(loaded > flat);
+    }
+
+    #[test]
+    fn test_infinite_horizon_inventory_out_of_range() {
+        // minimum ω for |q| = 20 is 0.0004 * 441 / 2 = 0.0882
+        let result = calculate_infinite_horizon_reservation_price(
+            dec!(100.0),
+            dec!(20.0),
+            dec!(0.1),
+            dec!(0.2),
+            dec!(0.05),
+        );
+        assert!(matches!(result, Err(MMError::InvalidMarketState(_))));
+    }
+
+    #[test]
+    fn test_infinite_horizon_invalid_discount_rate() {
+        let result = calculate_infinite_horizon_spread(
+            Decimal::ZERO,
+            dec!(0.1),
+            dec!(0.2),
+            Decimal::ZERO,
+            dec!(1.5),
+        );
+        if let Err(MMError::InvalidConfiguration(msg)) = result {
+            assert!(msg.contains("discount_rate must be positive"));
+        } else {
+            panic!("expected InvalidConfiguration");
+        }
+    }
+
+    #[test]
+    fn test_infinite_horizon_quotes_valid() {
+        let (bid, ask) = calculate_infinite_horizon_quotes(
+            dec!(100.0),
+            dec!(2.0),
+            dec!(0.1),
+            dec!(0.2),
+            dec!(0.05),
+            dec!(1.5),
+        )
+        .unwrap();
+        assert!(bid < ask);
+        assert!(bid > Decimal::ZERO);
+    }
 }
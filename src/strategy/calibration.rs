@@ -16,7 +16,8 @@
 //! - **CalibrationConfig**: Configuration for calibration process
 //! - **CalibrationResult**: Result with value, confidence interval, and quality
 //! - **RiskAversionCalibrator**: Calibrates γ from half-life or history
-//! - **OrderIntensityCalibrator**: Calibrates k from fill observations
+//! - **OrderIntensityCalibrator**: Calibrates k from fill observations, or
+//!   A and k jointly from quote lifetimes by censored maximum likelihood
 //! - **VolatilityRegimeDetector**: Detects and classifies volatility regimes
 //! - **ParameterOptimizer**: Combines all calibrators for full optimization
 //!
@@ -47,7 +48,7 @@
 //! ```
 
 use crate::Decimal;
-use crate::types::decimal::{decimal_ln, decimal_sqrt};
+use crate::types::decimal::{decimal_exp, decimal_ln, decimal_sqrt};
 use crate::types::error::{MMError, MMResult};
 use std::fmt;
 
@@ -158,6 +159,10 @@ pub struct CalibrationResult<T> {
     /// Quality score from 0 (poor) to 1 (excellent).
     pub quality: Decimal,
 
+    /// Standard error of the estimate, when the method provides one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub standard_error: Option<T>,
+
     /// Warnings or notes about the calibration.
     pub notes: Vec<String>,
 }
@@ -190,10 +195,18 @@ impl<T: Clone> CalibrationResult<T> {
             confidence_interval,
             sample_size,
             quality,
+            standard_error: None,
             notes: Vec::new(),
         }
     }
 
+    /// Sets the standard error of the estimate.
+    #[must_use]
+    pub fn with_standard_error(mut self, standard_error: T) -> Self {
+        self.standard_error = Some(standard_error);
+        self
+    }
+
     /// Adds a note to the result.
     pub fn add_note(&mut self, note: impl Into<String>) {
         self.notes.push(note.into());
@@ -533,6 +546,71 @@ impl FillObservation {
     }
 }
 
+/// Lifetime of a single resting quote, for censored intensity calibration.
+///
+/// A quote rests at a fixed distance from mid until it is either filled or
+/// cancelled. Cancelled quotes are right-censored observations: they tell us
+/// the quote survived `duration_ms` without a fill.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteLifetime {
+    /// Distance from mid in basis points.
+    pub distance_bps: Decimal,
+
+    /// Time the quote rested in milliseconds.
+    pub duration_ms: u64,
+
+    /// Whether the quote ended in a fill (otherwise it was cancelled).
+    pub filled: bool,
+
+    /// Timestamp when the quote ended in milliseconds.
+    pub timestamp: u64,
+}
+
+impl QuoteLifetime {
+    /// Creates a quote lifetime that ended in a fill.
+    #[must_use]
+    pub fn filled(distance_bps: Decimal, duration_ms: u64, timestamp: u64) -> Self {
+        Self {
+            distance_bps,
+            duration_ms,
+            filled: true,
+            timestamp,
+        }
+    }
+
+    /// Creates a quote lifetime that ended without a fill.
+    #[must_use]
+    pub fn cancelled(distance_bps: Decimal, duration_ms: u64, timestamp: u64) -> Self {
+        Self {
+            distance_bps,
+            duration_ms,
+            filled: false,
+            timestamp,
+        }
+    }
+}
+
+/// Joint maximum-likelihood estimate of the arrival model λ(δ) = A × exp(-k × δ).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IntensityCalibration {
+    /// Baseline arrival rate A in fills per second at zero distance.
+    pub baseline_rate: CalibrationResult<Decimal>,
+
+    /// Order intensity k per basis point of distance.
+    pub k: CalibrationResult<Decimal>,
+
+    /// Number of fills in the sample.
+    pub fills: usize,
+
+    /// Total quoting time in milliseconds, filled or not.
+    pub exposure_ms: u64,
+
+    /// Log-likelihood at the estimate.
+    pub log_likelihood: Decimal,
+}
+
 /// Order intensity (k) calibrator.
 ///
 /// Calibrates the order intensity parameter from fill observations
@@ -658,6 +736,196 @@ impl OrderIntensityCalibrator {
         Ok(result)
     }
 
+    /// Jointly calibrates A and k from quote lifetimes by maximum likelihood.
+    ///
+    /// Each quote is an exponential waiting time with rate
+    /// λ(δ) = A × exp(-k × δ), censored when it was cancelled before a fill:
+    ///
+    /// ```text
+    /// ℓ(A, k) = Σ_fills ln λ(δᵢ) - Σ_all λ(δᵢ) × τᵢ
+    /// ```
+    ///
+    /// For fixed k the optimal A is `N / Σ τᵢ exp(-k δᵢ)`, so k solves
+    /// `Σ_fills δᵢ / N = Σ τᵢ δᵢ exp(-k δᵢ) / Σ τᵢ exp(-k δᵢ)`: the mean fill
+    /// distance must equal the exposure-weighted mean quoted distance. Standard
+    /// errors come from the inverse Fisher information.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if there are fewer than
+    /// `min_samples` fills, all quotes with a duration rest at one distance,
+    /// or there is no quoting time, and `MMError::NumericalError` if the
+    /// exposure weights underflow.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use market_maker_rs::strategy::calibration::{
+    ///     CalibrationConfig, OrderIntensityCalibrator, QuoteLifetime,
+    /// };
+    /// use market_maker_rs::dec;
+    ///
+    /// let calibrator = OrderIntensityCalibrator::new(CalibrationConfig::quick());
+    ///
+    /// let mut lifetimes = Vec::new();
+    /// for i in 0..20 {
+    ///     // Close quotes fill after 1s, far quotes are cancelled after 4s.
+    ///     lifetimes.push(QuoteLifetime::filled(dec!(2.0), 1_000, i));
+    ///     lifetimes.push(QuoteLifetime::cancelled(dec!(10.0), 4_000, i));
+    /// }
+    /// lifetimes.push(QuoteLifetime::filled(dec!(10.0), 4_000, 20));
+    ///
+    /// let fit = calibrator.calibrate_censored(&lifetimes).unwrap();
+    /// assert!(fit.k.value > dec!(0.0));
+    /// assert!(fit.k.standard_error.is_some());
+    /// ```
+    pub fn calibrate_censored(
+        &self,
+        lifetimes: &[QuoteLifetime],
+    ) -> MMResult<IntensityCalibration> {
+        let fills: Vec<&QuoteLifetime> = lifetimes.iter().filter(|l| l.filled).collect();
+        let fill_count = fills.len();
+
+        if fill_count < self.config.min_samples {
+            return Err(MMError::InvalidConfiguration(format!(
+                "Insufficient fills: {} < {}",
+                fill_count, self.config.min_samples
+            )));
+        }
+
+        let exposure_ms: u64 = lifetimes.iter().map(|l| l.duration_ms).sum();
+        if exposure_ms == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "Quote lifetimes have no duration".to_string(),
+            ));
+        }
+
+        // Only quotes that rested for some time carry exposure weight.
+        let exposed: Vec<&QuoteLifetime> = lifetimes.iter().filter(|l| l.duration_ms > 0).collect();
+        let min_distance = exposed
+            .iter()
+            .map(|l| l.distance_bps)
+            .min()
+            .unwrap_or(Decimal::ZERO);
+        let max_distance = exposed
+            .iter()
+            .map(|l| l.distance_bps)
+            .max()
+            .unwrap_or(Decimal::ZERO);
+        if max_distance == min_distance {
+            return Err(MMError::InvalidConfiguration(
+                "Need at least 2 different quote distances".to_string(),
+            ));
+        }
+
+        let n = Decimal::from(fill_count);
+        let mean_fill_distance = fills.iter().map(|l| l.distance_bps).sum::<Decimal>() / n;
+
+        // Exposure-weighted moments of the quoted distance at a given k.
+        // Distances are shifted by the minimum so the weights cannot underflow.
+        let moments = |k: Decimal| -> MMResult<(Decimal, Decimal, Decimal)> {
+            let mut weight_sum = Decimal::ZERO;
+            let mut first = Decimal::ZERO;
+            let mut second = Decimal::ZERO;
+            for l in &exposed {
+                let tau = Decimal::from(l.duration_ms) / Decimal::from(1000);
+                let w = tau * decimal_exp(-k * (l.distance_bps - min_distance))?;
+                weight_sum += w;
+                first += w * l.distance_bps;
+                second += w * l.distance_bps * l.distance_bps;
+            }
+            if weight_sum.is_zero() {
+                return Err(MMError::NumericalError(
+                    "Quote exposure weights underflowed to zero".to_string(),
+                ));
+            }
+            Ok((weight_sum, first / weight_sum, second / weight_sum))
+        };
+
+        let min_k = Decimal::new(1, 3);
+        let max_k = Decimal::from(1000);
+        let mut result_notes = Vec::new();
+
+        // The weighted mean distance decreases in k; bracket and bisect.
+        let k = if moments(min_k)?.1 <= mean_fill_distance {
+            result_notes.push("Fills are not closer than quoted distances - k clamped");
+            min_k
+        } else if moments(max_k)?.1 > mean_fill_distance {
+            result_notes.push("Fills are closer than any k up to 1000 explains - k capped");
+            max_k
+        } else {
+            let mut low = min_k;
+            let mut high = Decimal::ONE;
+            while moments(high)?.1 > mean_fill_distance {
+                low = high;
+                high = (high * Decimal::TWO).min(max_k);
+            }
+            for _ in 0..100 {
+                let mid = (low + high) / Decimal::TWO;
+                if moments(mid)?.1 > mean_fill_distance {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+                if high - low < Decimal::new(1, 10) {
+                    break;
+                }
+            }
+            (low + high) / Decimal::TWO
+        };
+
+        let (weight_sum, mean, second) = moments(k)?;
+        let ln_a = decimal_ln(n / weight_sum)? + k * min_distance;
+        let baseline_rate = decimal_exp(ln_a)?;
+
+        // At the optimum ℓ = N ln A - k Σ δ_fill - N.
+        let log_likelihood = n * ln_a - k * mean_fill_distance * n - n;
+
+        // Inverse Fisher information in (ln A, k).
+        let variance = (second - mean * mean).max(Decimal::ZERO);
+        let (k_se, a_se) = if variance.is_zero() {
+            (Decimal::ZERO, Decimal::ZERO)
+        } else {
+            let k_se = decimal_sqrt(Decimal::ONE / (n * variance))?;
+            let ln_a_se = decimal_sqrt(second / (n * variance))?;
+            (k_se, baseline_rate * ln_a_se)
+        };
+
+        let z_score = Decimal::from_str_exact("1.96").unwrap();
+        let k_quality = self.calculate_quality(relative_precision(k, k_se), fill_count);
+        let a_quality = self.calculate_quality(relative_precision(baseline_rate, a_se), fill_count);
+
+        let mut k_result = CalibrationResult::new(
+            k,
+            ((k - z_score * k_se).max(min_k), k + z_score * k_se),
+            fill_count,
+            k_quality,
+        )
+        .with_standard_error(k_se);
+        for note in result_notes {
+            k_result.add_note(note);
+        }
+
+        let baseline_result = CalibrationResult::new(
+            baseline_rate,
+            (
+                (baseline_rate - z_score * a_se).max(Decimal::ZERO),
+                baseline_rate + z_score * a_se,
+            ),
+            fill_count,
+            a_quality,
+        )
+        .with_standard_error(a_se);
+
+        Ok(IntensityCalibration {
+            baseline_rate: baseline_result,
+            k: k_result,
+            fills: fill_count,
+            exposure_ms,
+            log_likelihood,
+        })
+    }
+
     /// Performs weighted linear regression.
     ///
     /// Returns (slope, intercept, r_squared).
@@ -770,6 +1038,14 @@ impl OrderIntensityCalibrator {
     }
 }
 
+/// Maps a standard error to a 0-1 precision score: 1 - SE / |value|.
+fn relative_precision(value: Decimal, standard_error: Decimal) -> Decimal {
+    if value.is_zero() {
+        return Decimal::ZERO;
+    }
+    (Decimal::ONE - standard_error / value.abs()).max(Decimal::ZERO)
+}
+
 impl Default for OrderIntensityCalibrator {
     fn default() -> Self {
         Self::new(CalibrationConfig::default())
@@ -912,6 +1188,12 @@ impl VolatilityRegimeDetector {
         }
     }
 
+    /// Returns the regime threshold multiplier.
+    #[must_use]
+    pub fn regime_threshold(&self) -> Decimal {
+        self.regime_threshold
+    }
+
     /// Returns the lookback window in milliseconds.
     #[must_use]
     pub fn lookback_ms(&self) -> u64 {
@@ -1355,6 +1637,92 @@ mod tests {
         assert!(result.value > Decimal::ZERO);
     }
 
+    #[test]
+    fn test_censored_calibration_recovers_parameters() {
+        let calibrator = OrderIntensityCalibrator::new(CalibrationConfig::quick());
+
+        // λ(δ) = 2 × exp(-0.1 δ): expected fills equal λ × exposure at each
+        // distance, so the MLE is exact.
+        let mut lifetimes = Vec::new();
+        for (distance, fills) in [(dec!(0.0), 200u64), (dec!(10.0), 74)] {
+            // 100 seconds of exposure at each distance.
+            for i in 0..fills {
+                lifetimes.push(QuoteLifetime::filled(distance, 0, i));
+            }
+            lifetimes.push(QuoteLifetime::cancelled(distance, 100_000, 0));
+        }
+
+        let fit = calibrator.calibrate_censored(&lifetimes).unwrap();
+        let expected_k = decimal_ln(dec!(200) / dec!(74)).unwrap() / dec!(10.0);
+        assert!((fit.k.value - expected_k).abs() < dec!(0.0001));
+        assert!((fit.baseline_rate.value - dec!(2.0)).abs() < dec!(0.001));
+        assert_eq!(fit.fills, 274);
+        assert_eq!(fit.exposure_ms, 200_000);
+
+        let se = fit.k.standard_error.unwrap();
+        assert!(se > Decimal::ZERO);
+        assert!(fit.k.confidence_interval.0 < fit.k.value);
+        assert!(fit.k.confidence_interval.1 > fit.k.value);
+        assert!(fit.baseline_rate.standard_error.unwrap() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_censored_calibration_uses_non_fills() {
+        let calibrator = OrderIntensityCalibrator::new(CalibrationConfig::quick());
+
+        // Same fills, but far quotes also sat unfilled for a long time.
+        let mut base = Vec::new();
+        for i in 0..20 {
+            base.push(QuoteLifetime::filled(dec!(2.0), 1_000, i));
+            base.push(QuoteLifetime::filled(dec!(8.0), 1_000, i));
+        }
+        let mut censored = base.clone();
+        censored.push(QuoteLifetime::cancelled(dec!(8.0), 60_000, 100));
+
+        let k_fills_only = calibrator.calibrate_censored(&base).unwrap().k.value;
+        let k_censored = calibrator.calibrate_censored(&censored).unwrap().k.value;
+        assert!(k_censored > k_fills_only);
+    }
+
+    #[test]
+    fn test_censored_calibration_errors() {
+        let calibrator = OrderIntensityCalibrator::new(CalibrationConfig::quick());
+
+        let too_few = vec![QuoteLifetime::filled(dec!(2.0), 1_000, 0)];
+        assert!(calibrator.calibrate_censored(&too_few).is_err());
+
+        let one_distance: Vec<_> = (0..20)
+            .map(|i| QuoteLifetime::filled(dec!(2.0), 1_000, i))
+            .collect();
+        assert!(calibrator.calibrate_censored(&one_distance).is_err());
+    }
+
+    #[test]
+    fn test_censored_calibration_caps_k_with_instant_close_fills() {
+        let calibrator = OrderIntensityCalibrator::new(CalibrationConfig::quick());
+
+        // The closest quotes fill instantly and carry no exposure, so no
+        // finite k matches their mean distance.
+        let mut lifetimes: Vec<_> = (0..20)
+            .map(|i| QuoteLifetime::filled(dec!(0.0), 0, i))
+            .collect();
+        lifetimes.push(QuoteLifetime::cancelled(dec!(0.01), 5_000, 20));
+        lifetimes.push(QuoteLifetime::cancelled(dec!(5.0), 5_000, 21));
+
+        let fit = calibrator.calibrate_censored(&lifetimes).unwrap();
+        assert_eq!(fit.k.value, dec!(1000));
+        assert!(fit.k.notes.iter().any(|note| note.contains("capped")));
+
+        // Further out the capped k implies an unrepresentable baseline rate.
+        lifetimes.truncate(20);
+        lifetimes.push(QuoteLifetime::cancelled(dec!(10.0), 5_000, 20));
+        lifetimes.push(QuoteLifetime::cancelled(dec!(20.0), 5_000, 21));
+        assert!(matches!(
+            calibrator.calibrate_censored(&lifetimes),
+            Err(MMError::NumericalError(_))
+        ));
+    }
+
     #[test]
     fn test_order_intensity_insufficient_data() {
         let calibrator = OrderIntensityCalibrator::default();
@@ -0,0 +1,776 @@
+//! Cartea–Jaimungal alpha-adjusted market making.
+//!
+//! Extends the Avellaneda-Stoikov reservation price with a short-term drift
+//! (alpha) signal, following Cartea, Jaimungal and Penalva, *Algorithmic and
+//! High-Frequency Trading* (2015), chapter 10. The mid price is assumed to
+//! follow
+//!
+//! ```text
+//! dS_t = α_t dt + σ dW_t
+//! dα_t = -ζ α_t dt + η dB_t
+//! ```
+//!
+//! so the expected price move over the remaining horizon `τ` is
+//! `α · (1 - e^{-ζτ}) / ζ`. This drift is added to the inventory-adjusted
+//! reservation price:
+//!
+//! ```text
+//! r = s + α · (1 - e^{-ζτ}) / ζ - q · γ · σ² · τ
+//! ```
+//!
+//! The spread is the usual Avellaneda-Stoikov optimal spread. A positive
+//! alpha shifts both quotes up (buy more eagerly, sell less eagerly) and a
+//! negative alpha shifts them down. With an infinite-horizon base
+//! configuration the stationary quotes are used and the drift takes its
+//! long-run value `α / ζ`.
+//!
+//! # Signals
+//!
+//! Alpha is the weighted sum of pluggable [`AlphaSignal`] sources, each
+//! returning a normalized value in `[-1, 1]`, multiplied by `alpha_scale`
+//! (expected drift in price units per second for a full-strength signal).
+//! Built-in sources:
+//!
+//! - [`OrderFlowSignal`]: trade-flow imbalance from [`OrderFlowAnalyzer`]
+//! - [`BookImbalanceSignal`]: [`OrderBookImbalance`] of the context order book
+//! - [`ExternalSignal`]: a value published by an external model
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::strategy::cartea_jaimungal::{
+//!     CarteaJaimungalConfig, CarteaJaimungalStrategy, ExternalSignal,
+//! };
+//! use market_maker_rs::strategy::config::StrategyConfig;
+//! use market_maker_rs::strategy::quoting::{QuoteContext, QuotingStrategy};
+//! use market_maker_rs::dec;
+//!
+//! let base = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+//! let config = CarteaJaimungalConfig::new(base, dec!(0.01), dec!(0.1)).unwrap();
+//!
+//! let desk_alpha = ExternalSignal::new("desk", 5_000);
+//! let mut strategy = CarteaJaimungalStrategy::new(config, dec!(1.0))
+//!     .unwrap()
+//!     .with_signal(Box::new(desk_alpha.clone()), dec!(1.0));
+//!
+//! let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0);
+//! let neutral = strategy.generate_quotes(&context).unwrap();
+//!
+//! desk_alpha.publish(dec!(0.8), 0);
+//! let bullish = strategy.generate_quotes(&context).unwrap();
+//! assert!(bullish.best_bid().unwrap() > neutral.best_bid().unwrap());
+//! ```
+
+use std::sync::{Arc, RwLock};
+
+use crate::Decimal;
+use crate::analytics::order_flow::{OrderFlowAnalyzer, Trade};
+use crate::strategy::adaptive_spread::{AdaptiveSpreadCalculator, OrderBookImbalance};
+use crate::strategy::config::StrategyConfig;
+use crate::strategy::interface::{AvellanedaStoikov, DefaultAvellanedaStoikov};
+use crate::strategy::quoting::{QuoteContext, QuoteSet, QuotingStrategy};
+use crate::types::decimal::decimal_exp;
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const MILLISECONDS_PER_SECOND: Decimal = Decimal::from_parts(1000, 0, 0, false, 0);
+
+/// A source of short-term directional signal.
+///
+/// Implementations return a normalized value in `[-1, 1]` where positive
+/// values predict upward price moves. The strategy forwards every quote
+/// context and every market trade to all of its signals, so sources can
+/// maintain their own state.
+pub trait AlphaSignal: Send + Sync {
+    /// Returns a short identifier for the signal.
+    fn name(&self) -> &str;
+
+    /// Returns the current signal value, or `None` if unavailable or stale.
+    fn value(&self, timestamp: u64) -> Option<Decimal>;
+
+    /// Observes the market context at the start of a quote cycle.
+    ///
+    /// The default implementation does nothing.
+    fn observe(&mut self, _context: &QuoteContext) {}
+
+    /// Observes a market trade. The default implementation does nothing.
+    fn on_trade(&mut self, _trade: &Trade) {}
+}
+
+/// Trade-flow imbalance signal backed by an [`OrderFlowAnalyzer`].
+///
+/// Returns `None` while the analyzer window holds no trades.
+#[derive(Debug, Clone)]
+pub struct OrderFlowSignal {
+    analyzer: OrderFlowAnalyzer,
+}
+
+impl OrderFlowSignal {
+    /// Creates a signal with a fresh analyzer over the given window.
+    #[must_use]
+    pub fn new(window_ms: u64) -> Self {
+        Self::from_analyzer(OrderFlowAnalyzer::new(window_ms))
+    }
+
+    /// Creates a signal from an existing analyzer.
+    #[must_use]
+    pub fn from_analyzer(analyzer: OrderFlowAnalyzer) -> Self {
+        Self { analyzer }
+    }
+
+    /// Returns the underlying analyzer.
+    #[must_use]
+    pub fn analyzer(&self) -> &OrderFlowAnalyzer {
+        &self.analyzer
+    }
+}
+
+impl AlphaSignal for OrderFlowSignal {
+    fn name(&self) -> &str {
+        "order_flow"
+    }
+
+    fn value(&self, timestamp: u64) -> Option<Decimal> {
+        let stats = self.analyzer.get_stats(timestamp);
+        if stats.total_count() == 0 {
+            None
+        } else {
+            Some(stats.imbalance)
+        }
+    }
+
+    fn on_trade(&mut self, trade: &Trade) {
+        self.analyzer.add_trade(trade.clone());
+    }
+}
+
+/// Order book imbalance signal computed from the context order book.
+///
+/// Uses [`AdaptiveSpreadCalculator::calculate_orderbook_imbalance`] over the
+/// top `levels` levels of the most recently observed book.
+#[derive(Debug, Clone)]
+pub struct BookImbalanceSignal {
+    levels: u32,
+    last: Option<(OrderBookImbalance, u64)>,
+    max_age_ms: u64,
+}
+
+impl BookImbalanceSignal {
+    /// Creates a new book imbalance signal.
+    ///
+    /// # Arguments
+    ///
+    /// * `levels` - Number of book levels to aggregate
+    /// * `max_age_ms` - Maximum age of the observed book before the signal is stale
+    #[must_use]
+    pub fn new(levels: u32, max_age_ms: u64) -> Self {
+        Self {
+            levels,
+            last: None,
+            max_age_ms,
+        }
+    }
+
+    /// Sets the imbalance directly, e.g. when computed elsewhere.
+    pub fn update(&mut self, imbalance: OrderBookImbalance, timestamp: u64) {
+        self.last = Some((imbalance, timestamp));
+    }
+
+    /// Returns the most recent imbalance, if any.
+    #[must_use]
+    pub fn last_imbalance(&self) -> Option<&OrderBookImbalance> {
+        self.last.as_ref().map(|(imbalance, _)| imbalance)
+    }
+}
+
+impl AlphaSignal for BookImbalanceSignal {
+    fn name(&self) -> &str {
+        "book_imbalance"
+    }
+
+    fn value(&self, timestamp: u64) -> Option<Decimal> {
+        let (imbalance, observed_at) = self.last.as_ref()?;
+        if timestamp.saturating_sub(*observed_at) > self.max_age_ms {
+            return None;
+        }
+        Some(imbalance.imbalance)
+    }
+
+    fn observe(&mut self, context: &QuoteContext) {
+        if let Some(book) = &context.orderbook {
+            let bids: Vec<(Decimal, Decimal)> =
+                book.bids.iter().map(|l| (l.price, l.quantity)).collect();
+            let asks: Vec<(Decimal, Decimal)> =
+                book.asks.iter().map(|l| (l.price, l.quantity)).collect();
+            let imbalance =
+                AdaptiveSpreadCalculator::calculate_orderbook_imbalance(&bids, &asks, self.levels);
+            self.update(imbalance, context.timestamp);
+        }
+    }
+}
+
+/// Signal published by an external model.
+///
+/// Clones share the same underlying value, so a desk can keep one handle to
+/// publish into while the strategy owns another.
+#[derive(Debug, Clone)]
+pub struct ExternalSignal {
+    name: String,
+    max_age_ms: u64,
+    latest: Arc<RwLock<Option<(Decimal, u64)>>>,
+}
+
+impl ExternalSignal {
+    /// Creates a new external signal.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Signal identifier
+    /// * `max_age_ms` - Maximum age of a published value before it is ignored
+    #[must_use]
+    pub fn new(name: impl Into<String>, max_age_ms: u64) -> Self {
+        Self {
+            name: name.into(),
+            max_age_ms,
+            latest: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Publishes a new value, clamped to `[-1, 1]`.
+    pub fn publish(&self, value: Decimal, timestamp: u64) {
+        let clamped = value.max(-Decimal::ONE).min(Decimal::ONE);
+        if let Ok(mut latest) = self.latest.write() {
+            *latest = Some((clamped, timestamp));
+        }
+    }
+
+    /// Clears the published value.
+    pub fn clear(&self) {
+        if let Ok(mut latest) = self.latest.write() {
+            *latest = None;
+        }
+    }
+}
+
+impl AlphaSignal for ExternalSignal {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn value(&self, timestamp: u64) -> Option<Decimal> {
+        let latest = self.latest.read().ok()?;
+        let (value, published_at) = (*latest)?;
+        if timestamp.saturating_sub(published_at) > self.max_age_ms {
+            return None;
+        }
+        Some(value)
+    }
+}
+
+/// Configuration for the Cartea–Jaimungal strategy.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CarteaJaimungalConfig {
+    /// Avellaneda-Stoikov parameters (γ, k, T, minimum spread).
+    pub base: StrategyConfig,
+
+    /// Expected drift in price units per second for a signal of 1.0.
+    pub alpha_scale: Decimal,
+
+    /// Alpha mean-reversion rate ζ, per second.
+    pub alpha_decay: Decimal,
+
+    /// Optional cap on the absolute reservation price shift from alpha.
+    pub max_alpha_adjustment: Option<Decimal>,
+}
+
+impl CarteaJaimungalConfig {
+    /// Creates a new configuration with validation.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - Avellaneda-Stoikov parameters
+    /// * `alpha_scale` - Drift per second for a full-strength signal, must be non-negative
+    /// * `alpha_decay` - Mean-reversion rate of alpha per second, must be positive
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if parameters are invalid.
+    pub fn new(base: StrategyConfig, alpha_scale: Decimal, alpha_decay: Decimal) -> MMResult<Self> {
+        if alpha_scale < Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "alpha_scale must be non-negative".to_string(),
+            ));
+        }
+
+        if alpha_decay <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "alpha_decay must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            base,
+            alpha_scale,
+            alpha_decay,
+            max_alpha_adjustment: None,
+        })
+    }
+
+    /// Caps the absolute reservation price shift caused by alpha.
+    #[must_use]
+    pub fn with_max_alpha_adjustment(mut self, max_adjustment: Decimal) -> Self {
+        self.max_alpha_adjustment = Some(max_adjustment.abs());
+        self
+    }
+}
+
+/// A signal and its weight in the combined alpha.
+struct WeightedSignal {
+    signal: Box<dyn AlphaSignal>,
+    weight: Decimal,
+}
+
+/// Alpha-adjusted Avellaneda-Stoikov market maker.
+pub struct CarteaJaimungalStrategy {
+    config: CarteaJaimungalConfig,
+    order_size: Decimal,
+    signals: Vec<WeightedSignal>,
+}
+
+impl std::fmt::Debug for CarteaJaimungalStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let signals: Vec<(&str, Decimal)> = self
+            .signals
+            .iter()
+            .map(|s| (s.signal.name(), s.weight))
+            .collect();
+        f.debug_struct("CarteaJaimungalStrategy")
+            .field("config", &self.config)
+            .field("order_size", &self.order_size)
+            .field("signals", &signals)
+            .finish()
+    }
+}
+
+impl CarteaJaimungalStrategy {
+    /// Creates a strategy without signals (equivalent to Avellaneda-Stoikov).
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `order_size` is not positive.
+    pub fn new(config: CarteaJaimungalConfig, order_size: Decimal) -> MMResult<Self> {
+        if order_size <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "order_size must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            config,
+            order_size,
+            signals: Vec::new(),
+        })
+    }
+
+    /// Adds a signal with the given weight.
+    #[must_use]
+    pub fn with_signal(mut self, signal: Box<dyn AlphaSignal>, weight: Decimal) -> Self {
+        self.add_signal(signal, weight);
+        self
+    }
+
+    /// Adds a signal with the given weight.
+    pub fn add_signal(&mut self, signal: Box<dyn AlphaSignal>, weight: Decimal) {
+        self.signals.push(WeightedSignal { signal, weight });
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn config(&self) -> &CarteaJaimungalConfig {
+        &self.config
+    }
+
+    /// Returns the number of attached signals.
+    #[must_use]
+    pub fn signal_count(&self) -> usize {
+        self.signals.len()
+    }
+
+    /// Forwards a market trade to every signal.
+    pub fn on_trade(&mut self, trade: &Trade) {
+        for weighted in &mut self.signals {
+            weighted.signal.on_trade(trade);
+        }
+    }
+
+    /// Returns the combined normalized signal: the weighted sum of every
+    /// available signal, clamped to `[-1, 1]`.
+    #[must_use]
+    pub fn combined_signal(&self, timestamp: u64) -> Decimal {
+        let sum: Decimal = self
+            .signals
+            .iter()
+            .filter_map(|s| s.signal.value(timestamp).map(|v| v * s.weight))
+            .sum();
+        sum.max(-Decimal::ONE).min(Decimal::ONE)
+    }
+
+    /// Returns the current alpha (expected drift per second).
+    #[must_use]
+    pub fn alpha(&self, timestamp: u64) -> Decimal {
+        self.combined_signal(timestamp) * self.config.alpha_scale
+    }
+
+    /// Calculates the expected price move `α · (1 - e^{-ζτ}) / ζ` over the
+    /// remaining horizon, capped by `max_alpha_adjustment`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::NumericalError` if the exponential cannot be computed
+    /// or the adjustment overflows.
+    pub fn drift_adjustment(&self, alpha: Decimal, time_to_terminal_ms: u64) -> MMResult<Decimal> {
+        let tau_seconds = Decimal::from(time_to_terminal_ms) / MILLISECONDS_PER_SECOND;
+        let decay = self.config.alpha_decay;
+        // e^{-50} is negligible; capping avoids underflow in the conversion
+        // and an overflowing product for very fast decay
+        let cap = Decimal::from(50);
+        let exponent = decay
+            .checked_mul(tau_seconds)
+            .map_or(cap, |exponent| exponent.min(cap));
+        let overflow = || MMError::NumericalError("drift adjustment overflow".to_string());
+        let horizon_factor = (Decimal::ONE - decimal_exp(-exponent)?)
+            .checked_div(decay)
+            .ok_or_else(overflow)?;
+        let adjustment = alpha.checked_mul(horizon_factor).ok_or_else(overflow)?;
+
+        Ok(match self.config.max_alpha_adjustment {
+            Some(cap) => adjustment.max(-cap).min(cap),
+            None => adjustment,
+        })
+    }
+
+    /// Calculates the alpha-adjusted reservation price.
+    ///
+    /// Honours the horizon selected in the base configuration; with an
+    /// infinite horizon the drift adjustment is its long-run limit `α / ζ`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the inputs are invalid for the Avellaneda-Stoikov model.
+    pub fn calculate_reservation_price(
+        &self,
+        mid_price: Decimal,
+        inventory: Decimal,
+        volatility: Decimal,
+        timestamp: u64,
+    ) -> MMResult<Decimal> {
+        self.reservation_and_spread(mid_price, inventory, volatility, timestamp)
+            .map(|(reservation, _)| reservation)
+    }
+
+    /// Returns the alpha-adjusted reservation price and the unfloored spread.
+    fn reservation_and_spread(
+        &self,
+        mid_price: Decimal,
+        inventory: Decimal,
+        volatility: Decimal,
+        timestamp: u64,
+    ) -> MMResult<(Decimal, Decimal)> {
+        let base = &self.config.base;
+        let (inventory_adjusted, spread) = DefaultAvellanedaStoikov
+            .calculate_reservation_and_spread(base, mid_price, inventory, volatility, timestamp)?;
+        let time_to_terminal_ms = if base.is_infinite_horizon() {
+            u64::MAX
+        } else {
+            base.terminal_time.saturating_sub(timestamp)
+        };
+        let drift = self.drift_adjustment(self.alpha(timestamp), time_to_terminal_ms)?;
+        Ok((inventory_adjusted + drift, spread))
+    }
+}
+
+impl QuotingStrategy for CarteaJaimungalStrategy {
+    fn name(&self) -> &str {
+        "cartea_jaimungal"
+    }
+
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+        for weighted in &mut self.signals {
+            weighted.signal.observe(context);
+        }
+
+        let (reservation, spread) = self.reservation_and_spread(
+            context.mid_price,
+            context.inventory,
+            context.volatility,
+            context.timestamp,
+        )?;
+        let spread = spread.max(self.config.base.min_spread);
+
+        let half_spread = spread / Decimal::TWO;
+        let bid = reservation - half_spread;
+        let ask = reservation + half_spread;
+
+        if bid <= Decimal::ZERO {
+            return Err(MMError::InvalidQuoteGeneration(
+                "bid price must be positive".to_string(),
+            ));
+        }
+
+        Ok(QuoteSet::single(
+            bid,
+            self.order_size,
+            ask,
+            self.order_size,
+            context.timestamp,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::order_flow::TradeSide;
+    use crate::dec;
+    use crate::execution::{BookLevel, OrderBookSnapshot};
+
+    fn config() -> CarteaJaimungalConfig {
+        let base = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+        CarteaJaimungalConfig::new(base, dec!(0.01), dec!(0.1)).unwrap()
+    }
+
+    fn context() -> QuoteContext {
+        QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0)
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let base = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+        assert!(CarteaJaimungalConfig::new(base.clone(), dec!(-0.1), dec!(0.1)).is_err());
+        assert!(CarteaJaimungalConfig::new(base.clone(), dec!(0.1), dec!(0.0)).is_err());
+        assert!(CarteaJaimungalConfig::new(base, dec!(0.0), dec!(0.1)).is_ok());
+    }
+
+    #[test]
+    fn test_no_signals_matches_avellaneda_stoikov() {
+        let mut strategy = CarteaJaimungalStrategy::new(config(), dec!(1.0)).unwrap();
+        let quotes = strategy.generate_quotes(&context()).unwrap();
+
+        let (bid, ask) = DefaultAvellanedaStoikov
+            .calculate_optimal_quotes(
+                dec!(100.0),
+                dec!(0.0),
+                dec!(0.1),
+                dec!(0.2),
+                3_600_000,
+                dec!(1.5),
+            )
+            .unwrap();
+        assert_eq!(quotes.best_bid(), Some(bid));
+        assert_eq!(quotes.best_ask(), Some(ask));
+    }
+
+    #[test]
+    fn test_infinite_horizon_ignores_terminal_time() {
+        let base = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01))
+            .unwrap()
+            .with_infinite_horizon(dec!(0.5))
+            .unwrap();
+        let config = CarteaJaimungalConfig::new(base.clone(), dec!(0.01), dec!(0.1)).unwrap();
+        let signal = ExternalSignal::new("desk", u64::MAX);
+        let mut strategy = CarteaJaimungalStrategy::new(config, dec!(1.0))
+            .unwrap()
+            .with_signal(Box::new(signal.clone()), dec!(1.0));
+
+        // Past the finite terminal time the quotes keep their full width.
+        let late = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(1.0), 7_200_000);
+        let (reservation, spread) = DefaultAvellanedaStoikov
+            .calculate_reservation_and_spread(&base, dec!(100.0), dec!(1.0), dec!(0.2), 7_200_000)
+            .unwrap();
+        let quotes = strategy.generate_quotes(&late).unwrap();
+        let half_spread = spread.max(base.min_spread) / Decimal::TWO;
+        assert_eq!(quotes.best_bid(), Some(reservation - half_spread));
+        assert_eq!(quotes.best_ask(), Some(reservation + half_spread));
+
+        // The drift takes its long-run value α / ζ.
+        signal.publish(dec!(1.0), 7_200_000);
+        let shifted = strategy
+            .calculate_reservation_price(dec!(100.0), dec!(1.0), dec!(0.2), 7_200_000)
+            .unwrap();
+        assert!((shifted - reservation - dec!(0.1)).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn test_drift_adjustment_long_horizon() {
+        let strategy = CarteaJaimungalStrategy::new(config(), dec!(1.0)).unwrap();
+        // With τ → ∞ the adjustment tends to α / ζ
+        let adjustment = strategy.drift_adjustment(dec!(0.01), 3_600_000).unwrap();
+        assert!((adjustment - dec!(0.1)).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn test_drift_adjustment_zero_horizon() {
+        let strategy = CarteaJaimungalStrategy::new(config(), dec!(1.0)).unwrap();
+        assert_eq!(
+            strategy.drift_adjustment(dec!(0.01), 0).unwrap(),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_drift_adjustment_extreme_decay() {
+        let base = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+        let fast = CarteaJaimungalConfig::new(base.clone(), dec!(0.01), Decimal::MAX).unwrap();
+        let strategy = CarteaJaimungalStrategy::new(fast, dec!(1.0)).unwrap();
+        assert_eq!(
+            strategy.drift_adjustment(dec!(0.01), u64::MAX).unwrap(),
+            Decimal::ZERO
+        );
+
+        let slow = CarteaJaimungalConfig::new(base, Decimal::MAX, dec!(0.0000000001)).unwrap();
+        let strategy = CarteaJaimungalStrategy::new(slow, dec!(1.0)).unwrap();
+        assert!(matches!(
+            strategy.drift_adjustment(Decimal::MAX, u64::MAX),
+            Err(MMError::NumericalError(_))
+        ));
+    }
+
+    #[test]
+    fn test_drift_adjustment_cap() {
+        let config = config().with_max_alpha_adjustment(dec!(0.05));
+        let strategy = CarteaJaimungalStrategy::new(config, dec!(1.0)).unwrap();
+        assert_eq!(
+            strategy.drift_adjustment(dec!(-0.01), 3_600_000).unwrap(),
+            dec!(-0.05)
+        );
+    }
+
+    #[test]
+    fn test_external_signal_shifts_quotes() {
+        let signal = ExternalSignal::new("desk", 1_000);
+        let mut strategy = CarteaJaimungalStrategy::new(config(), dec!(1.0))
+            .unwrap()
+            .with_signal(Box::new(signal.clone()), dec!(1.0));
+
+        let neutral = strategy.generate_quotes(&context()).unwrap();
+        signal.publish(dec!(-0.5), 0);
+        let bearish = strategy.generate_quotes(&context()).unwrap();
+
+        assert!(bearish.best_bid().unwrap() < neutral.best_bid().unwrap());
+        assert!(bearish.best_ask().unwrap() < neutral.best_ask().unwrap());
+        // Spread is unchanged by alpha
+        assert_eq!(bearish.spread(), neutral.spread());
+    }
+
+    #[test]
+    fn test_external_signal_staleness_and_clamp() {
+        let signal = ExternalSignal::new("desk", 1_000);
+        assert_eq!(signal.value(0), None);
+
+        signal.publish(dec!(3.0), 0);
+        assert_eq!(signal.value(500), Some(dec!(1.0)));
+        assert_eq!(signal.value(1_001), None);
+
+        signal.clear();
+        assert_eq!(signal.value(0), None);
+    }
+
+    #[test]
+    fn test_order_flow_signal() {
+        let mut signal = OrderFlowSignal::new(10_000);
+        assert_eq!(signal.value(0), None);
+
+        signal.on_trade(&Trade::new(dec!(100.0), dec!(3.0), TradeSide::Buy, 1_000));
+        signal.on_trade(&Trade::new(dec!(100.0), dec!(1.0), TradeSide::Sell, 2_000));
+
+        let value = signal.value(3_000).unwrap();
+        assert!(value > Decimal::ZERO);
+        assert_eq!(signal.analyzer().trade_count(), 2);
+    }
+
+    #[test]
+    fn test_strategy_forwards_trades_to_signals() {
+        let mut strategy = CarteaJaimungalStrategy::new(config(), dec!(1.0))
+            .unwrap()
+            .with_signal(Box::new(OrderFlowSignal::new(10_000)), dec!(1.0));
+
+        for i in 0..5 {
+            strategy.on_trade(&Trade::new(
+                dec!(100.0),
+                dec!(1.0),
+                TradeSide::Sell,
+                i * 100,
+            ));
+        }
+
+        assert_eq!(strategy.combined_signal(1_000), dec!(-1.0));
+        assert_eq!(strategy.alpha(1_000), dec!(-0.01));
+    }
+
+    #[test]
+    fn test_book_imbalance_signal_observes_context() {
+        let mut book = OrderBookSnapshot::new("TEST", 0);
+        book.bids = vec![BookLevel::new(dec!(99.9), dec!(30.0))];
+        book.asks = vec![BookLevel::new(dec!(100.1), dec!(10.0))];
+        let context = context().with_orderbook(book);
+
+        let mut signal = BookImbalanceSignal::new(5, 1_000);
+        assert_eq!(signal.value(0), None);
+
+        signal.observe(&context);
+        assert_eq!(signal.value(0), Some(dec!(0.5)));
+        assert_eq!(signal.value(2_000), None);
+        assert!(signal.last_imbalance().is_some());
+    }
+
+    #[test]
+    fn test_book_imbalance_raises_quotes_via_strategy() {
+        let mut book = OrderBookSnapshot::new("TEST", 0);
+        book.bids = vec![BookLevel::new(dec!(99.9), dec!(30.0))];
+        book.asks = vec![BookLevel::new(dec!(100.1), dec!(10.0))];
+
+        let mut plain = CarteaJaimungalStrategy::new(config(), dec!(1.0)).unwrap();
+        let mut with_book = CarteaJaimungalStrategy::new(config(), dec!(1.0))
+            .unwrap()
+            .with_signal(Box::new(BookImbalanceSignal::new(5, 1_000)), dec!(1.0));
+
+        let context = context().with_orderbook(book);
+        let base = plain.generate_quotes(&context).unwrap();
+        let adjusted = with_book.generate_quotes(&context).unwrap();
+
+        assert!(adjusted.best_bid().unwrap() > base.best_bid().unwrap());
+    }
+
+    #[test]
+    fn test_combined_signal_weights_and_clamp() {
+        let a = ExternalSignal::new("a", 1_000);
+        let b = ExternalSignal::new("b", 1_000);
+        let strategy = CarteaJaimungalStrategy::new(config(), dec!(1.0))
+            .unwrap()
+            .with_signal(Box::new(a.clone()), dec!(0.5))
+            .with_signal(Box::new(b.clone()), dec!(2.0));
+
+        a.publish(dec!(0.4), 0);
+        assert_eq!(strategy.combined_signal(0), dec!(0.2));
+
+        b.publish(dec!(0.9), 0);
+        assert_eq!(strategy.combined_signal(0), dec!(1.0));
+        assert_eq!(strategy.signal_count(), 2);
+    }
+
+    #[test]
+    fn test_invalid_order_size() {
+        assert!(CarteaJaimungalStrategy::new(config(), dec!(0.0)).is_err());
+    }
+
+    #[test]
+    fn test_debug_lists_signals() {
+        let strategy = CarteaJaimungalStrategy::new(config(), dec!(1.0))
+            .unwrap()
+            .with_signal(Box::new(OrderFlowSignal::new(1_000)), dec!(1.0));
+        assert!(format!("{strategy:?}").contains("order_flow"));
+    }
+}
@@ -5,6 +5,27 @@ use crate::types::error::{MMError, MMResult};
 
 #[cfg(feature = "serde")]
 use pretty_simple_display::{DebugPretty, DisplaySimple};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Trading horizon used by the Avellaneda-Stoikov model.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TimeHorizon {
+    /// Session ending at `StrategyConfig::terminal_time`.
+    #[default]
+    Finite,
+
+    /// Stationary, perpetual market with no terminal time.
+    ///
+    /// Uses the discounted infinite-horizon formulation of Avellaneda-Stoikov
+    /// (2008); `terminal_time` is ignored.
+    Infinite {
+        /// Discount rate (ω), annualized. Must exceed `γ²σ²(|q| + 1)² / 2`
+        /// for every inventory `q` the strategy is expected to hold.
+        discount_rate: Decimal,
+    },
+}
 
 /// Configuration parameters for the Avellaneda-Stoikov strategy.
 #[derive(Clone, PartialEq)]
@@ -27,6 +48,8 @@ pub struct StrategyConfig {
     pub order_intensity: Decimal,
 
     /// Terminal time (end of trading session) in milliseconds since Unix epoch.
+    ///
+    /// Ignored when `horizon` is [`TimeHorizon::Infinite`].
     pub terminal_time: u64,
 
     /// Minimum spread constraint, in price units.
@@ -34,6 +57,10 @@ pub struct StrategyConfig {
     /// Ensures quotes don't cross or get too tight.
     /// Must be non-negative.
     pub min_spread: Decimal,
+
+    /// Trading horizon, finite by default.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub horizon: TimeHorizon,
 }
 
 impl StrategyConfig {
@@ -78,8 +105,36 @@ impl StrategyConfig {
             order_intensity,
             terminal_time,
             min_spread,
+            horizon: TimeHorizon::Finite,
         })
     }
+
+    /// Sets the trading horizon.
+    #[must_use]
+    pub fn with_horizon(mut self, horizon: TimeHorizon) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    /// Switches to the infinite-horizon formulation with the given discount rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `discount_rate` is not positive.
+    pub fn with_infinite_horizon(self, discount_rate: Decimal) -> MMResult<Self> {
+        if discount_rate <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "discount_rate must be positive".to_string(),
+            ));
+        }
+        Ok(self.with_horizon(TimeHorizon::Infinite { discount_rate }))
+    }
+
+    /// Returns true if the configuration uses the infinite-horizon formulation.
+    #[must_use]
+    pub fn is_infinite_horizon(&self) -> bool {
+        matches!(self.horizon, TimeHorizon::Infinite { .. })
+    }
 }
 
 #[cfg(test)]
@@ -152,6 +207,39 @@ mod tests {
         assert!(config.is_ok());
     }
 
+    #[test]
+    fn test_default_horizon_is_finite() {
+        let config = StrategyConfig::new(dec!(0.5), dec!(1.5), 1000, dec!(0.01)).unwrap();
+        assert_eq!(config.horizon, TimeHorizon::Finite);
+        assert!(!config.is_infinite_horizon());
+    }
+
+    #[test]
+    fn test_with_infinite_horizon() {
+        let config = StrategyConfig::new(dec!(0.5), dec!(1.5), 0, dec!(0.01))
+            .unwrap()
+            .with_infinite_horizon(dec!(0.05))
+            .unwrap();
+        assert!(config.is_infinite_horizon());
+        assert_eq!(
+            config.horizon,
+            TimeHorizon::Infinite {
+                discount_rate: dec!(0.05)
+            }
+        );
+    }
+
+    #[test]
+    fn test_with_infinite_horizon_invalid_discount_rate() {
+        let config = StrategyConfig::new(dec!(0.5), dec!(1.5), 0, dec!(0.01)).unwrap();
+        let result = config.with_infinite_horizon(Decimal::ZERO);
+        if let Err(MMError::InvalidConfiguration(msg)) = result {
+            assert!(msg.contains("discount_rate must be positive"));
+        } else {
+            panic!("expected InvalidConfiguration");
+        }
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_config_display() {
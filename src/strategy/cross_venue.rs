@@ -0,0 +1,894 @@
+//! Cross-venue market making with hedging on a liquid venue.
+//!
+//! Quotes rest on a less liquid *quote venue*, and every fill there is
+//! offset with a market order on a liquid *hedge venue*. Each quote is
+//! priced from the hedge it would trigger:
+//!
+//! ```text
+//! bid = V_sell × (1 − f_hedge − edge − buffer) / (1 + f_quote)
+//! ask = V_buy  × (1 + f_hedge + edge + buffer) / (1 − f_quote)
+//! ```
+//!
+//! where `V_sell`/`V_buy` are the volume-weighted prices of selling/buying
+//! the quote size into the hedge book, so the expected hedge slippage is
+//! priced in. `f_quote` is the quote venue's maker fee (negative for a
+//! rebate), `f_hedge` the hedge venue's taker fee, `edge` the required
+//! profit and `buffer` a cushion for the hedge book moving before the
+//! hedge arrives.
+//!
+//! [`CrossVenueStrategy`] does the pricing and position bookkeeping and
+//! tracks the unhedged residual. Quote sizes are capped so a full fill never
+//! takes the residual beyond `max_residual`. [`CrossVenueMarketMaker`] drives
+//! it against two [`ExchangeConnector`]s.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::execution::{BookLevel, OrderBookSnapshot, Side};
+//! use market_maker_rs::strategy::cross_venue::{CrossVenueConfig, CrossVenueStrategy};
+//! use market_maker_rs::dec;
+//!
+//! let config = CrossVenueConfig::new("BTC-EUR", "BTC-USDT", dec!(1.0), dec!(2.0))
+//!     .unwrap()
+//!     .with_fees(dec!(0.0), dec!(0.001))
+//!     .unwrap();
+//! let mut strategy = CrossVenueStrategy::new(config);
+//!
+//! let mut book = OrderBookSnapshot::new("BTC-USDT", 0);
+//! book.bids.push(BookLevel::new(dec!(100.0), dec!(5.0)));
+//! book.asks.push(BookLevel::new(dec!(101.0), dec!(5.0)));
+//!
+//! let quote = strategy.quote(&book).unwrap();
+//! // Selling 1.0 into the hedge book nets 100 × (1 − 0.001).
+//! assert_eq!(quote.bid.unwrap().price, dec!(99.9));
+//!
+//! // A bid fill leaves a long residual until the hedge sells it.
+//! strategy.on_quote_fill(Side::Buy, dec!(1.0), dec!(99.9), dec!(0.0));
+//! let hedge = strategy.hedge_order().unwrap();
+//! assert_eq!(hedge.side, Side::Sell);
+//! strategy.on_hedge_fill(Side::Sell, dec!(1.0), dec!(100.0), dec!(0.1));
+//! assert_eq!(strategy.residual(), dec!(0.0));
+//! ```
+
+use crate::Decimal;
+use crate::execution::{
+    ExchangeConnector, Fill, OrderBookSnapshot, OrderId, OrderRequest, OrderResponse, OrderStatus,
+    OrderType, Side,
+};
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Configuration for [`CrossVenueStrategy`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CrossVenueConfig {
+    /// Symbol quoted on the quote venue.
+    pub quote_symbol: String,
+
+    /// Symbol traded on the hedge venue.
+    pub hedge_symbol: String,
+
+    /// Size quoted on each side.
+    pub quote_size: Decimal,
+
+    /// Maximum absolute unhedged position.
+    pub max_residual: Decimal,
+
+    /// Maker fee rate on the quote venue (negative for a rebate).
+    pub quote_fee_rate: Decimal,
+
+    /// Taker fee rate on the hedge venue.
+    pub hedge_fee_rate: Decimal,
+
+    /// Required profit as a fraction of the hedge price.
+    pub min_edge: Decimal,
+
+    /// Extra margin, as a fraction of the hedge price, for the hedge book
+    /// moving before the hedge is executed.
+    pub slippage_buffer: Decimal,
+
+    /// Smallest residual worth hedging.
+    pub min_hedge_size: Decimal,
+
+    /// Number of hedge book levels used for pricing.
+    pub hedge_depth: usize,
+
+    /// Quote venue tick size; bids round down and asks round up to it.
+    pub tick_size: Option<Decimal>,
+}
+
+impl CrossVenueConfig {
+    /// Creates a configuration with no fees, edge or buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if a symbol is empty or
+    /// `quote_size` or `max_residual` is not positive.
+    pub fn new(
+        quote_symbol: impl Into<String>,
+        hedge_symbol: impl Into<String>,
+        quote_size: Decimal,
+        max_residual: Decimal,
+    ) -> MMResult<Self> {
+        let quote_symbol = quote_symbol.into();
+        let hedge_symbol = hedge_symbol.into();
+        if quote_symbol.is_empty() || hedge_symbol.is_empty() {
+            return Err(MMError::InvalidConfiguration(
+                "symbols must not be empty".to_string(),
+            ));
+        }
+        if quote_size <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "quote_size must be positive".to_string(),
+            ));
+        }
+        if max_residual <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "max_residual must be positive".to_string(),
+            ));
+        }
+        Ok(Self {
+            quote_symbol,
+            hedge_symbol,
+            quote_size,
+            max_residual,
+            quote_fee_rate: Decimal::ZERO,
+            hedge_fee_rate: Decimal::ZERO,
+            min_edge: Decimal::ZERO,
+            slippage_buffer: Decimal::ZERO,
+            min_hedge_size: Decimal::ZERO,
+            hedge_depth: 10,
+            tick_size: None,
+        })
+    }
+
+    /// Sets the quote venue maker fee and hedge venue taker fee rates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `quote_fee_rate` is
+    /// outside (-1, 1) or `hedge_fee_rate` is outside [0, 1).
+    pub fn with_fees(mut self, quote_fee_rate: Decimal, hedge_fee_rate: Decimal) -> MMResult<Self> {
+        if quote_fee_rate <= -Decimal::ONE || quote_fee_rate >= Decimal::ONE {
+            return Err(MMError::InvalidConfiguration(
+                "quote_fee_rate must be in (-1, 1)".to_string(),
+            ));
+        }
+        if hedge_fee_rate < Decimal::ZERO || hedge_fee_rate >= Decimal::ONE {
+            return Err(MMError::InvalidConfiguration(
+                "hedge_fee_rate must be in [0, 1)".to_string(),
+            ));
+        }
+        self.quote_fee_rate = quote_fee_rate;
+        self.hedge_fee_rate = hedge_fee_rate;
+        Ok(self)
+    }
+
+    /// Sets the required edge and the slippage buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if either is negative.
+    pub fn with_edge(mut self, min_edge: Decimal, slippage_buffer: Decimal) -> MMResult<Self> {
+        if min_edge < Decimal::ZERO || slippage_buffer < Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "min_edge and slippage_buffer must be non-negative".to_string(),
+            ));
+        }
+        self.min_edge = min_edge;
+        self.slippage_buffer = slippage_buffer;
+        Ok(self)
+    }
+
+    /// Sets the smallest residual worth hedging.
+    #[must_use]
+    pub fn with_min_hedge_size(mut self, min_hedge_size: Decimal) -> Self {
+        self.min_hedge_size = min_hedge_size.max(Decimal::ZERO);
+        self
+    }
+
+    /// Sets the number of hedge book levels used for pricing.
+    #[must_use]
+    pub fn with_hedge_depth(mut self, hedge_depth: usize) -> Self {
+        self.hedge_depth = hedge_depth.max(1);
+        self
+    }
+
+    /// Sets the quote venue tick size.
+    #[must_use]
+    pub fn with_tick_size(mut self, tick_size: Decimal) -> Self {
+        self.tick_size = (tick_size > Decimal::ZERO).then_some(tick_size);
+        self
+    }
+}
+
+/// One side of a cross-venue quote with its expected hedge.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HedgedQuote {
+    /// Side quoted on the quote venue.
+    pub side: Side,
+    /// Quote price.
+    pub price: Decimal,
+    /// Quote size, capped by hedge book depth and the residual limit.
+    pub size: Decimal,
+    /// Volume-weighted hedge price for `size`.
+    pub hedge_price: Decimal,
+    /// Per-unit distance between the hedge price and the top of the hedge
+    /// book.
+    pub hedge_slippage: Decimal,
+    /// Expected per-unit profit after fees if filled and hedged at
+    /// `hedge_price`.
+    pub expected_edge: Decimal,
+}
+
+/// Bid and ask on the quote venue; a side is `None` when it cannot be
+/// hedged or would breach the residual limit.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CrossVenueQuote {
+    /// Bid, hedged by selling on the hedge venue.
+    pub bid: Option<HedgedQuote>,
+    /// Ask, hedged by buying on the hedge venue.
+    pub ask: Option<HedgedQuote>,
+}
+
+/// Pricing and position bookkeeping for cross-venue market making.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CrossVenueStrategy {
+    config: CrossVenueConfig,
+    quote_position: Decimal,
+    hedge_position: Decimal,
+    pending_hedge: Decimal,
+    cash: Decimal,
+    fees_paid: Decimal,
+}
+
+impl CrossVenueStrategy {
+    /// Creates a flat strategy.
+    #[must_use]
+    pub fn new(config: CrossVenueConfig) -> Self {
+        Self {
+            config,
+            quote_position: Decimal::ZERO,
+            hedge_position: Decimal::ZERO,
+            pending_hedge: Decimal::ZERO,
+            cash: Decimal::ZERO,
+            fees_paid: Decimal::ZERO,
+        }
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn config(&self) -> &CrossVenueConfig {
+        &self.config
+    }
+
+    /// Returns the net position filled on the quote venue.
+    #[must_use]
+    pub fn quote_position(&self) -> Decimal {
+        self.quote_position
+    }
+
+    /// Returns the net position filled on the hedge venue.
+    #[must_use]
+    pub fn hedge_position(&self) -> Decimal {
+        self.hedge_position
+    }
+
+    /// Returns the signed hedge quantity submitted but not yet filled.
+    #[must_use]
+    pub fn pending_hedge(&self) -> Decimal {
+        self.pending_hedge
+    }
+
+    /// Returns the unhedged position across both venues.
+    #[must_use]
+    pub fn residual(&self) -> Decimal {
+        self.quote_position + self.hedge_position
+    }
+
+    /// Returns the net cash flow across both venues, after fees.
+    #[must_use]
+    pub fn cash(&self) -> Decimal {
+        self.cash
+    }
+
+    /// Returns the fees paid across both venues (negative for net rebates).
+    #[must_use]
+    pub fn fees_paid(&self) -> Decimal {
+        self.fees_paid
+    }
+
+    /// Returns the PnL with the residual marked at `mark_price`.
+    #[must_use]
+    pub fn pnl(&self, mark_price: Decimal) -> Decimal {
+        self.cash + self.residual() * mark_price
+    }
+
+    /// Prices both sides from the hedge venue book.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if `hedge_book` is for a
+    /// different symbol.
+    pub fn quote(&self, hedge_book: &OrderBookSnapshot) -> MMResult<CrossVenueQuote> {
+        if hedge_book.symbol != self.config.hedge_symbol {
+            return Err(MMError::InvalidMarketState(format!(
+                "expected {} book, got {}",
+                self.config.hedge_symbol, hedge_book.symbol
+            )));
+        }
+        let residual = self.residual();
+        Ok(CrossVenueQuote {
+            bid: self.price_side(Side::Buy, hedge_book, self.config.max_residual - residual),
+            ask: self.price_side(Side::Sell, hedge_book, self.config.max_residual + residual),
+        })
+    }
+
+    /// Records a fill on the quote venue.
+    pub fn on_quote_fill(&mut self, side: Side, quantity: Decimal, price: Decimal, fee: Decimal) {
+        self.quote_position += signed(side, quantity);
+        self.book_cash(side, quantity, price, fee);
+    }
+
+    /// Returns the hedge order for the residual not covered by pending
+    /// hedges, and marks it pending.
+    ///
+    /// Returns `None` if the uncovered residual is below `min_hedge_size`.
+    pub fn hedge_order(&mut self) -> Option<OrderRequest> {
+        let needed = -(self.residual() + self.pending_hedge);
+        if needed.is_zero() || needed.abs() < self.config.min_hedge_size {
+            return None;
+        }
+        self.pending_hedge += needed;
+        let symbol = self.config.hedge_symbol.clone();
+        Some(if needed > Decimal::ZERO {
+            OrderRequest::market_buy(symbol, needed)
+        } else {
+            OrderRequest::market_sell(symbol, -needed)
+        })
+    }
+
+    /// Records a fill on the hedge venue and releases the matching pending
+    /// quantity.
+    pub fn on_hedge_fill(&mut self, side: Side, quantity: Decimal, price: Decimal, fee: Decimal) {
+        self.hedge_position += signed(side, quantity);
+        self.release_hedge(side, quantity);
+        self.book_cash(side, quantity, price, fee);
+    }
+
+    /// Releases pending hedge quantity that will not fill (rejected or
+    /// cancelled), so the next [`CrossVenueStrategy::hedge_order`] retries it.
+    pub fn release_hedge(&mut self, side: Side, quantity: Decimal) {
+        let released = signed(side, quantity);
+        self.pending_hedge = if self.pending_hedge > Decimal::ZERO {
+            (self.pending_hedge - released).clamp(Decimal::ZERO, self.pending_hedge)
+        } else {
+            (self.pending_hedge - released).clamp(self.pending_hedge, Decimal::ZERO)
+        };
+    }
+
+    fn book_cash(&mut self, side: Side, quantity: Decimal, price: Decimal, fee: Decimal) {
+        self.cash -= signed(side, quantity) * price + fee;
+        self.fees_paid += fee;
+    }
+
+    /// Prices the quote on `side`, hedged on the opposite side of the book.
+    fn price_side(
+        &self,
+        side: Side,
+        hedge_book: &OrderBookSnapshot,
+        residual_room: Decimal,
+    ) -> Option<HedgedQuote> {
+        let levels = match side {
+            Side::Buy => &hedge_book.bids,
+            Side::Sell => &hedge_book.asks,
+        };
+        let levels = &levels[..levels.len().min(self.config.hedge_depth)];
+        let best = levels.first()?.price;
+
+        let target = self.config.quote_size.min(residual_room);
+        if target <= Decimal::ZERO {
+            return None;
+        }
+        let mut size = Decimal::ZERO;
+        let mut notional = Decimal::ZERO;
+        for level in levels {
+            let take = level.quantity.min(target - size);
+            size += take;
+            notional += take * level.price;
+            if size >= target {
+                break;
+            }
+        }
+        if size <= Decimal::ZERO {
+            return None;
+        }
+        let hedge_price = notional / size;
+
+        let c = &self.config;
+        let margin = c.min_edge + c.slippage_buffer;
+        let (price, expected_edge) = match side {
+            Side::Buy => {
+                let raw = hedge_price * (Decimal::ONE - c.hedge_fee_rate - margin)
+                    / (Decimal::ONE + c.quote_fee_rate);
+                let price = self.round_to_tick(raw, side);
+                let edge = hedge_price * (Decimal::ONE - c.hedge_fee_rate)
+                    - price * (Decimal::ONE + c.quote_fee_rate);
+                (price, edge)
+            }
+            Side::Sell => {
+                let raw = hedge_price * (Decimal::ONE + c.hedge_fee_rate + margin)
+                    / (Decimal::ONE - c.quote_fee_rate);
+                let price = self.round_to_tick(raw, side);
+                let edge = price * (Decimal::ONE - c.quote_fee_rate)
+                    - hedge_price * (Decimal::ONE + c.hedge_fee_rate);
+                (price, edge)
+            }
+        };
+        if price <= Decimal::ZERO {
+            return None;
+        }
+        Some(HedgedQuote {
+            side,
+            price,
+            size,
+            hedge_price,
+            hedge_slippage: (hedge_price - best).abs(),
+            expected_edge,
+        })
+    }
+
+    fn round_to_tick(&self, price: Decimal, side: Side) -> Decimal {
+        let Some(tick) = self.config.tick_size else {
+            return price;
+        };
+        let ticks = price / tick;
+        match side {
+            Side::Buy => ticks.floor() * tick,
+            Side::Sell => ticks.ceil() * tick,
+        }
+    }
+}
+
+/// Runs a [`CrossVenueStrategy`] against a quote venue and a hedge venue.
+///
+/// Quote fills must be passed to [`CrossVenueMarketMaker::on_quote_fill`],
+/// which hedges them immediately with a market order.
+#[derive(Debug)]
+pub struct CrossVenueMarketMaker<Q, H> {
+    strategy: CrossVenueStrategy,
+    quote_venue: Q,
+    hedge_venue: H,
+    bid_order: Option<OrderId>,
+    ask_order: Option<OrderId>,
+}
+
+impl<Q: ExchangeConnector, H: ExchangeConnector> CrossVenueMarketMaker<Q, H> {
+    /// Creates a market maker with no resting quotes.
+    #[must_use]
+    pub fn new(strategy: CrossVenueStrategy, quote_venue: Q, hedge_venue: H) -> Self {
+        Self {
+            strategy,
+            quote_venue,
+            hedge_venue,
+            bid_order: None,
+            ask_order: None,
+        }
+    }
+
+    /// Returns the strategy.
+    #[must_use]
+    pub fn strategy(&self) -> &CrossVenueStrategy {
+        &self.strategy
+    }
+
+    /// Returns the quote venue connector.
+    #[must_use]
+    pub fn quote_venue(&self) -> &Q {
+        &self.quote_venue
+    }
+
+    /// Returns the hedge venue connector.
+    #[must_use]
+    pub fn hedge_venue(&self) -> &H {
+        &self.hedge_venue
+    }
+
+    /// Returns the IDs of the resting bid and ask.
+    #[must_use]
+    pub fn resting_orders(&self) -> (Option<&OrderId>, Option<&OrderId>) {
+        (self.bid_order.as_ref(), self.ask_order.as_ref())
+    }
+
+    /// Re-prices from the hedge book and replaces the resting quotes with
+    /// post-only orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hedge book cannot be fetched or a quote
+    /// cannot be submitted.
+    pub async fn refresh_quotes(&mut self) -> MMResult<CrossVenueQuote> {
+        let config = self.strategy.config();
+        let book = self
+            .hedge_venue
+            .get_orderbook(&config.hedge_symbol, config.hedge_depth)
+            .await?;
+        let quote = self.strategy.quote(&book)?;
+
+        self.cancel_quotes().await;
+        let symbol = self.strategy.config().quote_symbol.clone();
+        for hedged in quote.bid.iter().chain(quote.ask.iter()) {
+            let request = OrderRequest::new(
+                symbol.clone(),
+                hedged.side,
+                OrderType::PostOnly,
+                Some(hedged.price),
+                hedged.size,
+            );
+            let response = self.quote_venue.submit_order(request).await?;
+            match hedged.side {
+                Side::Buy => self.bid_order = Some(response.order_id),
+                Side::Sell => self.ask_order = Some(response.order_id),
+            }
+        }
+        Ok(quote)
+    }
+
+    /// Cancels the resting quotes.
+    ///
+    /// Cancel failures are ignored: the order has usually already filled.
+    pub async fn cancel_quotes(&mut self) {
+        for order_id in [self.bid_order.take(), self.ask_order.take()]
+            .into_iter()
+            .flatten()
+        {
+            let _ = self.quote_venue.cancel_order(&order_id).await;
+        }
+    }
+
+    /// Records a quote venue fill and hedges the residual.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hedge order fails; the residual stays
+    /// unhedged and is retried by the next [`CrossVenueMarketMaker::hedge`].
+    pub async fn on_quote_fill(&mut self, fill: &Fill) -> MMResult<Option<OrderResponse>> {
+        self.strategy
+            .on_quote_fill(fill.side, fill.quantity, fill.price, fill.fee);
+        self.hedge().await
+    }
+
+    /// Records a hedge venue fill reported after the hedge order was
+    /// accepted.
+    pub fn on_hedge_fill(&mut self, fill: &Fill) {
+        self.strategy
+            .on_hedge_fill(fill.side, fill.quantity, fill.price, fill.fee);
+    }
+
+    /// Submits a market order for any uncovered residual.
+    ///
+    /// Immediate fills are booked with the configured hedge fee rate.
+    /// Cancelled orders report no fill price, so their partial fills are
+    /// booked at the current hedge venue touch. Orders still working stay
+    /// pending until their fills arrive through
+    /// [`CrossVenueMarketMaker::on_hedge_fill`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the connector error if the order is not accepted, or if the
+    /// hedge book cannot be fetched to price a cancelled partial fill; that
+    /// fill then stays pending until booked through
+    /// [`CrossVenueMarketMaker::on_hedge_fill`].
+    pub async fn hedge(&mut self) -> MMResult<Option<OrderResponse>> {
+        let Some(request) = self.strategy.hedge_order() else {
+            return Ok(None);
+        };
+        let (side, quantity) = (request.side, request.quantity);
+        let response = match self.hedge_venue.submit_order(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                self.strategy.release_hedge(side, quantity);
+                return Err(e);
+            }
+        };
+        match &response.status {
+            OrderStatus::Filled {
+                filled_qty,
+                avg_price,
+            } => {
+                let fee = *filled_qty * *avg_price * self.strategy.config().hedge_fee_rate;
+                self.strategy
+                    .on_hedge_fill(side, *filled_qty, *avg_price, fee);
+                self.strategy.release_hedge(side, quantity - *filled_qty);
+            }
+            OrderStatus::Rejected { .. } => self.strategy.release_hedge(side, quantity),
+            OrderStatus::Cancelled { filled_qty } => {
+                self.strategy.release_hedge(side, quantity - *filled_qty);
+                if *filled_qty > Decimal::ZERO {
+                    let price = self.hedge_touch(side).await?;
+                    let fee = *filled_qty * price * self.strategy.config().hedge_fee_rate;
+                    self.strategy.on_hedge_fill(side, *filled_qty, price, fee);
+                }
+            }
+            OrderStatus::Pending
+            | OrderStatus::Open { .. }
+            | OrderStatus::PartiallyFilled { .. } => {}
+        }
+        Ok(Some(response))
+    }
+
+    /// Returns the hedge venue touch a market order on `side` takes.
+    async fn hedge_touch(&self, side: Side) -> MMResult<Decimal> {
+        let book = self
+            .hedge_venue
+            .get_orderbook(&self.strategy.config().hedge_symbol, 1)
+            .await?;
+        match side {
+            Side::Buy => book.best_ask(),
+            Side::Sell => book.best_bid(),
+        }
+        .ok_or_else(|| MMError::InvalidMarketState("hedge book is empty".to_string()))
+    }
+}
+
+fn signed(side: Side, quantity: Decimal) -> Decimal {
+    match side {
+        Side::Buy => quantity,
+        Side::Sell => -quantity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+    use crate::execution::{BookLevel, MockConfig, MockExchangeConnector};
+    use async_trait::async_trait;
+
+    /// Hedge venue that cancels market orders after filling `filled_qty`.
+    struct PartialHedgeVenue {
+        inner: MockExchangeConnector,
+        filled_qty: Decimal,
+    }
+
+    #[async_trait]
+    impl ExchangeConnector for PartialHedgeVenue {
+        async fn submit_order(&self, request: OrderRequest) -> MMResult<OrderResponse> {
+            let mut response = self.inner.submit_order(request).await?;
+            response.status = OrderStatus::Cancelled {
+                filled_qty: self.filled_qty,
+            };
+            Ok(response)
+        }
+
+        async fn cancel_order(&self, order_id: &OrderId) -> MMResult<OrderResponse> {
+            self.inner.cancel_order(order_id).await
+        }
+
+        async fn modify_order(
+            &self,
+            order_id: &OrderId,
+            new_price: Option<Decimal>,
+            new_quantity: Option<Decimal>,
+        ) -> MMResult<OrderResponse> {
+            self.inner
+                .modify_order(order_id, new_price, new_quantity)
+                .await
+        }
+
+        async fn get_order_status(&self, order_id: &OrderId) -> MMResult<OrderResponse> {
+            self.inner.get_order_status(order_id).await
+        }
+
+        async fn get_open_orders(&self, symbol: &str) -> MMResult<Vec<OrderResponse>> {
+            self.inner.get_open_orders(symbol).await
+        }
+
+        async fn cancel_all_orders(&self, symbol: &str) -> MMResult<Vec<OrderResponse>> {
+            self.inner.cancel_all_orders(symbol).await
+        }
+
+        async fn get_orderbook(&self, symbol: &str, depth: usize) -> MMResult<OrderBookSnapshot> {
+            self.inner.get_orderbook(symbol, depth).await
+        }
+
+        async fn get_balance(&self, asset: &str) -> MMResult<Decimal> {
+            self.inner.get_balance(asset).await
+        }
+    }
+
+    fn book() -> OrderBookSnapshot {
+        let mut book = OrderBookSnapshot::new("HEDGE", 0);
+        book.bids = vec![
+            BookLevel::new(dec!(100.0), dec!(1.0)),
+            BookLevel::new(dec!(99.0), dec!(1.0)),
+        ];
+        book.asks = vec![
+            BookLevel::new(dec!(101.0), dec!(1.0)),
+            BookLevel::new(dec!(102.0), dec!(1.0)),
+        ];
+        book
+    }
+
+    fn config(size: Decimal) -> CrossVenueConfig {
+        CrossVenueConfig::new("QUOTE", "HEDGE", size, dec!(5.0)).unwrap()
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(CrossVenueConfig::new("", "HEDGE", dec!(1.0), dec!(1.0)).is_err());
+        assert!(CrossVenueConfig::new("QUOTE", "HEDGE", dec!(0.0), dec!(1.0)).is_err());
+        assert!(CrossVenueConfig::new("QUOTE", "HEDGE", dec!(1.0), dec!(0.0)).is_err());
+        assert!(
+            config(dec!(1.0))
+                .with_fees(dec!(0.0), dec!(-0.001))
+                .is_err()
+        );
+        assert!(
+            config(dec!(1.0))
+                .with_edge(dec!(-0.001), dec!(0.0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_quote_prices_in_fees_and_slippage() {
+        let strategy = CrossVenueStrategy::new(config(dec!(1.0)));
+        let quote = strategy.quote(&book()).unwrap();
+        assert_eq!(quote.bid.as_ref().unwrap().price, dec!(100.0));
+        assert_eq!(quote.ask.as_ref().unwrap().price, dec!(101.0));
+
+        // Two units walk the book: VWAP 99.5 / 101.5, slippage 0.5.
+        let config = config(dec!(2.0))
+            .with_fees(dec!(-0.0002), dec!(0.001))
+            .unwrap()
+            .with_edge(dec!(0.001), dec!(0.0005))
+            .unwrap();
+        let strategy = CrossVenueStrategy::new(config);
+        let quote = strategy.quote(&book()).unwrap();
+        let bid = quote.bid.unwrap();
+        assert_eq!(bid.size, dec!(2.0));
+        assert_eq!(bid.hedge_price, dec!(99.5));
+        assert_eq!(bid.hedge_slippage, dec!(0.5));
+        // 99.5 × (1 − 0.001 − 0.0015) / 0.9998
+        assert_eq!(bid.price.round_dp(6), dec!(99.271104));
+        // Fees are covered, the required edge and buffer remain.
+        assert_eq!(bid.expected_edge.round_dp(6), dec!(0.14925));
+        let ask = quote.ask.unwrap();
+        assert_eq!(ask.hedge_price, dec!(101.5));
+        assert_eq!(ask.expected_edge.round_dp(6), dec!(0.15225));
+
+        assert!(strategy.quote(&OrderBookSnapshot::new("OTHER", 0)).is_err());
+    }
+
+    #[test]
+    fn test_depth_residual_and_tick_limits() {
+        let strategy = CrossVenueStrategy::new(config(dec!(3.0)).with_tick_size(dec!(0.5)));
+        let mut book = book();
+        book.asks.clear();
+        let quote = strategy.quote(&book).unwrap();
+        assert_eq!(quote.bid.unwrap().size, dec!(2.0));
+        assert!(quote.ask.is_none());
+
+        let mut strategy = CrossVenueStrategy::new(
+            config(dec!(1.0))
+                .with_fees(dec!(0.0), dec!(0.001))
+                .unwrap()
+                .with_tick_size(dec!(0.5)),
+        );
+        let quote = strategy.quote(&self::book()).unwrap();
+        assert_eq!(quote.bid.unwrap().price, dec!(99.5));
+        assert_eq!(quote.ask.unwrap().price, dec!(101.5));
+
+        // Long 4.5 unhedged: only 0.5 of bid room left, asks unaffected.
+        strategy.on_quote_fill(Side::Buy, dec!(4.5), dec!(99.5), dec!(0.0));
+        let quote = strategy.quote(&self::book()).unwrap();
+        assert_eq!(quote.bid.unwrap().size, dec!(0.5));
+        assert_eq!(quote.ask.unwrap().size, dec!(1.0));
+    }
+
+    #[test]
+    fn test_hedge_bookkeeping() {
+        let mut strategy =
+            CrossVenueStrategy::new(config(dec!(1.0)).with_min_hedge_size(dec!(0.4)));
+        strategy.on_quote_fill(Side::Sell, dec!(0.3), dec!(101.0), dec!(0.0));
+        assert!(strategy.hedge_order().is_none());
+
+        strategy.on_quote_fill(Side::Sell, dec!(0.7), dec!(101.0), dec!(-0.01));
+        let hedge = strategy.hedge_order().unwrap();
+        assert_eq!((hedge.side, hedge.quantity), (Side::Buy, dec!(1.0)));
+        assert_eq!(strategy.pending_hedge(), dec!(1.0));
+        // Already covered by the pending hedge.
+        assert!(strategy.hedge_order().is_none());
+
+        strategy.on_hedge_fill(Side::Buy, dec!(0.6), dec!(100.5), dec!(0.06));
+        strategy.release_hedge(Side::Buy, dec!(0.4));
+        assert_eq!(strategy.pending_hedge(), Decimal::ZERO);
+        assert_eq!(strategy.residual(), dec!(-0.4));
+        assert_eq!(strategy.hedge_order().unwrap().quantity, dec!(0.4));
+        strategy.on_hedge_fill(Side::Buy, dec!(0.4), dec!(100.5), dec!(0.04));
+
+        assert_eq!(strategy.residual(), Decimal::ZERO);
+        assert_eq!(strategy.fees_paid(), dec!(0.09));
+        // 101 − 100.5 per unit, less fees.
+        assert_eq!(strategy.pnl(dec!(100.0)), dec!(0.41));
+    }
+
+    #[tokio::test]
+    async fn test_market_maker_quotes_and_hedges() {
+        let hedge_venue = MockExchangeConnector::new(
+            MockConfig::default()
+                .with_base_price(dec!(100.0))
+                .with_spread(dec!(0.02)),
+        );
+        let quote_venue = MockExchangeConnector::with_defaults();
+        let config = CrossVenueConfig::new("QUOTE", "HEDGE", dec!(1.0), dec!(5.0))
+            .unwrap()
+            .with_fees(dec!(0.0), dec!(0.001))
+            .unwrap();
+        let mut maker =
+            CrossVenueMarketMaker::new(CrossVenueStrategy::new(config), quote_venue, hedge_venue);
+
+        let quote = maker.refresh_quotes().await.unwrap();
+        // Hedge book is 99 / 101 with one unit at the top.
+        assert_eq!(quote.bid.as_ref().unwrap().hedge_price, dec!(99.0));
+        assert_eq!(maker.quote_venue().open_order_count(), 2);
+        maker.refresh_quotes().await.unwrap();
+        assert_eq!(maker.quote_venue().open_order_count(), 2);
+
+        let bid = quote.bid.unwrap();
+        let fill = Fill {
+            order_id: maker.resting_orders().0.unwrap().clone(),
+            trade_id: "t1".to_string(),
+            price: bid.price,
+            quantity: dec!(1.0),
+            side: Side::Buy,
+            timestamp: 0,
+            fee: Decimal::ZERO,
+            fee_currency: "USD".to_string(),
+        };
+        let response = maker.on_quote_fill(&fill).await.unwrap().unwrap();
+        assert!(response.status.is_terminal());
+        assert_eq!(maker.strategy().residual(), Decimal::ZERO);
+        assert_eq!(maker.strategy().hedge_position(), dec!(-1.0));
+        assert!(maker.hedge().await.unwrap().is_none());
+
+        maker.cancel_quotes().await;
+        assert_eq!(maker.quote_venue().open_order_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_hedge_books_partial_fill() {
+        let hedge_venue = PartialHedgeVenue {
+            inner: MockExchangeConnector::new(
+                MockConfig::default()
+                    .with_base_price(dec!(100.0))
+                    .with_spread(dec!(0.02)),
+            ),
+            filled_qty: dec!(0.4),
+        };
+        let config = config(dec!(1.0)).with_fees(dec!(0.0), dec!(0.001)).unwrap();
+        let mut maker = CrossVenueMarketMaker::new(
+            CrossVenueStrategy::new(config),
+            MockExchangeConnector::with_defaults(),
+            hedge_venue,
+        );
+        maker
+            .strategy
+            .on_quote_fill(Side::Buy, dec!(1.0), dec!(98.0), Decimal::ZERO);
+
+        let response = maker.hedge().await.unwrap().unwrap();
+        assert!(matches!(response.status, OrderStatus::Cancelled { .. }));
+        // The partial fill sold at the 99 bid; the rest is released.
+        assert_eq!(maker.strategy().hedge_position(), dec!(-0.4));
+        assert_eq!(maker.strategy().pending_hedge(), Decimal::ZERO);
+        assert_eq!(maker.strategy().residual(), dec!(0.6));
+        assert_eq!(maker.strategy().fees_paid(), dec!(0.0396));
+        assert_eq!(maker.strategy.hedge_order().unwrap().quantity, dec!(0.6));
+    }
+}
@@ -0,0 +1,914 @@
+//! Fair-value estimators used as the reference price for quoting.
+//!
+//! The plain mid ignores how much size rests on each side of the book, so a
+//! strategy centered on it is systematically picked off when the book is wide
+//! and imbalanced. This module provides alternative reference prices behind a
+//! common [`FairValueEstimator`] trait:
+//!
+//! - [`MidPrice`]: the arithmetic mid, for comparison
+//! - [`Microprice`]: top-of-book mid weighted by the opposite side's size
+//! - [`DepthWeightedMid`]: the same weighting over the first N levels
+//! - [`StoikovMicroprice`]: Stoikov's (2018) micro-price, the expected mid in
+//!   the long run given the current imbalance and spread, fitted from history
+//!   with a discretized Markov model
+//!
+//! [`FairValueQuoter`] wraps any [`QuotingStrategy`] so that it quotes around
+//! the chosen estimator instead of `QuoteContext::mid_price`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::execution::{BookLevel, OrderBookSnapshot};
+//! use market_maker_rs::strategy::fair_value::{FairValueEstimator, Microprice};
+//! use market_maker_rs::dec;
+//!
+//! let mut book = OrderBookSnapshot::new("BTC-USD", 0);
+//! book.bids.push(BookLevel::new(dec!(99.0), dec!(9.0)));
+//! book.asks.push(BookLevel::new(dec!(101.0), dec!(1.0)));
+//!
+//! // Heavy bid: fair value sits close to the ask
+//! let fair = Microprice.fair_value(&book).unwrap();
+//! assert_eq!(fair, dec!(100.8));
+//! ```
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+use crate::Decimal;
+use crate::backtest::data::MarketTick;
+use crate::execution::{BookLevel, OrderBookSnapshot, Side};
+use crate::market_state::snapshot::cross_weighted;
+use crate::strategy::quoting::{QuoteContext, QuoteSet, QuotingStrategy};
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Mid-price changes, in ticks, tracked by the Stoikov model.
+const PRICE_MOVES: [f64; 4] = [-1.0, -0.5, 0.5, 1.0];
+
+/// Estimates the fair value of an instrument from its order book.
+pub trait FairValueEstimator: Send + Sync {
+    /// Returns a short identifier for the estimator.
+    fn name(&self) -> &str;
+
+    /// Estimates the fair value from an order book snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if the book lacks the levels the
+    /// estimator needs.
+    fn fair_value(&self, book: &OrderBookSnapshot) -> MMResult<Decimal>;
+
+    /// Estimates the fair value from the top of book of a market tick.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`fair_value`](Self::fair_value).
+    fn fair_value_tick(&self, tick: &MarketTick) -> MMResult<Decimal> {
+        let mut book = OrderBookSnapshot::new("", tick.timestamp);
+        book.bids
+            .push(BookLevel::new(tick.bid_price, tick.bid_size));
+        book.asks
+            .push(BookLevel::new(tick.ask_price, tick.ask_size));
+        self.fair_value(&book)
+    }
+}
+
+impl FairValueEstimator for Box<dyn FairValueEstimator> {
+    fn name(&self) -> &str {
+        self.as_ref().name()
+    }
+
+    fn fair_value(&self, book: &OrderBookSnapshot) -> MMResult<Decimal> {
+        self.as_ref().fair_value(book)
+    }
+
+    fn fair_value_tick(&self, tick: &MarketTick) -> MMResult<Decimal> {
+        self.as_ref().fair_value_tick(tick)
+    }
+}
+
+/// Returns the best bid and ask levels, or an error if either side is empty.
+fn top_of_book(book: &OrderBookSnapshot) -> MMResult<(&BookLevel, &BookLevel)> {
+    match (book.bids.first(), book.asks.first()) {
+        (Some(bid), Some(ask)) => Ok((bid, ask)),
+        _ => Err(MMError::InvalidMarketState(
+            "order book needs both a bid and an ask".to_string(),
+        )),
+    }
+}
+
+/// Arithmetic mid of the best bid and ask.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MidPrice;
+
+impl FairValueEstimator for MidPrice {
+    fn name(&self) -> &str {
+        "mid"
+    }
+
+    fn fair_value(&self, book: &OrderBookSnapshot) -> MMResult<Decimal> {
+        let (bid, ask) = top_of_book(book)?;
+        Ok((bid.price + ask.price) / Decimal::TWO)
+    }
+}
+
+/// Size-weighted microprice of the top of book.
+///
+/// `P = (bid * ask_size + ask * bid_size) / (bid_size + ask_size)`, which moves
+/// toward the side more likely to trade through next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Microprice;
+
+impl FairValueEstimator for Microprice {
+    fn name(&self) -> &str {
+        "microprice"
+    }
+
+    fn fair_value(&self, book: &OrderBookSnapshot) -> MMResult<Decimal> {
+        let (bid, ask) = top_of_book(book)?;
+        Ok(cross_weighted(
+            bid.price,
+            bid.quantity,
+            ask.price,
+            ask.quantity,
+        ))
+    }
+}
+
+/// Depth-weighted mid over the first N levels of each side.
+///
+/// The volume-weighted bid and ask prices of the first `levels` levels are
+/// combined with the same cross weighting as [`Microprice`], using the total
+/// depth of each side. With one level it equals the microprice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DepthWeightedMid {
+    levels: usize,
+}
+
+impl DepthWeightedMid {
+    /// Creates a depth-weighted mid over `levels` levels per side.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `levels` is zero.
+    pub fn new(levels: usize) -> MMResult<Self> {
+        if levels == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "depth-weighted mid needs at least 1 level".to_string(),
+            ));
+        }
+        Ok(Self { levels })
+    }
+
+    /// Returns the number of levels used per side.
+    #[must_use]
+    pub fn levels(&self) -> usize {
+        self.levels
+    }
+
+    /// Returns the volume-weighted price and total size of the first levels.
+    fn side_vwap(&self, levels: &[BookLevel]) -> (Decimal, Decimal) {
+        let mut depth = Decimal::ZERO;
+        let mut notional = Decimal::ZERO;
+        for level in levels.iter().take(self.levels) {
+            depth += level.quantity;
+            notional += level.notional();
+        }
+        if depth > Decimal::ZERO {
+            (notional / depth, depth)
+        } else {
+            (levels[0].price, Decimal::ZERO)
+        }
+    }
+}
+
+impl FairValueEstimator for DepthWeightedMid {
+    fn name(&self) -> &str {
+        "depth_weighted_mid"
+    }
+
+    fn fair_value(&self, book: &OrderBookSnapshot) -> MMResult<Decimal> {
+        top_of_book(book)?;
+        let (bid, bid_depth) = self.side_vwap(&book.bids);
+        let (ask, ask_depth) = self.side_vwap(&book.asks);
+        Ok(cross_weighted(bid, bid_depth, ask, ask_depth))
+    }
+}
+
+/// Discretization settings for [`StoikovMicroprice`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StoikovConfig {
+    /// Minimum price increment of the instrument.
+    pub tick_size: Decimal,
+
+    /// Number of equal-width buckets for the imbalance `bid_size / (bid_size + ask_size)`.
+    pub imbalance_buckets: usize,
+
+    /// Number of spread buckets, one per tick; wider spreads share the last.
+    pub spread_buckets: usize,
+
+    /// Number of terms of the series `g1 + B g1 + B² g1 + ...` to sum.
+    pub max_order: usize,
+}
+
+impl StoikovConfig {
+    /// Creates a configuration with 10 imbalance buckets, 2 spread buckets
+    /// and a 6th-order expansion.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the tick size is not positive.
+    pub fn new(tick_size: Decimal) -> MMResult<Self> {
+        if tick_size <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "tick_size must be positive".to_string(),
+            ));
+        }
+        Ok(Self {
+            tick_size,
+            imbalance_buckets: 10,
+            spread_buckets: 2,
+            max_order: 6,
+        })
+    }
+
+    /// Sets the number of imbalance buckets (at least 1).
+    #[must_use]
+    pub fn with_imbalance_buckets(mut self, buckets: usize) -> Self {
+        self.imbalance_buckets = buckets.max(1);
+        self
+    }
+
+    /// Sets the number of spread buckets (at least 1).
+    #[must_use]
+    pub fn with_spread_buckets(mut self, buckets: usize) -> Self {
+        self.spread_buckets = buckets.max(1);
+        self
+    }
+
+    /// Sets the expansion order (at least 1).
+    #[must_use]
+    pub fn with_max_order(mut self, max_order: usize) -> Self {
+        self.max_order = max_order.max(1);
+        self
+    }
+
+    /// Checks fields that may have been set directly or deserialized.
+    fn validate(&self) -> MMResult<()> {
+        if self.tick_size <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "tick_size must be positive".to_string(),
+            ));
+        }
+        if self.imbalance_buckets == 0 || self.spread_buckets == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "imbalance_buckets and spread_buckets must be at least 1".to_string(),
+            ));
+        }
+        if self.max_order == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "max_order must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn num_states(&self) -> usize {
+        self.imbalance_buckets * self.spread_buckets
+    }
+
+    /// Maps top-of-book sizes and spread to a state index.
+    ///
+    /// Returns `None` for locked or crossed books.
+    fn state(&self, bid_size: Decimal, ask_size: Decimal, spread: Decimal) -> Option<usize> {
+        let spread_ticks = (spread / self.tick_size).round().to_usize()?;
+        if spread_ticks == 0 {
+            return None;
+        }
+        let total = bid_size + ask_size;
+        let imbalance = if total > Decimal::ZERO {
+            bid_size / total
+        } else {
+            Decimal::new(5, 1)
+        };
+        let bucket = (imbalance * Decimal::from(self.imbalance_buckets))
+            .floor()
+            .to_usize()?
+            .min(self.imbalance_buckets - 1);
+        let spread_bucket = spread_ticks.min(self.spread_buckets) - 1;
+        Some(spread_bucket * self.imbalance_buckets + bucket)
+    }
+
+    /// Returns the state with the imbalance bucket mirrored.
+    fn mirror(&self, state: usize) -> usize {
+        let spread_bucket = state / self.imbalance_buckets;
+        let bucket = state % self.imbalance_buckets;
+        spread_bucket * self.imbalance_buckets + (self.imbalance_buckets - 1 - bucket)
+    }
+}
+
+/// Stoikov's micro-price fitted from top-of-book history.
+///
+/// States combine an imbalance bucket and a spread bucket. From consecutive
+/// ticks the model estimates the probability of staying without a mid change
+/// (`Q`), of the mid moving by each half-tick step (`R1`), and of the state
+/// after a move (`T`). The adjustment to the mid is
+/// `g1 + B g1 + B² g1 + ...` with `g1 = (I - Q)⁻¹ R1 K` and
+/// `B = (I - Q)⁻¹ T`. The data are symmetrized (imbalance mirrored, move
+/// negated) so adjustments are antisymmetric in the imbalance.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::backtest::MarketTick;
+/// use market_maker_rs::strategy::fair_value::{StoikovConfig, StoikovMicroprice};
+/// use market_maker_rs::dec;
+///
+/// // Heavy bids are followed by an up-tick, heavy asks by a down-tick
+/// let mut ticks = Vec::new();
+/// let mut bid = dec!(100.00);
+/// for i in 0..400u64 {
+///     let heavy_bid = i % 3 == 0;
+///     let (bid_size, ask_size) = if heavy_bid { (dec!(9), dec!(1)) } else { (dec!(1), dec!(9)) };
+///     ticks.push(MarketTick::new(i, bid, bid_size, bid + dec!(0.01), ask_size));
+///     bid += if heavy_bid { dec!(0.01) } else { dec!(-0.005) };
+/// }
+///
+/// let config = StoikovConfig::new(dec!(0.01)).unwrap().with_imbalance_buckets(4);
+/// let model = StoikovMicroprice::fit(&ticks, config).unwrap();
+///
+/// assert!(model.adjustment(dec!(9), dec!(1), dec!(0.01)).unwrap() > dec!(0));
+/// assert!(model.adjustment(dec!(1), dec!(9), dec!(0.01)).unwrap() < dec!(0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "StoikovMicropriceData")
+)]
+pub struct StoikovMicroprice {
+    config: StoikovConfig,
+    adjustments: Vec<Decimal>,
+    observations: usize,
+}
+
+/// Unchecked fields of a deserialized [`StoikovMicroprice`].
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StoikovMicropriceData {
+    config: StoikovConfig,
+    adjustments: Vec<Decimal>,
+    observations: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<StoikovMicropriceData> for StoikovMicroprice {
+    type Error = MMError;
+
+    fn try_from(data: StoikovMicropriceData) -> MMResult<Self> {
+        data.config.validate()?;
+        if data.adjustments.len() != data.config.num_states() {
+            return Err(MMError::InvalidConfiguration(format!(
+                "expected {} adjustments, got {}",
+                data.config.num_states(),
+                data.adjustments.len()
+            )));
+        }
+        Ok(Self {
+            config: data.config,
+            adjustments: data.adjustments,
+            observations: data.observations,
+        })
+    }
+}
+
+impl StoikovMicroprice {
+    /// Fits the model from chronologically ordered ticks.
+    ///
+    /// Pairs involving a locked or crossed book are skipped, and mid changes
+    /// are rounded to half ticks and capped at one tick.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the tick size is not
+    /// positive or a bucket count or the expansion order is zero,
+    /// `MMError::InvalidMarketState` if fewer than two valid ticks
+    /// are provided, or `MMError::NumericalError` if some visited state never
+    /// leads to a mid change, which makes `I - Q` singular.
+    pub fn fit(ticks: &[MarketTick], config: StoikovConfig) -> MMResult<Self> {
+        config.validate()?;
+        let n = config.num_states();
+        let mut stay = vec![vec![0.0f64; n]; n];
+        let mut moves = vec![vec![0.0f64; PRICE_MOVES.len()]; n];
+        let mut after_move = vec![vec![0.0f64; n]; n];
+        let mut observations = 0;
+
+        let tick_size = to_f64(config.tick_size)?;
+        for pair in ticks.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            let (Some(x), Some(y)) = (
+                config.state(from.bid_size, from.ask_size, from.spread()),
+                config.state(to.bid_size, to.ask_size, to.spread()),
+            ) else {
+                continue;
+            };
+            observations += 1;
+
+            let change = to_f64(to.mid_price() - from.mid_price())? / tick_size;
+            let change = ((change * 2.0).round() / 2.0).clamp(-1.0, 1.0);
+
+            for (x, y, change) in [
+                (x, y, change),
+                (config.mirror(x), config.mirror(y), -change),
+            ] {
+                match PRICE_MOVES.iter().position(|&k| k == change) {
+                    Some(k) => {
+                        moves[x][k] += 1.0;
+                        after_move[x][y] += 1.0;
+                    }
+                    None => stay[x][y] += 1.0,
+                }
+            }
+        }
+
+        if observations == 0 {
+            return Err(MMError::InvalidMarketState(
+                "need at least 2 ticks with a positive spread to fit the micro-price".to_string(),
+            ));
+        }
+
+        for x in 0..n {
+            let total: f64 = stay[x].iter().sum::<f64>() + moves[x].iter().sum::<f64>();
+            if total > 0.0 {
+                stay[x].iter_mut().for_each(|p| *p /= total);
+                moves[x].iter_mut().for_each(|p| *p /= total);
+                after_move[x].iter_mut().for_each(|p| *p /= total);
+            }
+        }
+
+        // N = (I - Q)⁻¹ is the expected number of visits before the mid moves
+        let mut absorbing = vec![vec![0.0f64; n]; n];
+        for (x, row) in absorbing.iter_mut().enumerate() {
+            for (y, value) in row.iter_mut().enumerate() {
+                *value = if x == y { 1.0 } else { 0.0 } - stay[x][y];
+            }
+        }
+        let fundamental = invert(absorbing)?;
+
+        let expected_move: Vec<f64> = moves
+            .iter()
+            .map(|row| row.iter().zip(PRICE_MOVES).map(|(p, k)| p * k).sum())
+            .collect();
+        let g1 = mat_vec(&fundamental, &expected_move);
+        let transition: Vec<Vec<f64>> = fundamental
+            .iter()
+            .map(|row| {
+                (0..n)
+                    .map(|y| (0..n).map(|z| row[z] * after_move[z][y]).sum())
+                    .collect()
+            })
+            .collect();
+
+        let mut adjustment = g1.clone();
+        let mut term = g1;
+        for _ in 1..config.max_order {
+            term = mat_vec(&transition, &term);
+            adjustment
+                .iter_mut()
+                .zip(&term)
+                .for_each(|(total, value)| *total += value);
+        }
+
+        let adjustments = adjustment
+            .into_iter()
+            .map(|ticks| from_f64(ticks * tick_size))
+            .collect::<MMResult<Vec<_>>>()?;
+
+        Ok(Self {
+            config,
+            adjustments,
+            observations,
+        })
+    }
+
+    /// Returns the discretization settings.
+    #[must_use]
+    pub fn config(&self) -> &StoikovConfig {
+        &self.config
+    }
+
+    /// Returns the number of tick pairs used in the fit.
+    #[must_use]
+    pub fn observations(&self) -> usize {
+        self.observations
+    }
+
+    /// Returns the fitted mid adjustments in price units, indexed by
+    /// `spread_bucket * imbalance_buckets + imbalance_bucket`.
+    #[must_use]
+    pub fn adjustments(&self) -> &[Decimal] {
+        &self.adjustments
+    }
+
+    /// Returns the mid adjustment for the given top-of-book sizes and spread.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if the spread is below one tick.
+    pub fn adjustment(
+        &self,
+        bid_size: Decimal,
+        ask_size: Decimal,
+        spread: Decimal,
+    ) -> MMResult<Decimal> {
+        self.config
+            .state(bid_size, ask_size, spread)
+            .map(|state| self.adjustments[state])
+            .ok_or_else(|| {
+                MMError::InvalidMarketState("micro-price needs a positive spread".to_string())
+            })
+    }
+}
+
+impl FairValueEstimator for StoikovMicroprice {
+    fn name(&self) -> &str {
+        "stoikov_microprice"
+    }
+
+    fn fair_value(&self, book: &OrderBookSnapshot) -> MMResult<Decimal> {
+        let (bid, ask) = top_of_book(book)?;
+        let mid = (bid.price + ask.price) / Decimal::TWO;
+        Ok(mid + self.adjustment(bid.quantity, ask.quantity, ask.price - bid.price)?)
+    }
+}
+
+/// Runs a [`QuotingStrategy`] around a fair value instead of the context mid.
+///
+/// Before each call the context's `mid_price` is replaced by the estimator's
+/// value for the attached order book, so every strategy can be centered on
+/// any estimator without changes.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::execution::{BookLevel, OrderBookSnapshot};
+/// use market_maker_rs::strategy::fair_value::{FairValueQuoter, Microprice};
+/// use market_maker_rs::strategy::grid::GridConfig;
+/// use market_maker_rs::strategy::quoting::{QuoteContext, QuotingStrategy, QuotingStrategyConfig};
+/// use market_maker_rs::dec;
+///
+/// let grid = QuotingStrategyConfig::Grid(GridConfig::new(1, dec!(0.01), dec!(1.0), dec!(10.0)).unwrap())
+///     .build()
+///     .unwrap();
+/// let mut quoter = FairValueQuoter::new(grid, Microprice);
+///
+/// let mut book = OrderBookSnapshot::new("BTC-USD", 0);
+/// book.bids.push(BookLevel::new(dec!(99.0), dec!(3.0)));
+/// book.asks.push(BookLevel::new(dec!(101.0), dec!(1.0)));
+/// let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0).with_orderbook(book);
+///
+/// let quotes = quoter.generate_quotes(&context).unwrap();
+/// assert!(quotes.best_bid().unwrap() > dec!(99.0));
+/// ```
+pub struct FairValueQuoter {
+    strategy: Box<dyn QuotingStrategy>,
+    estimator: Box<dyn FairValueEstimator>,
+}
+
+impl FairValueQuoter {
+    /// Wraps a strategy so that it quotes around the estimator's fair value.
+    #[must_use]
+    pub fn new(
+        strategy: Box<dyn QuotingStrategy>,
+        estimator: impl FairValueEstimator + 'static,
+    ) -> Self {
+        Self {
+            strategy,
+            estimator: Box::new(estimator),
+        }
+    }
+
+    /// Returns the wrapped strategy.
+    #[must_use]
+    pub fn strategy(&self) -> &dyn QuotingStrategy {
+        self.strategy.as_ref()
+    }
+
+    /// Returns the fair-value estimator.
+    #[must_use]
+    pub fn estimator(&self) -> &dyn FairValueEstimator {
+        self.estimator.as_ref()
+    }
+
+    /// Returns a copy of the context centered on the fair value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if the context has no order
+    /// book, or the estimator cannot value it.
+    pub fn reprice(&self, context: &QuoteContext) -> MMResult<QuoteContext> {
+        let fair_value = self.estimator.fair_value(context.require_orderbook()?)?;
+        let mut context = context.clone();
+        context.mid_price = fair_value;
+        Ok(context)
+    }
+}
+
+impl std::fmt::Debug for FairValueQuoter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FairValueQuoter")
+            .field("strategy", &self.strategy.name())
+            .field("estimator", &self.estimator.name())
+            .finish()
+    }
+}
+
+impl QuotingStrategy for FairValueQuoter {
+    fn name(&self) -> &str {
+        self.strategy.name()
+    }
+
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+        let context = self.reprice(context)?;
+        self.strategy.generate_quotes(&context)
+    }
+
+    fn on_fill(&mut self, side: Side, price: Decimal, quantity: Decimal, timestamp: u64) {
+        self.strategy.on_fill(side, price, quantity, timestamp);
+    }
+
+    fn reset(&mut self) {
+        self.strategy.reset();
+    }
+}
+
+fn to_f64(value: Decimal) -> MMResult<f64> {
+    value
+        .to_f64()
+        .ok_or_else(|| MMError::NumericalError(format!("cannot convert {value} to f64")))
+}
+
+fn from_f64(value: f64) -> MMResult<Decimal> {
+    Decimal::from_f64(value)
+        .ok_or_else(|| MMError::NumericalError(format!("cannot convert {value} to decimal")))
+}
+
+fn mat_vec(matrix: &[Vec<f64>], vector: &[f64]) -> Vec<f64> {
+    matrix
+        .iter()
+        .map(|row| row.iter().zip(vector).map(|(a, b)| a * b).sum())
+        .collect()
+}
+
+/// Inverts a square matrix by Gauss-Jordan elimination with partial pivoting.
+fn invert(mut matrix: Vec<Vec<f64>>) -> MMResult<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut inverse: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for col in 0..n {
+        let pivot_row = (col..n)
+            .max_by(|&a, &b| matrix[a][col].abs().total_cmp(&matrix[b][col].abs()))
+            .unwrap_or(col);
+        if matrix[pivot_row][col].abs() < 1e-12 {
+            return Err(MMError::NumericalError(
+                "a visited state never leads to a mid-price change".to_string(),
+            ));
+        }
+        matrix.swap(col, pivot_row);
+        inverse.swap(col, pivot_row);
+
+        let pivot = matrix[col][col];
+        for j in 0..n {
+            matrix[col][j] /= pivot;
+            inverse[col][j] /= pivot;
+        }
+        for row in 0..n {
+            if row == col {
+                continue;
+            }
+            let factor = matrix[row][col];
+            if factor == 0.0 {
+                continue;
+            }
+            for j in 0..n {
+                matrix[row][j] -= factor * matrix[col][j];
+                inverse[row][j] -= factor * inverse[col][j];
+            }
+        }
+    }
+    Ok(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+    use crate::strategy::grid::GridConfig;
+    use crate::strategy::quoting::QuotingStrategyConfig;
+    use crate::types::numerics::Lcg;
+
+    fn book(levels: &[(Decimal, Decimal)], asks: &[(Decimal, Decimal)]) -> OrderBookSnapshot {
+        let mut book = OrderBookSnapshot::new("TEST", 0);
+        book.bids = levels.iter().map(|&(p, q)| BookLevel::new(p, q)).collect();
+        book.asks = asks.iter().map(|&(p, q)| BookLevel::new(p, q)).collect();
+        book
+    }
+
+    /// Ticks where the next one-tick mid move is up with probability equal
+    /// to the current bid share.
+    fn imbalance_driven_ticks(count: usize) -> Vec<MarketTick> {
+        let mut rng = Lcg::new(11);
+        let mut next = || rng.uniform();
+
+        let mut ticks = Vec::with_capacity(count);
+        let mut bid = dec!(100.00);
+        for i in 0..count {
+            let bid_size = Decimal::from(1 + (next() * 19.0) as i64);
+            let ask_size = Decimal::from(1 + (next() * 19.0) as i64);
+            ticks.push(MarketTick::new(
+                i as u64,
+                bid,
+                bid_size,
+                bid + dec!(0.01),
+                ask_size,
+            ));
+
+            let share = (bid_size / (bid_size + ask_size)).to_f64().unwrap();
+            if next() < 0.3 {
+                bid += if next() < share {
+                    dec!(0.01)
+                } else {
+                    dec!(-0.01)
+                };
+            }
+        }
+        ticks
+    }
+
+    #[test]
+    fn test_mid_and_microprice() {
+        let book = book(&[(dec!(99), dec!(1))], &[(dec!(101), dec!(3))]);
+        assert_eq!(MidPrice.fair_value(&book).unwrap(), dec!(100));
+        assert_eq!(Microprice.fair_value(&book).unwrap(), dec!(99.5));
+
+        let empty = OrderBookSnapshot::new("TEST", 0);
+        assert!(matches!(
+            Microprice.fair_value(&empty),
+            Err(MMError::InvalidMarketState(_))
+        ));
+
+        let tick = MarketTick::new(0, dec!(99), dec!(0), dec!(101), dec!(0));
+        assert_eq!(Microprice.fair_value_tick(&tick).unwrap(), dec!(100));
+    }
+
+    #[test]
+    fn test_depth_weighted_mid() {
+        assert!(DepthWeightedMid::new(0).is_err());
+
+        let book = book(
+            &[(dec!(99), dec!(1)), (dec!(98), dec!(3))],
+            &[(dec!(101), dec!(1)), (dec!(102), dec!(1))],
+        );
+        let top = DepthWeightedMid::new(1).unwrap();
+        assert_eq!(
+            top.fair_value(&book).unwrap(),
+            Microprice.fair_value(&book).unwrap()
+        );
+
+        // Bid VWAP 98.25 over depth 4, ask VWAP 101.5 over depth 2
+        let deep = DepthWeightedMid::new(5).unwrap();
+        let expected = (dec!(98.25) * dec!(2) + dec!(101.5) * dec!(4)) / dec!(6);
+        assert_eq!(deep.fair_value(&book).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_stoikov_adjustments_follow_imbalance() {
+        let config = StoikovConfig::new(dec!(0.01))
+            .unwrap()
+            .with_imbalance_buckets(5)
+            .with_spread_buckets(1);
+        let model = StoikovMicroprice::fit(&imbalance_driven_ticks(20_000), config).unwrap();
+        let adjustments = model.adjustments();
+
+        assert_eq!(adjustments.len(), 5);
+        for pair in adjustments.windows(2) {
+            assert!(pair[0] < pair[1], "{adjustments:?}");
+        }
+        // Symmetrization makes the adjustments antisymmetric
+        for b in 0..5 {
+            assert!((adjustments[b] + adjustments[4 - b]).abs() < dec!(0.000001));
+        }
+        assert!(adjustments[4] > dec!(0.002) && adjustments[4] < dec!(0.01));
+
+        let heavy_bid = book(&[(dec!(100.00), dec!(19))], &[(dec!(100.01), dec!(1))]);
+        let fair = model.fair_value(&heavy_bid).unwrap();
+        assert!(fair > dec!(100.005) && fair < dec!(100.015));
+    }
+
+    #[test]
+    fn test_stoikov_rejects_degenerate_history() {
+        let config = StoikovConfig::new(dec!(0.01)).unwrap();
+        assert!(StoikovConfig::new(dec!(0)).is_err());
+
+        let locked = vec![MarketTick::new(0, dec!(100), dec!(1), dec!(100), dec!(1)); 3];
+        assert!(matches!(
+            StoikovMicroprice::fit(&locked, config.clone()),
+            Err(MMError::InvalidMarketState(_))
+        ));
+
+        // The mid never moves, so the chain never leaves its transient states
+        let frozen = vec![MarketTick::new(0, dec!(100), dec!(1), dec!(100.01), dec!(1)); 10];
+        assert!(matches!(
+            StoikovMicroprice::fit(&frozen, config.clone()),
+            Err(MMError::NumericalError(_))
+        ));
+
+        let model = StoikovMicroprice::fit(&imbalance_driven_ticks(5_000), config).unwrap();
+        assert!(model.adjustment(dec!(1), dec!(1), dec!(0)).is_err());
+        assert_eq!(model.observations(), 4_999);
+    }
+
+    #[test]
+    fn test_stoikov_rejects_zero_buckets() {
+        let ticks = imbalance_driven_ticks(1_000);
+        let valid = StoikovConfig::new(dec!(0.01)).unwrap();
+
+        for config in [
+            StoikovConfig {
+                imbalance_buckets: 0,
+                ..valid.clone()
+            },
+            StoikovConfig {
+                spread_buckets: 0,
+                ..valid.clone()
+            },
+            StoikovConfig {
+                max_order: 0,
+                ..valid
+            },
+        ] {
+            assert!(matches!(
+                StoikovMicroprice::fit(&ticks, config),
+                Err(MMError::InvalidConfiguration(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_stoikov_deserialization_is_validated() {
+        let config = StoikovConfig::new(dec!(0.01))
+            .unwrap()
+            .with_imbalance_buckets(5)
+            .with_spread_buckets(1);
+        let model = StoikovMicroprice::fit(&imbalance_driven_ticks(5_000), config).unwrap();
+        let value = serde_json::to_value(&model).unwrap();
+        let restored: StoikovMicroprice = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(restored, model);
+
+        let mut empty = value.clone();
+        empty["adjustments"] = serde_json::json!([]);
+        assert!(serde_json::from_value::<StoikovMicroprice>(empty).is_err());
+
+        let mut zero_order = value;
+        zero_order["config"]["max_order"] = serde_json::json!(0);
+        assert!(serde_json::from_value::<StoikovMicroprice>(zero_order).is_err());
+    }
+
+    #[test]
+    fn test_fair_value_quoter_recenters_strategy() {
+        let grid = QuotingStrategyConfig::Grid(
+            GridConfig::new(1, dec!(0.01), dec!(1.0), dec!(10.0)).unwrap(),
+        )
+        .build()
+        .unwrap();
+        let mut plain = QuotingStrategyConfig::Grid(
+            GridConfig::new(1, dec!(0.01), dec!(1.0), dec!(10.0)).unwrap(),
+        )
+        .build()
+        .unwrap();
+        let mut quoter = FairValueQuoter::new(grid, Microprice);
+        assert_eq!(quoter.estimator().name(), "microprice");
+
+        let book = book(&[(dec!(99), dec!(3))], &[(dec!(101), dec!(1))]);
+        let context = QuoteContext::new(dec!(100), dec!(0.2), dec!(0), 0).with_orderbook(book);
+
+        let repriced = quoter.reprice(&context).unwrap();
+        assert_eq!(repriced.mid_price, dec!(100.5));
+
+        let shifted = quoter.generate_quotes(&context).unwrap();
+        let baseline = plain.generate_quotes(&context).unwrap();
+        assert!(shifted.best_bid().unwrap() > baseline.best_bid().unwrap());
+        assert!(shifted.best_ask().unwrap() > baseline.best_ask().unwrap());
+
+        let without_book = QuoteContext::new(dec!(100), dec!(0.2), dec!(0), 0);
+        assert!(quoter.generate_quotes(&without_book).is_err());
+    }
+}
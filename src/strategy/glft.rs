@@ -36,10 +36,40 @@
 //! ```
 //!
 //! Where `α` controls how much gamma increases near terminal time.
+//!
+//! # Inventory Bounds
+//!
+//! The GLFT paper works on a finite inventory grid `q ∈ {-Q, ..., Q}` in
+//! multiples of a lot size. With [`InventoryBounds`] configured, the strategy
+//! stops quoting the side whose fill would push inventory beyond `Q`, instead
+//! of relying on downstream risk checks to reject the order.
+//!
+//! # Solutions
+//!
+//! [`GLFTSolution`] selects how quotes are computed:
+//!
+//! - [`GLFTSolution::Penalty`]: the terminal-penalty reservation price and
+//!   spread described above (default)
+//! - [`GLFTSolution::Asymptotic`]: the paper's closed-form asymptotic quotes
+//!
+//! ```text
+//! δ_b ≈ (1/(γΔ)) ln(1 + γΔ/k) + (2n + 1)/2 · ω
+//! δ_a ≈ (1/(γΔ)) ln(1 + γΔ/k) - (2n - 1)/2 · ω
+//! ω   = sqrt(γσ²Δ / (2kA) · (1 + γΔ/k)^(1 + k/(γΔ)))
+//! ```
+//!
+//! - [`GLFTSolution::Exact`]: the solution of the ODE system on the inventory
+//!   grid, `w(t) = exp(-M (T - t)) w(T)`, where `M` is tridiagonal with
+//!   `M[n][n] = kγσ²Δ n²/2` and `M[n][n±1] = -A (1 + γΔ/k)^-(1 + k/(γΔ))`,
+//!   giving `δ_b(n) = (1/k) ln(w_n / w_{n+1}) + (1/(γΔ)) ln(1 + γΔ/k)`
+//!
+//! Here `n = q/Δ` is inventory in lots and `A` is the arrival rate of market
+//! orders at zero distance from the mid, in orders per second.
 
 use crate::Decimal;
-use crate::types::decimal::{decimal_ln, decimal_powi};
+use crate::types::decimal::{decimal_exp, decimal_ln, decimal_powi, decimal_sqrt};
 use crate::types::error::{MMError, MMResult};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -47,6 +77,12 @@ use serde::{Deserialize, Serialize};
 const SECONDS_PER_MILLISECOND: Decimal = Decimal::from_parts(1, 0, 0, false, 3); // 0.001
 const SECONDS_PER_YEAR: Decimal = Decimal::from_parts(31_536_000, 0, 0, false, 0);
 
+/// Largest inventory grid (in lots per side) accepted by the exact solution.
+pub const MAX_EXACT_GRID_LEVELS: u32 = 100;
+
+/// Number of Taylor terms used for the scaled matrix exponential.
+const TAYLOR_TERMS: usize = 20;
+
 /// Penalty function type for terminal inventory.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -60,6 +96,101 @@ pub enum PenaltyFunction {
     Quadratic,
 }
 
+/// Finite inventory grid `{-Q, ..., Q}` in multiples of `lot_size`.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::strategy::glft::InventoryBounds;
+/// use market_maker_rs::dec;
+///
+/// let bounds = InventoryBounds::new(dec!(5.0), dec!(1.0)).unwrap();
+/// assert!(bounds.can_buy(dec!(4.0)));
+/// assert!(!bounds.can_buy(dec!(5.0)));
+/// assert!(bounds.can_sell(dec!(5.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InventoryBounds {
+    /// Maximum absolute inventory (Q).
+    pub max_inventory: Decimal,
+
+    /// Inventory change per fill (Δ), normally the quoted order size.
+    pub lot_size: Decimal,
+}
+
+impl InventoryBounds {
+    /// Creates new inventory bounds.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `lot_size` is not positive
+    /// or `max_inventory` is smaller than one lot.
+    pub fn new(max_inventory: Decimal, lot_size: Decimal) -> MMResult<Self> {
+        if lot_size <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "lot_size must be positive".to_string(),
+            ));
+        }
+
+        if max_inventory < lot_size {
+            return Err(MMError::InvalidConfiguration(
+                "max_inventory must be at least one lot".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            max_inventory,
+            lot_size,
+        })
+    }
+
+    /// Returns the number of grid levels on each side of zero, `floor(Q / Δ)`.
+    #[must_use]
+    pub fn levels(&self) -> u32 {
+        (self.max_inventory / self.lot_size)
+            .floor()
+            .to_u32()
+            .unwrap_or(u32::MAX)
+    }
+
+    /// Returns true if a bid fill of one lot keeps inventory within bounds.
+    #[must_use]
+    pub fn can_buy(&self, inventory: Decimal) -> bool {
+        inventory + self.lot_size <= self.max_inventory
+    }
+
+    /// Returns true if an ask fill of one lot keeps inventory within bounds.
+    #[must_use]
+    pub fn can_sell(&self, inventory: Decimal) -> bool {
+        inventory - self.lot_size >= -self.max_inventory
+    }
+}
+
+/// Method used to compute GLFT quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GLFTSolution {
+    /// Terminal-penalty reservation price and spread.
+    #[default]
+    Penalty,
+
+    /// Closed-form asymptotic quotes from the GLFT paper.
+    Asymptotic {
+        /// Market order arrival rate at zero distance (A), per second.
+        arrival_rate: Decimal,
+    },
+
+    /// Exact solution of the ODE system on the finite inventory grid.
+    ///
+    /// Requires [`InventoryBounds`]. The terminal penalty is applied as a
+    /// quadratic liquidation cost `φ · q²`.
+    Exact {
+        /// Market order arrival rate at zero distance (A), per second.
+        arrival_rate: Decimal,
+    },
+}
+
 /// Configuration for the GLFT strategy.
 ///
 /// # Example
@@ -103,6 +234,13 @@ pub struct GLFTConfig {
 
     /// Penalty function type.
     pub penalty_function: PenaltyFunction,
+
+    /// Optional finite inventory grid; sides that would breach it are not quoted.
+    pub inventory_bounds: Option<InventoryBounds>,
+
+    /// Method used to compute quotes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub solution: GLFTSolution,
 }
 
 impl GLFTConfig {
@@ -176,6 +314,8 @@ impl GLFTConfig {
             dynamic_gamma: false,
             gamma_scaling_factor: Decimal::ONE,
             penalty_function: PenaltyFunction::default(),
+            inventory_bounds: None,
+            solution: GLFTSolution::default(),
         })
     }
 
@@ -197,6 +337,20 @@ impl GLFTConfig {
         self.penalty_function = penalty_function;
         self
     }
+
+    /// Sets the finite inventory grid.
+    #[must_use]
+    pub fn with_inventory_bounds(mut self, bounds: InventoryBounds) -> Self {
+        self.inventory_bounds = Some(bounds);
+        self
+    }
+
+    /// Sets the solution method.
+    #[must_use]
+    pub fn with_solution(mut self, solution: GLFTSolution) -> Self {
+        self.solution = solution;
+        self
+    }
 }
 
 /// GLFT strategy implementation.
@@ -570,6 +724,433 @@ impl GLFTStrategy {
 
         Ok((glft_quotes, as_quotes))
     }
+
+    /// Calculates quotes using the configured solution and inventory bounds.
+    ///
+    /// A side is `None` when its fill would push inventory beyond the
+    /// configured [`InventoryBounds`]. Without bounds both sides are always
+    /// quoted. The quoted spread is widened symmetrically to `min_spread`
+    /// when both sides are present.
+    ///
+    /// # Arguments
+    ///
+    /// * `mid_price` - Current mid price
+    /// * `inventory` - Current inventory position
+    /// * `config` - GLFT configuration
+    /// * `volatility` - Annualized volatility
+    /// * `current_time` - Current time in milliseconds from session start
+    ///
+    /// # Returns
+    ///
+    /// Tuple of (bid_price, ask_price), each optional.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if inputs are invalid, the solution is misconfigured or
+    /// the resulting quotes are invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use market_maker_rs::strategy::glft::{GLFTConfig, GLFTStrategy, InventoryBounds};
+    /// use market_maker_rs::dec;
+    ///
+    /// let config = GLFTConfig::new(dec!(0.1), dec!(1.5), dec!(0.05), 3_600_000, dec!(0.0001))
+    ///     .unwrap()
+    ///     .with_inventory_bounds(InventoryBounds::new(dec!(5.0), dec!(1.0)).unwrap());
+    ///
+    /// // At the long limit only the ask is quoted
+    /// let (bid, ask) = GLFTStrategy::calculate_bounded_quotes(
+    ///     dec!(100.0), dec!(5.0), &config, dec!(0.2), 0
+    /// ).unwrap();
+    /// assert!(bid.is_none());
+    /// assert!(ask.is_some());
+    /// ```
+    pub fn calculate_bounded_quotes(
+        mid_price: Decimal,
+        inventory: Decimal,
+        config: &GLFTConfig,
+        volatility: Decimal,
+        current_time: u64,
+    ) -> MMResult<(Option<Decimal>, Option<Decimal>)> {
+        if mid_price <= Decimal::ZERO {
+            return Err(MMError::InvalidMarketState(
+                "mid_price must be positive".to_string(),
+            ));
+        }
+
+        let (can_buy, can_sell) = match &config.inventory_bounds {
+            Some(bounds) => (bounds.can_buy(inventory), bounds.can_sell(inventory)),
+            None => (true, true),
+        };
+
+        let (bid_offset, ask_offset) = match config.solution {
+            GLFTSolution::Penalty => {
+                let (bid, ask) = Self::calculate_optimal_quotes(
+                    mid_price,
+                    inventory,
+                    config,
+                    volatility,
+                    current_time,
+                )?;
+                (Some(mid_price - bid), Some(ask - mid_price))
+            }
+            GLFTSolution::Asymptotic { arrival_rate } => {
+                let (bid, ask) = Self::calculate_asymptotic_offsets(
+                    inventory,
+                    config,
+                    volatility,
+                    arrival_rate,
+                )?;
+                (Some(bid), Some(ask))
+            }
+            GLFTSolution::Exact { arrival_rate } => Self::calculate_exact_offsets(
+                inventory,
+                config,
+                volatility,
+                arrival_rate,
+                current_time,
+            )?,
+        };
+
+        let mut bid_offset = bid_offset.filter(|_| can_buy);
+        let mut ask_offset = ask_offset.filter(|_| can_sell);
+
+        if let (Some(bid), Some(ask)) = (bid_offset.as_mut(), ask_offset.as_mut()) {
+            let spread = *bid + *ask;
+            if spread < config.min_spread {
+                let widening = (config.min_spread - spread) / Decimal::TWO;
+                *bid += widening;
+                *ask += widening;
+            }
+        }
+
+        let bid_price = bid_offset.map(|offset| mid_price - offset);
+        let ask_price = ask_offset.map(|offset| mid_price + offset);
+
+        if matches!((bid_price, ask_price), (Some(bid), Some(ask)) if bid >= ask) {
+            return Err(MMError::InvalidQuoteGeneration(
+                "bid price must be less than ask price".to_string(),
+            ));
+        }
+
+        if matches!(bid_price, Some(bid) if bid <= Decimal::ZERO) {
+            return Err(MMError::InvalidQuoteGeneration(
+                "bid price must be positive".to_string(),
+            ));
+        }
+
+        Ok((bid_price, ask_price))
+    }
+
+    /// Calculates the closed-form asymptotic GLFT quote offsets from the mid.
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// δ_b = (1/(γΔ)) ln(1 + γΔ/k) + (2n + 1)/2 · ω
+    /// δ_a = (1/(γΔ)) ln(1 + γΔ/k) - (2n - 1)/2 · ω
+    /// ω   = sqrt(γσ²Δ / (2kA) · (1 + γΔ/k)^(1 + k/(γΔ)))
+    /// ```
+    ///
+    /// where `n = q/Δ` and `Δ` is the configured lot size (1 without bounds).
+    ///
+    /// # Arguments
+    ///
+    /// * `inventory` - Current inventory position
+    /// * `config` - GLFT configuration
+    /// * `volatility` - Annualized volatility
+    /// * `arrival_rate` - Market order arrival rate at zero distance, per second
+    ///
+    /// # Returns
+    ///
+    /// Tuple of (bid_offset, ask_offset), distances below and above the mid.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if inputs are invalid or a numerical operation fails.
+    pub fn calculate_asymptotic_offsets(
+        inventory: Decimal,
+        config: &GLFTConfig,
+        volatility: Decimal,
+        arrival_rate: Decimal,
+    ) -> MMResult<(Decimal, Decimal)> {
+        Self::validate_solution_inputs(volatility, arrival_rate)?;
+
+        let lot = Self::lot_size(config);
+        let gamma = config.risk_aversion;
+        let k = config.order_intensity;
+        let gamma_lot = gamma * lot;
+
+        let base = Self::base_offset(gamma_lot, k)?;
+
+        // (1 + γΔ/k)^(1 + k/(γΔ))
+        let growth = decimal_exp(
+            (Decimal::ONE + k / gamma_lot) * decimal_ln(Decimal::ONE + gamma_lot / k)?,
+        )?;
+        let variance_per_second = decimal_powi(volatility, 2)? / SECONDS_PER_YEAR;
+        let omega = decimal_sqrt(
+            gamma * variance_per_second * lot / (Decimal::TWO * k * arrival_rate) * growth,
+        )?;
+
+        let lots = inventory / lot;
+        let bid_offset = base + (Decimal::TWO * lots + Decimal::ONE) / Decimal::TWO * omega;
+        let ask_offset = base - (Decimal::TWO * lots - Decimal::ONE) / Decimal::TWO * omega;
+
+        Ok((bid_offset, ask_offset))
+    }
+
+    /// Calculates the exact GLFT quote offsets on the finite inventory grid.
+    ///
+    /// Solves `w(t) = exp(-M (T - t)) w(T)` with `w_n(T) = exp(-kφΔ n²)` and
+    /// derives the optimal offsets from neighbouring grid values. Inventory
+    /// is rounded to the nearest lot and clamped to the grid. The offset for
+    /// a side is `None` at the corresponding edge of the grid.
+    ///
+    /// The matrix exponential is computed by scaling and squaring a
+    /// non-negative shifted matrix, which avoids cancellation. Cost grows
+    /// cubically with the grid size, which is capped at
+    /// [`MAX_EXACT_GRID_LEVELS`] lots per side.
+    ///
+    /// # Arguments
+    ///
+    /// * `inventory` - Current inventory position
+    /// * `config` - GLFT configuration, must have inventory bounds
+    /// * `volatility` - Annualized volatility
+    /// * `arrival_rate` - Market order arrival rate at zero distance, per second
+    /// * `current_time` - Current time in milliseconds from session start
+    ///
+    /// # Returns
+    ///
+    /// Tuple of (bid_offset, ask_offset), distances below and above the mid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` without inventory bounds or
+    /// with a grid larger than [`MAX_EXACT_GRID_LEVELS`], and
+    /// `MMError::NumericalError` if the solution degenerates.
+    pub fn calculate_exact_offsets(
+        inventory: Decimal,
+        config: &GLFTConfig,
+        volatility: Decimal,
+        arrival_rate: Decimal,
+        current_time: u64,
+    ) -> MMResult<(Option<Decimal>, Option<Decimal>)> {
+        Self::validate_solution_inputs(volatility, arrival_rate)?;
+
+        let bounds = config.inventory_bounds.ok_or_else(|| {
+            MMError::InvalidConfiguration(
+                "exact GLFT solution requires inventory bounds".to_string(),
+            )
+        })?;
+        let levels = bounds.levels();
+        if levels > MAX_EXACT_GRID_LEVELS {
+            return Err(MMError::InvalidConfiguration(format!(
+                "inventory grid of {levels} lots exceeds the exact solution limit of {MAX_EXACT_GRID_LEVELS}"
+            )));
+        }
+
+        let lot = bounds.lot_size;
+        let gamma = config.risk_aversion;
+        let k = config.order_intensity;
+        let gamma_lot = gamma * lot;
+        let base = Self::base_offset(gamma_lot, k)?;
+
+        let variance_per_second = decimal_powi(volatility, 2)? / SECONDS_PER_YEAR;
+        let alpha = k * gamma * variance_per_second * lot / Decimal::TWO;
+        let eta = arrival_rate
+            * decimal_exp(
+                -(Decimal::ONE + k / gamma_lot) * decimal_ln(Decimal::ONE + gamma_lot / k)?,
+            )?;
+        let terminal_coeff = k * config.terminal_penalty * lot;
+        let time_to_terminal_seconds =
+            Decimal::from(config.terminal_time.saturating_sub(current_time))
+                * SECONDS_PER_MILLISECOND;
+
+        let log_w = exact_log_values(
+            levels as usize,
+            to_f64(alpha)?,
+            to_f64(eta)?,
+            to_f64(terminal_coeff)?,
+            to_f64(time_to_terminal_seconds)?,
+        )?;
+
+        let max_lots = i64::from(levels);
+        let lots = (inventory / lot)
+            .round()
+            .to_i64()
+            .unwrap_or(0)
+            .clamp(-max_lots, max_lots);
+        let index = (lots + max_lots) as usize;
+        let k_f64 = to_f64(k)?;
+
+        let bid_offset = if lots < max_lots {
+            Some(base + from_f64((log_w[index] - log_w[index + 1]) / k_f64)?)
+        } else {
+            None
+        };
+        let ask_offset = if lots > -max_lots {
+            Some(base + from_f64((log_w[index] - log_w[index - 1]) / k_f64)?)
+        } else {
+            None
+        };
+
+        Ok((bid_offset, ask_offset))
+    }
+
+    /// Returns the lot size from the inventory bounds, or one without bounds.
+    fn lot_size(config: &GLFTConfig) -> Decimal {
+        config
+            .inventory_bounds
+            .map_or(Decimal::ONE, |bounds| bounds.lot_size)
+    }
+
+    /// Adverse-selection offset `(1/(γΔ)) ln(1 + γΔ/k)`.
+    fn base_offset(gamma_lot: Decimal, k: Decimal) -> MMResult<Decimal> {
+        Ok(decimal_ln(Decimal::ONE + gamma_lot / k)? / gamma_lot)
+    }
+
+    /// Validates inputs shared by the asymptotic and exact solutions.
+    fn validate_solution_inputs(volatility: Decimal, arrival_rate: Decimal) -> MMResult<()> {
+        if volatility <= Decimal::ZERO {
+            return Err(MMError::InvalidMarketState(
+                "volatility must be positive".to_string(),
+            ));
+        }
+
+        if arrival_rate <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "arrival_rate must be positive".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts a decimal to `f64`.
+fn to_f64(value: Decimal) -> MMResult<f64> {
+    value
+        .to_f64()
+        .ok_or_else(|| MMError::NumericalError(format!("cannot convert {value} to f64")))
+}
+
+/// Converts an `f64` to a decimal.
+fn from_f64(value: f64) -> MMResult<Decimal> {
+    Decimal::from_f64(value)
+        .ok_or_else(|| MMError::NumericalError(format!("cannot convert {value} to Decimal")))
+}
+
+/// Computes `ln w(t)` on the grid `n = -levels..=levels`, up to a constant.
+///
+/// `w(t) = exp(-M τ) w(T)` with `M[n][n] = alpha · n²`, `M[n][n±1] = -eta`
+/// and `ln w_n(T) = -terminal_coeff · n²`.
+fn exact_log_values(
+    levels: usize,
+    alpha: f64,
+    eta: f64,
+    terminal_coeff: f64,
+    tau: f64,
+) -> MMResult<Vec<f64>> {
+    let size = 2 * levels + 1;
+    let lots = |i: usize| i as f64 - levels as f64;
+    let terminal: Vec<f64> = (0..size)
+        .map(|i| -terminal_coeff * lots(i) * lots(i))
+        .collect();
+
+    if tau <= 0.0 {
+        return Ok(terminal);
+    }
+
+    // exp(-Mτ) = exp(-cτ) · exp(Bτ) with B = cI - M non-negative for c = max diagonal
+    let max_diagonal = alpha * (levels * levels) as f64;
+    let mut matrix = vec![0.0; size * size];
+    for i in 0..size {
+        matrix[i * size + i] = (max_diagonal - alpha * lots(i) * lots(i)) * tau;
+        if i + 1 < size {
+            matrix[i * size + i + 1] = eta * tau;
+            matrix[(i + 1) * size + i] = eta * tau;
+        }
+    }
+
+    let norm = (max_diagonal + 2.0 * eta) * tau;
+    if !norm.is_finite() {
+        return Err(MMError::NumericalError(
+            "exact GLFT solution overflowed".to_string(),
+        ));
+    }
+    let squarings = if norm > 0.5 {
+        (norm / 0.5).log2().ceil() as u32
+    } else {
+        0
+    };
+    let scale = 2f64.powi(-(squarings as i32));
+    matrix.iter_mut().for_each(|x| *x *= scale);
+
+    // Taylor series of the scaled matrix; every term is non-negative
+    let mut exponential = identity(size);
+    let mut term = identity(size);
+    for order in 1..=TAYLOR_TERMS {
+        term = multiply(&term, &matrix, size);
+        term.iter_mut().for_each(|x| *x /= order as f64);
+        exponential.iter_mut().zip(&term).for_each(|(e, t)| *e += t);
+    }
+
+    // Only ratios between grid values matter, so renormalize after each squaring
+    for _ in 0..squarings {
+        exponential = multiply(&exponential, &exponential, size);
+        let max = exponential.iter().cloned().fold(0.0, f64::max);
+        if !(max.is_finite() && max > 0.0) {
+            return Err(MMError::NumericalError(
+                "exact GLFT solution degenerated".to_string(),
+            ));
+        }
+        exponential.iter_mut().for_each(|x| *x /= max);
+    }
+
+    let terminal_max = terminal.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let terminal_w: Vec<f64> = terminal.iter().map(|v| (v - terminal_max).exp()).collect();
+
+    (0..size)
+        .map(|i| {
+            let value: f64 = (0..size)
+                .map(|j| exponential[i * size + j] * terminal_w[j])
+                .sum();
+            if value > 0.0 && value.is_finite() {
+                Ok(value.ln())
+            } else {
+                Err(MMError::NumericalError(
+                    "exact GLFT solution degenerated".to_string(),
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Returns a row-major identity matrix.
+fn identity(size: usize) -> Vec<f64> {
+    let mut matrix = vec![0.0; size * size];
+    for i in 0..size {
+        matrix[i * size + i] = 1.0;
+    }
+    matrix
+}
+
+/// Multiplies two row-major square matrices.
+fn multiply(a: &[f64], b: &[f64], size: usize) -> Vec<f64> {
+    let mut result = vec![0.0; size * size];
+    for i in 0..size {
+        for k in 0..size {
+            let a_ik = a[i * size + k];
+            if a_ik == 0.0 {
+                continue;
+            }
+            for j in 0..size {
+                result[i * size + j] += a_ik * b[k * size + j];
+            }
+        }
+    }
+    result
 }
 
 #[cfg(test)]
@@ -958,6 +1539,263 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn bounded_config(solution: GLFTSolution) -> GLFTConfig {
+        GLFTConfig::new(dec!(0.1), dec!(1.5), dec!(0.0), 3_600_000, dec!(0.0))
+            .unwrap()
+            .with_inventory_bounds(InventoryBounds::new(dec!(5.0), dec!(1.0)).unwrap())
+            .with_solution(solution)
+    }
+
+    #[test]
+    fn test_inventory_bounds_validation() {
+        assert!(InventoryBounds::new(dec!(5.0), dec!(0.0)).is_err());
+        assert!(InventoryBounds::new(dec!(0.5), dec!(1.0)).is_err());
+
+        let bounds = InventoryBounds::new(dec!(5.5), dec!(2.0)).unwrap();
+        assert_eq!(bounds.levels(), 2);
+    }
+
+    #[test]
+    fn test_inventory_bounds_sides() {
+        let bounds = InventoryBounds::new(dec!(3.0), dec!(1.0)).unwrap();
+        assert!(bounds.can_buy(dec!(2.0)));
+        assert!(!bounds.can_buy(dec!(2.5)));
+        assert!(bounds.can_sell(dec!(-2.0)));
+        assert!(!bounds.can_sell(dec!(-2.5)));
+    }
+
+    #[test]
+    fn test_config_defaults_unbounded_penalty() {
+        let config =
+            GLFTConfig::new(dec!(0.1), dec!(1.5), dec!(0.05), 3_600_000, dec!(0.0001)).unwrap();
+        assert!(config.inventory_bounds.is_none());
+        assert_eq!(config.solution, GLFTSolution::Penalty);
+    }
+
+    #[test]
+    fn test_bounded_quotes_match_penalty_without_bounds() {
+        let config =
+            GLFTConfig::new(dec!(0.1), dec!(1.5), dec!(0.05), 3_600_000, dec!(0.0001)).unwrap();
+
+        let (bid, ask) =
+            GLFTStrategy::calculate_bounded_quotes(dec!(100.0), dec!(3.0), &config, dec!(0.2), 0)
+                .unwrap();
+        let expected =
+            GLFTStrategy::calculate_optimal_quotes(dec!(100.0), dec!(3.0), &config, dec!(0.2), 0)
+                .unwrap();
+
+        assert_eq!((bid.unwrap(), ask.unwrap()), expected);
+    }
+
+    #[test]
+    fn test_bounded_quotes_drop_side_at_limit() {
+        let config = bounded_config(GLFTSolution::Penalty);
+
+        let (bid, ask) =
+            GLFTStrategy::calculate_bounded_quotes(dec!(100.0), dec!(5.0), &config, dec!(0.2), 0)
+                .unwrap();
+        assert!(bid.is_none());
+        assert!(ask.is_some());
+
+        let (bid, ask) =
+            GLFTStrategy::calculate_bounded_quotes(dec!(100.0), dec!(-4.5), &config, dec!(0.2), 0)
+                .unwrap();
+        assert!(bid.is_some());
+        assert!(ask.is_none());
+    }
+
+    #[test]
+    fn test_asymptotic_offsets_symmetric_when_flat() {
+        let config = bounded_config(GLFTSolution::Asymptotic {
+            arrival_rate: dec!(1.0),
+        });
+
+        let (bid, ask) =
+            GLFTStrategy::calculate_asymptotic_offsets(dec!(0.0), &config, dec!(50.0), dec!(1.0))
+                .unwrap();
+        assert!((bid - ask).abs() < dec!(0.0000000001));
+        assert!(bid > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_asymptotic_offsets_skew_with_inventory() {
+        let config = bounded_config(GLFTSolution::Asymptotic {
+            arrival_rate: dec!(1.0),
+        });
+
+        let (flat_bid, flat_ask) =
+            GLFTStrategy::calculate_asymptotic_offsets(dec!(0.0), &config, dec!(50.0), dec!(1.0))
+                .unwrap();
+        let (long_bid, long_ask) =
+            GLFTStrategy::calculate_asymptotic_offsets(dec!(3.0), &config, dec!(50.0), dec!(1.0))
+                .unwrap();
+
+        // Long inventory: bid further away, ask closer, spread unchanged
+        assert!(long_bid > flat_bid);
+        assert!(long_ask < flat_ask);
+        assert!(((long_bid + long_ask) - (flat_bid + flat_ask)).abs() < dec!(0.0000000001));
+    }
+
+    #[test]
+    fn test_asymptotic_invalid_arrival_rate() {
+        let config = bounded_config(GLFTSolution::Penalty);
+        let result =
+            GLFTStrategy::calculate_asymptotic_offsets(dec!(0.0), &config, dec!(50.0), dec!(0.0));
+        assert!(matches!(result, Err(MMError::InvalidConfiguration(_))));
+    }
+
+    #[test]
+    fn test_exact_requires_bounds() {
+        let config = GLFTConfig::new(dec!(0.1), dec!(1.5), dec!(0.0), 3_600_000, dec!(0.0))
+            .unwrap()
+            .with_solution(GLFTSolution::Exact {
+                arrival_rate: dec!(1.0),
+            });
+
+        let result =
+            GLFTStrategy::calculate_bounded_quotes(dec!(100.0), dec!(0.0), &config, dec!(50.0), 0);
+        assert!(matches!(result, Err(MMError::InvalidConfiguration(_))));
+    }
+
+    #[test]
+    fn test_exact_rejects_large_grid() {
+        let config = bounded_config(GLFTSolution::Penalty).with_inventory_bounds(
+            InventoryBounds::new(Decimal::from(MAX_EXACT_GRID_LEVELS + 1), dec!(1.0)).unwrap(),
+        );
+        let result =
+            GLFTStrategy::calculate_exact_offsets(dec!(0.0), &config, dec!(50.0), dec!(1.0), 0);
+        assert!(matches!(result, Err(MMError::InvalidConfiguration(_))));
+    }
+
+    #[test]
+    fn test_exact_at_terminal_without_penalty_is_base_offset() {
+        let config = bounded_config(GLFTSolution::Penalty);
+        let (bid, ask) = GLFTStrategy::calculate_exact_offsets(
+            dec!(2.0),
+            &config,
+            dec!(50.0),
+            dec!(1.0),
+            3_600_000,
+        )
+        .unwrap();
+
+        let base = decimal_ln(dec!(1.0) + dec!(0.1) / dec!(1.5)).unwrap() / dec!(0.1);
+        assert!((bid.unwrap() - base).abs() < dec!(0.0000001));
+        assert!((ask.unwrap() - base).abs() < dec!(0.0000001));
+    }
+
+    #[test]
+    fn test_exact_edges_quote_one_side() {
+        let config = bounded_config(GLFTSolution::Penalty);
+
+        let (bid, ask) =
+            GLFTStrategy::calculate_exact_offsets(dec!(5.0), &config, dec!(50.0), dec!(1.0), 0)
+                .unwrap();
+        assert!(bid.is_none());
+        assert!(ask.is_some());
+
+        let (bid, ask) =
+            GLFTStrategy::calculate_exact_offsets(dec!(-5.0), &config, dec!(50.0), dec!(1.0), 0)
+                .unwrap();
+        assert!(bid.is_some());
+        assert!(ask.is_none());
+    }
+
+    #[test]
+    fn test_exact_symmetric_and_skewed() {
+        let config = bounded_config(GLFTSolution::Penalty);
+
+        let (flat_bid, flat_ask) =
+            GLFTStrategy::calculate_exact_offsets(dec!(0.0), &config, dec!(50.0), dec!(1.0), 0)
+                .unwrap();
+        assert!((flat_bid.unwrap() - flat_ask.unwrap()).abs() < dec!(0.0000001));
+
+        let (long_bid, long_ask) =
+            GLFTStrategy::calculate_exact_offsets(dec!(2.0), &config, dec!(50.0), dec!(1.0), 0)
+                .unwrap();
+        assert!(long_bid.unwrap() > flat_bid.unwrap());
+        assert!(long_ask.unwrap() < flat_ask.unwrap());
+    }
+
+    #[test]
+    fn test_exact_converges_to_asymptotic() {
+        // Far from terminal and away from the grid edges the exact quotes
+        // approach the closed-form asymptotic quotes
+        let config = GLFTConfig::new(dec!(0.1), dec!(1.5), dec!(0.0), 36_000_000, dec!(0.0))
+            .unwrap()
+            .with_inventory_bounds(InventoryBounds::new(dec!(20.0), dec!(1.0)).unwrap());
+
+        let (exact_bid, exact_ask) =
+            GLFTStrategy::calculate_exact_offsets(dec!(1.0), &config, dec!(50.0), dec!(1.0), 0)
+                .unwrap();
+        let (asym_bid, asym_ask) =
+            GLFTStrategy::calculate_asymptotic_offsets(dec!(1.0), &config, dec!(50.0), dec!(1.0))
+                .unwrap();
+
+        let tolerance = asym_bid * dec!(0.01);
+        assert!((exact_bid.unwrap() - asym_bid).abs() < tolerance);
+        assert!((exact_ask.unwrap() - asym_ask).abs() < tolerance);
+    }
+
+    #[test]
+    fn test_exact_terminal_penalty_increases_skew() {
+        let no_penalty = bounded_config(GLFTSolution::Penalty);
+        let with_penalty = GLFTConfig {
+            terminal_penalty: dec!(0.01),
+            ..no_penalty.clone()
+        };
+
+        let (bid_none, _) = GLFTStrategy::calculate_exact_offsets(
+            dec!(2.0),
+            &no_penalty,
+            dec!(50.0),
+            dec!(1.0),
+            3_590_000,
+        )
+        .unwrap();
+        let (bid_pen, _) = GLFTStrategy::calculate_exact_offsets(
+            dec!(2.0),
+            &with_penalty,
+            dec!(50.0),
+            dec!(1.0),
+            3_590_000,
+        )
+        .unwrap();
+
+        assert!(bid_pen.unwrap() > bid_none.unwrap());
+    }
+
+    #[test]
+    fn test_bounded_quotes_exact_solution() {
+        let config = bounded_config(GLFTSolution::Exact {
+            arrival_rate: dec!(1.0),
+        });
+
+        let (bid, ask) =
+            GLFTStrategy::calculate_bounded_quotes(dec!(100.0), dec!(1.0), &config, dec!(50.0), 0)
+                .unwrap();
+        let (bid, ask) = (bid.unwrap(), ask.unwrap());
+        assert!(bid < dec!(100.0));
+        assert!(ask > dec!(100.0));
+        // Long inventory skews quotes down
+        assert!(dec!(100.0) - bid > ask - dec!(100.0));
+    }
+
+    #[test]
+    fn test_bounded_quotes_min_spread_widening() {
+        let config = GLFTConfig {
+            min_spread: dec!(10.0),
+            ..bounded_config(GLFTSolution::Asymptotic {
+                arrival_rate: dec!(1.0),
+            })
+        };
+
+        let (bid, ask) =
+            GLFTStrategy::calculate_bounded_quotes(dec!(100.0), dec!(0.0), &config, dec!(50.0), 0)
+                .unwrap();
+        assert!((ask.unwrap() - bid.unwrap() - dec!(10.0)).abs() < dec!(0.0000001));
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serialization() {
@@ -969,4 +1807,17 @@ mod tests {
 
         assert_eq!(config, deserialized);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialization_with_bounds_and_solution() {
+        let config = bounded_config(GLFTSolution::Exact {
+            arrival_rate: dec!(1.0),
+        });
+
+        let json = serde_json::to_string(&config).unwrap();
+        let deserialized: GLFTConfig = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(config, deserialized);
+    }
 }
@@ -0,0 +1,770 @@
+//! Stateful grid trading engine.
+//!
+//! [`GridStrategy`] computes a static set of [`GridOrder`]s. [`GridEngine`]
+//! runs a grid over time: it places the initial orders, reacts to fills and
+//! price moves, and reports the orders to add or cancel as
+//! [`GridAction`]s for the execution layer to carry out.
+//!
+//! Grid levels are indexed on a fixed ladder anchored at the start price,
+//! `anchor * (1 + index * grid_spacing)`, so levels keep their identity when
+//! the grid moves. Two kinds of order rest on the ladder:
+//!
+//! - **Entry** orders open a position: buys below the grid center, sells
+//!   above it.
+//! - **Take-profit** orders close it. When an entry fills, the opposite
+//!   order is placed one level away for the filled quantity; when that
+//!   take-profit fills, the round trip's realized PnL is credited to the
+//!   entry level and the entry order is placed again.
+//!
+//! How the center follows the price is set by [`GridMode`]. Moving the
+//! center cancels entry orders that fall outside the window or end up on
+//! the wrong side and fills empty levels with new entries. Take-profit
+//! orders are never cancelled by a move since they carry open inventory.
+//!
+//! Entry sizes are clipped so that the position reached if every resting
+//! order on a side fills stays within `max_position`. Take-profit orders are
+//! always placed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::strategy::grid::{GridConfig, GridStrategy, OrderSide};
+//! use market_maker_rs::strategy::grid_engine::{GridAction, GridEngine, GridMode};
+//! use market_maker_rs::dec;
+//!
+//! let config = GridConfig::new(2, dec!(0.01), dec!(1.0), dec!(10.0)).unwrap();
+//! let mut engine = GridEngine::new(GridStrategy::new(config).unwrap(), GridMode::Static);
+//!
+//! let actions = engine.start(dec!(100.0)).unwrap();
+//! assert_eq!(actions.len(), 4);
+//!
+//! // The buy at 99 fills: a take-profit sell goes up at 100.
+//! let buy = engine
+//!     .open_orders()
+//!     .find(|o| o.order.price == dec!(99.0))
+//!     .unwrap()
+//!     .id;
+//! let actions = engine.on_fill(buy, dec!(1.0), dec!(99.0)).unwrap();
+//! let GridAction::Place(take_profit) = &actions[0] else { panic!() };
+//! assert_eq!(take_profit.order.side, OrderSide::Sell);
+//! assert_eq!(take_profit.order.price, dec!(100.0));
+//!
+//! // The take-profit fills: one round trip of 1.0 at level -1.
+//! engine.on_fill(take_profit.id, dec!(1.0), dec!(100.0)).unwrap();
+//! assert_eq!(engine.realized_pnl(), dec!(1.0));
+//! assert_eq!(engine.level_stats(-1).unwrap().round_trips, 1);
+//! ```
+
+use std::collections::BTreeMap;
+
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::Decimal;
+use crate::strategy::grid::{GridOrder, GridStrategy, OrderSide};
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Smallest entry size worth placing.
+const MIN_ORDER_SIZE: Decimal = Decimal::from_parts(1, 0, 0, false, 8);
+
+/// How the grid center follows the price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GridMode {
+    /// The grid never moves.
+    #[default]
+    Static,
+    /// The grid is rebuilt around the nearest level once the price leaves
+    /// the range covered by the grid.
+    Recenter,
+    /// The center follows the nearest level whenever the price crosses one.
+    Trailing,
+    /// Like [`GridMode::Trailing`] but only upwards: there is no upper
+    /// bound, and on the way down the grid stays put.
+    Infinity,
+}
+
+/// Role of a resting grid order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GridOrderKind {
+    /// Opens a position at its level.
+    Entry,
+    /// Closes a position opened at another level.
+    TakeProfit {
+        /// Price at which the position was opened.
+        entry_price: Decimal,
+        /// Level of the entry order that opened the position.
+        origin: i32,
+    },
+}
+
+/// A grid order tracked by the engine.
+///
+/// `order.level` is the absolute ladder index, not the distance from the
+/// center.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LiveGridOrder {
+    /// Engine-assigned order ID.
+    pub id: u64,
+    /// Price, remaining size, side and level.
+    pub order: GridOrder,
+    /// Entry or take-profit.
+    pub kind: GridOrderKind,
+}
+
+/// Order change requested by the engine.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GridAction {
+    /// Place a new order.
+    Place(LiveGridOrder),
+    /// Cancel a resting order.
+    Cancel(LiveGridOrder),
+}
+
+/// Per-level performance.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridLevelStats {
+    /// Ladder index.
+    pub level: i32,
+    /// Level price.
+    pub price: Decimal,
+    /// Realized PnL of round trips opened at this level, gross of fees.
+    pub realized_pnl: Decimal,
+    /// Completed take-profit orders for entries at this level.
+    pub round_trips: u64,
+    /// Quantity opened at this level and not yet closed.
+    pub open_quantity: Decimal,
+}
+
+/// Stateful grid that consumes fills and price updates.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridEngine {
+    strategy: GridStrategy,
+    mode: GridMode,
+    anchor: Decimal,
+    running: bool,
+    center: i32,
+    orders: BTreeMap<u64, LiveGridOrder>,
+    cancelling: BTreeMap<u64, LiveGridOrder>,
+    levels: BTreeMap<i32, GridLevelStats>,
+    position: Decimal,
+    next_id: u64,
+}
+
+impl GridEngine {
+    /// Creates an engine that has not been started.
+    #[must_use]
+    pub fn new(strategy: GridStrategy, mode: GridMode) -> Self {
+        Self {
+            strategy,
+            mode,
+            anchor: Decimal::ZERO,
+            running: false,
+            center: 0,
+            orders: BTreeMap::new(),
+            cancelling: BTreeMap::new(),
+            levels: BTreeMap::new(),
+            position: Decimal::ZERO,
+            next_id: 1,
+        }
+    }
+
+    /// Returns the underlying grid strategy.
+    #[must_use]
+    pub fn strategy(&self) -> &GridStrategy {
+        &self.strategy
+    }
+
+    /// Returns the grid mode.
+    #[must_use]
+    pub fn mode(&self) -> GridMode {
+        self.mode
+    }
+
+    /// Returns true once [`GridEngine::start`] has been called and the grid
+    /// has not been stopped.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Returns the ladder index of the grid center.
+    #[must_use]
+    pub fn center_level(&self) -> i32 {
+        self.center
+    }
+
+    /// Returns the price of the grid center, if running.
+    #[must_use]
+    pub fn center_price(&self) -> Option<Decimal> {
+        self.running.then(|| self.level_price(self.center))
+    }
+
+    /// Returns the lowest and highest entry level prices, if running.
+    #[must_use]
+    pub fn price_range(&self) -> Option<(Decimal, Decimal)> {
+        let levels = self.levels_per_side();
+        self.running.then(|| {
+            (
+                self.level_price(self.center - levels),
+                self.level_price(self.center + levels),
+            )
+        })
+    }
+
+    /// Returns the net position filled through the grid.
+    #[must_use]
+    pub fn position(&self) -> Decimal {
+        self.position
+    }
+
+    /// Returns the realized PnL across all levels, gross of fees.
+    #[must_use]
+    pub fn realized_pnl(&self) -> Decimal {
+        self.levels.values().map(|l| l.realized_pnl).sum()
+    }
+
+    /// Returns the statistics for a ladder level, if it has traded.
+    #[must_use]
+    pub fn level_stats(&self, level: i32) -> Option<&GridLevelStats> {
+        self.levels.get(&level)
+    }
+
+    /// Returns the statistics of every level that has traded, lowest first.
+    pub fn levels(&self) -> impl Iterator<Item = &GridLevelStats> {
+        self.levels.values()
+    }
+
+    /// Returns the resting orders, by ID.
+    pub fn open_orders(&self) -> impl Iterator<Item = &LiveGridOrder> {
+        self.orders.values()
+    }
+
+    /// Returns orders the engine has cancelled but that may still fill.
+    pub fn pending_cancels(&self) -> impl Iterator<Item = &LiveGridOrder> {
+        self.cancelling.values()
+    }
+
+    /// Starts the grid around `reference_price` and returns the initial
+    /// orders.
+    ///
+    /// The first start anchors the ladder at `reference_price`; a restart
+    /// after [`GridEngine::stop`] keeps the ladder and centers on the
+    /// nearest level.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `reference_price` is not
+    /// positive and `MMError::InvalidMarketState` if the grid is already
+    /// running.
+    pub fn start(&mut self, reference_price: Decimal) -> MMResult<Vec<GridAction>> {
+        if reference_price <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "reference_price must be positive".to_string(),
+            ));
+        }
+        if self.is_running() {
+            return Err(MMError::InvalidMarketState(
+                "grid is already running".to_string(),
+            ));
+        }
+        if self.anchor.is_zero() {
+            self.anchor = reference_price;
+        }
+        self.running = true;
+        self.center = self.nearest_level(reference_price).ok_or_else(|| {
+            MMError::InvalidConfiguration(format!(
+                "reference_price {reference_price} is too far from the grid"
+            ))
+        })?;
+        self.strategy
+            .update_reference_price(self.level_price(self.center));
+        Ok(self.rebuild())
+    }
+
+    /// Cancels every resting order and stops the grid.
+    ///
+    /// The position and level statistics are kept; cancelled orders stay in
+    /// [`GridEngine::pending_cancels`] until confirmed.
+    pub fn stop(&mut self) -> Vec<GridAction> {
+        let ids: Vec<u64> = self.orders.keys().copied().collect();
+        let actions = ids.into_iter().filter_map(|id| self.cancel(id)).collect();
+        self.running = false;
+        actions
+    }
+
+    /// Moves the grid center according to the [`GridMode`].
+    ///
+    /// Returns no actions while the grid is not running or the center does
+    /// not move.
+    pub fn on_price(&mut self, price: Decimal) -> Vec<GridAction> {
+        if !self.running || price <= Decimal::ZERO {
+            return Vec::new();
+        }
+        let Some(nearest) = self.nearest_level(price) else {
+            return Vec::new();
+        };
+        let next = match self.mode {
+            GridMode::Static => return Vec::new(),
+            GridMode::Recenter => {
+                let levels = self.levels_per_side();
+                if (self.center - levels..=self.center + levels).contains(&nearest) {
+                    return Vec::new();
+                }
+                nearest
+            }
+            GridMode::Trailing => nearest,
+            GridMode::Infinity => nearest.max(self.center),
+        };
+        if next == self.center {
+            return Vec::new();
+        }
+        self.center = next;
+        self.strategy
+            .update_reference_price(self.level_price(self.center));
+        self.rebuild()
+    }
+
+    /// Applies a fill and returns the resulting take-profit or re-entry
+    /// order.
+    ///
+    /// Fills for orders awaiting cancel confirmation are accepted. Once the
+    /// grid is stopped, fills still update the position and level
+    /// statistics but place no new orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidPositionUpdate` if the order is unknown or
+    /// `quantity` is not positive or exceeds the remaining size.
+    pub fn on_fill(
+        &mut self,
+        order_id: u64,
+        quantity: Decimal,
+        price: Decimal,
+    ) -> MMResult<Vec<GridAction>> {
+        let Some(live) = self
+            .orders
+            .get_mut(&order_id)
+            .or_else(|| self.cancelling.get_mut(&order_id))
+        else {
+            return Err(MMError::InvalidPositionUpdate(format!(
+                "unknown grid order: {order_id}"
+            )));
+        };
+        if quantity <= Decimal::ZERO || quantity > live.order.size {
+            return Err(MMError::InvalidPositionUpdate(format!(
+                "fill quantity {quantity} invalid for remaining size {}",
+                live.order.size
+            )));
+        }
+        live.order.size -= quantity;
+        let filled = live.clone();
+        if filled.order.size.is_zero() {
+            self.orders.remove(&order_id);
+            self.cancelling.remove(&order_id);
+        }
+
+        let side = filled.order.side;
+        self.position += match side {
+            OrderSide::Buy => quantity,
+            OrderSide::Sell => -quantity,
+        };
+
+        let mut actions = Vec::new();
+        match filled.kind {
+            GridOrderKind::Entry => {
+                let level = filled.order.level;
+                self.stats_mut(level).open_quantity += quantity;
+                let (target, tp_side) = match side {
+                    OrderSide::Buy => (level + 1, OrderSide::Sell),
+                    OrderSide::Sell => (level - 1, OrderSide::Buy),
+                };
+                let tp_price = self.level_price(target);
+                if self.running && tp_price > Decimal::ZERO {
+                    let kind = GridOrderKind::TakeProfit {
+                        entry_price: price,
+                        origin: level,
+                    };
+                    actions.push(self.place(target, tp_side, quantity, kind));
+                }
+            }
+            GridOrderKind::TakeProfit {
+                entry_price,
+                origin,
+            } => {
+                let pnl = match side {
+                    OrderSide::Sell => (price - entry_price) * quantity,
+                    OrderSide::Buy => (entry_price - price) * quantity,
+                };
+                let stats = self.stats_mut(origin);
+                stats.realized_pnl += pnl;
+                stats.open_quantity -= quantity;
+                if filled.order.size.is_zero() {
+                    stats.round_trips += 1;
+                }
+
+                let entry_side = match side {
+                    OrderSide::Sell => OrderSide::Buy,
+                    OrderSide::Buy => OrderSide::Sell,
+                };
+                // A rebuild after the grid moved may already have refilled
+                // the level.
+                if self.running
+                    && self.wants_entry(origin, entry_side)
+                    && !self.orders.values().any(|o| o.order.level == origin)
+                {
+                    let size = quantity.min(self.headroom(entry_side));
+                    if size >= MIN_ORDER_SIZE {
+                        actions.push(self.place(origin, entry_side, size, GridOrderKind::Entry));
+                    }
+                }
+            }
+        }
+        Ok(actions)
+    }
+
+    /// Forgets a cancelled order once the venue has confirmed the cancel.
+    ///
+    /// Returns the order, or `None` if it was not awaiting confirmation.
+    pub fn confirm_cancel(&mut self, order_id: u64) -> Option<LiveGridOrder> {
+        self.cancelling.remove(&order_id)
+    }
+
+    /// Cancels misplaced entries and places entries on empty levels.
+    ///
+    /// Levels closest to the center are filled first so they get priority
+    /// for the position headroom.
+    fn rebuild(&mut self) -> Vec<GridAction> {
+        let stale: Vec<u64> = self
+            .orders
+            .values()
+            .filter(|o| {
+                o.kind == GridOrderKind::Entry && !self.wants_entry(o.order.level, o.order.side)
+            })
+            .map(|o| o.id)
+            .collect();
+        let mut actions: Vec<GridAction> =
+            stale.into_iter().filter_map(|id| self.cancel(id)).collect();
+
+        for distance in 1..=self.levels_per_side() {
+            for (level, side) in [
+                (self.center - distance, OrderSide::Buy),
+                (self.center + distance, OrderSide::Sell),
+            ] {
+                if self.level_price(level) <= Decimal::ZERO
+                    || self.orders.values().any(|o| o.order.level == level)
+                {
+                    continue;
+                }
+                let size = self
+                    .strategy
+                    .calculate_level_size(distance)
+                    .min(self.headroom(side));
+                if size >= MIN_ORDER_SIZE {
+                    actions.push(self.place(level, side, size, GridOrderKind::Entry));
+                }
+            }
+        }
+        actions
+    }
+
+    /// Returns true if an entry on `side` belongs at `level`.
+    fn wants_entry(&self, level: i32, side: OrderSide) -> bool {
+        let levels = self.levels_per_side();
+        match side {
+            OrderSide::Buy => level < self.center && level >= self.center - levels,
+            OrderSide::Sell => level > self.center && level <= self.center + levels,
+        }
+    }
+
+    /// Size that can still be added on `side` without the worst-case
+    /// position exceeding `max_position`.
+    fn headroom(&self, side: OrderSide) -> Decimal {
+        let resting: Decimal = self
+            .orders
+            .values()
+            .chain(self.cancelling.values())
+            .filter(|o| o.order.side == side)
+            .map(|o| o.order.size)
+            .sum();
+        let exposure = match side {
+            OrderSide::Buy => self.position + resting,
+            OrderSide::Sell => resting - self.position,
+        };
+        (self.strategy.config().max_position - exposure).max(Decimal::ZERO)
+    }
+
+    fn place(
+        &mut self,
+        level: i32,
+        side: OrderSide,
+        size: Decimal,
+        kind: GridOrderKind,
+    ) -> GridAction {
+        let live = LiveGridOrder {
+            id: self.next_id,
+            order: GridOrder::new(self.level_price(level), size, side, level),
+            kind,
+        };
+        self.next_id += 1;
+        self.orders.insert(live.id, live.clone());
+        GridAction::Place(live)
+    }
+
+    fn cancel(&mut self, order_id: u64) -> Option<GridAction> {
+        let live = self.orders.remove(&order_id)?;
+        self.cancelling.insert(order_id, live.clone());
+        Some(GridAction::Cancel(live))
+    }
+
+    fn stats_mut(&mut self, level: i32) -> &mut GridLevelStats {
+        let price = self.level_price(level);
+        self.levels.entry(level).or_insert_with(|| GridLevelStats {
+            level,
+            price,
+            realized_pnl: Decimal::ZERO,
+            round_trips: 0,
+            open_quantity: Decimal::ZERO,
+        })
+    }
+
+    fn level_price(&self, level: i32) -> Decimal {
+        self.strategy.calculate_price(self.anchor, level)
+    }
+
+    /// Ladder index closest to `price`.
+    fn nearest_level(&self, price: Decimal) -> Option<i32> {
+        ((price / self.anchor - Decimal::ONE) / self.strategy.config().grid_spacing)
+            .round()
+            .to_i32()
+    }
+
+    fn levels_per_side(&self) -> i32 {
+        i32::try_from(self.strategy.config().levels_per_side).unwrap_or(i32::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+    use crate::strategy::grid::GridConfig;
+
+    fn engine(levels: u32, max_position: Decimal, mode: GridMode) -> GridEngine {
+        let config = GridConfig::new(levels, dec!(0.01), dec!(1.0), max_position).unwrap();
+        GridEngine::new(GridStrategy::new(config).unwrap(), mode)
+    }
+
+    fn order_at(engine: &GridEngine, level: i32) -> LiveGridOrder {
+        engine
+            .open_orders()
+            .find(|o| o.order.level == level)
+            .cloned()
+            .unwrap()
+    }
+
+    fn placed(actions: &[GridAction]) -> Vec<(i32, OrderSide)> {
+        actions
+            .iter()
+            .filter_map(|a| match a {
+                GridAction::Place(o) => Some((o.order.level, o.order.side)),
+                GridAction::Cancel(_) => None,
+            })
+            .collect()
+    }
+
+    fn cancelled(actions: &[GridAction]) -> Vec<i32> {
+        actions
+            .iter()
+            .filter_map(|a| match a {
+                GridAction::Cancel(o) => Some(o.order.level),
+                GridAction::Place(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_start_places_full_grid() {
+        let mut engine = engine(3, dec!(100.0), GridMode::Static);
+        let actions = engine.start(dec!(100.0)).unwrap();
+        assert_eq!(actions.len(), 6);
+        assert_eq!(engine.price_range(), Some((dec!(97.0), dec!(103.0))));
+        assert!(
+            engine
+                .open_orders()
+                .all(|o| (o.order.side == OrderSide::Buy) == (o.order.level < 0))
+        );
+        assert!(engine.start(dec!(100.0)).is_err());
+        assert!(engine.on_price(dec!(110.0)).is_empty());
+    }
+
+    #[test]
+    fn test_round_trip_and_re_entry() {
+        let mut engine = engine(2, dec!(100.0), GridMode::Static);
+        engine.start(dec!(100.0)).unwrap();
+
+        let buy = order_at(&engine, -1);
+        let actions = engine.on_fill(buy.id, dec!(1.0), dec!(99.0)).unwrap();
+        assert_eq!(placed(&actions), vec![(0, OrderSide::Sell)]);
+        assert_eq!(engine.position(), dec!(1.0));
+        assert_eq!(engine.level_stats(-1).unwrap().open_quantity, dec!(1.0));
+
+        let take_profit = order_at(&engine, 0);
+        let actions = engine
+            .on_fill(take_profit.id, dec!(1.0), dec!(100.0))
+            .unwrap();
+        assert_eq!(placed(&actions), vec![(-1, OrderSide::Buy)]);
+        assert_eq!(engine.position(), Decimal::ZERO);
+        let stats = engine.level_stats(-1).unwrap();
+        assert_eq!(stats.realized_pnl, dec!(1.0));
+        assert_eq!(stats.round_trips, 1);
+        assert_eq!(stats.open_quantity, Decimal::ZERO);
+
+        // Short side: sell at 101, buy back at 100.
+        let sell = order_at(&engine, 1);
+        engine.on_fill(sell.id, dec!(1.0), dec!(101.0)).unwrap();
+        let take_profit = order_at(&engine, 0);
+        assert_eq!(take_profit.order.side, OrderSide::Buy);
+        engine
+            .on_fill(take_profit.id, dec!(1.0), dec!(100.0))
+            .unwrap();
+        assert_eq!(engine.realized_pnl(), dec!(2.0));
+    }
+
+    #[test]
+    fn test_partial_fills() {
+        let mut engine = engine(2, dec!(100.0), GridMode::Static);
+        engine.start(dec!(100.0)).unwrap();
+        let buy = order_at(&engine, -1);
+
+        engine.on_fill(buy.id, dec!(0.4), dec!(99.0)).unwrap();
+        assert_eq!(order_at(&engine, -1).order.size, dec!(0.6));
+        assert!(engine.on_fill(buy.id, dec!(1.0), dec!(99.0)).is_err());
+        engine.on_fill(buy.id, dec!(0.6), dec!(99.0)).unwrap();
+
+        let take_profits: Vec<_> = engine
+            .open_orders()
+            .filter(|o| o.order.level == 0)
+            .map(|o| o.order.size)
+            .collect();
+        assert_eq!(take_profits, vec![dec!(0.4), dec!(0.6)]);
+        assert!(engine.on_fill(999, dec!(1.0), dec!(99.0)).is_err());
+    }
+
+    #[test]
+    fn test_max_position_clips_entries() {
+        let mut engine = engine(3, dec!(2.5), GridMode::Static);
+        engine.start(dec!(100.0)).unwrap();
+        let buys: Vec<Decimal> = engine
+            .open_orders()
+            .filter(|o| o.order.side == OrderSide::Buy)
+            .map(|o| o.order.size)
+            .collect();
+        assert_eq!(buys.iter().copied().sum::<Decimal>(), dec!(2.5));
+        assert_eq!(order_at(&engine, -3).order.size, dec!(0.5));
+    }
+
+    #[test]
+    fn test_recenter_keeps_take_profits() {
+        let mut engine = engine(2, dec!(100.0), GridMode::Recenter);
+        engine.start(dec!(100.0)).unwrap();
+        let buy = order_at(&engine, -1);
+        engine.on_fill(buy.id, dec!(1.0), dec!(99.0)).unwrap();
+
+        assert!(engine.on_price(dec!(101.5)).is_empty());
+        let actions = engine.on_price(dec!(105.0));
+        assert_eq!(engine.center_level(), 5);
+        // Entries left behind are cancelled, the take-profit at 0 stays.
+        let mut gone = cancelled(&actions);
+        gone.sort_unstable();
+        assert_eq!(gone, vec![-2, 1, 2]);
+        assert_eq!(order_at(&engine, 0).order.side, OrderSide::Sell);
+        assert_eq!(
+            placed(&actions),
+            vec![
+                (4, OrderSide::Buy),
+                (6, OrderSide::Sell),
+                (3, OrderSide::Buy),
+                (7, OrderSide::Sell),
+            ]
+        );
+
+        // A cancelled order that fills before the cancel lands still counts.
+        let stale = engine.pending_cancels().next().unwrap().clone();
+        engine
+            .on_fill(stale.id, stale.order.size, stale.order.price)
+            .unwrap();
+        assert!(engine.confirm_cancel(stale.id).is_none());
+    }
+
+    #[test]
+    fn test_take_profit_does_not_re_enter_refilled_level() {
+        let mut engine = engine(2, dec!(100.0), GridMode::Trailing);
+        engine.start(dec!(100.0)).unwrap();
+        let buy = order_at(&engine, -1);
+        engine.on_fill(buy.id, dec!(1.0), dec!(99.0)).unwrap();
+        let take_profit = order_at(&engine, 0);
+
+        // Trailing up refills the vacated level -1 with a fresh entry.
+        let actions = engine.on_price(dec!(101.0));
+        assert!(placed(&actions).contains(&(-1, OrderSide::Buy)));
+
+        let actions = engine
+            .on_fill(take_profit.id, dec!(1.0), dec!(100.0))
+            .unwrap();
+        assert!(placed(&actions).is_empty());
+        assert_eq!(
+            engine.open_orders().filter(|o| o.order.level == -1).count(),
+            1
+        );
+        assert_eq!(engine.level_stats(-1).unwrap().round_trips, 1);
+    }
+
+    #[test]
+    fn test_trailing_and_infinity() {
+        let mut trailing = engine(2, dec!(100.0), GridMode::Trailing);
+        trailing.start(dec!(100.0)).unwrap();
+        let actions = trailing.on_price(dec!(101.0));
+        assert_eq!(trailing.center_level(), 1);
+        assert_eq!(cancelled(&actions), vec![1, -2]);
+        assert_eq!(
+            placed(&actions),
+            vec![(0, OrderSide::Buy), (3, OrderSide::Sell)]
+        );
+        trailing.on_price(dec!(99.0));
+        assert_eq!(trailing.center_level(), -1);
+
+        let mut infinity = engine(2, dec!(100.0), GridMode::Infinity);
+        infinity.start(dec!(100.0)).unwrap();
+        infinity.on_price(dec!(102.0));
+        assert_eq!(infinity.center_price(), Some(dec!(102.0)));
+        assert!(infinity.on_price(dec!(95.0)).is_empty());
+        assert_eq!(infinity.center_level(), 2);
+
+        let actions = infinity.stop();
+        assert_eq!(actions.len(), 4);
+        assert!(!infinity.is_running());
+        assert_eq!(infinity.open_orders().count(), 0);
+    }
+
+    #[test]
+    fn test_fill_after_stop_places_nothing() {
+        let mut engine = engine(2, dec!(100.0), GridMode::Static);
+        engine.start(dec!(100.0)).unwrap();
+        let entry = order_at(&engine, -1);
+        engine.stop();
+
+        let actions = engine.on_fill(entry.id, dec!(1.0), dec!(99.0)).unwrap();
+        assert!(actions.is_empty());
+        assert_eq!(engine.position(), dec!(1.0));
+        assert_eq!(engine.level_stats(-1).unwrap().open_quantity, dec!(1.0));
+        assert_eq!(engine.open_orders().count(), 0);
+    }
+}
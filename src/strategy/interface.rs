@@ -23,8 +23,14 @@
 //!
 //! assert!(bid < ask);
 //! ```
+//!
+//! Both traits also provide the infinite-horizon formulation for perpetual
+//! markets, and [`AvellanedaStoikov::calculate_reservation_and_spread`] selects
+//! between the two from [`StrategyConfig::horizon`].
 
 use crate::Decimal;
+use crate::strategy::avellaneda_stoikov;
+use crate::strategy::config::{StrategyConfig, TimeHorizon};
 use crate::types::error::MMResult;
 use async_trait::async_trait;
 
@@ -169,6 +175,144 @@ pub trait AvellanedaStoikov {
         time_to_terminal_ms: u64,
         order_intensity: Decimal,
     ) -> MMResult<(Decimal, Decimal)>;
+
+    /// Calculates the stationary reservation price for an infinite horizon.
+    ///
+    /// Defaults to
+    /// [`avellaneda_stoikov::calculate_infinite_horizon_reservation_price`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parameters are invalid or the inventory is outside
+    /// the range allowed by `discount_rate`.
+    fn calculate_infinite_horizon_reservation_price(
+        &self,
+        mid_price: Decimal,
+        inventory: Decimal,
+        risk_aversion: Decimal,
+        volatility: Decimal,
+        discount_rate: Decimal,
+    ) -> MMResult<Decimal> {
+        avellaneda_stoikov::calculate_infinite_horizon_reservation_price(
+            mid_price,
+            inventory,
+            risk_aversion,
+            volatility,
+            discount_rate,
+        )
+    }
+
+    /// Calculates the stationary optimal spread for an infinite horizon.
+    ///
+    /// Defaults to [`avellaneda_stoikov::calculate_infinite_horizon_spread`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parameters are invalid or the inventory is outside
+    /// the range allowed by `discount_rate`.
+    fn calculate_infinite_horizon_spread(
+        &self,
+        inventory: Decimal,
+        risk_aversion: Decimal,
+        volatility: Decimal,
+        discount_rate: Decimal,
+        order_intensity: Decimal,
+    ) -> MMResult<Decimal> {
+        avellaneda_stoikov::calculate_infinite_horizon_spread(
+            inventory,
+            risk_aversion,
+            volatility,
+            discount_rate,
+            order_intensity,
+        )
+    }
+
+    /// Calculates optimal bid and ask prices for an infinite horizon.
+    ///
+    /// Defaults to [`avellaneda_stoikov::calculate_infinite_horizon_quotes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from underlying calculations if inputs are invalid.
+    fn calculate_infinite_horizon_quotes(
+        &self,
+        mid_price: Decimal,
+        inventory: Decimal,
+        risk_aversion: Decimal,
+        volatility: Decimal,
+        discount_rate: Decimal,
+        order_intensity: Decimal,
+    ) -> MMResult<(Decimal, Decimal)> {
+        avellaneda_stoikov::calculate_infinite_horizon_quotes(
+            mid_price,
+            inventory,
+            risk_aversion,
+            volatility,
+            discount_rate,
+            order_intensity,
+        )
+    }
+
+    /// Calculates the reservation price and spread for the horizon selected in
+    /// `config`.
+    ///
+    /// With [`TimeHorizon::Finite`] the time to terminal is derived from
+    /// `config.terminal_time` and `timestamp`; with [`TimeHorizon::Infinite`]
+    /// the stationary formulas are used and `timestamp` is ignored. The spread
+    /// is not floored at `config.min_spread`.
+    ///
+    /// # Returns
+    ///
+    /// A tuple `(reservation_price, spread)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from underlying calculations if inputs are invalid.
+    fn calculate_reservation_and_spread(
+        &self,
+        config: &StrategyConfig,
+        mid_price: Decimal,
+        inventory: Decimal,
+        volatility: Decimal,
+        timestamp: u64,
+    ) -> MMResult<(Decimal, Decimal)> {
+        match config.horizon {
+            TimeHorizon::Finite => {
+                let time_to_terminal_ms = config.terminal_time.saturating_sub(timestamp);
+                let reservation = self.calculate_reservation_price(
+                    mid_price,
+                    inventory,
+                    config.risk_aversion,
+                    volatility,
+                    time_to_terminal_ms,
+                )?;
+                let spread = self.calculate_optimal_spread(
+                    config.risk_aversion,
+                    volatility,
+                    time_to_terminal_ms,
+                    config.order_intensity,
+                )?;
+                Ok((reservation, spread))
+            }
+            TimeHorizon::Infinite { discount_rate } => {
+                let reservation = self.calculate_infinite_horizon_reservation_price(
+                    mid_price,
+                    inventory,
+                    config.risk_aversion,
+                    volatility,
+                    discount_rate,
+                )?;
+                let spread = self.calculate_infinite_horizon_spread(
+                    inventory,
+                    config.risk_aversion,
+                    volatility,
+                    discount_rate,
+                    config.order_intensity,
+                )?;
+                Ok((reservation, spread))
+            }
+        }
+    }
 }
 
 /// Trait for implementing the Avellaneda-Stoikov strategy with async operations.
@@ -244,6 +388,126 @@ pub trait AsyncAvellanedaStoikov {
         time_to_terminal_ms: u64,
         order_intensity: Decimal,
     ) -> MMResult<(Decimal, Decimal)>;
+
+    /// Asynchronously calculates the infinite-horizon reservation price.
+    ///
+    /// See `AvellanedaStoikov::calculate_infinite_horizon_reservation_price` for details.
+    async fn calculate_infinite_horizon_reservation_price(
+        &self,
+        mid_price: Decimal,
+        inventory: Decimal,
+        risk_aversion: Decimal,
+        volatility: Decimal,
+        discount_rate: Decimal,
+    ) -> MMResult<Decimal> {
+        avellaneda_stoikov::calculate_infinite_horizon_reservation_price(
+            mid_price,
+            inventory,
+            risk_aversion,
+            volatility,
+            discount_rate,
+        )
+    }
+
+    /// Asynchronously calculates the infinite-horizon spread.
+    ///
+    /// See `AvellanedaStoikov::calculate_infinite_horizon_spread` for details.
+    async fn calculate_infinite_horizon_spread(
+        &self,
+        inventory: Decimal,
+        risk_aversion: Decimal,
+        volatility: Decimal,
+        discount_rate: Decimal,
+        order_intensity: Decimal,
+    ) -> MMResult<Decimal> {
+        avellaneda_stoikov::calculate_infinite_horizon_spread(
+            inventory,
+            risk_aversion,
+            volatility,
+            discount_rate,
+            order_intensity,
+        )
+    }
+
+    /// Asynchronously calculates infinite-horizon bid and ask prices.
+    ///
+    /// See `AvellanedaStoikov::calculate_infinite_horizon_quotes` for details.
+    async fn calculate_infinite_horizon_quotes(
+        &self,
+        mid_price: Decimal,
+        inventory: Decimal,
+        risk_aversion: Decimal,
+        volatility: Decimal,
+        discount_rate: Decimal,
+        order_intensity: Decimal,
+    ) -> MMResult<(Decimal, Decimal)> {
+        avellaneda_stoikov::calculate_infinite_horizon_quotes(
+            mid_price,
+            inventory,
+            risk_aversion,
+            volatility,
+            discount_rate,
+            order_intensity,
+        )
+    }
+
+    /// Asynchronously calculates the reservation price and spread for the
+    /// horizon selected in `config`.
+    ///
+    /// See `AvellanedaStoikov::calculate_reservation_and_spread` for details.
+    async fn calculate_reservation_and_spread(
+        &self,
+        config: &StrategyConfig,
+        mid_price: Decimal,
+        inventory: Decimal,
+        volatility: Decimal,
+        timestamp: u64,
+    ) -> MMResult<(Decimal, Decimal)> {
+        match config.horizon {
+            TimeHorizon::Finite => {
+                let time_to_terminal_ms = config.terminal_time.saturating_sub(timestamp);
+                let reservation = self
+                    .calculate_reservation_price(
+                        mid_price,
+                        inventory,
+                        config.risk_aversion,
+                        volatility,
+                        time_to_terminal_ms,
+                    )
+                    .await?;
+                let spread = self
+                    .calculate_optimal_spread(
+                        config.risk_aversion,
+                        volatility,
+                        time_to_terminal_ms,
+                        config.order_intensity,
+                    )
+                    .await?;
+                Ok((reservation, spread))
+            }
+            TimeHorizon::Infinite { discount_rate } => {
+                let reservation = self
+                    .calculate_infinite_horizon_reservation_price(
+                        mid_price,
+                        inventory,
+                        config.risk_aversion,
+                        volatility,
+                        discount_rate,
+                    )
+                    .await?;
+                let spread = self
+                    .calculate_infinite_horizon_spread(
+                        inventory,
+                        config.risk_aversion,
+                        volatility,
+                        discount_rate,
+                        config.order_intensity,
+                    )
+                    .await?;
+                Ok((reservation, spread))
+            }
+        }
+    }
 }
 
 /// Default implementation of the Avellaneda-Stoikov strategy.
@@ -486,4 +750,102 @@ mod tests {
         assert!(bid < dec!(100.0));
         assert!(ask > dec!(100.0));
     }
+
+    #[test]
+    fn test_reservation_and_spread_finite_matches_model() {
+        let strategy = DefaultAvellanedaStoikov;
+        let config = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+
+        let (reservation, spread) = AvellanedaStoikov::calculate_reservation_and_spread(
+            &strategy,
+            &config,
+            dec!(100.0),
+            dec!(5.0),
+            dec!(0.2),
+            0,
+        )
+        .unwrap();
+
+        let expected_reservation = avellaneda_stoikov::calculate_reservation_price(
+            dec!(100.0),
+            dec!(5.0),
+            dec!(0.1),
+            dec!(0.2),
+            3_600_000,
+        )
+        .unwrap();
+        let expected_spread = avellaneda_stoikov::calculate_optimal_spread(
+            dec!(0.1),
+            dec!(0.2),
+            3_600_000,
+            dec!(1.5),
+        )
+        .unwrap();
+
+        assert_eq!(reservation, expected_reservation);
+        assert_eq!(spread, expected_spread);
+    }
+
+    #[test]
+    fn test_reservation_and_spread_infinite_ignores_timestamp() {
+        let strategy = DefaultAvellanedaStoikov;
+        let config = StrategyConfig::new(dec!(0.1), dec!(1.5), 0, dec!(0.01))
+            .unwrap()
+            .with_infinite_horizon(dec!(0.05))
+            .unwrap();
+
+        let early = AvellanedaStoikov::calculate_reservation_and_spread(
+            &strategy,
+            &config,
+            dec!(100.0),
+            dec!(5.0),
+            dec!(0.2),
+            0,
+        )
+        .unwrap();
+        let late = AvellanedaStoikov::calculate_reservation_and_spread(
+            &strategy,
+            &config,
+            dec!(100.0),
+            dec!(5.0),
+            dec!(0.2),
+            u64::MAX,
+        )
+        .unwrap();
+
+        assert_eq!(early, late);
+        assert!(early.0 < dec!(100.0));
+        assert!(early.1 > Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_infinite_horizon_async_matches_sync() {
+        let strategy = DefaultAvellanedaStoikov;
+        let config = StrategyConfig::new(dec!(0.1), dec!(1.5), 0, dec!(0.01))
+            .unwrap()
+            .with_infinite_horizon(dec!(0.05))
+            .unwrap();
+
+        let sync = AvellanedaStoikov::calculate_reservation_and_spread(
+            &strategy,
+            &config,
+            dec!(100.0),
+            dec!(-3.0),
+            dec!(0.2),
+            0,
+        )
+        .unwrap();
+        let async_result = AsyncAvellanedaStoikov::calculate_reservation_and_spread(
+            &strategy,
+            &config,
+            dec!(100.0),
+            dec!(-3.0),
+            dec!(0.2),
+            0,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(sync, async_result);
+    }
 }
@@ -0,0 +1,1190 @@
+//! Multi-level quote ladders for the Avellaneda-Stoikov and GLFT models.
+//!
+//! The closed-form models produce a single optimal bid/ask pair around the
+//! reservation price. A ladder extends that pair outwards to `N` levels per
+//! side, sizing each level from its fill intensity and the remaining room
+//! under the inventory limit.
+//!
+//! # Level Prices
+//!
+//! ```text
+//! bid_i = r - δ*/2 - i * spacing
+//! ask_i = r + δ*/2 + i * spacing        for i = 0..N-1
+//! ```
+//!
+//! Where `r` is the reservation price and `δ*` the optimal spread of the model.
+//!
+//! # Level Sizes
+//!
+//! The intensity weight of level `i` is its fill intensity relative to the
+//! first level on the same side:
+//!
+//! ```text
+//! w_i = λ(d_i) / λ(d_0) = exp(-k * (d_i - d_0))
+//! ```
+//!
+//! where `d_i` is the distance of the level from mid. When an
+//! [`OrderIntensityEstimator`] with a current estimate is supplied, the ratio
+//! of its fill probabilities over the configured horizon is used instead.
+//! Sizes are then capped so that the cumulative size on each side never takes
+//! the position beyond `max_inventory`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::strategy::config::StrategyConfig;
+//! use market_maker_rs::strategy::ladder::{LadderConfig, QuoteLadderGenerator};
+//! use market_maker_rs::strategy::quoting::QuoteContext;
+//! use market_maker_rs::dec;
+//!
+//! let config = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+//! let generator = QuoteLadderGenerator::new(
+//!     LadderConfig::new(3, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap(),
+//! );
+//!
+//! let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0);
+//! let ladder = generator.avellaneda_stoikov(&context, &config, None).unwrap();
+//!
+//! assert_eq!(ladder.bids.len(), 3);
+//! assert_eq!(ladder.asks.len(), 3);
+//! assert!(ladder.bids[0].price > ladder.bids[1].price);
+//! ```
+
+use crate::Decimal;
+use crate::analytics::intensity::OrderIntensityEstimator;
+use crate::execution::{ManagedOrder, OrderId, OrderRequest, Side};
+use crate::strategy::config::StrategyConfig;
+use crate::strategy::glft::{GLFTConfig, GLFTStrategy};
+use crate::strategy::interface::{AvellanedaStoikov, DefaultAvellanedaStoikov};
+use crate::strategy::quoting::{QuoteContext, QuoteLevel, QuoteSet, QuotingStrategy};
+use crate::types::decimal::decimal_exp;
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Distance between consecutive ladder levels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LadderSpacing {
+    /// Fixed price distance between levels.
+    Fixed(Decimal),
+    /// Distance as a multiple of the model's optimal half-spread.
+    HalfSpreadMultiple(Decimal),
+}
+
+/// How level sizes are derived from the intensity weight `w_i`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LadderSizing {
+    /// Every level gets `base_size`.
+    Flat,
+    /// `base_size * w_i`: more size where fills are likely.
+    #[default]
+    FillIntensity,
+    /// `base_size / w_i`, capped at `max_multiplier * base_size`: equalizes
+    /// the expected filled quantity across levels.
+    ExpectedFillParity {
+        /// Maximum size multiplier relative to `base_size`.
+        max_multiplier: Decimal,
+    },
+}
+
+/// Configuration for ladder generation.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LadderConfig {
+    /// Number of levels on each side.
+    pub levels_per_side: u32,
+
+    /// Distance between consecutive levels.
+    pub spacing: LadderSpacing,
+
+    /// Size of the first level on each side.
+    pub base_size: Decimal,
+
+    /// Maximum absolute inventory.
+    pub max_inventory: Decimal,
+
+    /// Sizing mode.
+    pub sizing: LadderSizing,
+
+    /// Minimum level size; smaller levels are dropped.
+    pub min_size: Decimal,
+
+    /// Optional tick size. Bids are rounded down and asks up to the tick.
+    pub tick_size: Option<Decimal>,
+
+    /// Horizon used for fill probabilities from an intensity estimator, in milliseconds.
+    pub fill_horizon_ms: u64,
+}
+
+impl LadderConfig {
+    /// Creates a new `LadderConfig` with fixed level spacing.
+    ///
+    /// # Arguments
+    ///
+    /// * `levels_per_side` - Number of levels per side, must be > 0
+    /// * `level_spacing` - Price distance between levels, must be positive
+    /// * `base_size` - Size of the first level, must be positive
+    /// * `max_inventory` - Maximum absolute inventory, must be positive
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if parameters are invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use market_maker_rs::strategy::ladder::LadderConfig;
+    /// use market_maker_rs::dec;
+    ///
+    /// let config = LadderConfig::new(5, dec!(0.01), dec!(1.0), dec!(20.0)).unwrap();
+    /// assert_eq!(config.levels_per_side, 5);
+    /// ```
+    pub fn new(
+        levels_per_side: u32,
+        level_spacing: Decimal,
+        base_size: Decimal,
+        max_inventory: Decimal,
+    ) -> MMResult<Self> {
+        if levels_per_side == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "levels_per_side must be greater than 0".to_string(),
+            ));
+        }
+
+        if level_spacing <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "level_spacing must be positive".to_string(),
+            ));
+        }
+
+        if base_size <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "base_size must be positive".to_string(),
+            ));
+        }
+
+        if max_inventory <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "max_inventory must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            levels_per_side,
+            spacing: LadderSpacing::Fixed(level_spacing),
+            base_size,
+            max_inventory,
+            sizing: LadderSizing::default(),
+            min_size: Decimal::ZERO,
+            tick_size: None,
+            fill_horizon_ms: 1_000,
+        })
+    }
+
+    /// Sets the level spacing.
+    #[must_use]
+    pub fn with_spacing(mut self, spacing: LadderSpacing) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets the sizing mode.
+    #[must_use]
+    pub fn with_sizing(mut self, sizing: LadderSizing) -> Self {
+        self.sizing = sizing;
+        self
+    }
+
+    /// Sets the minimum level size.
+    #[must_use]
+    pub fn with_min_size(mut self, min_size: Decimal) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Sets the tick size used to round level prices.
+    #[must_use]
+    pub fn with_tick_size(mut self, tick_size: Decimal) -> Self {
+        self.tick_size = Some(tick_size);
+        self
+    }
+
+    /// Sets the horizon for estimator-based fill probabilities.
+    #[must_use]
+    pub fn with_fill_horizon_ms(mut self, fill_horizon_ms: u64) -> Self {
+        self.fill_horizon_ms = fill_horizon_ms;
+        self
+    }
+}
+
+/// A single level of a [`QuoteLadder`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LadderLevel {
+    /// Level index, 0 being closest to the reservation price.
+    pub level: u32,
+
+    /// Level price.
+    pub price: Decimal,
+
+    /// Level size.
+    pub size: Decimal,
+
+    /// Relative fill intensity compared with level 0 on the same side.
+    pub intensity_weight: Decimal,
+}
+
+/// Multi-level quotes generated from a pricing model.
+///
+/// Bids are ordered from best (highest) to worst, asks from best (lowest)
+/// to worst.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteLadder {
+    /// Bid levels, best first.
+    pub bids: Vec<LadderLevel>,
+
+    /// Ask levels, best first.
+    pub asks: Vec<LadderLevel>,
+
+    /// Reservation price the ladder is centered on.
+    pub reservation_price: Decimal,
+
+    /// Optimal half-spread of the model.
+    pub half_spread: Decimal,
+
+    /// Generation timestamp in milliseconds.
+    pub timestamp: u64,
+}
+
+impl QuoteLadder {
+    /// Returns the levels for the given side.
+    #[must_use]
+    pub fn levels(&self, side: Side) -> &[LadderLevel] {
+        match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        }
+    }
+
+    /// Returns the best (highest) bid price.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<Decimal> {
+        self.bids.first().map(|l| l.price)
+    }
+
+    /// Returns the best (lowest) ask price.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<Decimal> {
+        self.asks.first().map(|l| l.price)
+    }
+
+    /// Returns the total size on the bid side.
+    #[must_use]
+    pub fn total_bid_size(&self) -> Decimal {
+        self.bids.iter().map(|l| l.size).sum()
+    }
+
+    /// Returns the total size on the ask side.
+    #[must_use]
+    pub fn total_ask_size(&self) -> Decimal {
+        self.asks.iter().map(|l| l.size).sum()
+    }
+
+    /// Converts the ladder into a generic [`QuoteSet`].
+    #[must_use]
+    pub fn to_quote_set(&self) -> QuoteSet {
+        QuoteSet::new(
+            self.bids
+                .iter()
+                .map(|l| QuoteLevel::new(l.price, l.size))
+                .collect(),
+            self.asks
+                .iter()
+                .map(|l| QuoteLevel::new(l.price, l.size))
+                .collect(),
+            self.timestamp,
+        )
+    }
+
+    /// Diffs the ladder against resting orders.
+    ///
+    /// A resting open order for `symbol` is kept if it matches an unmatched
+    /// ladder level on the same side, within `price_tolerance` on price and
+    /// `size_tolerance` on remaining quantity. Unmatched resting orders are
+    /// cancelled and unmatched levels become new limit orders.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - Symbol the ladder is quoted on
+    /// * `resting` - Orders currently known to the order manager
+    /// * `price_tolerance` - Maximum absolute price difference to keep an order
+    /// * `size_tolerance` - Maximum absolute size difference to keep an order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use market_maker_rs::execution::{ManagedOrder, OrderId, OrderType, Side};
+    /// use market_maker_rs::strategy::config::StrategyConfig;
+    /// use market_maker_rs::strategy::ladder::{LadderConfig, QuoteLadderGenerator};
+    /// use market_maker_rs::strategy::quoting::QuoteContext;
+    /// use market_maker_rs::dec;
+    ///
+    /// let config = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+    /// let generator = QuoteLadderGenerator::new(
+    ///     LadderConfig::new(2, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap(),
+    /// );
+    /// let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0);
+    /// let ladder = generator.avellaneda_stoikov(&context, &config, None).unwrap();
+    ///
+    /// // A stale bid far from the ladder
+    /// let stale = ManagedOrder::new(
+    ///     OrderId::new("1"), "c1".to_string(), "BTC".to_string(),
+    ///     Side::Buy, OrderType::Limit, dec!(90.0), dec!(1.0), 0,
+    /// );
+    ///
+    /// let diff = ladder.diff("BTC", &[&stale], dec!(0.001), dec!(0.001));
+    /// assert_eq!(diff.to_cancel, vec![OrderId::new("1")]);
+    /// assert_eq!(diff.to_place.len(), 4);
+    /// ```
+    #[must_use]
+    pub fn diff(
+        &self,
+        symbol: &str,
+        resting: &[&ManagedOrder],
+        price_tolerance: Decimal,
+        size_tolerance: Decimal,
+    ) -> LadderDiff {
+        let mut diff = LadderDiff::default();
+
+        for side in [Side::Buy, Side::Sell] {
+            let mut candidates: Vec<&ManagedOrder> = resting
+                .iter()
+                .copied()
+                .filter(|o| o.symbol == symbol && o.side == side && !o.is_terminal())
+                .collect();
+
+            for level in self.levels(side) {
+                let matched = candidates.iter().position(|o| {
+                    (o.original_price - level.price).abs() <= price_tolerance
+                        && (o.remaining_quantity - level.size).abs() <= size_tolerance
+                });
+
+                match matched {
+                    Some(index) => {
+                        diff.unchanged
+                            .push(candidates.remove(index).order_id.clone());
+                    }
+                    None => {
+                        let request = match side {
+                            Side::Buy => OrderRequest::limit_buy(symbol, level.price, level.size),
+                            Side::Sell => OrderRequest::limit_sell(symbol, level.price, level.size),
+                        };
+                        diff.to_place.push(request);
+                    }
+                }
+            }
+
+            diff.to_cancel
+                .extend(candidates.into_iter().map(|o| o.order_id.clone()));
+        }
+
+        diff
+    }
+}
+
+impl From<QuoteLadder> for QuoteSet {
+    fn from(ladder: QuoteLadder) -> Self {
+        ladder.to_quote_set()
+    }
+}
+
+/// Actions needed to move resting orders to a target [`QuoteLadder`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LadderDiff {
+    /// Resting orders that already match a ladder level.
+    pub unchanged: Vec<OrderId>,
+
+    /// Resting orders to cancel.
+    pub to_cancel: Vec<OrderId>,
+
+    /// New orders to submit.
+    pub to_place: Vec<OrderRequest>,
+}
+
+impl LadderDiff {
+    /// Returns true if no orders need to be placed or cancelled.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.to_cancel.is_empty() && self.to_place.is_empty()
+    }
+}
+
+/// Generates [`QuoteLadder`]s from the Avellaneda-Stoikov and GLFT models.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteLadderGenerator {
+    config: LadderConfig,
+}
+
+impl QuoteLadderGenerator {
+    /// Creates a new ladder generator.
+    #[must_use]
+    pub fn new(config: LadderConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the ladder configuration.
+    #[must_use]
+    pub fn config(&self) -> &LadderConfig {
+        &self.config
+    }
+
+    /// Builds a ladder around the Avellaneda-Stoikov optimal quotes.
+    ///
+    /// Honours the horizon selected in `config`. The spread is floored at
+    /// `config.min_spread`. Intensity weights use `config.order_intensity`
+    /// unless an estimator with a current estimate is supplied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model rejects the inputs.
+    pub fn avellaneda_stoikov(
+        &self,
+        context: &QuoteContext,
+        config: &StrategyConfig,
+        estimator: Option<&OrderIntensityEstimator>,
+    ) -> MMResult<QuoteLadder> {
+        let (reservation, spread) = DefaultAvellanedaStoikov.calculate_reservation_and_spread(
+            config,
+            context.mid_price,
+            context.inventory,
+            context.volatility,
+            context.timestamp,
+        )?;
+        let spread = spread.max(config.min_spread);
+
+        self.build(
+            context,
+            reservation,
+            spread / Decimal::TWO,
+            config.order_intensity,
+            estimator,
+        )
+    }
+
+    /// Builds a ladder around the GLFT optimal quotes.
+    ///
+    /// The first level on each side is the quote of
+    /// [`GLFTStrategy::calculate_bounded_quotes`], so the configured solution
+    /// and inventory bounds apply. A side the bounds disable gets no levels;
+    /// the ladder is then centered on the mid and the half-spread is the
+    /// distance of the remaining quote from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model rejects the inputs.
+    pub fn glft(
+        &self,
+        context: &QuoteContext,
+        config: &GLFTConfig,
+        estimator: Option<&OrderIntensityEstimator>,
+    ) -> MMResult<QuoteLadder> {
+        let (bid, ask) = GLFTStrategy::calculate_bounded_quotes(
+            context.mid_price,
+            context.inventory,
+            config,
+            context.volatility,
+            context.timestamp,
+        )?;
+        let (reservation, half_spread) = match (bid, ask) {
+            (Some(bid), Some(ask)) => ((bid + ask) / Decimal::TWO, (ask - bid) / Decimal::TWO),
+            (Some(quote), None) | (None, Some(quote)) => {
+                (context.mid_price, (quote - context.mid_price).abs())
+            }
+            (None, None) => (context.mid_price, Decimal::ZERO),
+        };
+
+        self.build_from(
+            context,
+            reservation,
+            half_spread,
+            (bid, ask),
+            config.order_intensity,
+            estimator,
+        )
+    }
+
+    /// Builds a ladder from an arbitrary reservation price and half-spread.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Market context (mid price, inventory, timestamp)
+    /// * `reservation_price` - Center of the ladder
+    /// * `half_spread` - Distance from the reservation price to the first level
+    /// * `order_intensity` - Intensity decay `k` in price units, used when no estimate is available
+    /// * `estimator` - Optional intensity estimator for fill probabilities
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if the mid price is not positive,
+    /// or `MMError::InvalidQuoteGeneration` if the first levels cross.
+    pub fn build(
+        &self,
+        context: &QuoteContext,
+        reservation_price: Decimal,
+        half_spread: Decimal,
+        order_intensity: Decimal,
+        estimator: Option<&OrderIntensityEstimator>,
+    ) -> MMResult<QuoteLadder> {
+        self.build_from(
+            context,
+            reservation_price,
+            half_spread,
+            (
+                Some(reservation_price - half_spread),
+                Some(reservation_price + half_spread),
+            ),
+            order_intensity,
+            estimator,
+        )
+    }
+
+    /// Builds a ladder from the first bid and ask prices; a `None` side
+    /// gets no levels.
+    fn build_from(
+        &self,
+        context: &QuoteContext,
+        reservation_price: Decimal,
+        half_spread: Decimal,
+        (first_bid, first_ask): (Option<Decimal>, Option<Decimal>),
+        order_intensity: Decimal,
+        estimator: Option<&OrderIntensityEstimator>,
+    ) -> MMResult<QuoteLadder> {
+        if context.mid_price <= Decimal::ZERO {
+            return Err(MMError::InvalidMarketState(
+                "mid_price must be positive".to_string(),
+            ));
+        }
+
+        let spacing = match self.config.spacing {
+            LadderSpacing::Fixed(spacing) => spacing,
+            LadderSpacing::HalfSpreadMultiple(multiple) => half_spread * multiple,
+        };
+        if spacing <= Decimal::ZERO && (first_bid.is_some() || first_ask.is_some()) {
+            return Err(MMError::InvalidConfiguration(
+                "level spacing must be positive".to_string(),
+            ));
+        }
+
+        let bid_room = (self.config.max_inventory - context.inventory).max(Decimal::ZERO);
+        let ask_room = (self.config.max_inventory + context.inventory).max(Decimal::ZERO);
+
+        let bids = match first_bid {
+            Some(price) => self.build_side(
+                Side::Buy,
+                context.mid_price,
+                price,
+                spacing,
+                bid_room,
+                order_intensity,
+                estimator,
+            )?,
+            None => Vec::new(),
+        };
+        let asks = match first_ask {
+            Some(price) => self.build_side(
+                Side::Sell,
+                context.mid_price,
+                price,
+                spacing,
+                ask_room,
+                order_intensity,
+                estimator,
+            )?,
+            None => Vec::new(),
+        };
+
+        let crossed = matches!(
+            (bids.first(), asks.first()),
+            (Some(bid), Some(ask)) if bid.price >= ask.price
+        );
+        if crossed {
+            return Err(MMError::InvalidQuoteGeneration(
+                "bid price must be less than ask price".to_string(),
+            ));
+        }
+
+        Ok(QuoteLadder {
+            bids,
+            asks,
+            reservation_price,
+            half_spread,
+            timestamp: context.timestamp,
+        })
+    }
+
+    /// Builds the levels of one side, starting at `first_price`.
+    #[allow(clippy::too_many_arguments)]
+    fn build_side(
+        &self,
+        side: Side,
+        mid_price: Decimal,
+        first_price: Decimal,
+        spacing: Decimal,
+        room: Decimal,
+        order_intensity: Decimal,
+        estimator: Option<&OrderIntensityEstimator>,
+    ) -> MMResult<Vec<LadderLevel>> {
+        let mut levels = Vec::with_capacity(self.config.levels_per_side as usize);
+        let mut remaining = room;
+        let mut first_distance = None;
+        let mut first_probability = None;
+
+        for level in 0..self.config.levels_per_side {
+            let offset = spacing * Decimal::from(level);
+            let price = match side {
+                Side::Buy => self.round_price(first_price - offset, side),
+                Side::Sell => self.round_price(first_price + offset, side),
+            };
+            if price <= Decimal::ZERO {
+                break;
+            }
+
+            let distance = match side {
+                Side::Buy => mid_price - price,
+                Side::Sell => price - mid_price,
+            }
+            .max(Decimal::ZERO);
+            let d0 = *first_distance.get_or_insert(distance);
+
+            let probability = estimator.and_then(|e| {
+                e.fill_probability(distance / mid_price, self.config.fill_horizon_ms)
+            });
+            if level == 0 {
+                first_probability = probability;
+            }
+
+            let weight = match (probability, first_probability) {
+                (Some(p), Some(p0)) if p0 > Decimal::ZERO => p / p0,
+                _ => decimal_exp(-order_intensity * (distance - d0))?,
+            };
+
+            let size = match self.config.sizing {
+                LadderSizing::Flat => self.config.base_size,
+                LadderSizing::FillIntensity => self.config.base_size * weight,
+                LadderSizing::ExpectedFillParity { max_multiplier } => {
+                    let cap = self.config.base_size * max_multiplier;
+                    if weight > Decimal::ZERO {
+                        (self.config.base_size / weight).min(cap)
+                    } else {
+                        cap
+                    }
+                }
+            }
+            .min(remaining);
+
+            if size <= Decimal::ZERO || size < self.config.min_size {
+                break;
+            }
+
+            remaining -= size;
+            levels.push(LadderLevel {
+                level,
+                price,
+                size,
+                intensity_weight: weight,
+            });
+        }
+
+        Ok(levels)
+    }
+
+    /// Rounds a price to the tick grid, away from the market.
+    fn round_price(&self, price: Decimal, side: Side) -> Decimal {
+        match self.config.tick_size {
+            Some(tick) if tick > Decimal::ZERO => {
+                let ticks = price / tick;
+                let ticks = match side {
+                    Side::Buy => ticks.floor(),
+                    Side::Sell => ticks.ceil(),
+                };
+                ticks * tick
+            }
+            _ => price,
+        }
+    }
+}
+
+/// Pricing model used by a [`LadderQuoter`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LadderModel {
+    /// Avellaneda-Stoikov model.
+    AvellanedaStoikov(StrategyConfig),
+    /// GLFT model.
+    Glft(GLFTConfig),
+}
+
+/// Quoting strategy producing a multi-level ladder on every cycle.
+///
+/// Optionally owns an [`OrderIntensityEstimator`]; fills reported through
+/// [`QuotingStrategy::on_fill`] are not recorded automatically because the
+/// time-to-fill is only known to the order manager. Feed the estimator via
+/// [`LadderQuoter::estimator_mut`].
+#[derive(Debug, Clone)]
+pub struct LadderQuoter {
+    model: LadderModel,
+    generator: QuoteLadderGenerator,
+    estimator: Option<OrderIntensityEstimator>,
+}
+
+impl LadderQuoter {
+    /// Creates a new ladder quoter.
+    #[must_use]
+    pub fn new(model: LadderModel, config: LadderConfig) -> Self {
+        Self {
+            model,
+            generator: QuoteLadderGenerator::new(config),
+            estimator: None,
+        }
+    }
+
+    /// Attaches an intensity estimator used to size levels.
+    #[must_use]
+    pub fn with_estimator(mut self, estimator: OrderIntensityEstimator) -> Self {
+        self.estimator = Some(estimator);
+        self
+    }
+
+    /// Returns the pricing model.
+    #[must_use]
+    pub fn model(&self) -> &LadderModel {
+        &self.model
+    }
+
+    /// Returns the ladder generator.
+    #[must_use]
+    pub fn generator(&self) -> &QuoteLadderGenerator {
+        &self.generator
+    }
+
+    /// Returns a mutable reference to the intensity estimator, if any.
+    pub fn estimator_mut(&mut self) -> Option<&mut OrderIntensityEstimator> {
+        self.estimator.as_mut()
+    }
+
+    /// Generates the full ladder for the given context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model rejects the inputs.
+    pub fn generate_ladder(&self, context: &QuoteContext) -> MMResult<QuoteLadder> {
+        let estimator = self.estimator.as_ref();
+        match &self.model {
+            LadderModel::AvellanedaStoikov(config) => self
+                .generator
+                .avellaneda_stoikov(context, config, estimator),
+            LadderModel::Glft(config) => self.generator.glft(context, config, estimator),
+        }
+    }
+}
+
+impl QuotingStrategy for LadderQuoter {
+    fn name(&self) -> &str {
+        match self.model {
+            LadderModel::AvellanedaStoikov(_) => "avellaneda_stoikov_ladder",
+            LadderModel::Glft(_) => "glft_ladder",
+        }
+    }
+
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+        self.generate_ladder(context).map(QuoteSet::from)
+    }
+
+    fn reset(&mut self) {
+        if let Some(estimator) = self.estimator.as_mut() {
+            estimator.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::intensity::{FillObservation, OrderIntensityConfig};
+    use crate::dec;
+    use crate::execution::OrderType;
+    use crate::strategy::glft::{GLFTSolution, InventoryBounds};
+
+    fn as_config() -> StrategyConfig {
+        StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap()
+    }
+
+    fn glft_config() -> GLFTConfig {
+        GLFTConfig::new(dec!(0.1), dec!(1.5), dec!(0.05), 3_600_000, dec!(0.01)).unwrap()
+    }
+
+    fn context(inventory: Decimal) -> QuoteContext {
+        QuoteContext::new(dec!(100.0), dec!(0.2), inventory, 0)
+    }
+
+    fn resting(id: &str, side: Side, price: Decimal, size: Decimal) -> ManagedOrder {
+        ManagedOrder::new(
+            OrderId::new(id),
+            format!("c{id}"),
+            "BTC".to_string(),
+            side,
+            OrderType::Limit,
+            price,
+            size,
+            0,
+        )
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(LadderConfig::new(0, dec!(0.1), dec!(1.0), dec!(10.0)).is_err());
+        assert!(LadderConfig::new(3, dec!(0.0), dec!(1.0), dec!(10.0)).is_err());
+        assert!(LadderConfig::new(3, dec!(0.1), dec!(-1.0), dec!(10.0)).is_err());
+        assert!(LadderConfig::new(3, dec!(0.1), dec!(1.0), dec!(0.0)).is_err());
+        assert!(LadderConfig::new(3, dec!(0.1), dec!(1.0), dec!(10.0)).is_ok());
+    }
+
+    #[test]
+    fn test_first_level_matches_single_quote() {
+        let generator = QuoteLadderGenerator::new(
+            LadderConfig::new(3, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap(),
+        );
+        let ladder = generator
+            .avellaneda_stoikov(&context(dec!(2.0)), &as_config(), None)
+            .unwrap();
+
+        let (bid, ask) = DefaultAvellanedaStoikov
+            .calculate_optimal_quotes(
+                dec!(100.0),
+                dec!(2.0),
+                dec!(0.1),
+                dec!(0.2),
+                3_600_000,
+                dec!(1.5),
+            )
+            .unwrap();
+
+        assert_eq!(ladder.best_bid(), Some(bid));
+        assert_eq!(ladder.best_ask(), Some(ask));
+    }
+
+    #[test]
+    fn test_level_spacing() {
+        let generator = QuoteLadderGenerator::new(
+            LadderConfig::new(3, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap(),
+        );
+        let ladder = generator
+            .avellaneda_stoikov(&context(dec!(0.0)), &as_config(), None)
+            .unwrap();
+
+        assert_eq!(ladder.bids[0].price - ladder.bids[1].price, dec!(0.05));
+        assert_eq!(ladder.asks[2].price - ladder.asks[1].price, dec!(0.05));
+        assert_eq!(ladder.bids[2].level, 2);
+    }
+
+    #[test]
+    fn test_half_spread_multiple_spacing() {
+        let config = LadderConfig::new(2, dec!(0.05), dec!(1.0), dec!(10.0))
+            .unwrap()
+            .with_spacing(LadderSpacing::HalfSpreadMultiple(dec!(0.5)));
+        let generator = QuoteLadderGenerator::new(config);
+        let ladder = generator
+            .build(&context(dec!(0.0)), dec!(100.0), dec!(0.2), dec!(1.5), None)
+            .unwrap();
+
+        assert_eq!(ladder.bids[1].price, dec!(99.7));
+        assert_eq!(ladder.asks[1].price, dec!(100.3));
+    }
+
+    #[test]
+    fn test_fill_intensity_sizes_decrease() {
+        let generator = QuoteLadderGenerator::new(
+            LadderConfig::new(3, dec!(0.5), dec!(1.0), dec!(100.0)).unwrap(),
+        );
+        let ladder = generator
+            .avellaneda_stoikov(&context(dec!(0.0)), &as_config(), None)
+            .unwrap();
+
+        assert_eq!(ladder.bids[0].size, dec!(1.0));
+        assert!(ladder.bids[1].size < ladder.bids[0].size);
+        assert!(ladder.bids[2].size < ladder.bids[1].size);
+        assert!(ladder.asks[1].intensity_weight < Decimal::ONE);
+    }
+
+    #[test]
+    fn test_expected_fill_parity_sizes_increase_with_cap() {
+        let config = LadderConfig::new(3, dec!(0.5), dec!(1.0), dec!(100.0))
+            .unwrap()
+            .with_sizing(LadderSizing::ExpectedFillParity {
+                max_multiplier: dec!(2.0),
+            });
+        let generator = QuoteLadderGenerator::new(config);
+        let ladder = generator
+            .avellaneda_stoikov(&context(dec!(0.0)), &as_config(), None)
+            .unwrap();
+
+        assert_eq!(ladder.bids[0].size, dec!(1.0));
+        assert!(ladder.bids[1].size > dec!(1.0));
+        assert_eq!(ladder.bids[2].size, dec!(2.0));
+    }
+
+    #[test]
+    fn test_glft_uses_solution_and_inventory_bounds() {
+        let generator = QuoteLadderGenerator::new(
+            LadderConfig::new(3, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap(),
+        );
+        let config = glft_config()
+            .with_inventory_bounds(InventoryBounds::new(dec!(5.0), dec!(1.0)).unwrap())
+            .with_solution(GLFTSolution::Asymptotic {
+                arrival_rate: dec!(140.0),
+            });
+
+        let ladder = generator.glft(&context(dec!(2.0)), &config, None).unwrap();
+        let (bid, ask) =
+            GLFTStrategy::calculate_bounded_quotes(dec!(100.0), dec!(2.0), &config, dec!(0.2), 0)
+                .unwrap();
+        assert_eq!(ladder.best_bid(), bid);
+        assert_eq!(ladder.best_ask(), ask);
+
+        // At the long limit the bid side is switched off.
+        let ladder = generator.glft(&context(dec!(5.0)), &config, None).unwrap();
+        assert!(ladder.bids.is_empty());
+        assert_eq!(ladder.asks.len(), 3);
+        assert_eq!(ladder.reservation_price, dec!(100.0));
+        assert_eq!(ladder.half_spread, ladder.best_ask().unwrap() - dec!(100.0));
+    }
+
+    #[test]
+    fn test_flat_sizing() {
+        let config = LadderConfig::new(3, dec!(0.5), dec!(2.0), dec!(100.0))
+            .unwrap()
+            .with_sizing(LadderSizing::Flat);
+        let ladder = QuoteLadderGenerator::new(config)
+            .glft(&context(dec!(0.0)), &glft_config(), None)
+            .unwrap();
+
+        assert!(ladder.bids.iter().all(|l| l.size == dec!(2.0)));
+        assert_eq!(ladder.total_ask_size(), dec!(6.0));
+    }
+
+    #[test]
+    fn test_inventory_limit_caps_bids() {
+        let config = LadderConfig::new(5, dec!(0.05), dec!(1.0), dec!(10.0))
+            .unwrap()
+            .with_sizing(LadderSizing::Flat);
+        let ladder = QuoteLadderGenerator::new(config)
+            .avellaneda_stoikov(&context(dec!(8.5)), &as_config(), None)
+            .unwrap();
+
+        // Only 1.5 units of room to buy
+        assert_eq!(ladder.total_bid_size(), dec!(1.5));
+        assert_eq!(ladder.bids.len(), 2);
+        assert_eq!(ladder.bids[1].size, dec!(0.5));
+        assert_eq!(ladder.total_ask_size(), dec!(5.0));
+    }
+
+    #[test]
+    fn test_inventory_at_limit_stops_side() {
+        let config = LadderConfig::new(3, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap();
+        let ladder = QuoteLadderGenerator::new(config)
+            .avellaneda_stoikov(&context(dec!(-10.0)), &as_config(), None)
+            .unwrap();
+
+        assert!(ladder.asks.is_empty());
+        assert!(!ladder.bids.is_empty());
+    }
+
+    #[test]
+    fn test_min_size_drops_small_levels() {
+        let config = LadderConfig::new(5, dec!(1.0), dec!(1.0), dec!(100.0))
+            .unwrap()
+            .with_min_size(dec!(0.1));
+        let ladder = QuoteLadderGenerator::new(config)
+            .avellaneda_stoikov(&context(dec!(0.0)), &as_config(), None)
+            .unwrap();
+
+        assert!(ladder.bids.len() < 5);
+        assert!(ladder.bids.iter().all(|l| l.size >= dec!(0.1)));
+    }
+
+    #[test]
+    fn test_tick_rounding_away_from_market() {
+        let config = LadderConfig::new(1, dec!(0.05), dec!(1.0), dec!(10.0))
+            .unwrap()
+            .with_tick_size(dec!(0.1));
+        let ladder = QuoteLadderGenerator::new(config)
+            .build(
+                &context(dec!(0.0)),
+                dec!(100.0),
+                dec!(0.25),
+                dec!(1.5),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(ladder.best_bid(), Some(dec!(99.7)));
+        assert_eq!(ladder.best_ask(), Some(dec!(100.3)));
+    }
+
+    #[test]
+    fn test_estimator_weights() {
+        let config = OrderIntensityConfig::new(60_000, 5, dec!(0.1)).unwrap();
+        let mut estimator = OrderIntensityEstimator::new(config);
+        for i in 0..10 {
+            let spread = dec!(0.001) + dec!(0.0005) * Decimal::from(i % 3);
+            estimator.record_fill(FillObservation::new(spread, 300 + i * 50, i * 1000));
+        }
+        estimator.estimate(11_000).unwrap();
+
+        let generator = QuoteLadderGenerator::new(
+            LadderConfig::new(3, dec!(0.1), dec!(1.0), dec!(100.0)).unwrap(),
+        );
+        let ladder = generator
+            .build(
+                &context(dec!(0.0)),
+                dec!(100.0),
+                dec!(0.1),
+                dec!(1.5),
+                Some(&estimator),
+            )
+            .unwrap();
+
+        let expected = estimator.fill_probability(dec!(0.002), 1_000).unwrap()
+            / estimator.fill_probability(dec!(0.001), 1_000).unwrap();
+        assert_eq!(ladder.bids[1].intensity_weight, expected);
+    }
+
+    #[test]
+    fn test_build_invalid_mid() {
+        let generator = QuoteLadderGenerator::new(
+            LadderConfig::new(1, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap(),
+        );
+        let result = generator.build(
+            &QuoteContext::new(dec!(0.0), dec!(0.2), dec!(0.0), 0),
+            dec!(100.0),
+            dec!(0.1),
+            dec!(1.5),
+            None,
+        );
+        assert!(matches!(result, Err(MMError::InvalidMarketState(_))));
+    }
+
+    #[test]
+    fn test_to_quote_set() {
+        let generator = QuoteLadderGenerator::new(
+            LadderConfig::new(2, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap(),
+        );
+        let ladder = generator
+            .glft(&context(dec!(0.0)), &glft_config(), None)
+            .unwrap();
+        let set = QuoteSet::from(ladder.clone());
+
+        assert_eq!(set.bids.len(), 2);
+        assert_eq!(set.best_bid(), ladder.best_bid());
+        assert_eq!(set.best_ask(), ladder.best_ask());
+    }
+
+    #[test]
+    fn test_diff_keeps_matching_orders() {
+        let config = LadderConfig::new(2, dec!(1.0), dec!(1.0), dec!(10.0))
+            .unwrap()
+            .with_sizing(LadderSizing::Flat);
+        let ladder = QuoteLadderGenerator::new(config)
+            .build(&context(dec!(0.0)), dec!(100.0), dec!(0.5), dec!(1.5), None)
+            .unwrap();
+
+        let keep_bid = resting("1", Side::Buy, dec!(99.5), dec!(1.0));
+        let keep_ask = resting("2", Side::Sell, dec!(100.5), dec!(1.0));
+        let stale = resting("3", Side::Sell, dec!(105.0), dec!(1.0));
+        let other_symbol = ManagedOrder::new(
+            OrderId::new("4"),
+            "c4".to_string(),
+            "ETH".to_string(),
+            Side::Buy,
+            OrderType::Limit,
+            dec!(99.5),
+            dec!(1.0),
+            0,
+        );
+
+        let diff = ladder.diff(
+            "BTC",
+            &[&keep_bid, &keep_ask, &stale, &other_symbol],
+            dec!(0.01),
+            dec!(0.01),
+        );
+
+        assert_eq!(diff.unchanged, vec![OrderId::new("1"), OrderId::new("2")]);
+        assert_eq!(diff.to_cancel, vec![OrderId::new("3")]);
+        assert_eq!(diff.to_place.len(), 2);
+        assert_eq!(diff.to_place[0].side, Side::Buy);
+        assert_eq!(diff.to_place[0].price, Some(dec!(98.5)));
+        assert_eq!(diff.to_place[1].side, Side::Sell);
+    }
+
+    #[test]
+    fn test_diff_resizes_on_size_mismatch() {
+        let config = LadderConfig::new(1, dec!(1.0), dec!(1.0), dec!(10.0)).unwrap();
+        let ladder = QuoteLadderGenerator::new(config)
+            .build(&context(dec!(0.0)), dec!(100.0), dec!(0.5), dec!(1.5), None)
+            .unwrap();
+
+        let wrong_size = resting("1", Side::Buy, dec!(99.5), dec!(3.0));
+        let diff = ladder.diff("BTC", &[&wrong_size], dec!(0.01), dec!(0.01));
+
+        assert_eq!(diff.to_cancel, vec![OrderId::new("1")]);
+        assert_eq!(diff.to_place.len(), 2);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_identical_is_empty() {
+        let config = LadderConfig::new(1, dec!(1.0), dec!(1.0), dec!(10.0)).unwrap();
+        let ladder = QuoteLadderGenerator::new(config)
+            .build(&context(dec!(0.0)), dec!(100.0), dec!(0.5), dec!(1.5), None)
+            .unwrap();
+
+        let bid = resting("1", Side::Buy, dec!(99.5), dec!(1.0));
+        let ask = resting("2", Side::Sell, dec!(100.5), dec!(1.0));
+        let diff = ladder.diff("BTC", &[&bid, &ask], dec!(0.0), dec!(0.0));
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_ladder_quoter_names_and_quotes() {
+        let config = LadderConfig::new(3, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap();
+        let mut quoter = LadderQuoter::new(LadderModel::Glft(glft_config()), config.clone());
+        assert_eq!(quoter.name(), "glft_ladder");
+        let quotes = quoter.generate_quotes(&context(dec!(0.0))).unwrap();
+        assert_eq!(quotes.bids.len(), 3);
+
+        let quoter = LadderQuoter::new(LadderModel::AvellanedaStoikov(as_config()), config);
+        assert_eq!(quoter.name(), "avellaneda_stoikov_ladder");
+    }
+
+    #[test]
+    fn test_ladder_quoter_reset_clears_estimator() {
+        let estimator =
+            OrderIntensityEstimator::new(OrderIntensityConfig::new(60_000, 5, dec!(0.1)).unwrap());
+        let config = LadderConfig::new(1, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap();
+        let mut quoter =
+            LadderQuoter::new(LadderModel::Glft(glft_config()), config).with_estimator(estimator);
+
+        quoter
+            .estimator_mut()
+            .unwrap()
+            .record_fill(FillObservation::new(dec!(0.001), 100, 0));
+        quoter.reset();
+        assert_eq!(quoter.estimator_mut().unwrap().observation_count(), 0);
+    }
+}
@@ -4,8 +4,20 @@
 //! - Avellaneda-Stoikov model using stochastic control theory
 //! - Guéant-Lehalle-Fernandez-Tapia (GLFT) model extension
 //! - Grid trading for ranging markets
+//! - Stateful grid lifecycle: take-profits on fills, recentering, trailing and infinity grids
 //! - Depth-based offering
 //! - Adaptive spread based on order book imbalance
+//! - Cartea–Jaimungal alpha-signal-adjusted quoting
+//! - Queue-aware placement at the touch (join, improve or back off)
+//! - Composition pipelines of a base model followed by overlays
+//! - VPIN toxicity guard that widens, shrinks and pulls quotes
+//! - Volatility regime tracking with hysteresis, dwell time and an optional HMM
+//! - Online re-calibration of γ, k and minimum spread from live data
+//! - Fair-value reference prices (microprice, depth-weighted mid, Stoikov micro-price)
+//! - Cross-venue quoting priced off a hedge venue, with fills hedged there
+//!
+//! All of them implement the object-safe [`quoting::QuotingStrategy`] trait,
+//! so they can be selected from configuration and used interchangeably.
 //!
 //! # Key Formulas (Avellaneda-Stoikov)
 //!
@@ -41,11 +53,44 @@ pub mod depth_based;
 /// Grid trading strategy.
 pub mod grid;
 
+/// Stateful grid engine handling fills, recentering and trailing grids.
+pub mod grid_engine;
+
 /// Adaptive spread based on order book imbalance.
 pub mod adaptive_spread;
 
 /// Guéant-Lehalle-Fernandez-Tapia (GLFT) model extension.
 pub mod glft;
 
+/// Multi-level quote ladders for the Avellaneda-Stoikov and GLFT models.
+pub mod ladder;
+
+/// Unified quoting interface implemented by every strategy.
+pub mod quoting;
+
+/// Cartea–Jaimungal alpha-adjusted reservation price with pluggable signals.
+pub mod cartea_jaimungal;
+
+/// Queue-aware join/improve/back-off placement at the touch.
+pub mod queue_placement;
+
+/// Microprice and fair-value estimators used as reference prices.
+pub mod fair_value;
+
+/// Base model plus overlay composition pipeline.
+pub mod pipeline;
+
+/// VPIN-driven spread/size curves and quote pulling with hysteresis.
+pub mod toxicity_guard;
+
 /// Parameter calibration tools for strategy optimization.
 pub mod calibration;
+
+/// Stateful volatility regime tracking with hysteresis and dwell time.
+pub mod regime;
+
+/// Streaming re-calibration of live strategy parameters.
+pub mod recalibration;
+
+/// Cross-venue quoting with fills hedged on a second venue.
+pub mod cross_venue;
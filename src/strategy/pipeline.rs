@@ -0,0 +1,904 @@
+//! Composable quoting pipeline: a base pricing model followed by overlays.
+//!
+//! Bots usually combine a pricing model with several independent adjustments
+//! (inventory skew, imbalance widening, regime scaling, toxicity widening,
+//! risk-limit sizing). [`QuotePipeline`] runs a base [`QuotingStrategy`] and
+//! then each [`QuoteOverlay`] in order, recording what every stage changed so
+//! that a quote can be explained after the fact.
+//!
+//! # Data Flow
+//!
+//! ```text
+//! QuoteContext ──► base model ──► overlay 1 ──► ... ──► overlay n ──► QuoteSet
+//!                      │              │                     │
+//!                      └──────────────┴─── QuoteExplanation ┘
+//! ```
+//!
+//! Widening overlays scale each level's distance from the quote center (the
+//! midpoint of the best bid and ask), so skew applied by earlier stages is
+//! preserved.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::strategy::config::StrategyConfig;
+//! use market_maker_rs::strategy::pipeline::{InventorySkewOverlay, QuotePipeline};
+//! use market_maker_rs::strategy::quoting::{
+//!     AvellanedaStoikovQuoter, QuoteContext, QuotingStrategy,
+//! };
+//! use market_maker_rs::dec;
+//!
+//! let config = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+//! let base = AvellanedaStoikovQuoter::new(config, dec!(1.0)).unwrap();
+//!
+//! let mut pipeline = QuotePipeline::new(Box::new(base))
+//!     .with_overlay(InventorySkewOverlay::new(dec!(0.01)));
+//!
+//! let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(5.0), 0);
+//! let quotes = pipeline.generate_quotes(&context).unwrap();
+//! assert!(quotes.best_bid().unwrap() < quotes.best_ask().unwrap());
+//!
+//! let explanation = pipeline.last_explanation().unwrap();
+//! assert_eq!(explanation.stages[0].stage, "inventory_skew");
+//! assert_eq!(explanation.stages[0].bid_price_change, Some(dec!(-0.05)));
+//! ```
+
+use crate::Decimal;
+use crate::analytics::vpin::VPINCalculator;
+use crate::execution::Side;
+use crate::risk::RiskLimits;
+use crate::strategy::adaptive_spread::AdaptiveSpreadCalculator;
+use crate::strategy::calibration::VolatilityRegimeDetector;
+use crate::strategy::quoting::{QuoteContext, QuoteLevel, QuoteSet, QuotingStrategy};
+use crate::strategy::regime::{RegimeTrackerConfig, VolatilityRegimeTracker};
+use crate::strategy::toxicity_guard::{ToxicityGuard, ToxicityState};
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A stage applied to the quotes produced by the base model.
+///
+/// Overlays modify the [`QuoteSet`] in place and may return a short note
+/// describing why, which is stored in the stage's [`StageContribution`].
+pub trait QuoteOverlay: Send + Sync {
+    /// Returns a short identifier for the overlay.
+    fn name(&self) -> &str;
+
+    /// Adjusts the quotes for the given context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the context is invalid for this overlay.
+    fn apply(&mut self, context: &QuoteContext, quotes: &mut QuoteSet) -> MMResult<Option<String>>;
+
+    /// Notifies the overlay that an order was filled.
+    ///
+    /// The default implementation does nothing.
+    fn on_fill(&mut self, _side: Side, _price: Decimal, _quantity: Decimal, _timestamp: u64) {}
+
+    /// Resets any internal state. The default implementation does nothing.
+    fn reset(&mut self) {}
+}
+
+/// What a single pipeline stage changed.
+///
+/// Price changes refer to the best level and are `None` when the side was
+/// empty before or after the stage. Size changes are in total quoted size.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StageContribution {
+    /// Stage name.
+    pub stage: String,
+
+    /// Change of the best bid price.
+    pub bid_price_change: Option<Decimal>,
+
+    /// Change of the best ask price.
+    pub ask_price_change: Option<Decimal>,
+
+    /// Change of the total bid size.
+    pub bid_size_change: Decimal,
+
+    /// Change of the total ask size.
+    pub ask_size_change: Decimal,
+
+    /// Optional note from the stage.
+    pub note: Option<String>,
+}
+
+impl StageContribution {
+    fn between(stage: &str, before: &QuoteSet, after: &QuoteSet, note: Option<String>) -> Self {
+        let change = |a: Option<Decimal>, b: Option<Decimal>| match (a, b) {
+            (Some(a), Some(b)) => Some(b - a),
+            _ => None,
+        };
+        Self {
+            stage: stage.to_string(),
+            bid_price_change: change(before.best_bid(), after.best_bid()),
+            ask_price_change: change(before.best_ask(), after.best_ask()),
+            bid_size_change: after.total_bid_size() - before.total_bid_size(),
+            ask_size_change: after.total_ask_size() - before.total_ask_size(),
+            note,
+        }
+    }
+
+    /// Returns true if the stage left the quotes unchanged.
+    #[must_use]
+    pub fn is_neutral(&self) -> bool {
+        self.bid_price_change.is_none_or(|c| c.is_zero())
+            && self.ask_price_change.is_none_or(|c| c.is_zero())
+            && self.bid_size_change.is_zero()
+            && self.ask_size_change.is_zero()
+    }
+}
+
+/// Record of how the pipeline arrived at its last quotes.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteExplanation {
+    /// Name of the base model.
+    pub base: String,
+
+    /// Quotes produced by the base model.
+    pub base_quotes: QuoteSet,
+
+    /// Contribution of each overlay, in application order.
+    pub stages: Vec<StageContribution>,
+}
+
+impl QuoteExplanation {
+    /// Returns the contribution of the named stage, if present.
+    #[must_use]
+    pub fn stage(&self, name: &str) -> Option<&StageContribution> {
+        self.stages.iter().find(|s| s.stage == name)
+    }
+}
+
+/// A base quoting strategy followed by a sequence of overlays.
+///
+/// The pipeline is itself a [`QuotingStrategy`], so it can be boxed and used
+/// anywhere a single model is expected.
+pub struct QuotePipeline {
+    base: Box<dyn QuotingStrategy>,
+    overlays: Vec<Box<dyn QuoteOverlay>>,
+    last_explanation: Option<QuoteExplanation>,
+}
+
+impl std::fmt::Debug for QuotePipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuotePipeline")
+            .field("base", &self.base.name())
+            .field(
+                "overlays",
+                &self.overlays.iter().map(|o| o.name()).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl QuotePipeline {
+    /// Creates a pipeline with no overlays.
+    #[must_use]
+    pub fn new(base: Box<dyn QuotingStrategy>) -> Self {
+        Self {
+            base,
+            overlays: Vec::new(),
+            last_explanation: None,
+        }
+    }
+
+    /// Appends an overlay.
+    #[must_use]
+    pub fn with_overlay(mut self, overlay: impl QuoteOverlay + 'static) -> Self {
+        self.overlays.push(Box::new(overlay));
+        self
+    }
+
+    /// Appends a boxed overlay.
+    pub fn push_overlay(&mut self, overlay: Box<dyn QuoteOverlay>) {
+        self.overlays.push(overlay);
+    }
+
+    /// Returns the base strategy.
+    #[must_use]
+    pub fn base(&self) -> &dyn QuotingStrategy {
+        self.base.as_ref()
+    }
+
+    /// Returns the overlay names in application order.
+    #[must_use]
+    pub fn overlay_names(&self) -> Vec<&str> {
+        self.overlays.iter().map(|o| o.name()).collect()
+    }
+
+    /// Returns a mutable reference to the overlay at `index`.
+    pub fn overlay_mut(&mut self, index: usize) -> Option<&mut Box<dyn QuoteOverlay>> {
+        self.overlays.get_mut(index)
+    }
+
+    /// Returns the explanation of the last successful quote cycle.
+    #[must_use]
+    pub fn last_explanation(&self) -> Option<&QuoteExplanation> {
+        self.last_explanation.as_ref()
+    }
+
+    /// Runs the pipeline and returns the quotes with their explanation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the base model or any overlay fails, or
+    /// `MMError::InvalidQuoteGeneration` if the final quotes are crossed.
+    pub fn generate_explained(
+        &mut self,
+        context: &QuoteContext,
+    ) -> MMResult<(QuoteSet, QuoteExplanation)> {
+        let base_quotes = self.base.generate_quotes(context)?;
+        let mut quotes = base_quotes.clone();
+        let mut stages = Vec::with_capacity(self.overlays.len());
+
+        for overlay in &mut self.overlays {
+            let before = quotes.clone();
+            let note = overlay.apply(context, &mut quotes)?;
+            quotes = QuoteSet::new(quotes.bids, quotes.asks, quotes.timestamp);
+            stages.push(StageContribution::between(
+                overlay.name(),
+                &before,
+                &quotes,
+                note,
+            ));
+        }
+
+        if matches!((quotes.best_bid(), quotes.best_ask()), (Some(bid), Some(ask)) if bid >= ask) {
+            return Err(MMError::InvalidQuoteGeneration(
+                "bid price must be less than ask price".to_string(),
+            ));
+        }
+
+        let explanation = QuoteExplanation {
+            base: self.base.name().to_string(),
+            base_quotes,
+            stages,
+        };
+        self.last_explanation = Some(explanation.clone());
+        Ok((quotes, explanation))
+    }
+}
+
+impl QuotingStrategy for QuotePipeline {
+    fn name(&self) -> &str {
+        "pipeline"
+    }
+
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+        self.generate_explained(context).map(|(quotes, _)| quotes)
+    }
+
+    fn on_fill(&mut self, side: Side, price: Decimal, quantity: Decimal, timestamp: u64) {
+        self.base.on_fill(side, price, quantity, timestamp);
+        for overlay in &mut self.overlays {
+            overlay.on_fill(side, price, quantity, timestamp);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.base.reset();
+        for overlay in &mut self.overlays {
+            overlay.reset();
+        }
+        self.last_explanation = None;
+    }
+}
+
+/// Midpoint of the best bid and ask, or the context mid if a side is empty.
+fn quote_center(context: &QuoteContext, quotes: &QuoteSet) -> Decimal {
+    match (quotes.best_bid(), quotes.best_ask()) {
+        (Some(bid), Some(ask)) => (bid + ask) / Decimal::TWO,
+        _ => context.mid_price,
+    }
+}
+
+/// Scales each level's distance from `center` by the side's multiplier.
+fn scale_distances(
+    quotes: &mut QuoteSet,
+    center: Decimal,
+    bid_multiplier: Decimal,
+    ask_multiplier: Decimal,
+) {
+    for level in &mut quotes.bids {
+        level.price = center - (center - level.price) * bid_multiplier;
+    }
+    for level in &mut quotes.asks {
+        level.price = center + (level.price - center) * ask_multiplier;
+    }
+}
+
+/// Shifts all prices against the current inventory.
+///
+/// Every level moves by `-inventory × skew_per_unit`, optionally capped at
+/// `max_skew` in absolute value: long inventory lowers both sides to attract
+/// buyers of our position, short inventory raises them.
+#[derive(Debug, Clone)]
+pub struct InventorySkewOverlay {
+    skew_per_unit: Decimal,
+    max_skew: Option<Decimal>,
+}
+
+impl InventorySkewOverlay {
+    /// Creates a skew overlay with the given price shift per unit of inventory.
+    #[must_use]
+    pub fn new(skew_per_unit: Decimal) -> Self {
+        Self {
+            skew_per_unit,
+            max_skew: None,
+        }
+    }
+
+    /// Caps the absolute price shift.
+    #[must_use]
+    pub fn with_max_skew(mut self, max_skew: Decimal) -> Self {
+        self.max_skew = Some(max_skew.abs());
+        self
+    }
+}
+
+impl QuoteOverlay for InventorySkewOverlay {
+    fn name(&self) -> &str {
+        "inventory_skew"
+    }
+
+    fn apply(&mut self, context: &QuoteContext, quotes: &mut QuoteSet) -> MMResult<Option<String>> {
+        let mut shift = -context.inventory * self.skew_per_unit;
+        if let Some(cap) = self.max_skew {
+            shift = shift.max(-cap).min(cap);
+        }
+
+        for level in quotes.bids.iter_mut().chain(quotes.asks.iter_mut()) {
+            level.price += shift;
+        }
+
+        Ok(Some(format!(
+            "inventory {} shifted quotes by {}",
+            context.inventory, shift
+        )))
+    }
+}
+
+/// Widens the side that order book imbalance points against.
+///
+/// Uses [`AdaptiveSpreadCalculator::calculate_spread`] on the context order
+/// book and applies the resulting per-side adjustment factors to the
+/// distances from the quote center. Without an order book the stage is
+/// neutral.
+#[derive(Debug, Clone)]
+pub struct ImbalanceWideningOverlay {
+    calculator: AdaptiveSpreadCalculator,
+    depth_levels: u32,
+}
+
+impl ImbalanceWideningOverlay {
+    /// Creates an imbalance overlay using the top 5 book levels.
+    #[must_use]
+    pub fn new(calculator: AdaptiveSpreadCalculator) -> Self {
+        Self {
+            calculator,
+            depth_levels: 5,
+        }
+    }
+
+    /// Sets the number of book levels used for the imbalance calculation.
+    #[must_use]
+    pub fn with_depth_levels(mut self, depth_levels: u32) -> Self {
+        self.depth_levels = depth_levels;
+        self
+    }
+}
+
+impl QuoteOverlay for ImbalanceWideningOverlay {
+    fn name(&self) -> &str {
+        "imbalance_widening"
+    }
+
+    fn apply(&mut self, context: &QuoteContext, quotes: &mut QuoteSet) -> MMResult<Option<String>> {
+        let Some(book) = &context.orderbook else {
+            return Ok(None);
+        };
+
+        let pairs = |levels: &[crate::execution::BookLevel]| {
+            levels
+                .iter()
+                .map(|l| (l.price, l.quantity))
+                .collect::<Vec<_>>()
+        };
+        let imbalance = AdaptiveSpreadCalculator::calculate_orderbook_imbalance(
+            &pairs(&book.bids),
+            &pairs(&book.asks),
+            self.depth_levels,
+        );
+        let spread = self.calculator.calculate_spread(&imbalance, None);
+        let half_spread = self.calculator.config().base_spread / Decimal::TWO;
+        let bid_multiplier = spread.bid_spread / half_spread;
+        let ask_multiplier = spread.ask_spread / half_spread;
+
+        let center = quote_center(context, quotes);
+        scale_distances(quotes, center, bid_multiplier, ask_multiplier);
+
+        Ok(Some(format!(
+            "imbalance {} scaled bid x{} ask x{}",
+            imbalance.imbalance.round_dp(4),
+            bid_multiplier.round_dp(4),
+            ask_multiplier.round_dp(4)
+        )))
+    }
+}
+
+/// Scales the spread by the volatility regime's spread multiplier.
+///
+/// The context volatility is compared against a baseline volatility by a
+/// [`VolatilityRegimeTracker`]; the
+/// [`crate::strategy::calibration::RegimeAdjustments::spread_multiplier`] of
+/// the confirmed regime is applied to both sides. Built with
+/// [`VolatilityRegimeOverlay::new`] the tracker has no hysteresis or dwell
+/// time and follows [`VolatilityRegimeDetector::detect_and_adjust`];
+/// [`VolatilityRegimeOverlay::from_tracker`] accepts a configured one.
+#[derive(Debug)]
+pub struct VolatilityRegimeOverlay {
+    tracker: VolatilityRegimeTracker,
+    baseline_volatility: Decimal,
+}
+
+impl VolatilityRegimeOverlay {
+    /// Creates a regime overlay.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `baseline_volatility` is
+    /// not positive or the detector's regime threshold is not above 1.
+    pub fn new(detector: VolatilityRegimeDetector, baseline_volatility: Decimal) -> MMResult<Self> {
+        let config = RegimeTrackerConfig::from_detector(&detector)?;
+        Self::from_tracker(
+            VolatilityRegimeTracker::new(detector, config),
+            baseline_volatility,
+        )
+    }
+
+    /// Creates a regime overlay around an existing tracker.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `baseline_volatility` is
+    /// not positive.
+    pub fn from_tracker(
+        tracker: VolatilityRegimeTracker,
+        baseline_volatility: Decimal,
+    ) -> MMResult<Self> {
+        if baseline_volatility <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "baseline_volatility must be positive".to_string(),
+            ));
+        }
+        Ok(Self {
+            tracker,
+            baseline_volatility,
+        })
+    }
+
+    /// Updates the baseline volatility.
+    pub fn set_baseline_volatility(&mut self, baseline_volatility: Decimal) {
+        self.baseline_volatility = baseline_volatility;
+    }
+
+    /// Returns the regime tracker.
+    #[must_use]
+    pub fn tracker(&self) -> &VolatilityRegimeTracker {
+        &self.tracker
+    }
+
+    /// Returns a mutable reference to the regime tracker.
+    pub fn tracker_mut(&mut self) -> &mut VolatilityRegimeTracker {
+        &mut self.tracker
+    }
+}
+
+impl QuoteOverlay for VolatilityRegimeOverlay {
+    fn name(&self) -> &str {
+        "volatility_regime"
+    }
+
+    fn apply(&mut self, context: &QuoteContext, quotes: &mut QuoteSet) -> MMResult<Option<String>> {
+        self.tracker.update(
+            context.volatility,
+            self.baseline_volatility,
+            context.timestamp,
+        );
+        let regime = self.tracker.regime();
+        let multiplier = self.tracker.adjustments().spread_multiplier;
+
+        let center = quote_center(context, quotes);
+        scale_distances(quotes, center, multiplier, multiplier);
+
+        Ok(Some(format!("{regime} regime scaled spread x{multiplier}")))
+    }
+
+    fn reset(&mut self) {
+        self.tracker.reset();
+    }
+}
+
+/// Widens quotes as VPIN rises.
+///
+/// Distances from the quote center are multiplied by
+/// `1 + sensitivity × VPIN`. Trades must be fed to the calculator through
+/// [`ToxicityOverlay::calculator_mut`]; while VPIN is unavailable the stage
+/// is neutral.
+///
+/// An attached [`ToxicityGuard`] adds its spread and size curves on top of
+/// the linear widening and pulls all quotes while VPIN is in its pulled band.
+#[derive(Debug)]
+pub struct ToxicityOverlay {
+    calculator: VPINCalculator,
+    sensitivity: Decimal,
+    guard: Option<ToxicityGuard>,
+}
+
+impl ToxicityOverlay {
+    /// Creates a toxicity overlay.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `sensitivity` is negative.
+    pub fn new(calculator: VPINCalculator, sensitivity: Decimal) -> MMResult<Self> {
+        if sensitivity < Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "sensitivity must be non-negative".to_string(),
+            ));
+        }
+        Ok(Self {
+            calculator,
+            sensitivity,
+            guard: None,
+        })
+    }
+
+    /// Attaches a guard with VPIN curves and quote pulling.
+    #[must_use]
+    pub fn with_guard(mut self, guard: ToxicityGuard) -> Self {
+        self.guard = Some(guard);
+        self
+    }
+
+    /// Returns the VPIN calculator.
+    #[must_use]
+    pub fn calculator(&self) -> &VPINCalculator {
+        &self.calculator
+    }
+
+    /// Returns a mutable reference to the VPIN calculator.
+    pub fn calculator_mut(&mut self) -> &mut VPINCalculator {
+        &mut self.calculator
+    }
+
+    /// Returns the attached guard.
+    #[must_use]
+    pub fn guard(&self) -> Option<&ToxicityGuard> {
+        self.guard.as_ref()
+    }
+
+    /// Returns a mutable reference to the attached guard.
+    pub fn guard_mut(&mut self) -> Option<&mut ToxicityGuard> {
+        self.guard.as_mut()
+    }
+}
+
+impl QuoteOverlay for ToxicityOverlay {
+    fn name(&self) -> &str {
+        "toxicity"
+    }
+
+    fn apply(&mut self, context: &QuoteContext, quotes: &mut QuoteSet) -> MMResult<Option<String>> {
+        let Some(vpin) = self.calculator.get_vpin() else {
+            return Ok(None);
+        };
+        let mut multiplier = Decimal::ONE + self.sensitivity * vpin;
+
+        let Some(guard) = self.guard.as_mut() else {
+            let center = quote_center(context, quotes);
+            scale_distances(quotes, center, multiplier, multiplier);
+
+            return Ok(Some(format!(
+                "VPIN {} scaled spread x{}",
+                vpin.round_dp(4),
+                multiplier.round_dp(4)
+            )));
+        };
+
+        let level = self.calculator.toxicity_level();
+        if guard.update(vpin, context.timestamp) == ToxicityState::Pulled {
+            quotes.bids.clear();
+            quotes.asks.clear();
+            return Ok(Some(format!(
+                "VPIN {} ({level}) pulled quotes",
+                vpin.round_dp(4)
+            )));
+        }
+
+        let (spread_multiplier, size_multiplier) = guard.multipliers(vpin);
+        multiplier *= spread_multiplier;
+
+        let center = quote_center(context, quotes);
+        scale_distances(quotes, center, multiplier, multiplier);
+        for level in quotes.bids.iter_mut().chain(quotes.asks.iter_mut()) {
+            level.size *= size_multiplier;
+        }
+
+        Ok(Some(format!(
+            "VPIN {} ({level}) scaled spread x{}, size x{}",
+            vpin.round_dp(4),
+            multiplier.round_dp(4),
+            size_multiplier.round_dp(4)
+        )))
+    }
+
+    fn reset(&mut self) {
+        self.calculator.reset();
+        if let Some(guard) = self.guard.as_mut() {
+            guard.reset();
+        }
+    }
+}
+
+/// Scales sizes on the side that would grow the position with
+/// [`RiskLimits::scale_order_size`].
+///
+/// Bids are scaled while long or flat, asks while short or flat; the side
+/// that reduces the position keeps its size. Levels scaled to zero are
+/// dropped.
+#[derive(Debug, Clone)]
+pub struct RiskLimitOverlay {
+    limits: RiskLimits,
+}
+
+impl RiskLimitOverlay {
+    /// Creates a risk-limit overlay.
+    #[must_use]
+    pub fn new(limits: RiskLimits) -> Self {
+        Self { limits }
+    }
+
+    /// Returns the risk limits.
+    #[must_use]
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+}
+
+impl QuoteOverlay for RiskLimitOverlay {
+    fn name(&self) -> &str {
+        "risk_limits"
+    }
+
+    fn apply(&mut self, context: &QuoteContext, quotes: &mut QuoteSet) -> MMResult<Option<String>> {
+        let inventory = context.inventory;
+        let scale = |levels: &mut Vec<QuoteLevel>| {
+            for level in levels.iter_mut() {
+                level.size = self.limits.scale_order_size(inventory, level.size);
+            }
+            levels.retain(|l| l.size > Decimal::ZERO);
+        };
+
+        if inventory >= Decimal::ZERO {
+            scale(&mut quotes.bids);
+        }
+        if inventory <= Decimal::ZERO {
+            scale(&mut quotes.asks);
+        }
+
+        Ok(Some(format!(
+            "position utilization {}",
+            self.limits.position_utilization(inventory).round_dp(4)
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::order_flow::{Trade, TradeSide};
+    use crate::analytics::vpin::VPINConfig;
+    use crate::dec;
+    use crate::execution::{BookLevel, OrderBookSnapshot};
+    use crate::strategy::adaptive_spread::AdaptiveSpreadConfig;
+
+    /// Quotes 99/101 with size 10 on each side.
+    struct FixedQuoter;
+
+    impl QuotingStrategy for FixedQuoter {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+            Ok(QuoteSet::single(
+                context.mid_price - dec!(1.0),
+                dec!(10.0),
+                context.mid_price + dec!(1.0),
+                dec!(10.0),
+                context.timestamp,
+            ))
+        }
+    }
+
+    fn pipeline() -> QuotePipeline {
+        QuotePipeline::new(Box::new(FixedQuoter))
+    }
+
+    fn context(inventory: Decimal) -> QuoteContext {
+        QuoteContext::new(dec!(100.0), dec!(0.2), inventory, 0)
+    }
+
+    #[test]
+    fn test_pipeline_without_overlays_returns_base() {
+        let mut pipeline = pipeline();
+        let quotes = pipeline.generate_quotes(&context(dec!(0.0))).unwrap();
+        assert_eq!(quotes.best_bid(), Some(dec!(99.0)));
+        assert_eq!(quotes.best_ask(), Some(dec!(101.0)));
+
+        let explanation = pipeline.last_explanation().unwrap();
+        assert_eq!(explanation.base, "fixed");
+        assert!(explanation.stages.is_empty());
+    }
+
+    #[test]
+    fn test_inventory_skew() {
+        let mut pipeline = pipeline().with_overlay(InventorySkewOverlay::new(dec!(0.1)));
+        let quotes = pipeline.generate_quotes(&context(dec!(5.0))).unwrap();
+        assert_eq!(quotes.best_bid(), Some(dec!(98.5)));
+        assert_eq!(quotes.best_ask(), Some(dec!(100.5)));
+
+        let stage = pipeline
+            .last_explanation()
+            .unwrap()
+            .stage("inventory_skew")
+            .unwrap();
+        assert_eq!(stage.bid_price_change, Some(dec!(-0.5)));
+        assert_eq!(stage.ask_price_change, Some(dec!(-0.5)));
+        assert!(stage.note.is_some());
+    }
+
+    #[test]
+    fn test_inventory_skew_cap() {
+        let mut pipeline =
+            pipeline().with_overlay(InventorySkewOverlay::new(dec!(0.1)).with_max_skew(dec!(0.2)));
+        let quotes = pipeline.generate_quotes(&context(dec!(-5.0))).unwrap();
+        assert_eq!(quotes.best_bid(), Some(dec!(99.2)));
+    }
+
+    #[test]
+    fn test_imbalance_widening() {
+        let config =
+            AdaptiveSpreadConfig::new(dec!(0.001), dec!(2.0), dec!(1.0), dec!(0.0)).unwrap();
+        let mut pipeline = pipeline().with_overlay(ImbalanceWideningOverlay::new(
+            AdaptiveSpreadCalculator::new(config),
+        ));
+
+        let mut book = OrderBookSnapshot::new("TEST", 0);
+        book.bids = vec![BookLevel::new(dec!(99.9), dec!(150.0))];
+        book.asks = vec![BookLevel::new(dec!(100.1), dec!(50.0))];
+        let quotes = pipeline
+            .generate_quotes(&context(dec!(0.0)).with_orderbook(book))
+            .unwrap();
+
+        // Bid-heavy book widens the ask and tightens the bid.
+        assert!(quotes.best_ask().unwrap() > dec!(101.0));
+        assert!(quotes.best_bid().unwrap() > dec!(99.0));
+    }
+
+    #[test]
+    fn test_imbalance_widening_without_book_is_neutral() {
+        let config =
+            AdaptiveSpreadConfig::new(dec!(0.001), dec!(2.0), dec!(1.0), dec!(0.0)).unwrap();
+        let mut pipeline = pipeline().with_overlay(ImbalanceWideningOverlay::new(
+            AdaptiveSpreadCalculator::new(config),
+        ));
+        pipeline.generate_quotes(&context(dec!(0.0))).unwrap();
+        assert!(pipeline.last_explanation().unwrap().stages[0].is_neutral());
+    }
+
+    #[test]
+    fn test_volatility_regime_widening() {
+        let overlay = VolatilityRegimeOverlay::new(
+            VolatilityRegimeDetector::new(dec!(1.5), 3_600_000),
+            dec!(0.1),
+        )
+        .unwrap();
+        let mut pipeline = pipeline().with_overlay(overlay);
+
+        // 0.2 / 0.1 = 2.0x baseline: High regime, spread x1.5
+        let quotes = pipeline.generate_quotes(&context(dec!(0.0))).unwrap();
+        assert_eq!(quotes.spread(), Some(dec!(3.0)));
+        assert!(
+            VolatilityRegimeOverlay::new(
+                VolatilityRegimeDetector::new(dec!(1.5), 3_600_000),
+                dec!(0.0)
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_toxicity_widening() {
+        let calculator = VPINCalculator::new(VPINConfig::new(dec!(10.0), 1, dec!(0.7)).unwrap());
+        let mut overlay = ToxicityOverlay::new(calculator, dec!(1.0)).unwrap();
+        overlay
+            .calculator_mut()
+            .add_trade(&Trade::new(dec!(100.0), dec!(10.0), TradeSide::Buy, 0));
+        let mut pipeline = pipeline().with_overlay(overlay);
+
+        // One fully one-sided bucket: VPIN = 1, spread doubles.
+        let quotes = pipeline.generate_quotes(&context(dec!(0.0))).unwrap();
+        assert_eq!(quotes.spread(), Some(dec!(4.0)));
+    }
+
+    #[test]
+    fn test_risk_limits_scale_growing_side_only() {
+        let limits = RiskLimits::new(dec!(100.0), dec!(1_000_000.0), dec!(1.0)).unwrap();
+        let mut pipeline = pipeline().with_overlay(RiskLimitOverlay::new(limits));
+
+        let quotes = pipeline.generate_quotes(&context(dec!(50.0))).unwrap();
+        assert_eq!(quotes.total_bid_size(), dec!(5.0));
+        assert_eq!(quotes.total_ask_size(), dec!(10.0));
+
+        let stage = &pipeline.last_explanation().unwrap().stages[0];
+        assert_eq!(stage.bid_size_change, dec!(-5.0));
+        assert_eq!(stage.ask_size_change, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_risk_limits_drop_side_at_limit() {
+        let limits = RiskLimits::new(dec!(100.0), dec!(1_000_000.0), dec!(1.0)).unwrap();
+        let mut pipeline = pipeline().with_overlay(RiskLimitOverlay::new(limits));
+
+        let quotes = pipeline.generate_quotes(&context(dec!(-100.0))).unwrap();
+        assert!(quotes.asks.is_empty());
+        assert_eq!(quotes.total_bid_size(), dec!(10.0));
+        assert_eq!(
+            pipeline.last_explanation().unwrap().stages[0].ask_price_change,
+            None
+        );
+    }
+
+    #[test]
+    fn test_stages_apply_in_order() {
+        let limits = RiskLimits::new(dec!(100.0), dec!(1_000_000.0), dec!(1.0)).unwrap();
+        let mut pipeline = pipeline()
+            .with_overlay(InventorySkewOverlay::new(dec!(0.1)))
+            .with_overlay(
+                VolatilityRegimeOverlay::new(
+                    VolatilityRegimeDetector::new(dec!(1.5), 3_600_000),
+                    dec!(0.1),
+                )
+                .unwrap(),
+            )
+            .with_overlay(RiskLimitOverlay::new(limits));
+        assert_eq!(
+            pipeline.overlay_names(),
+            vec!["inventory_skew", "volatility_regime", "risk_limits"]
+        );
+
+        let (quotes, explanation) = pipeline.generate_explained(&context(dec!(10.0))).unwrap();
+        // Skew moves the center to 99.0, the regime widens around it.
+        assert_eq!(quotes.best_bid(), Some(dec!(97.5)));
+        assert_eq!(quotes.best_ask(), Some(dec!(100.5)));
+        assert_eq!(explanation.stages.len(), 3);
+        assert_eq!(explanation.base_quotes.best_bid(), Some(dec!(99.0)));
+    }
+
+    #[test]
+    fn test_reset_clears_explanation() {
+        let mut pipeline = pipeline().with_overlay(InventorySkewOverlay::new(dec!(0.1)));
+        pipeline.generate_quotes(&context(dec!(1.0))).unwrap();
+        assert!(pipeline.last_explanation().is_some());
+        pipeline.reset();
+        assert!(pipeline.last_explanation().is_none());
+    }
+}
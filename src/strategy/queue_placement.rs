@@ -0,0 +1,855 @@
+//! Queue-aware quote placement at the touch.
+//!
+//! Pricing models decide *how far* from fair value to quote, but on a
+//! price-time priority book the fill rate at a given price depends mostly on
+//! how much size is queued ahead of us. This module takes an
+//! [`OrderBookSnapshot`] and our resting orders and, for each side, chooses
+//! between:
+//!
+//! - **Join**: rest at the current best price, behind the displayed queue
+//! - **Improve**: step one tick inside the spread with an empty queue
+//! - **Back off**: rest one or more ticks behind the touch
+//!
+//! # Model
+//!
+//! Market orders reaching a relative distance `δ` from mid arrive at rate
+//! `λ(δ) = A · exp(-k · δ)` (from [`OrderIntensityEstimator`]). Each arrival
+//! consumes on average `v` units, so an order of size `s` with `Q` units
+//! queued ahead needs `n = ceil((Q + s) / v)` arrivals to fill completely.
+//! Behind the touch, `Q` includes every level at a better price:
+//!
+//! ```text
+//! P(fill within t) = P(N ≥ n),  N ~ Poisson(λ(δ) · t)
+//! ```
+//!
+//! Each candidate price is scored by its expected edge
+//! `P(fill) · (fair_value - price)` for bids (mirrored for asks) and the best
+//! one is chosen. Join and improve are only considered when their edge is at
+//! least `min_edge`; backing off is always allowed.
+//!
+//! Queue ahead of our own resting orders is tracked with [`RestingOrder`]:
+//! it starts at the displayed size when the order is placed and can only
+//! shrink as later snapshots show the level being consumed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::execution::{BookLevel, OrderBookSnapshot, Side};
+//! use market_maker_rs::strategy::queue_placement::{
+//!     PlacementAction, QueuePlacementConfig, QueuePlacer,
+//! };
+//! use market_maker_rs::dec;
+//!
+//! let mut book = OrderBookSnapshot::new("BTC-USD", 0);
+//! book.bids = vec![BookLevel::new(dec!(99.5), dec!(50.0))];
+//! book.asks = vec![BookLevel::new(dec!(100.5), dec!(50.0))];
+//!
+//! let config = QueuePlacementConfig::new(dec!(0.1), dec!(1.0), 1_000).unwrap();
+//! let placer = QueuePlacer::new(config);
+//!
+//! let decision = placer
+//!     .decide(Side::Buy, &book, &[], dec!(100.0), dec!(1.0), None)
+//!     .unwrap();
+//!
+//! // Fifty units queued at the touch: stepping inside is worth the tick.
+//! assert_eq!(decision.action, PlacementAction::Improve);
+//! assert_eq!(decision.price, dec!(99.6));
+//! ```
+
+use crate::Decimal;
+use crate::analytics::intensity::OrderIntensityEstimator;
+use crate::execution::order_manager::ManagedOrder;
+use crate::execution::{OrderBookSnapshot, OrderId, Side};
+use crate::types::decimal::decimal_exp;
+use crate::types::error::{MMError, MMResult};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Configuration for queue-aware placement.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QueuePlacementConfig {
+    /// Minimum price increment.
+    pub tick_size: Decimal,
+
+    /// Average size of a market order consuming the queue.
+    pub average_trade_size: Decimal,
+
+    /// Horizon over which fill probabilities are evaluated, in milliseconds.
+    pub fill_horizon_ms: u64,
+
+    /// Minimum edge per unit versus fair value required to join or improve.
+    pub min_edge: Decimal,
+
+    /// Deepest back-off considered, in ticks behind the touch.
+    pub max_back_off_ticks: u32,
+
+    /// Arrival rate at zero distance (A), per second, used when the
+    /// estimator has no estimate.
+    pub fallback_arrival_rate: Decimal,
+
+    /// Order intensity (k), on relative distance from mid, used when the
+    /// estimator has no estimate.
+    pub fallback_order_intensity: Decimal,
+}
+
+impl QueuePlacementConfig {
+    /// Creates a new `QueuePlacementConfig` with validation.
+    ///
+    /// # Arguments
+    ///
+    /// * `tick_size` - Minimum price increment, must be positive
+    /// * `average_trade_size` - Average market order size, must be positive
+    /// * `fill_horizon_ms` - Fill probability horizon, must be positive
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if parameters are invalid.
+    pub fn new(
+        tick_size: Decimal,
+        average_trade_size: Decimal,
+        fill_horizon_ms: u64,
+    ) -> MMResult<Self> {
+        if tick_size <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "tick_size must be positive".to_string(),
+            ));
+        }
+
+        if average_trade_size <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "average_trade_size must be positive".to_string(),
+            ));
+        }
+
+        if fill_horizon_ms == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "fill_horizon_ms must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            tick_size,
+            average_trade_size,
+            fill_horizon_ms,
+            min_edge: Decimal::ZERO,
+            max_back_off_ticks: 1,
+            fallback_arrival_rate: Decimal::ONE,
+            fallback_order_intensity: Decimal::from(100),
+        })
+    }
+
+    /// Sets the minimum edge required to join or improve.
+    #[must_use]
+    pub fn with_min_edge(mut self, min_edge: Decimal) -> Self {
+        self.min_edge = min_edge;
+        self
+    }
+
+    /// Sets the deepest back-off considered, in ticks. Values below one are
+    /// treated as one.
+    #[must_use]
+    pub fn with_max_back_off_ticks(mut self, ticks: u32) -> Self {
+        self.max_back_off_ticks = ticks.max(1);
+        self
+    }
+
+    /// Sets the intensity parameters used without an estimate.
+    #[must_use]
+    pub fn with_fallback_intensity(
+        mut self,
+        arrival_rate: Decimal,
+        order_intensity: Decimal,
+    ) -> Self {
+        self.fallback_arrival_rate = arrival_rate;
+        self.fallback_order_intensity = order_intensity;
+        self
+    }
+}
+
+/// One of our orders resting on the book, with its estimated queue position.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RestingOrder {
+    /// Exchange-assigned order ID.
+    pub order_id: OrderId,
+
+    /// Order side.
+    pub side: Side,
+
+    /// Limit price.
+    pub price: Decimal,
+
+    /// Remaining quantity.
+    pub quantity: Decimal,
+
+    /// Estimated quantity queued ahead of this order at its price.
+    pub queue_ahead: Decimal,
+}
+
+impl RestingOrder {
+    /// Creates a resting order with a known queue position.
+    #[must_use]
+    pub fn new(
+        order_id: OrderId,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+        queue_ahead: Decimal,
+    ) -> Self {
+        Self {
+            order_id,
+            side,
+            price,
+            quantity,
+            queue_ahead: queue_ahead.max(Decimal::ZERO),
+        }
+    }
+
+    /// Creates a resting order that has just been placed, queued behind the
+    /// size displayed at its price in `snapshot` (taken before the order
+    /// reached the book).
+    #[must_use]
+    pub fn placed(
+        order_id: OrderId,
+        side: Side,
+        price: Decimal,
+        quantity: Decimal,
+        snapshot: &OrderBookSnapshot,
+    ) -> Self {
+        let queue_ahead = level_quantity(snapshot, side, price);
+        Self::new(order_id, side, price, quantity, queue_ahead)
+    }
+
+    /// Creates a resting order from a managed order, queued behind the size
+    /// displayed at its price in `snapshot`.
+    #[must_use]
+    pub fn from_managed(order: &ManagedOrder, snapshot: &OrderBookSnapshot) -> Self {
+        Self::placed(
+            order.order_id.clone(),
+            order.side,
+            order.original_price,
+            order.remaining_quantity,
+            snapshot,
+        )
+    }
+
+    /// Refines the queue estimate from a new snapshot.
+    ///
+    /// The displayed level includes this order, so at most
+    /// `level - quantity` can be ahead of it. The estimate never grows: size
+    /// joining the level later queues behind us.
+    pub fn update(&mut self, snapshot: &OrderBookSnapshot) {
+        let levels = match self.side {
+            Side::Buy => &snapshot.bids,
+            Side::Sell => &snapshot.asks,
+        };
+
+        match levels.iter().find(|l| l.price == self.price) {
+            Some(level) => {
+                let bound = (level.quantity - self.quantity).max(Decimal::ZERO);
+                self.queue_ahead = self.queue_ahead.min(bound);
+            }
+            None if at_or_better_than_touch(snapshot, self.side, self.price) => {
+                self.queue_ahead = Decimal::ZERO;
+            }
+            None => {}
+        }
+    }
+}
+
+/// Placement chosen for one side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PlacementAction {
+    /// Rest at the current best price.
+    Join,
+    /// Rest one tick inside the spread.
+    Improve,
+    /// Rest behind the touch.
+    BackOff {
+        /// Distance behind the touch, in ticks.
+        ticks: u32,
+    },
+}
+
+/// Placement decision for one side of the book.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlacementDecision {
+    /// Side of the book.
+    pub side: Side,
+
+    /// Chosen action.
+    pub action: PlacementAction,
+
+    /// Price to quote.
+    pub price: Decimal,
+
+    /// Estimated quantity ahead of an order at `price`, including all size
+    /// displayed at better prices.
+    pub queue_ahead: Decimal,
+
+    /// Probability of filling the whole order within the horizon.
+    pub fill_probability: Decimal,
+
+    /// Expected edge per unit: `fill_probability` times the distance to fair value.
+    pub expected_edge: Decimal,
+
+    /// Resting order already at `price` that should be kept to preserve its
+    /// queue position.
+    pub keep_order: Option<OrderId>,
+}
+
+/// Chooses between joining, improving and backing off at the touch.
+#[derive(Debug, Clone)]
+pub struct QueuePlacer {
+    config: QueuePlacementConfig,
+}
+
+impl QueuePlacer {
+    /// Creates a new queue placer.
+    #[must_use]
+    pub fn new(config: QueuePlacementConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the placement configuration.
+    #[must_use]
+    pub fn config(&self) -> &QueuePlacementConfig {
+        &self.config
+    }
+
+    /// Decides where to place an order on one side of the book.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - Side to place on
+    /// * `snapshot` - Current order book
+    /// * `resting` - Our resting orders; only those on `side` are used
+    /// * `fair_value` - Price the edge is measured against (mid, microprice,
+    ///   reservation price...)
+    /// * `order_size` - Size of the order to place, must be positive
+    /// * `estimator` - Optional intensity estimator; the configured fallback
+    ///   intensity is used when it has no estimate
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if either side of the book is
+    /// empty, and `MMError::InvalidConfiguration` if `order_size` is not
+    /// positive.
+    pub fn decide(
+        &self,
+        side: Side,
+        snapshot: &OrderBookSnapshot,
+        resting: &[RestingOrder],
+        fair_value: Decimal,
+        order_size: Decimal,
+        estimator: Option<&OrderIntensityEstimator>,
+    ) -> MMResult<PlacementDecision> {
+        if order_size <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "order_size must be positive".to_string(),
+            ));
+        }
+
+        let (touch, opposite) = match (snapshot.best_bid(), snapshot.best_ask()) {
+            (Some(bid), Some(ask)) => match side {
+                Side::Buy => (bid, ask),
+                Side::Sell => (ask, bid),
+            },
+            _ => {
+                return Err(MMError::InvalidMarketState(
+                    "order book must have both bids and asks".to_string(),
+                ));
+            }
+        };
+        let mid_price = (touch + opposite) / Decimal::TWO;
+        let (baseline_rate, order_intensity) = self.intensity(estimator);
+        let tick = self.config.tick_size;
+
+        let inward = |ticks: Decimal| match side {
+            Side::Buy => touch + tick * ticks,
+            Side::Sell => touch - tick * ticks,
+        };
+
+        let mut candidates = Vec::with_capacity(self.config.max_back_off_ticks as usize + 2);
+
+        let improve_price = inward(Decimal::ONE);
+        let improves_on_others = !resting.iter().any(|order| {
+            order.side == side
+                && order.price == touch
+                && order.quantity >= level_quantity(snapshot, side, touch)
+        });
+        let crosses = match side {
+            Side::Buy => improve_price >= opposite,
+            Side::Sell => improve_price <= opposite,
+        };
+        if improves_on_others && !crosses && improve_price > Decimal::ZERO {
+            candidates.push((PlacementAction::Improve, improve_price));
+        }
+        candidates.push((PlacementAction::Join, touch));
+        for ticks in 1..=self.config.max_back_off_ticks {
+            let price = inward(-Decimal::from(ticks));
+            if price <= Decimal::ZERO {
+                break;
+            }
+            candidates.push((PlacementAction::BackOff { ticks }, price));
+        }
+
+        let mut best: Option<PlacementDecision> = None;
+        for (action, price) in candidates {
+            let edge = match side {
+                Side::Buy => fair_value - price,
+                Side::Sell => price - fair_value,
+            };
+            if !matches!(action, PlacementAction::BackOff { .. }) && edge < self.config.min_edge {
+                continue;
+            }
+
+            let own = resting
+                .iter()
+                .find(|order| order.side == side && order.price == price);
+            let queue_ahead = depth_ahead(snapshot, side, price)
+                + match own {
+                    Some(order) => order.queue_ahead,
+                    None => level_quantity(snapshot, side, price),
+                };
+
+            let distance = match side {
+                Side::Buy => mid_price - price,
+                Side::Sell => price - mid_price,
+            }
+            .max(Decimal::ZERO)
+                / mid_price;
+            let fill_probability = self.queue_fill_probability(
+                baseline_rate,
+                order_intensity,
+                distance,
+                queue_ahead + order_size,
+            )?;
+            let expected_edge = fill_probability * edge;
+
+            if best
+                .as_ref()
+                .is_none_or(|current| expected_edge > current.expected_edge)
+            {
+                best = Some(PlacementDecision {
+                    side,
+                    action,
+                    price,
+                    queue_ahead,
+                    fill_probability,
+                    expected_edge,
+                    keep_order: own.map(|order| order.order_id.clone()),
+                });
+            }
+        }
+
+        best.ok_or_else(|| MMError::InvalidQuoteGeneration("no valid placement price".to_string()))
+    }
+
+    /// Decides placements for both sides.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors from [`QueuePlacer::decide`].
+    pub fn decide_both(
+        &self,
+        snapshot: &OrderBookSnapshot,
+        resting: &[RestingOrder],
+        fair_value: Decimal,
+        order_size: Decimal,
+        estimator: Option<&OrderIntensityEstimator>,
+    ) -> MMResult<(PlacementDecision, PlacementDecision)> {
+        let bid = self.decide(
+            Side::Buy,
+            snapshot,
+            resting,
+            fair_value,
+            order_size,
+            estimator,
+        )?;
+        let ask = self.decide(
+            Side::Sell,
+            snapshot,
+            resting,
+            fair_value,
+            order_size,
+            estimator,
+        )?;
+        Ok((bid, ask))
+    }
+
+    /// Probability that at least `ceil(volume / v)` market orders reach
+    /// relative distance `distance` within the fill horizon, where `volume`
+    /// is the queue ahead plus our own size.
+    fn queue_fill_probability(
+        &self,
+        baseline_rate: Decimal,
+        order_intensity: Decimal,
+        distance: Decimal,
+        volume: Decimal,
+    ) -> MMResult<Decimal> {
+        let rate = baseline_rate * decimal_exp(-order_intensity * distance)?;
+        let horizon_seconds = Decimal::from(self.config.fill_horizon_ms) / Decimal::from(1000);
+        let mean = (rate * horizon_seconds)
+            .to_f64()
+            .ok_or_else(|| MMError::NumericalError("arrival mean out of range".to_string()))?;
+        let arrivals_needed = (volume / self.config.average_trade_size)
+            .ceil()
+            .to_u64()
+            .ok_or_else(|| MMError::NumericalError("queue size out of range".to_string()))?;
+
+        Decimal::from_f64(poisson_tail(mean, arrivals_needed))
+            .ok_or_else(|| MMError::NumericalError("fill probability out of range".to_string()))
+    }
+
+    fn intensity(&self, estimator: Option<&OrderIntensityEstimator>) -> (Decimal, Decimal) {
+        estimator
+            .and_then(OrderIntensityEstimator::get_estimate)
+            .map(|estimate| (estimate.baseline_rate, estimate.k))
+            .unwrap_or((
+                self.config.fallback_arrival_rate,
+                self.config.fallback_order_intensity,
+            ))
+    }
+}
+
+/// Displayed quantity at `price` on `side`, zero if the level is absent.
+fn level_quantity(snapshot: &OrderBookSnapshot, side: Side, price: Decimal) -> Decimal {
+    let levels = match side {
+        Side::Buy => &snapshot.bids,
+        Side::Sell => &snapshot.asks,
+    };
+    levels
+        .iter()
+        .find(|l| l.price == price)
+        .map_or(Decimal::ZERO, |l| l.quantity)
+}
+
+/// Displayed quantity at prices strictly better than `price` on `side`.
+fn depth_ahead(snapshot: &OrderBookSnapshot, side: Side, price: Decimal) -> Decimal {
+    match side {
+        Side::Buy => snapshot
+            .bids
+            .iter()
+            .filter(|l| l.price > price)
+            .map(|l| l.quantity)
+            .sum(),
+        Side::Sell => snapshot
+            .asks
+            .iter()
+            .filter(|l| l.price < price)
+            .map(|l| l.quantity)
+            .sum(),
+    }
+}
+
+fn at_or_better_than_touch(snapshot: &OrderBookSnapshot, side: Side, price: Decimal) -> bool {
+    match side {
+        Side::Buy => snapshot.best_bid().is_none_or(|bid| price >= bid),
+        Side::Sell => snapshot.best_ask().is_none_or(|ask| price <= ask),
+    }
+}
+
+/// `P(N ≥ n)` for `N ~ Poisson(mean)`, summing the CDF in log space.
+fn poisson_tail(mean: f64, n: u64) -> f64 {
+    if n == 0 {
+        return 1.0;
+    }
+    if mean <= 0.0 {
+        return 0.0;
+    }
+
+    let ln_mean = mean.ln();
+    let mut ln_term = -mean;
+    let mut cdf = 0.0;
+    for i in 0..n {
+        if i > 0 {
+            ln_term += ln_mean - (i as f64).ln();
+        }
+        cdf += ln_term.exp();
+        if i as f64 > mean && ln_term < -745.0 {
+            break;
+        }
+    }
+
+    (1.0 - cdf).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+    use crate::execution::{BookLevel, OrderType};
+
+    fn book(bid_size: Decimal) -> OrderBookSnapshot {
+        let mut snapshot = OrderBookSnapshot::new("TEST", 0);
+        snapshot.bids = vec![
+            BookLevel::new(dec!(99.5), bid_size),
+            BookLevel::new(dec!(99.4), dec!(5.0)),
+        ];
+        snapshot.asks = vec![
+            BookLevel::new(dec!(100.5), dec!(50.0)),
+            BookLevel::new(dec!(100.6), dec!(5.0)),
+        ];
+        snapshot
+    }
+
+    fn placer() -> QueuePlacer {
+        QueuePlacer::new(QueuePlacementConfig::new(dec!(0.1), dec!(1.0), 1_000).unwrap())
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(QueuePlacementConfig::new(dec!(0.0), dec!(1.0), 1_000).is_err());
+        assert!(QueuePlacementConfig::new(dec!(0.1), dec!(0.0), 1_000).is_err());
+        assert!(QueuePlacementConfig::new(dec!(0.1), dec!(1.0), 0).is_err());
+
+        let config = QueuePlacementConfig::new(dec!(0.1), dec!(1.0), 1_000)
+            .unwrap()
+            .with_max_back_off_ticks(0);
+        assert_eq!(config.max_back_off_ticks, 1);
+    }
+
+    #[test]
+    fn test_poisson_tail() {
+        assert_eq!(poisson_tail(1.0, 0), 1.0);
+        assert_eq!(poisson_tail(0.0, 1), 0.0);
+        assert!((poisson_tail(2.0, 1) - (1.0 - (-2.0f64).exp())).abs() < 1e-12);
+        assert!(poisson_tail(1.0, 50) < 1e-12);
+    }
+
+    #[test]
+    fn test_improves_over_long_queue() {
+        let decision = placer()
+            .decide(
+                Side::Buy,
+                &book(dec!(50.0)),
+                &[],
+                dec!(100.0),
+                dec!(1.0),
+                None,
+            )
+            .unwrap();
+        assert_eq!(decision.action, PlacementAction::Improve);
+        assert_eq!(decision.price, dec!(99.6));
+        assert_eq!(decision.queue_ahead, Decimal::ZERO);
+        assert!(decision.keep_order.is_none());
+    }
+
+    #[test]
+    fn test_joins_short_queue() {
+        let decision = placer()
+            .decide(
+                Side::Buy,
+                &book(dec!(0.5)),
+                &[],
+                dec!(100.0),
+                dec!(0.5),
+                None,
+            )
+            .unwrap();
+        assert_eq!(decision.action, PlacementAction::Join);
+        assert_eq!(decision.price, dec!(99.5));
+    }
+
+    #[test]
+    fn test_order_size_counts_toward_fill() {
+        // Queue and order fit in one average trade: joining keeps the edge.
+        let small = placer()
+            .decide(
+                Side::Buy,
+                &book(dec!(0.5)),
+                &[],
+                dec!(100.0),
+                dec!(0.5),
+                None,
+            )
+            .unwrap();
+        // A full unit behind the same queue needs a second trade to complete.
+        let large = placer()
+            .decide(
+                Side::Buy,
+                &book(dec!(0.5)),
+                &[],
+                dec!(100.0),
+                dec!(1.0),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(small.action, PlacementAction::Join);
+        assert_eq!(large.action, PlacementAction::Improve);
+    }
+
+    #[test]
+    fn test_ask_side_mirrors_bid() {
+        let decision = placer()
+            .decide(
+                Side::Sell,
+                &book(dec!(0.5)),
+                &[],
+                dec!(100.0),
+                dec!(1.0),
+                None,
+            )
+            .unwrap();
+        assert_eq!(decision.action, PlacementAction::Improve);
+        assert_eq!(decision.price, dec!(100.4));
+    }
+
+    #[test]
+    fn test_no_improve_when_spread_is_one_tick() {
+        let mut snapshot = book(dec!(50.0));
+        snapshot.asks[0].price = dec!(99.6);
+        let decision = placer()
+            .decide(Side::Buy, &snapshot, &[], dec!(99.55), dec!(1.0), None)
+            .unwrap();
+        assert_ne!(decision.action, PlacementAction::Improve);
+    }
+
+    #[test]
+    fn test_backs_off_when_touch_has_no_edge() {
+        let placer = QueuePlacer::new(
+            QueuePlacementConfig::new(dec!(0.1), dec!(1.0), 1_000)
+                .unwrap()
+                .with_min_edge(dec!(0.05))
+                .with_max_back_off_ticks(3),
+        );
+        let decision = placer
+            .decide(
+                Side::Buy,
+                &book(dec!(0.5)),
+                &[],
+                dec!(99.52),
+                dec!(1.0),
+                None,
+            )
+            .unwrap();
+        assert!(matches!(decision.action, PlacementAction::BackOff { .. }));
+        assert!(decision.price < dec!(99.5));
+    }
+
+    #[test]
+    fn test_back_off_queues_behind_better_levels() {
+        let placer = QueuePlacer::new(
+            QueuePlacementConfig::new(dec!(0.1), dec!(1.0), 1_000)
+                .unwrap()
+                .with_min_edge(dec!(1.0)),
+        );
+        let decision = placer
+            .decide(
+                Side::Buy,
+                &book(dec!(50.0)),
+                &[],
+                dec!(100.0),
+                dec!(1.0),
+                None,
+            )
+            .unwrap();
+        assert_eq!(decision.action, PlacementAction::BackOff { ticks: 1 });
+        assert_eq!(decision.queue_ahead, dec!(55.0));
+    }
+
+    #[test]
+    fn test_keeps_resting_order_with_queue_priority() {
+        let resting = [RestingOrder::new(
+            OrderId::new("ours"),
+            Side::Buy,
+            dec!(99.5),
+            dec!(1.0),
+            Decimal::ZERO,
+        )];
+        let decision = placer()
+            .decide(
+                Side::Buy,
+                &book(dec!(50.0)),
+                &resting,
+                dec!(100.0),
+                dec!(1.0),
+                None,
+            )
+            .unwrap();
+        assert_eq!(decision.action, PlacementAction::Join);
+        assert_eq!(decision.keep_order, Some(OrderId::new("ours")));
+        assert_eq!(decision.queue_ahead, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_does_not_penny_own_order() {
+        let resting = [RestingOrder::new(
+            OrderId::new("ours"),
+            Side::Buy,
+            dec!(99.5),
+            dec!(1.0),
+            Decimal::ZERO,
+        )];
+        let decision = placer()
+            .decide(
+                Side::Buy,
+                &book(dec!(1.0)),
+                &resting,
+                dec!(100.0),
+                dec!(1.0),
+                None,
+            )
+            .unwrap();
+        assert_eq!(decision.action, PlacementAction::Join);
+    }
+
+    #[test]
+    fn test_resting_order_queue_only_shrinks() {
+        let mut order = RestingOrder::placed(
+            OrderId::new("ours"),
+            Side::Buy,
+            dec!(99.5),
+            dec!(1.0),
+            &book(dec!(10.0)),
+        );
+        assert_eq!(order.queue_ahead, dec!(10.0));
+
+        order.update(&book(dec!(5.0)));
+        assert_eq!(order.queue_ahead, dec!(4.0));
+
+        order.update(&book(dec!(30.0)));
+        assert_eq!(order.queue_ahead, dec!(4.0));
+    }
+
+    #[test]
+    fn test_resting_order_from_managed() {
+        let managed = ManagedOrder::new(
+            OrderId::new("managed"),
+            "client".to_string(),
+            "TEST".to_string(),
+            Side::Sell,
+            OrderType::Limit,
+            dec!(100.5),
+            dec!(2.0),
+            0,
+        );
+        let order = RestingOrder::from_managed(&managed, &book(dec!(1.0)));
+        assert_eq!(order.queue_ahead, dec!(50.0));
+        assert_eq!(order.quantity, dec!(2.0));
+    }
+
+    #[test]
+    fn test_empty_book_error() {
+        let snapshot = OrderBookSnapshot::new("TEST", 0);
+        let result = placer().decide(Side::Buy, &snapshot, &[], dec!(100.0), dec!(1.0), None);
+        assert!(matches!(result, Err(MMError::InvalidMarketState(_))));
+    }
+
+    #[test]
+    fn test_decide_both() {
+        let (bid, ask) = placer()
+            .decide_both(&book(dec!(50.0)), &[], dec!(100.0), dec!(1.0), None)
+            .unwrap();
+        assert_eq!(bid.side, Side::Buy);
+        assert_eq!(ask.side, Side::Sell);
+        assert!(bid.price < ask.price);
+    }
+}
@@ -0,0 +1,1306 @@
+//! Unified quoting interface shared by every strategy in the crate.
+//!
+//! Each pricing model in [`crate::strategy`] historically exposed its own API:
+//! the Avellaneda-Stoikov trait works on raw parameters, [`GLFTStrategy`] takes a
+//! [`GLFTConfig`], [`GridStrategy`] emits [`GridOrder`]s and the adaptive and
+//! depth-based calculators return partial results. This module puts all of them
+//! behind a single object-safe trait, [`QuotingStrategy`], so that engines,
+//! backtests and the API can hold a `Box<dyn QuotingStrategy>` and swap the
+//! underlying model from configuration.
+//!
+//! # Data Flow
+//!
+//! ```text
+//! QuoteContext (mid, book, σ, q, t) ──► QuotingStrategy ──► QuoteSet (bids[], asks[])
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::strategy::config::StrategyConfig;
+//! use market_maker_rs::strategy::quoting::{
+//!     AvellanedaStoikovQuoter, QuoteContext, QuotingStrategy,
+//! };
+//! use market_maker_rs::dec;
+//!
+//! let config = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+//! let mut strategy: Box<dyn QuotingStrategy> =
+//!     Box::new(AvellanedaStoikovQuoter::new(config, dec!(1.0)).unwrap());
+//!
+//! let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0);
+//! let quotes = strategy.generate_quotes(&context).unwrap();
+//!
+//! assert_eq!(quotes.bids.len(), 1);
+//! assert_eq!(quotes.asks.len(), 1);
+//! assert!(quotes.best_bid().unwrap() < quotes.best_ask().unwrap());
+//! ```
+
+use crate::Decimal;
+use crate::execution::{BookLevel, OrderBookSnapshot, Side};
+use crate::market_state::snapshot::MarketState;
+use crate::strategy::adaptive_spread::{AdaptiveSpreadCalculator, AdaptiveSpreadConfig};
+use crate::strategy::config::StrategyConfig;
+use crate::strategy::depth_based::DepthBasedOffering;
+use crate::strategy::glft::{GLFTConfig, GLFTStrategy};
+use crate::strategy::grid::{GridConfig, GridOrder, GridStrategy, OrderSide};
+use crate::strategy::interface::{AvellanedaStoikov, DefaultAvellanedaStoikov};
+use crate::strategy::ladder::{LadderConfig, LadderModel, LadderQuoter};
+use crate::strategy::quote::Quote;
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Market context passed to a [`QuotingStrategy`] on every quote cycle.
+///
+/// Only the mid price, volatility, inventory and timestamp are mandatory.
+/// Strategies that need depth information (depth-based offering, adaptive
+/// spread) read it from the optional order book snapshot.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteContext {
+    /// Current reference mid price.
+    pub mid_price: Decimal,
+
+    /// Volatility estimate (annualized).
+    pub volatility: Decimal,
+
+    /// Current inventory (positive = long, negative = short).
+    pub inventory: Decimal,
+
+    /// Current timestamp in milliseconds.
+    pub timestamp: u64,
+
+    /// Optional order book snapshot.
+    pub orderbook: Option<OrderBookSnapshot>,
+}
+
+impl QuoteContext {
+    /// Creates a new quote context without order book information.
+    ///
+    /// # Arguments
+    ///
+    /// * `mid_price` - Current mid price
+    /// * `volatility` - Volatility estimate (annualized)
+    /// * `inventory` - Current inventory position
+    /// * `timestamp` - Current timestamp in milliseconds
+    #[must_use]
+    pub fn new(
+        mid_price: Decimal,
+        volatility: Decimal,
+        inventory: Decimal,
+        timestamp: u64,
+    ) -> Self {
+        Self {
+            mid_price,
+            volatility,
+            inventory,
+            timestamp,
+            orderbook: None,
+        }
+    }
+
+    /// Creates a quote context from a [`MarketState`] and the current inventory.
+    ///
+    /// The state's mid price, volatility, timestamp and order book are used.
+    #[must_use]
+    pub fn from_market_state(state: &MarketState, inventory: Decimal) -> Self {
+        Self {
+            mid_price: state.mid_price,
+            volatility: state.volatility,
+            inventory,
+            timestamp: state.timestamp,
+            orderbook: state.orderbook.clone(),
+        }
+    }
+
+    /// Attaches an order book snapshot to the context.
+    #[must_use]
+    pub fn with_orderbook(mut self, orderbook: OrderBookSnapshot) -> Self {
+        self.orderbook = Some(orderbook);
+        self
+    }
+
+    /// Returns the order book snapshot, or an error if none is attached.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if the context has no order book.
+    pub fn require_orderbook(&self) -> MMResult<&OrderBookSnapshot> {
+        self.orderbook.as_ref().ok_or_else(|| {
+            MMError::InvalidMarketState("order book snapshot is required".to_string())
+        })
+    }
+}
+
+/// A single price level of a [`QuoteSet`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteLevel {
+    /// Quote price.
+    pub price: Decimal,
+
+    /// Quote size.
+    pub size: Decimal,
+}
+
+impl QuoteLevel {
+    /// Creates a new quote level.
+    #[must_use]
+    pub fn new(price: Decimal, size: Decimal) -> Self {
+        Self { price, size }
+    }
+
+    /// Returns the notional value of the level (price × size).
+    #[must_use]
+    pub fn notional(&self) -> Decimal {
+        self.price * self.size
+    }
+}
+
+/// Multi-level quotes produced by a [`QuotingStrategy`].
+///
+/// Bids are ordered from best (highest) to worst, asks from best (lowest)
+/// to worst. A side may be empty when the strategy decides not to quote it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteSet {
+    /// Bid levels, best first.
+    pub bids: Vec<QuoteLevel>,
+
+    /// Ask levels, best first.
+    pub asks: Vec<QuoteLevel>,
+
+    /// Timestamp at which the quotes were generated, in milliseconds.
+    pub timestamp: u64,
+}
+
+impl QuoteSet {
+    /// Creates a quote set, sorting both sides best-first and dropping
+    /// levels with a non-positive price or size.
+    #[must_use]
+    pub fn new(mut bids: Vec<QuoteLevel>, mut asks: Vec<QuoteLevel>, timestamp: u64) -> Self {
+        bids.retain(|l| l.price > Decimal::ZERO && l.size > Decimal::ZERO);
+        asks.retain(|l| l.price > Decimal::ZERO && l.size > Decimal::ZERO);
+        bids.sort_by_key(|l| std::cmp::Reverse(l.price));
+        asks.sort_by_key(|l| l.price);
+        Self {
+            bids,
+            asks,
+            timestamp,
+        }
+    }
+
+    /// Creates an empty quote set (no quotes on either side).
+    #[must_use]
+    pub fn empty(timestamp: u64) -> Self {
+        Self {
+            bids: Vec::new(),
+            asks: Vec::new(),
+            timestamp,
+        }
+    }
+
+    /// Creates a single-level quote set from one bid and one ask.
+    #[must_use]
+    pub fn single(
+        bid_price: Decimal,
+        bid_size: Decimal,
+        ask_price: Decimal,
+        ask_size: Decimal,
+        timestamp: u64,
+    ) -> Self {
+        Self::new(
+            vec![QuoteLevel::new(bid_price, bid_size)],
+            vec![QuoteLevel::new(ask_price, ask_size)],
+            timestamp,
+        )
+    }
+
+    /// Returns true if neither side has any level.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+
+    /// Returns the levels for the given side.
+    #[must_use]
+    pub fn levels(&self, side: Side) -> &[QuoteLevel] {
+        match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        }
+    }
+
+    /// Returns the best (highest) bid price.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<Decimal> {
+        self.bids.first().map(|l| l.price)
+    }
+
+    /// Returns the best (lowest) ask price.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<Decimal> {
+        self.asks.first().map(|l| l.price)
+    }
+
+    /// Returns the spread between the best ask and best bid.
+    #[must_use]
+    pub fn spread(&self) -> Option<Decimal> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => Some(ask - bid),
+            _ => None,
+        }
+    }
+
+    /// Returns the total size quoted on the bid side.
+    #[must_use]
+    pub fn total_bid_size(&self) -> Decimal {
+        self.bids.iter().map(|l| l.size).sum()
+    }
+
+    /// Returns the total size quoted on the ask side.
+    #[must_use]
+    pub fn total_ask_size(&self) -> Decimal {
+        self.asks.iter().map(|l| l.size).sum()
+    }
+
+    /// Converts the top of the set into a single-level [`Quote`].
+    ///
+    /// Returns `None` if either side is empty.
+    #[must_use]
+    pub fn to_quote(&self) -> Option<Quote> {
+        let bid = self.bids.first()?;
+        let ask = self.asks.first()?;
+        Some(Quote {
+            bid_price: bid.price,
+            bid_size: bid.size,
+            ask_price: ask.price,
+            ask_size: ask.size,
+            timestamp: self.timestamp,
+        })
+    }
+}
+
+impl From<Quote> for QuoteSet {
+    fn from(quote: Quote) -> Self {
+        Self::single(
+            quote.bid_price,
+            quote.bid_size,
+            quote.ask_price,
+            quote.ask_size,
+            quote.timestamp,
+        )
+    }
+}
+
+/// Object-safe interface implemented by every quoting model.
+///
+/// Strategies receive a [`QuoteContext`] and return a [`QuoteSet`]. The
+/// `&mut self` receiver allows stateful strategies to update internal state
+/// between cycles; stateless models simply ignore it.
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::strategy::quoting::{QuoteContext, QuoteSet, QuotingStrategy};
+/// use market_maker_rs::types::error::MMResult;
+/// use market_maker_rs::dec;
+///
+/// struct FixedSpread;
+///
+/// impl QuotingStrategy for FixedSpread {
+///     fn name(&self) -> &str {
+///         "fixed_spread"
+///     }
+///
+///     fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+///         Ok(QuoteSet::single(
+///             context.mid_price - dec!(0.5),
+///             dec!(1.0),
+///             context.mid_price + dec!(0.5),
+///             dec!(1.0),
+///             context.timestamp,
+///         ))
+///     }
+/// }
+///
+/// let mut strategy: Box<dyn QuotingStrategy> = Box::new(FixedSpread);
+/// let quotes = strategy
+///     .generate_quotes(&QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0))
+///     .unwrap();
+/// assert_eq!(quotes.spread(), Some(dec!(1.0)));
+/// ```
+pub trait QuotingStrategy: Send + Sync {
+    /// Returns a short identifier for the strategy.
+    fn name(&self) -> &str;
+
+    /// Generates quotes for the given market context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the context is invalid for this strategy or the
+    /// underlying model fails to produce valid quotes.
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet>;
+
+    /// Generates quotes from a full [`MarketState`].
+    ///
+    /// The default implementation refuses stale states and otherwise quotes
+    /// on [`QuoteContext::from_market_state`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidMarketState` if the state is stale, or any
+    /// error from [`generate_quotes`](Self::generate_quotes).
+    fn quote_market_state(
+        &mut self,
+        state: &MarketState,
+        inventory: Decimal,
+    ) -> MMResult<QuoteSet> {
+        if state.is_stale() {
+            return Err(MMError::InvalidMarketState(
+                "market state is stale".to_string(),
+            ));
+        }
+        self.generate_quotes(&QuoteContext::from_market_state(state, inventory))
+    }
+
+    /// Notifies the strategy that one of its orders was filled.
+    ///
+    /// The default implementation does nothing.
+    fn on_fill(&mut self, _side: Side, _price: Decimal, _quantity: Decimal, _timestamp: u64) {}
+
+    /// Resets any internal state. The default implementation does nothing.
+    fn reset(&mut self) {}
+}
+
+impl QuotingStrategy for Box<dyn QuotingStrategy> {
+    fn name(&self) -> &str {
+        self.as_ref().name()
+    }
+
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+        self.as_mut().generate_quotes(context)
+    }
+
+    fn quote_market_state(
+        &mut self,
+        state: &MarketState,
+        inventory: Decimal,
+    ) -> MMResult<QuoteSet> {
+        self.as_mut().quote_market_state(state, inventory)
+    }
+
+    fn on_fill(&mut self, side: Side, price: Decimal, quantity: Decimal, timestamp: u64) {
+        self.as_mut().on_fill(side, price, quantity, timestamp);
+    }
+
+    fn reset(&mut self) {
+        self.as_mut().reset();
+    }
+}
+
+/// Validates that an order size is positive.
+fn validate_order_size(order_size: Decimal) -> MMResult<()> {
+    if order_size <= Decimal::ZERO {
+        return Err(MMError::InvalidConfiguration(
+            "order_size must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Avellaneda-Stoikov quoting strategy.
+///
+/// Wraps any [`AvellanedaStoikov`] implementation (by default
+/// [`DefaultAvellanedaStoikov`]) together with a [`StrategyConfig`] and
+/// produces one quote level per side. The spread is floored at
+/// `config.min_spread` around the reservation price.
+///
+/// The finite or infinite horizon formulation is selected by
+/// `config.horizon`.
+#[derive(Debug, Clone)]
+pub struct AvellanedaStoikovQuoter<M: AvellanedaStoikov = DefaultAvellanedaStoikov> {
+    model: M,
+    config: StrategyConfig,
+    order_size: Decimal,
+}
+
+impl AvellanedaStoikovQuoter<DefaultAvellanedaStoikov> {
+    /// Creates a quoter backed by [`DefaultAvellanedaStoikov`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `order_size` is not positive.
+    pub fn new(config: StrategyConfig, order_size: Decimal) -> MMResult<Self> {
+        Self::with_model(DefaultAvellanedaStoikov, config, order_size)
+    }
+}
+
+impl<M: AvellanedaStoikov> AvellanedaStoikovQuoter<M> {
+    /// Creates a quoter backed by a custom Avellaneda-Stoikov implementation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `order_size` is not positive.
+    pub fn with_model(model: M, config: StrategyConfig, order_size: Decimal) -> MMResult<Self> {
+        validate_order_size(order_size)?;
+        Ok(Self {
+            model,
+            config,
+            order_size,
+        })
+    }
+
+    /// Returns the strategy configuration.
+    #[must_use]
+    pub fn config(&self) -> &StrategyConfig {
+        &self.config
+    }
+
+    /// Returns the order size quoted on each side.
+    #[must_use]
+    pub fn order_size(&self) -> Decimal {
+        self.order_size
+    }
+}
+
+impl<M: AvellanedaStoikov + Send + Sync> QuotingStrategy for AvellanedaStoikovQuoter<M> {
+    fn name(&self) -> &str {
+        "avellaneda_stoikov"
+    }
+
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+        let (reservation, spread) = self.model.calculate_reservation_and_spread(
+            &self.config,
+            context.mid_price,
+            context.inventory,
+            context.volatility,
+            context.timestamp,
+        )?;
+        let spread = spread.max(self.config.min_spread);
+
+        let half_spread = spread / Decimal::TWO;
+        let bid = reservation - half_spread;
+        let ask = reservation + half_spread;
+
+        if bid <= Decimal::ZERO || bid >= ask {
+            return Err(MMError::InvalidQuoteGeneration(
+                "bid price must be positive and below ask price".to_string(),
+            ));
+        }
+
+        Ok(QuoteSet::single(
+            bid,
+            self.order_size,
+            ask,
+            self.order_size,
+            context.timestamp,
+        ))
+    }
+}
+
+/// GLFT quoting strategy.
+///
+/// Produces one quote level per side from [`GLFTStrategy::calculate_bounded_quotes`],
+/// omitting the side that would breach the configured inventory bounds.
+#[derive(Debug, Clone)]
+pub struct GLFTQuoter {
+    config: GLFTConfig,
+    order_size: Decimal,
+}
+
+impl GLFTQuoter {
+    /// Creates a new GLFT quoter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `order_size` is not positive.
+    pub fn new(config: GLFTConfig, order_size: Decimal) -> MMResult<Self> {
+        validate_order_size(order_size)?;
+        Ok(Self { config, order_size })
+    }
+
+    /// Returns the GLFT configuration.
+    #[must_use]
+    pub fn config(&self) -> &GLFTConfig {
+        &self.config
+    }
+
+    /// Returns the order size quoted on each side.
+    #[must_use]
+    pub fn order_size(&self) -> Decimal {
+        self.order_size
+    }
+}
+
+impl QuotingStrategy for GLFTQuoter {
+    fn name(&self) -> &str {
+        "glft"
+    }
+
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+        let (bid, ask) = GLFTStrategy::calculate_bounded_quotes(
+            context.mid_price,
+            context.inventory,
+            &self.config,
+            context.volatility,
+            context.timestamp,
+        )?;
+
+        Ok(QuoteSet::new(
+            bid.map(|price| QuoteLevel::new(price, self.order_size))
+                .into_iter()
+                .collect(),
+            ask.map(|price| QuoteLevel::new(price, self.order_size))
+                .into_iter()
+                .collect(),
+            context.timestamp,
+        ))
+    }
+}
+
+/// Converts grid orders into a quote set.
+fn grid_orders_to_quote_set(orders: Vec<GridOrder>, timestamp: u64) -> QuoteSet {
+    let (bids, asks): (Vec<GridOrder>, Vec<GridOrder>) = orders
+        .into_iter()
+        .partition(|order| order.side == OrderSide::Buy);
+
+    QuoteSet::new(
+        bids.into_iter()
+            .map(|o| QuoteLevel::new(o.price, o.size))
+            .collect(),
+        asks.into_iter()
+            .map(|o| QuoteLevel::new(o.price, o.size))
+            .collect(),
+        timestamp,
+    )
+}
+
+/// Grid strategies quote every grid level around their reference price.
+///
+/// If no reference price has been set, the context mid price is used.
+/// Sizes on the side that would increase inventory are scaled down as in
+/// [`GridStrategy::generate_grid_with_inventory`].
+impl QuotingStrategy for GridStrategy {
+    fn name(&self) -> &str {
+        "grid"
+    }
+
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+        let reference_price = if self.reference_price() > Decimal::ZERO {
+            self.reference_price()
+        } else {
+            context.mid_price
+        };
+
+        if reference_price <= Decimal::ZERO {
+            return Err(MMError::InvalidMarketState(
+                "mid_price must be positive".to_string(),
+            ));
+        }
+
+        let orders = self.generate_grid_with_inventory(reference_price, context.inventory);
+        Ok(grid_orders_to_quote_set(orders, context.timestamp))
+    }
+}
+
+/// Adaptive-spread quoting strategy.
+///
+/// Uses the order book in the context (if any) to compute the book imbalance
+/// and prices one level per side with [`AdaptiveSpreadCalculator::calculate_spread`].
+/// When a baseline volatility is configured, the base spread is additionally
+/// scaled by the ratio of context volatility to baseline.
+#[derive(Debug, Clone)]
+pub struct AdaptiveSpreadQuoter {
+    calculator: AdaptiveSpreadCalculator,
+    order_size: Decimal,
+    depth_levels: u32,
+    baseline_volatility: Option<Decimal>,
+}
+
+impl AdaptiveSpreadQuoter {
+    /// Creates a new adaptive-spread quoter using the top 5 book levels.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `order_size` is not positive.
+    pub fn new(config: AdaptiveSpreadConfig, order_size: Decimal) -> MMResult<Self> {
+        validate_order_size(order_size)?;
+        Ok(Self {
+            calculator: AdaptiveSpreadCalculator::new(config),
+            order_size,
+            depth_levels: 5,
+            baseline_volatility: None,
+        })
+    }
+
+    /// Sets the number of book levels used for the imbalance calculation.
+    #[must_use]
+    pub fn with_depth_levels(mut self, depth_levels: u32) -> Self {
+        self.depth_levels = depth_levels;
+        self
+    }
+
+    /// Enables volatility scaling against the given baseline volatility.
+    #[must_use]
+    pub fn with_baseline_volatility(mut self, baseline_volatility: Decimal) -> Self {
+        self.baseline_volatility = Some(baseline_volatility);
+        self
+    }
+
+    /// Returns the underlying spread calculator.
+    #[must_use]
+    pub fn calculator(&self) -> &AdaptiveSpreadCalculator {
+        &self.calculator
+    }
+}
+
+/// Flattens book levels into `(price, quantity)` pairs.
+fn book_pairs(levels: &[BookLevel]) -> Vec<(Decimal, Decimal)> {
+    levels.iter().map(|l| (l.price, l.quantity)).collect()
+}
+
+impl QuotingStrategy for AdaptiveSpreadQuoter {
+    fn name(&self) -> &str {
+        "adaptive_spread"
+    }
+
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+        if context.mid_price <= Decimal::ZERO {
+            return Err(MMError::InvalidMarketState(
+                "mid_price must be positive".to_string(),
+            ));
+        }
+
+        let imbalance = match &context.orderbook {
+            Some(book) => AdaptiveSpreadCalculator::calculate_orderbook_imbalance(
+                &book_pairs(&book.bids),
+                &book_pairs(&book.asks),
+                self.depth_levels,
+            ),
+            None => AdaptiveSpreadCalculator::calculate_orderbook_imbalance(&[], &[], 0),
+        };
+
+        let spread = match self.baseline_volatility {
+            Some(baseline) => self.calculator.calculate_spread_with_volatility(
+                &imbalance,
+                None,
+                context.volatility,
+                baseline,
+            ),
+            None => self.calculator.calculate_spread(&imbalance, None),
+        };
+
+        Ok(QuoteSet::single(
+            spread.bid_price(context.mid_price),
+            self.order_size,
+            spread.ask_price(context.mid_price),
+            self.order_size,
+            context.timestamp,
+        ))
+    }
+}
+
+/// Depth-based quoting strategy.
+///
+/// Walks each side of the order book until the cumulative depth reaches the
+/// target, then quotes one tick inside that level with inventory-adjusted
+/// sizes from [`DepthBasedOffering`]. Requires an order book in the context.
+#[derive(Debug, Clone)]
+pub struct DepthBasedQuoter {
+    offering: DepthBasedOffering,
+    tick_size: Decimal,
+}
+
+impl DepthBasedQuoter {
+    /// Creates a new depth-based quoter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `tick_size` is not positive.
+    pub fn new(offering: DepthBasedOffering, tick_size: Decimal) -> MMResult<Self> {
+        if tick_size <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "tick_size must be positive".to_string(),
+            ));
+        }
+        Ok(Self {
+            offering,
+            tick_size,
+        })
+    }
+
+    /// Returns the underlying depth-based offering.
+    #[must_use]
+    pub fn offering(&self) -> &DepthBasedOffering {
+        &self.offering
+    }
+
+    /// Returns the tick size.
+    #[must_use]
+    pub fn tick_size(&self) -> Decimal {
+        self.tick_size
+    }
+
+    /// Finds the price at which cumulative depth reaches the target, and
+    /// the cumulative depth at that price. Falls back to the deepest level.
+    fn price_at_depth(&self, levels: &[BookLevel]) -> Option<(Decimal, Decimal)> {
+        let mut cumulative = Decimal::ZERO;
+        for level in levels {
+            cumulative += level.quantity;
+            if cumulative >= self.offering.target_depth() {
+                return Some((level.price, cumulative));
+            }
+        }
+        levels.last().map(|l| (l.price, cumulative))
+    }
+}
+
+impl QuotingStrategy for DepthBasedQuoter {
+    fn name(&self) -> &str {
+        "depth_based"
+    }
+
+    fn generate_quotes(&mut self, context: &QuoteContext) -> MMResult<QuoteSet> {
+        let book = context.require_orderbook()?;
+
+        let bids = self
+            .price_at_depth(&book.bids)
+            .map(|(price, depth)| {
+                let price = price + self.offering.price_adjustment(depth, self.tick_size, false);
+                QuoteLevel::new(price, self.offering.calculate_bid_size(context.inventory))
+            })
+            .into_iter()
+            .collect();
+
+        let asks = self
+            .price_at_depth(&book.asks)
+            .map(|(price, depth)| {
+                let price = price + self.offering.price_adjustment(depth, self.tick_size, true);
+                QuoteLevel::new(price, self.offering.calculate_ask_size(context.inventory))
+            })
+            .into_iter()
+            .collect();
+
+        let quotes = QuoteSet::new(bids, asks, context.timestamp);
+        if matches!((quotes.best_bid(), quotes.best_ask()), (Some(bid), Some(ask)) if bid >= ask) {
+            return Err(MMError::InvalidQuoteGeneration(
+                "bid price must be less than ask price".to_string(),
+            ));
+        }
+
+        Ok(quotes)
+    }
+}
+
+/// Serializable selection of a quoting strategy.
+///
+/// Allows engines, backtests and the API to pick a model from configuration
+/// and instantiate it with [`QuotingStrategyConfig::build`].
+///
+/// # Example
+///
+/// ```rust
+/// use market_maker_rs::strategy::grid::GridConfig;
+/// use market_maker_rs::strategy::quoting::{QuoteContext, QuotingStrategyConfig};
+/// use market_maker_rs::dec;
+///
+/// let config = QuotingStrategyConfig::Grid(
+///     GridConfig::new(3, dec!(0.01), dec!(1.0), dec!(10.0)).unwrap(),
+/// );
+/// let mut strategy = config.build().unwrap();
+/// assert_eq!(strategy.name(), "grid");
+///
+/// let quotes = strategy
+///     .generate_quotes(&QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0))
+///     .unwrap();
+/// assert_eq!(quotes.bids.len(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum QuotingStrategyConfig {
+    /// Avellaneda-Stoikov model with [`DefaultAvellanedaStoikov`].
+    AvellanedaStoikov {
+        /// Model parameters.
+        config: StrategyConfig,
+        /// Size quoted on each side.
+        order_size: Decimal,
+    },
+    /// GLFT model.
+    Glft {
+        /// Model parameters.
+        config: GLFTConfig,
+        /// Size quoted on each side.
+        order_size: Decimal,
+    },
+    /// Grid strategy centered on the context mid price.
+    Grid(GridConfig),
+    /// Adaptive spread based on order book imbalance.
+    AdaptiveSpread {
+        /// Spread parameters.
+        config: AdaptiveSpreadConfig,
+        /// Size quoted on each side.
+        order_size: Decimal,
+    },
+    /// Depth-based offering.
+    DepthBased {
+        /// Maximum absolute exposure.
+        max_exposure: Decimal,
+        /// Target cumulative depth.
+        target_depth: Decimal,
+        /// Minimum price increment.
+        tick_size: Decimal,
+    },
+    /// Multi-level ladder around the Avellaneda-Stoikov or GLFT quotes.
+    Ladder {
+        /// Pricing model.
+        model: LadderModel,
+        /// Ladder parameters.
+        ladder: LadderConfig,
+    },
+}
+
+impl QuotingStrategyConfig {
+    /// Instantiates the configured strategy.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the parameters are invalid.
+    pub fn build(&self) -> MMResult<Box<dyn QuotingStrategy>> {
+        Ok(match self {
+            Self::AvellanedaStoikov { config, order_size } => {
+                Box::new(AvellanedaStoikovQuoter::new(config.clone(), *order_size)?)
+            }
+            Self::Glft { config, order_size } => {
+                Box::new(GLFTQuoter::new(config.clone(), *order_size)?)
+            }
+            Self::Grid(config) => Box::new(GridStrategy::new(config.clone())?),
+            Self::AdaptiveSpread { config, order_size } => {
+                Box::new(AdaptiveSpreadQuoter::new(config.clone(), *order_size)?)
+            }
+            Self::DepthBased {
+                max_exposure,
+                target_depth,
+                tick_size,
+            } => Box::new(DepthBasedQuoter::new(
+                DepthBasedOffering::new(*max_exposure, *target_depth),
+                *tick_size,
+            )?),
+            Self::Ladder { model, ladder } => {
+                Box::new(LadderQuoter::new(model.clone(), ladder.clone()))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+    use crate::strategy::glft::InventoryBounds;
+
+    fn sample_book() -> OrderBookSnapshot {
+        let mut book = OrderBookSnapshot::new("TEST", 0);
+        book.bids = vec![
+            BookLevel::new(dec!(99.9), dec!(20.0)),
+            BookLevel::new(dec!(99.8), dec!(40.0)),
+            BookLevel::new(dec!(99.7), dec!(60.0)),
+        ];
+        book.asks = vec![
+            BookLevel::new(dec!(100.1), dec!(20.0)),
+            BookLevel::new(dec!(100.2), dec!(40.0)),
+            BookLevel::new(dec!(100.3), dec!(60.0)),
+        ];
+        book
+    }
+
+    fn as_config() -> StrategyConfig {
+        StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap()
+    }
+
+    fn glft_config() -> GLFTConfig {
+        GLFTConfig::new(dec!(0.1), dec!(1.5), dec!(0.05), 3_600_000, dec!(0.01)).unwrap()
+    }
+
+    #[test]
+    fn test_quote_set_sorts_and_filters() {
+        let set = QuoteSet::new(
+            vec![
+                QuoteLevel::new(dec!(99.0), dec!(1.0)),
+                QuoteLevel::new(dec!(99.5), dec!(1.0)),
+                QuoteLevel::new(dec!(98.0), dec!(0.0)),
+            ],
+            vec![
+                QuoteLevel::new(dec!(101.0), dec!(1.0)),
+                QuoteLevel::new(dec!(100.5), dec!(2.0)),
+            ],
+            42,
+        );
+
+        assert_eq!(set.bids.len(), 2);
+        assert_eq!(set.best_bid(), Some(dec!(99.5)));
+        assert_eq!(set.best_ask(), Some(dec!(100.5)));
+        assert_eq!(set.spread(), Some(dec!(1.0)));
+        assert_eq!(set.total_ask_size(), dec!(3.0));
+        assert_eq!(set.levels(Side::Buy).len(), 2);
+    }
+
+    #[test]
+    fn test_quote_set_to_quote_roundtrip() {
+        let quote = Quote {
+            bid_price: dec!(99.0),
+            bid_size: dec!(1.0),
+            ask_price: dec!(101.0),
+            ask_size: dec!(2.0),
+            timestamp: 7,
+        };
+        let set = QuoteSet::from(quote.clone());
+        assert_eq!(set.to_quote(), Some(quote));
+    }
+
+    #[test]
+    fn test_quote_set_empty() {
+        let set = QuoteSet::empty(0);
+        assert!(set.is_empty());
+        assert!(set.to_quote().is_none());
+        assert!(set.spread().is_none());
+    }
+
+    #[test]
+    fn test_context_require_orderbook() {
+        let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0);
+        assert!(matches!(
+            context.require_orderbook(),
+            Err(MMError::InvalidMarketState(_))
+        ));
+
+        let context = context.with_orderbook(sample_book());
+        assert!(context.require_orderbook().is_ok());
+    }
+
+    #[test]
+    fn test_quote_market_state() {
+        let mut state = MarketState::new(dec!(100.0), dec!(0.2), 42).with_orderbook(sample_book());
+        let context = QuoteContext::from_market_state(&state, dec!(3.0));
+        assert_eq!(context.inventory, dec!(3.0));
+        assert_eq!(context.timestamp, 42);
+        assert_eq!(context.orderbook, state.orderbook);
+
+        let mut strategy: Box<dyn QuotingStrategy> =
+            Box::new(AvellanedaStoikovQuoter::new(as_config(), dec!(1.0)).unwrap());
+        let expected = strategy.generate_quotes(&context).unwrap();
+        assert_eq!(
+            strategy.quote_market_state(&state, dec!(3.0)).unwrap(),
+            expected
+        );
+
+        state.stale = true;
+        assert!(matches!(
+            strategy.quote_market_state(&state, dec!(3.0)),
+            Err(MMError::InvalidMarketState(_))
+        ));
+    }
+
+    #[test]
+    fn test_avellaneda_stoikov_quoter_matches_model() {
+        let mut quoter = AvellanedaStoikovQuoter::new(as_config(), dec!(2.0)).unwrap();
+        let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(5.0), 0);
+        let quotes = quoter.generate_quotes(&context).unwrap();
+
+        let (bid, ask) = DefaultAvellanedaStoikov
+            .calculate_optimal_quotes(
+                dec!(100.0),
+                dec!(5.0),
+                dec!(0.1),
+                dec!(0.2),
+                3_600_000,
+                dec!(1.5),
+            )
+            .unwrap();
+
+        assert_eq!(quotes.best_bid(), Some(bid));
+        assert_eq!(quotes.best_ask(), Some(ask));
+        assert_eq!(quotes.total_bid_size(), dec!(2.0));
+    }
+
+    #[test]
+    fn test_avellaneda_stoikov_quoter_min_spread() {
+        let config = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(5.0)).unwrap();
+        let mut quoter = AvellanedaStoikovQuoter::new(config, dec!(1.0)).unwrap();
+        let quotes = quoter
+            .generate_quotes(&QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0))
+            .unwrap();
+        assert!(quotes.spread().unwrap() >= dec!(5.0));
+    }
+
+    #[test]
+    fn test_avellaneda_stoikov_quoter_infinite_horizon() {
+        let config = as_config().with_infinite_horizon(dec!(0.05)).unwrap();
+        let mut quoter = AvellanedaStoikovQuoter::new(config, dec!(1.0)).unwrap();
+        let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(5.0), 10_000_000);
+        let quotes = quoter.generate_quotes(&context).unwrap();
+
+        let (bid, ask) = DefaultAvellanedaStoikov
+            .calculate_infinite_horizon_quotes(
+                dec!(100.0),
+                dec!(5.0),
+                dec!(0.1),
+                dec!(0.2),
+                dec!(0.05),
+                dec!(1.5),
+            )
+            .unwrap();
+
+        assert_eq!(quotes.best_bid(), Some(bid));
+        assert_eq!(quotes.best_ask(), Some(ask));
+    }
+
+    #[test]
+    fn test_avellaneda_stoikov_quoter_invalid_size() {
+        let result = AvellanedaStoikovQuoter::new(as_config(), dec!(0.0));
+        assert!(matches!(result, Err(MMError::InvalidConfiguration(_))));
+    }
+
+    #[test]
+    fn test_glft_quoter_matches_model() {
+        let mut quoter = GLFTQuoter::new(glft_config(), dec!(1.0)).unwrap();
+        let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(-3.0), 1000);
+        let quotes = quoter.generate_quotes(&context).unwrap();
+
+        let (bid, ask) = GLFTStrategy::calculate_optimal_quotes(
+            dec!(100.0),
+            dec!(-3.0),
+            &glft_config(),
+            dec!(0.2),
+            1000,
+        )
+        .unwrap();
+
+        assert_eq!(quotes.best_bid(), Some(bid));
+        assert_eq!(quotes.best_ask(), Some(ask));
+        assert_eq!(quoter.name(), "glft");
+    }
+
+    #[test]
+    fn test_glft_quoter_respects_inventory_bounds() {
+        let config = glft_config()
+            .with_inventory_bounds(InventoryBounds::new(dec!(3.0), dec!(1.0)).unwrap());
+        let mut quoter = GLFTQuoter::new(config, dec!(1.0)).unwrap();
+
+        let quotes = quoter
+            .generate_quotes(&QuoteContext::new(dec!(100.0), dec!(0.2), dec!(-3.0), 0))
+            .unwrap();
+        assert!(quotes.asks.is_empty());
+        assert_eq!(quotes.bids.len(), 1);
+        assert!(quotes.to_quote().is_none());
+    }
+
+    #[test]
+    fn test_glft_quoter_invalid_volatility() {
+        let mut quoter = GLFTQuoter::new(glft_config(), dec!(1.0)).unwrap();
+        let context = QuoteContext::new(dec!(100.0), dec!(0.0), dec!(0.0), 0);
+        assert!(quoter.generate_quotes(&context).is_err());
+    }
+
+    #[test]
+    fn test_grid_quotes_multiple_levels() {
+        let config = GridConfig::new(3, dec!(0.01), dec!(1.0), dec!(10.0)).unwrap();
+        let mut grid = GridStrategy::new(config).unwrap();
+        let quotes = grid
+            .generate_quotes(&QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0))
+            .unwrap();
+
+        assert_eq!(quotes.bids.len(), 3);
+        assert_eq!(quotes.asks.len(), 3);
+        assert!(quotes.bids[0].price > quotes.bids[1].price);
+        assert!(quotes.asks[0].price < quotes.asks[1].price);
+        assert!(quotes.best_bid().unwrap() < dec!(100.0));
+        assert!(quotes.best_ask().unwrap() > dec!(100.0));
+    }
+
+    #[test]
+    fn test_grid_quotes_use_reference_price() {
+        let config = GridConfig::new(2, dec!(0.01), dec!(1.0), dec!(10.0)).unwrap();
+        let mut grid = GridStrategy::with_reference_price(config, dec!(200.0)).unwrap();
+        let quotes = grid
+            .generate_quotes(&QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0))
+            .unwrap();
+        assert!(quotes.best_bid().unwrap() > dec!(190.0));
+    }
+
+    #[test]
+    fn test_grid_quotes_reduce_buys_when_long() {
+        let config = GridConfig::new(2, dec!(0.01), dec!(1.0), dec!(10.0)).unwrap();
+        let mut grid = GridStrategy::new(config).unwrap();
+        let quotes = grid
+            .generate_quotes(&QuoteContext::new(dec!(100.0), dec!(0.2), dec!(5.0), 0))
+            .unwrap();
+        assert!(quotes.total_bid_size() < quotes.total_ask_size());
+    }
+
+    #[test]
+    fn test_adaptive_quoter_without_book_is_symmetric() {
+        let config =
+            AdaptiveSpreadConfig::new(dec!(0.002), dec!(2.0), dec!(0.5), dec!(0.5)).unwrap();
+        let mut quoter = AdaptiveSpreadQuoter::new(config, dec!(1.0)).unwrap();
+        let quotes = quoter
+            .generate_quotes(&QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0))
+            .unwrap();
+
+        assert_eq!(quotes.best_bid(), Some(dec!(99.9)));
+        assert_eq!(quotes.best_ask(), Some(dec!(100.1)));
+    }
+
+    #[test]
+    fn test_adaptive_quoter_widens_ask_on_bid_heavy_book() {
+        let config =
+            AdaptiveSpreadConfig::new(dec!(0.002), dec!(2.0), dec!(1.0), dec!(0.5)).unwrap();
+        let mut quoter = AdaptiveSpreadQuoter::new(config, dec!(1.0)).unwrap();
+
+        let mut book = sample_book();
+        book.bids[0].quantity = dec!(500.0);
+        let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0).with_orderbook(book);
+        let quotes = quoter.generate_quotes(&context).unwrap();
+
+        let bid_distance = dec!(100.0) - quotes.best_bid().unwrap();
+        let ask_distance = quotes.best_ask().unwrap() - dec!(100.0);
+        assert!(ask_distance > bid_distance);
+    }
+
+    #[test]
+    fn test_adaptive_quoter_volatility_scaling() {
+        let config =
+            AdaptiveSpreadConfig::new(dec!(0.002), dec!(2.0), dec!(0.5), dec!(0.5)).unwrap();
+        let mut quoter = AdaptiveSpreadQuoter::new(config, dec!(1.0))
+            .unwrap()
+            .with_baseline_volatility(dec!(0.1));
+        let quotes = quoter
+            .generate_quotes(&QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0))
+            .unwrap();
+        // Volatility is twice the baseline, so the spread doubles
+        assert_eq!(quotes.spread(), Some(dec!(0.4)));
+    }
+
+    #[test]
+    fn test_depth_based_quoter_requires_book() {
+        let mut quoter =
+            DepthBasedQuoter::new(DepthBasedOffering::new(dec!(10.0), dec!(50.0)), dec!(0.1))
+                .unwrap();
+        let result =
+            quoter.generate_quotes(&QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0));
+        assert!(matches!(result, Err(MMError::InvalidMarketState(_))));
+    }
+
+    #[test]
+    fn test_depth_based_quoter_prices_inside_target_depth() {
+        let mut quoter =
+            DepthBasedQuoter::new(DepthBasedOffering::new(dec!(10.0), dec!(50.0)), dec!(0.1))
+                .unwrap();
+        let context =
+            QuoteContext::new(dec!(100.0), dec!(0.2), dec!(2.0), 0).with_orderbook(sample_book());
+        let quotes = quoter.generate_quotes(&context).unwrap();
+
+        // Cumulative depth reaches 50 at the second level; quote one tick inside
+        assert_eq!(quotes.best_bid(), Some(dec!(99.9)));
+        assert_eq!(quotes.best_ask(), Some(dec!(100.1)));
+        assert_eq!(quotes.total_bid_size(), dec!(8.0));
+        assert_eq!(quotes.total_ask_size(), dec!(12.0));
+    }
+
+    #[test]
+    fn test_depth_based_quoter_invalid_tick() {
+        let result =
+            DepthBasedQuoter::new(DepthBasedOffering::new(dec!(10.0), dec!(50.0)), dec!(0));
+        assert!(matches!(result, Err(MMError::InvalidConfiguration(_))));
+    }
+
+    #[test]
+    fn test_config_builds_every_strategy() {
+        let configs = [
+            QuotingStrategyConfig::AvellanedaStoikov {
+                config: as_config(),
+                order_size: dec!(1.0),
+            },
+            QuotingStrategyConfig::Glft {
+                config: glft_config(),
+                order_size: dec!(1.0),
+            },
+            QuotingStrategyConfig::Grid(
+                GridConfig::new(2, dec!(0.01), dec!(1.0), dec!(10.0)).unwrap(),
+            ),
+            QuotingStrategyConfig::AdaptiveSpread {
+                config: AdaptiveSpreadConfig::new(dec!(0.002), dec!(2.0), dec!(0.5), dec!(0.5))
+                    .unwrap(),
+                order_size: dec!(1.0),
+            },
+            QuotingStrategyConfig::DepthBased {
+                max_exposure: dec!(10.0),
+                target_depth: dec!(50.0),
+                tick_size: dec!(0.1),
+            },
+            QuotingStrategyConfig::Ladder {
+                model: LadderModel::Glft(glft_config()),
+                ladder: LadderConfig::new(3, dec!(0.05), dec!(1.0), dec!(10.0)).unwrap(),
+            },
+        ];
+
+        let context =
+            QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0).with_orderbook(sample_book());
+
+        let names: Vec<String> = configs
+            .iter()
+            .map(|config| {
+                let mut strategy = config.build().unwrap();
+                let quotes = strategy.generate_quotes(&context).unwrap();
+                assert!(quotes.best_bid().unwrap() < quotes.best_ask().unwrap());
+                strategy.name().to_string()
+            })
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "avellaneda_stoikov",
+                "glft",
+                "grid",
+                "adaptive_spread",
+                "depth_based",
+                "glft_ladder"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_build_rejects_invalid_size() {
+        let config = QuotingStrategyConfig::Glft {
+            config: glft_config(),
+            order_size: dec!(-1.0),
+        };
+        assert!(config.build().is_err());
+    }
+
+    #[test]
+    fn test_boxed_strategy_delegates() {
+        let mut boxed: Box<dyn QuotingStrategy> =
+            Box::new(GLFTQuoter::new(glft_config(), dec!(1.0)).unwrap());
+        boxed.on_fill(Side::Buy, dec!(100.0), dec!(1.0), 0);
+        boxed.reset();
+        assert_eq!(QuotingStrategy::name(&boxed), "glft");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_config_serde_roundtrip() {
+        let config = QuotingStrategyConfig::DepthBased {
+            max_exposure: dec!(10.0),
+            target_depth: dec!(50.0),
+            tick_size: dec!(0.1),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains("\"type\":\"depth_based\""));
+        let back: QuotingStrategyConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config, back);
+    }
+}
@@ -0,0 +1,773 @@
+//! Online parameter re-calibration for live strategies.
+//!
+//! [`OnlineRecalibrator`] ingests prices, trades, fills and quote lifetimes as
+//! they happen and periodically refits the strategy parameters:
+//!
+//! - **Volatility regime**: realized volatility over a short window is
+//!   compared with a longer baseline using a [`VolatilityRegimeDetector`].
+//! - **Risk aversion (γ)**: the base γ scaled by the regime's gamma multiplier.
+//! - **Minimum spread**: the base minimum spread scaled by the regime's spread
+//!   multiplier.
+//! - **Order intensity (k)**: jointly fitted with the baseline arrival rate by
+//!   [`OrderIntensityCalibrator::calibrate_censored`] and converted from
+//!   basis points to price units at the current mid.
+//!
+//! New values are clamped to sanity bounds, limited to a maximum relative
+//! step per update, ignored inside a dead band and applied at most once per
+//! `min_update_interval_ms` per parameter. Every change is returned as a
+//! [`ParameterUpdate`]; with the `events` feature the updates can be
+//! broadcast as `MarketMakerEvent::ConfigChanged`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::strategy::calibration::{CalibrationConfig, VolatilityRegimeDetector};
+//! use market_maker_rs::strategy::config::StrategyConfig;
+//! use market_maker_rs::strategy::recalibration::{OnlineRecalibrator, RecalibrationConfig};
+//! use market_maker_rs::dec;
+//!
+//! let strategy = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+//! let config = RecalibrationConfig::new(1_000, 10_000, 60_000).unwrap();
+//! let mut recalibrator = OnlineRecalibrator::new(
+//!     strategy,
+//!     config,
+//!     CalibrationConfig::quick(),
+//!     VolatilityRegimeDetector::new(dec!(1.5), 60_000),
+//! );
+//!
+//! recalibrator.on_price(0, dec!(100.0));
+//! let updates = recalibrator.poll(1_000).unwrap();
+//! assert!(updates.is_empty()); // not enough data yet
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::Decimal;
+use crate::analytics::intensity::FillObservation;
+use crate::analytics::order_flow::Trade;
+use crate::strategy::calibration::{
+    CalibrationConfig, OrderIntensityCalibrator, QuoteLifetime, RegimeAdjustments,
+    VolatilityRegime, VolatilityRegimeDetector,
+};
+use crate::strategy::config::StrategyConfig;
+use crate::strategy::glft::GLFTConfig;
+use crate::types::decimal::{decimal_ln, decimal_sqrt};
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "events")]
+use crate::events::{EventBroadcaster, MarketMakerEvent};
+
+/// Milliseconds per year, matching the annualized volatility convention.
+const MS_PER_YEAR: i64 = 31_536_000_000;
+
+/// Basis points per unit of relative distance.
+const BPS: i64 = 10_000;
+
+/// Configuration for [`OnlineRecalibrator`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecalibrationConfig {
+    /// Interval between refits in milliseconds.
+    pub refit_interval_ms: u64,
+
+    /// Window for the current realized volatility in milliseconds.
+    pub volatility_window_ms: u64,
+
+    /// Window for the baseline volatility (and price retention) in milliseconds.
+    pub baseline_window_ms: u64,
+
+    /// Window of quote lifetimes used to fit the order intensity.
+    pub intensity_window_ms: u64,
+
+    /// Minimum time between two changes of the same parameter.
+    pub min_update_interval_ms: u64,
+
+    /// Maximum relative change of a parameter per update (e.g. 0.2 = 20%).
+    pub max_relative_change: Decimal,
+
+    /// Relative changes smaller than this are ignored.
+    pub min_relative_change: Decimal,
+
+    /// Allowed range for risk aversion.
+    pub risk_aversion_bounds: (Decimal, Decimal),
+
+    /// Allowed range for order intensity.
+    pub order_intensity_bounds: (Decimal, Decimal),
+
+    /// Allowed range for the minimum spread.
+    pub min_spread_bounds: (Decimal, Decimal),
+}
+
+impl RecalibrationConfig {
+    /// Creates a configuration with default rate limits and bounds.
+    ///
+    /// Defaults: intensity window equal to the baseline window, at most one
+    /// change per parameter per refit interval, 20% maximum step, 1% dead
+    /// band, γ in [0.001, 100], k in [0.001, 1000] and minimum spread in
+    /// [0, 1000].
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if an interval is zero or the
+    /// volatility window is not shorter than the baseline window.
+    pub fn new(
+        refit_interval_ms: u64,
+        volatility_window_ms: u64,
+        baseline_window_ms: u64,
+    ) -> MMResult<Self> {
+        if refit_interval_ms == 0 || volatility_window_ms == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "refit and volatility windows must be positive".to_string(),
+            ));
+        }
+        if volatility_window_ms >= baseline_window_ms {
+            return Err(MMError::InvalidConfiguration(
+                "volatility_window_ms must be shorter than baseline_window_ms".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            refit_interval_ms,
+            volatility_window_ms,
+            baseline_window_ms,
+            intensity_window_ms: baseline_window_ms,
+            min_update_interval_ms: refit_interval_ms,
+            max_relative_change: Decimal::from_str_exact("0.2").unwrap(),
+            min_relative_change: Decimal::from_str_exact("0.01").unwrap(),
+            risk_aversion_bounds: (
+                Decimal::from_str_exact("0.001").unwrap(),
+                Decimal::from(100),
+            ),
+            order_intensity_bounds: (
+                Decimal::from_str_exact("0.001").unwrap(),
+                Decimal::from(1000),
+            ),
+            min_spread_bounds: (Decimal::ZERO, Decimal::from(1000)),
+        })
+    }
+
+    /// Sets the quote lifetime window used to fit the order intensity.
+    #[must_use]
+    pub fn with_intensity_window(mut self, intensity_window_ms: u64) -> Self {
+        self.intensity_window_ms = intensity_window_ms;
+        self
+    }
+
+    /// Sets the per-parameter rate limit and maximum relative step.
+    #[must_use]
+    pub fn with_rate_limit(
+        mut self,
+        min_update_interval_ms: u64,
+        max_relative_change: Decimal,
+    ) -> Self {
+        self.min_update_interval_ms = min_update_interval_ms;
+        self.max_relative_change = max_relative_change;
+        self
+    }
+
+    /// Sets the dead band below which changes are ignored.
+    #[must_use]
+    pub fn with_min_relative_change(mut self, min_relative_change: Decimal) -> Self {
+        self.min_relative_change = min_relative_change;
+        self
+    }
+
+    /// Sets the risk aversion bounds.
+    #[must_use]
+    pub fn with_risk_aversion_bounds(mut self, min: Decimal, max: Decimal) -> Self {
+        self.risk_aversion_bounds = (min, max);
+        self
+    }
+
+    /// Sets the order intensity bounds.
+    #[must_use]
+    pub fn with_order_intensity_bounds(mut self, min: Decimal, max: Decimal) -> Self {
+        self.order_intensity_bounds = (min, max);
+        self
+    }
+
+    /// Sets the minimum spread bounds.
+    #[must_use]
+    pub fn with_min_spread_bounds(mut self, min: Decimal, max: Decimal) -> Self {
+        self.min_spread_bounds = (min, max);
+        self
+    }
+}
+
+/// A parameter managed by the recalibrator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CalibratedParameter {
+    /// Risk aversion γ.
+    RiskAversion,
+    /// Order intensity k.
+    OrderIntensity,
+    /// Minimum spread.
+    MinSpread,
+}
+
+impl CalibratedParameter {
+    /// Returns the configuration key for this parameter.
+    #[must_use]
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::RiskAversion => "risk_aversion",
+            Self::OrderIntensity => "order_intensity",
+            Self::MinSpread => "min_spread",
+        }
+    }
+}
+
+/// A parameter change pushed by the recalibrator.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParameterUpdate {
+    /// Parameter that changed.
+    pub parameter: CalibratedParameter,
+
+    /// Value before the change.
+    pub old_value: Decimal,
+
+    /// Value after the change (after bounds and rate limits).
+    pub new_value: Decimal,
+
+    /// Value the refit asked for.
+    pub target_value: Decimal,
+
+    /// Timestamp of the change in milliseconds.
+    pub timestamp: u64,
+}
+
+#[cfg(feature = "events")]
+impl ParameterUpdate {
+    /// Converts the update into a `ConfigChanged` event.
+    #[must_use]
+    pub fn to_event(&self, changed_by: &str) -> MarketMakerEvent {
+        MarketMakerEvent::ConfigChanged {
+            key: self.parameter.key().to_string(),
+            old_value: Some(serde_json::json!(self.old_value)),
+            new_value: serde_json::json!(self.new_value),
+            changed_by: changed_by.to_string(),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Streaming re-calibrator for [`StrategyConfig`] and [`GLFTConfig`].
+///
+/// The configs passed at construction are the base values: regime
+/// multipliers are applied to them rather than compounded on the current
+/// values. A GLFT config, when attached, receives the same γ, k and minimum
+/// spread as the strategy config.
+#[derive(Debug, Clone)]
+pub struct OnlineRecalibrator {
+    config: RecalibrationConfig,
+    calibrator: OrderIntensityCalibrator,
+    detector: VolatilityRegimeDetector,
+    base: StrategyConfig,
+    strategy: StrategyConfig,
+    glft: Option<GLFTConfig>,
+    prices: VecDeque<(u64, Decimal)>,
+    lifetimes: VecDeque<QuoteLifetime>,
+    regime: VolatilityRegime,
+    volatility: Option<Decimal>,
+    last_refit: Option<u64>,
+    last_changes: [Option<u64>; 3],
+}
+
+impl OnlineRecalibrator {
+    /// Creates a recalibrator starting from `strategy`.
+    #[must_use]
+    pub fn new(
+        strategy: StrategyConfig,
+        config: RecalibrationConfig,
+        calibration: CalibrationConfig,
+        detector: VolatilityRegimeDetector,
+    ) -> Self {
+        Self {
+            config,
+            calibrator: OrderIntensityCalibrator::new(calibration),
+            detector,
+            base: strategy.clone(),
+            strategy,
+            glft: None,
+            prices: VecDeque::new(),
+            lifetimes: VecDeque::new(),
+            regime: VolatilityRegime::Normal,
+            volatility: None,
+            last_refit: None,
+            last_changes: [None; 3],
+        }
+    }
+
+    /// Also pushes parameter changes into a GLFT config.
+    #[must_use]
+    pub fn with_glft(mut self, glft: GLFTConfig) -> Self {
+        self.glft = Some(glft);
+        self
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn config(&self) -> &RecalibrationConfig {
+        &self.config
+    }
+
+    /// Returns the current strategy config.
+    #[must_use]
+    pub fn strategy_config(&self) -> &StrategyConfig {
+        &self.strategy
+    }
+
+    /// Returns the current GLFT config, if attached.
+    #[must_use]
+    pub fn glft_config(&self) -> Option<&GLFTConfig> {
+        self.glft.as_ref()
+    }
+
+    /// Returns the last detected volatility regime.
+    #[must_use]
+    pub fn regime(&self) -> VolatilityRegime {
+        self.regime
+    }
+
+    /// Returns the last realized volatility (annualized), if computed.
+    #[must_use]
+    pub fn volatility(&self) -> Option<Decimal> {
+        self.volatility
+    }
+
+    /// Records a mid price.
+    ///
+    /// Prices stamped before the last recorded one are ignored, so the
+    /// history stays in time order.
+    pub fn on_price(&mut self, timestamp: u64, price: Decimal) {
+        if price <= Decimal::ZERO
+            || self
+                .prices
+                .back()
+                .is_some_and(|&(last, _)| timestamp < last)
+        {
+            return;
+        }
+        self.prices.push_back((timestamp, price));
+    }
+
+    /// Records a trade price.
+    pub fn on_trade(&mut self, trade: &Trade) {
+        self.on_price(trade.timestamp, trade.price);
+    }
+
+    /// Records a fill, as a filled quote lifetime.
+    ///
+    /// The fill's relative spread is converted to basis points. Fills with
+    /// no time to fill carry no quoting exposure and are ignored.
+    pub fn on_fill(&mut self, fill: &FillObservation) {
+        if fill.time_to_fill_ms == 0 {
+            return;
+        }
+        self.on_quote_lifetime(QuoteLifetime::filled(
+            fill.spread_at_fill * Decimal::from(BPS),
+            fill.time_to_fill_ms,
+            fill.timestamp,
+        ));
+    }
+
+    /// Records the lifetime of a quote, filled or cancelled.
+    pub fn on_quote_lifetime(&mut self, lifetime: QuoteLifetime) {
+        self.lifetimes.push_back(lifetime);
+    }
+
+    /// Refits the parameters if the refit interval has elapsed.
+    ///
+    /// Returns the parameter changes that were applied, which is empty when
+    /// no refit was due or no parameter moved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a numerical step of the volatility estimate fails.
+    pub fn poll(&mut self, timestamp: u64) -> MMResult<Vec<ParameterUpdate>> {
+        if self
+            .last_refit
+            .is_some_and(|last| timestamp.saturating_sub(last) < self.config.refit_interval_ms)
+        {
+            return Ok(Vec::new());
+        }
+        self.refit(timestamp)
+    }
+
+    /// Refits the parameters now, ignoring the refit interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a numerical step of the volatility estimate fails.
+    pub fn refit(&mut self, timestamp: u64) -> MMResult<Vec<ParameterUpdate>> {
+        self.last_refit = Some(timestamp);
+        self.cleanup(timestamp);
+
+        let current = self.realized_volatility(self.config.volatility_window_ms, timestamp)?;
+        let baseline = self.realized_volatility(self.config.baseline_window_ms, timestamp)?;
+        if let Some(current) = current {
+            self.volatility = Some(current);
+            if let Some(baseline) = baseline {
+                self.regime = self.detector.detect_regime(current, baseline);
+            }
+        }
+        let adjustments: RegimeAdjustments = self.detector.regime_adjustments(self.regime);
+
+        let mut updates = Vec::new();
+
+        let gamma = self.base.risk_aversion * adjustments.gamma_multiplier;
+        self.push(
+            CalibratedParameter::RiskAversion,
+            gamma,
+            timestamp,
+            &mut updates,
+        );
+
+        let min_spread = self.base.min_spread * adjustments.spread_multiplier;
+        self.push(
+            CalibratedParameter::MinSpread,
+            min_spread,
+            timestamp,
+            &mut updates,
+        );
+
+        if let Some(k) = self.fit_order_intensity() {
+            self.push(
+                CalibratedParameter::OrderIntensity,
+                k,
+                timestamp,
+                &mut updates,
+            );
+        }
+
+        Ok(updates)
+    }
+
+    /// Refits if due and broadcasts each change as a `ConfigChanged` event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the refit fails.
+    #[cfg(feature = "events")]
+    pub async fn poll_and_broadcast(
+        &mut self,
+        timestamp: u64,
+        broadcaster: &EventBroadcaster,
+    ) -> MMResult<Vec<ParameterUpdate>> {
+        let updates = self.poll(timestamp)?;
+        for update in &updates {
+            broadcaster
+                .broadcast(update.to_event("online_recalibrator"))
+                .await;
+        }
+        Ok(updates)
+    }
+
+    /// Drops prices and quote lifetimes outside their windows.
+    fn cleanup(&mut self, timestamp: u64) {
+        let price_start = timestamp.saturating_sub(self.config.baseline_window_ms);
+        while self.prices.front().is_some_and(|&(t, _)| t < price_start) {
+            self.prices.pop_front();
+        }
+        let lifetime_start = timestamp.saturating_sub(self.config.intensity_window_ms);
+        while self
+            .lifetimes
+            .front()
+            .is_some_and(|l| l.timestamp < lifetime_start)
+        {
+            self.lifetimes.pop_front();
+        }
+    }
+
+    /// Annualized realized volatility of log returns inside the window.
+    fn realized_volatility(&self, window_ms: u64, timestamp: u64) -> MMResult<Option<Decimal>> {
+        let start = timestamp.saturating_sub(window_ms);
+        let prices: Vec<&(u64, Decimal)> =
+            self.prices.iter().filter(|(t, _)| *t >= start).collect();
+        if prices.len() < 3 {
+            return Ok(None);
+        }
+
+        let elapsed_ms = prices[prices.len() - 1].0 - prices[0].0;
+        if elapsed_ms == 0 {
+            return Ok(None);
+        }
+
+        let mut sum_sq = Decimal::ZERO;
+        for pair in prices.windows(2) {
+            let ret = decimal_ln(pair[1].1 / pair[0].1)?;
+            sum_sq += ret * ret;
+        }
+
+        let elapsed_years = Decimal::from(elapsed_ms) / Decimal::from(MS_PER_YEAR);
+        decimal_sqrt(sum_sq / elapsed_years).map(Some)
+    }
+
+    /// Fits k in price units, or `None` if the data is insufficient.
+    fn fit_order_intensity(&self) -> Option<Decimal> {
+        let (_, mid) = *self.prices.back()?;
+        let lifetimes: Vec<QuoteLifetime> = self.lifetimes.iter().cloned().collect();
+        let fit = self.calibrator.calibrate_censored(&lifetimes).ok()?;
+        // k per bps of δ/mid → k per unit of price distance.
+        Some(fit.k.value * Decimal::from(BPS) / mid)
+    }
+
+    /// Applies bounds, dead band and rate limits, then writes the value.
+    fn push(
+        &mut self,
+        parameter: CalibratedParameter,
+        target: Decimal,
+        timestamp: u64,
+        updates: &mut Vec<ParameterUpdate>,
+    ) {
+        let slot = parameter as usize;
+        if self.last_changes[slot]
+            .is_some_and(|last| timestamp.saturating_sub(last) < self.config.min_update_interval_ms)
+        {
+            return;
+        }
+
+        let (min, max) = match parameter {
+            CalibratedParameter::RiskAversion => self.config.risk_aversion_bounds,
+            CalibratedParameter::OrderIntensity => self.config.order_intensity_bounds,
+            CalibratedParameter::MinSpread => self.config.min_spread_bounds,
+        };
+        let old_value = match parameter {
+            CalibratedParameter::RiskAversion => self.strategy.risk_aversion,
+            CalibratedParameter::OrderIntensity => self.strategy.order_intensity,
+            CalibratedParameter::MinSpread => self.strategy.min_spread,
+        };
+
+        let mut new_value = target.max(min).min(max);
+        if !old_value.is_zero() {
+            let max_step = old_value.abs() * self.config.max_relative_change;
+            new_value = new_value
+                .max(old_value - max_step)
+                .min(old_value + max_step);
+            if (new_value - old_value).abs() < old_value.abs() * self.config.min_relative_change {
+                return;
+            }
+        }
+        if new_value == old_value {
+            return;
+        }
+
+        match parameter {
+            CalibratedParameter::RiskAversion => {
+                self.strategy.risk_aversion = new_value;
+                if let Some(glft) = self.glft.as_mut() {
+                    glft.risk_aversion = new_value;
+                }
+            }
+            CalibratedParameter::OrderIntensity => {
+                self.strategy.order_intensity = new_value;
+                if let Some(glft) = self.glft.as_mut() {
+                    glft.order_intensity = new_value;
+                }
+            }
+            CalibratedParameter::MinSpread => {
+                self.strategy.min_spread = new_value;
+                if let Some(glft) = self.glft.as_mut() {
+                    glft.min_spread = new_value;
+                }
+            }
+        }
+        self.last_changes[slot] = Some(timestamp);
+
+        updates.push(ParameterUpdate {
+            parameter,
+            old_value,
+            new_value,
+            target_value: target,
+            timestamp,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::order_flow::TradeSide;
+    use crate::dec;
+
+    fn recalibrator(config: RecalibrationConfig) -> OnlineRecalibrator {
+        let strategy = StrategyConfig::new(dec!(0.1), dec!(1.5), 3_600_000, dec!(0.01)).unwrap();
+        OnlineRecalibrator::new(
+            strategy,
+            config,
+            CalibrationConfig::quick(),
+            VolatilityRegimeDetector::new(dec!(1.5), 60_000),
+        )
+    }
+
+    /// Calm prices for the first 50s, then a 10x larger oscillation.
+    fn feed_volatility_spike(recalibrator: &mut OnlineRecalibrator) {
+        for i in 0..60u64 {
+            let amplitude = if i < 50 { dec!(0.01) } else { dec!(0.1) };
+            let price = if i % 2 == 0 {
+                dec!(100.0)
+            } else {
+                dec!(100.0) + amplitude
+            };
+            recalibrator.on_price(i * 1_000, price);
+        }
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(RecalibrationConfig::new(1_000, 10_000, 60_000).is_ok());
+        assert!(RecalibrationConfig::new(0, 10_000, 60_000).is_err());
+        assert!(RecalibrationConfig::new(1_000, 60_000, 60_000).is_err());
+    }
+
+    #[test]
+    fn test_parameter_keys() {
+        assert_eq!(CalibratedParameter::RiskAversion.key(), "risk_aversion");
+        assert_eq!(CalibratedParameter::OrderIntensity.key(), "order_intensity");
+        assert_eq!(CalibratedParameter::MinSpread.key(), "min_spread");
+    }
+
+    #[test]
+    fn test_no_updates_without_data() {
+        let mut recalibrator =
+            recalibrator(RecalibrationConfig::new(1_000, 10_000, 60_000).unwrap());
+        assert!(recalibrator.poll(0).unwrap().is_empty());
+        assert_eq!(recalibrator.regime(), VolatilityRegime::Normal);
+        assert!(recalibrator.volatility().is_none());
+    }
+
+    #[test]
+    fn test_regime_change_is_rate_limited() {
+        let config = RecalibrationConfig::new(1_000, 10_000, 60_000)
+            .unwrap()
+            .with_rate_limit(5_000, dec!(0.2));
+        let mut recalibrator = recalibrator(config).with_glft(
+            GLFTConfig::new(dec!(0.1), dec!(1.5), dec!(0.01), 3_600_000, dec!(0.01)).unwrap(),
+        );
+        feed_volatility_spike(&mut recalibrator);
+
+        let updates = recalibrator.poll(59_000).unwrap();
+        assert_eq!(recalibrator.regime(), VolatilityRegime::Extreme);
+
+        // Extreme regime targets γ x2 and spread x2.5, but steps are capped at 20%.
+        let gamma = updates
+            .iter()
+            .find(|u| u.parameter == CalibratedParameter::RiskAversion)
+            .unwrap();
+        assert_eq!(gamma.old_value, dec!(0.1));
+        assert_eq!(gamma.target_value, dec!(0.2));
+        assert_eq!(gamma.new_value, dec!(0.12));
+        assert_eq!(recalibrator.strategy_config().risk_aversion, dec!(0.12));
+        assert_eq!(
+            recalibrator.glft_config().unwrap().risk_aversion,
+            dec!(0.12)
+        );
+        assert_eq!(recalibrator.strategy_config().min_spread, dec!(0.012));
+
+        // Within the refit interval nothing happens.
+        assert!(recalibrator.poll(59_500).unwrap().is_empty());
+        // Past the refit interval but inside the update rate limit.
+        assert!(recalibrator.poll(61_000).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_bounds_clamp_targets() {
+        let config = RecalibrationConfig::new(1_000, 10_000, 60_000)
+            .unwrap()
+            .with_rate_limit(0, dec!(10.0))
+            .with_risk_aversion_bounds(dec!(0.01), dec!(0.15));
+        let mut recalibrator = recalibrator(config);
+        feed_volatility_spike(&mut recalibrator);
+
+        recalibrator.poll(59_000).unwrap();
+        assert_eq!(recalibrator.strategy_config().risk_aversion, dec!(0.15));
+    }
+
+    #[test]
+    fn test_order_intensity_refit_from_lifetimes() {
+        let config = RecalibrationConfig::new(1_000, 10_000, 60_000)
+            .unwrap()
+            .with_rate_limit(0, dec!(100.0));
+        let mut recalibrator = recalibrator(config);
+
+        recalibrator.on_price(0, dec!(100.0));
+        recalibrator.on_trade(&Trade::new(dec!(100.0), dec!(1.0), TradeSide::Buy, 1_000));
+        for i in 0..20u64 {
+            // 2 bps fills expressed as relative spread.
+            recalibrator.on_fill(&FillObservation::new(dec!(0.0002), 1_000, i * 100));
+            recalibrator.on_quote_lifetime(QuoteLifetime::cancelled(dec!(10.0), 4_000, i * 100));
+        }
+        recalibrator.on_quote_lifetime(QuoteLifetime::filled(dec!(10.0), 4_000, 2_000));
+
+        let updates = recalibrator.refit(3_000).unwrap();
+        let k = updates
+            .iter()
+            .find(|u| u.parameter == CalibratedParameter::OrderIntensity)
+            .unwrap();
+        assert_eq!(k.old_value, dec!(1.5));
+        assert!(k.new_value > dec!(1.5));
+        assert_eq!(recalibrator.strategy_config().order_intensity, k.new_value);
+    }
+
+    #[test]
+    fn test_out_of_order_prices_and_instant_fills_are_ignored() {
+        let mut recalibrator =
+            recalibrator(RecalibrationConfig::new(1_000, 10_000, 60_000).unwrap());
+
+        recalibrator.on_price(5_000, dec!(100.0));
+        recalibrator.on_price(6_000, dec!(100.1));
+        recalibrator.on_price(1_000, dec!(99.0));
+        recalibrator.on_price(7_000, dec!(100.0));
+        assert_eq!(recalibrator.prices.len(), 3);
+        assert!(recalibrator.refit(7_000).is_ok());
+        assert!(recalibrator.volatility().is_some());
+
+        recalibrator.on_fill(&FillObservation::new(dec!(0.0002), 0, 7_000));
+        assert!(recalibrator.lifetimes.is_empty());
+        recalibrator.on_fill(&FillObservation::new(dec!(0.0002), 500, 7_000));
+        assert_eq!(recalibrator.lifetimes.len(), 1);
+    }
+
+    #[test]
+    fn test_dead_band_suppresses_small_changes() {
+        let config = RecalibrationConfig::new(1_000, 10_000, 60_000)
+            .unwrap()
+            .with_min_relative_change(dec!(0.5));
+        let mut recalibrator = recalibrator(config);
+        feed_volatility_spike(&mut recalibrator);
+
+        // A 20% step is inside the 50% dead band.
+        assert!(recalibrator.poll(59_000).unwrap().is_empty());
+        assert_eq!(recalibrator.strategy_config().risk_aversion, dec!(0.1));
+    }
+
+    #[cfg(feature = "events")]
+    #[test]
+    fn test_update_to_event() {
+        let update = ParameterUpdate {
+            parameter: CalibratedParameter::RiskAversion,
+            old_value: dec!(0.1),
+            new_value: dec!(0.12),
+            target_value: dec!(0.2),
+            timestamp: 5,
+        };
+        match update.to_event("test") {
+            MarketMakerEvent::ConfigChanged {
+                key,
+                changed_by,
+                timestamp,
+                ..
+            } => {
+                assert_eq!(key, "risk_aversion");
+                assert_eq!(changed_by, "test");
+                assert_eq!(timestamp, 5);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,1117 @@
+//! Stateful volatility regime tracking.
+//!
+//! [`VolatilityRegimeDetector`] classifies each observation on its own, so a
+//! volatility ratio hovering around a boundary flips the regime (and every
+//! parameter derived from it) back and forth. [`VolatilityRegimeTracker`]
+//! keeps the confirmed regime between observations and only moves it when:
+//!
+//! - the classifier reports a different regime. With
+//!   [`RegimeClassifier::Thresholds`] each boundary has separate enter and
+//!   exit levels, so a ratio must cross the band rather than touch the line.
+//!   [`RegimeClassifier::Hmm`] filters a Gaussian hidden Markov model instead;
+//! - the new regime has persisted for `confirmation_ms`; and
+//! - the current regime has lasted at least `min_dwell_ms`.
+//!
+//! Confirmed transitions are recorded as [`RegimeTransition`]s, raised as
+//! [`AlertType::MarketCondition`] alerts when an [`AlertManager`] is attached
+//! and, with the `events` feature, broadcast as
+//! `MarketMakerEvent::VolatilityRegimeChanged`. [`VolatilityRegimeTracker::adjustments`]
+//! always reflects the confirmed regime.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::strategy::calibration::{VolatilityRegime, VolatilityRegimeDetector};
+//! use market_maker_rs::strategy::regime::{
+//!     RegimeClassifier, RegimeThresholds, RegimeTrackerConfig, VolatilityRegimeTracker,
+//! };
+//! use market_maker_rs::dec;
+//!
+//! let detector = VolatilityRegimeDetector::new(dec!(1.5), 3_600_000);
+//! let thresholds = RegimeThresholds::symmetric(dec!(1.5), dec!(0.1)).unwrap();
+//! let config = RegimeTrackerConfig::new(RegimeClassifier::Thresholds(thresholds))
+//!     .with_min_dwell_ms(1_000);
+//! let mut tracker = VolatilityRegimeTracker::new(detector, config);
+//!
+//! // 1.6x baseline is inside the hysteresis band around 1.5x: no change.
+//! assert!(tracker.update(dec!(0.16), dec!(0.1), 0).is_none());
+//! // 1.7x crosses the High entry level.
+//! let transition = tracker.update(dec!(0.17), dec!(0.1), 1_000).unwrap();
+//! assert_eq!(transition.to, VolatilityRegime::High);
+//! // Falling back to 1.4x is not enough to leave High.
+//! assert!(tracker.update(dec!(0.14), dec!(0.1), 2_000).is_none());
+//! assert_eq!(tracker.regime(), VolatilityRegime::High);
+//! ```
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+use crate::Decimal;
+use crate::risk::alerts::{AlertManager, AlertSeverity, AlertType};
+use crate::strategy::calibration::{RegimeAdjustments, VolatilityRegime, VolatilityRegimeDetector};
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "events")]
+use crate::events::{EventBroadcaster, MarketMakerEvent, VolatilityRegimeLevel};
+
+/// Alert condition raised on a confirmed regime change.
+const REGIME_CHANGED_CONDITION: &str = "volatility_regime_changed";
+
+/// Minimum number of observations per hidden state when fitting an HMM.
+const MIN_OBSERVATIONS_PER_STATE: usize = 10;
+
+/// Log-likelihood improvement below which Baum-Welch stops early.
+const CONVERGENCE_TOLERANCE: f64 = 1e-8;
+
+/// Lower bound for fitted state standard deviations, in log-ratio units.
+const MIN_STD_DEV: f64 = 1e-4;
+
+/// Volatility ratio boundaries with separate enter and exit levels.
+///
+/// A regime is entered when the ratio of current to baseline volatility
+/// crosses its enter level and left only once the ratio crosses back over
+/// the exit level. Setting enter and exit equal reproduces the stateless
+/// [`VolatilityRegimeDetector::detect_regime`] classification.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegimeThresholds {
+    /// Ratio below which Low is entered.
+    pub low_enter: Decimal,
+    /// Ratio above which Low is left.
+    pub low_exit: Decimal,
+    /// Ratio above which High is entered.
+    pub high_enter: Decimal,
+    /// Ratio below which High is left.
+    pub high_exit: Decimal,
+    /// Ratio above which Extreme is entered.
+    pub extreme_enter: Decimal,
+    /// Ratio below which Extreme is left.
+    pub extreme_exit: Decimal,
+}
+
+impl RegimeThresholds {
+    /// Creates thresholds from explicit levels.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` unless
+    /// `0 < low_enter <= low_exit < high_exit <= high_enter`,
+    /// `high_exit < extreme_exit <= extreme_enter` and
+    /// `high_enter < extreme_enter`.
+    pub fn new(
+        low_enter: Decimal,
+        low_exit: Decimal,
+        high_enter: Decimal,
+        high_exit: Decimal,
+        extreme_enter: Decimal,
+        extreme_exit: Decimal,
+    ) -> MMResult<Self> {
+        if low_enter <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "low_enter must be positive".to_string(),
+            ));
+        }
+        if low_enter > low_exit || high_exit > high_enter || extreme_exit > extreme_enter {
+            return Err(MMError::InvalidConfiguration(
+                "enter levels must lie outside their exit levels".to_string(),
+            ));
+        }
+        if low_exit >= high_exit || high_exit >= extreme_exit || high_enter >= extreme_enter {
+            return Err(MMError::InvalidConfiguration(
+                "regime thresholds must increase from Low to Extreme".to_string(),
+            ));
+        }
+        Ok(Self {
+            low_enter,
+            low_exit,
+            high_enter,
+            high_exit,
+            extreme_enter,
+            extreme_exit,
+        })
+    }
+
+    /// Creates thresholds around the [`VolatilityRegimeDetector`] boundaries.
+    ///
+    /// The boundaries are `1/t`, `t` and `2t - 1` for a regime threshold
+    /// `t`. Each is widened by the relative `hysteresis`: the enter level
+    /// moves `hysteresis` away from Normal and the exit level the same
+    /// amount towards it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `hysteresis` is outside
+    /// [0, 1) or the resulting levels overlap (for example when
+    /// `regime_threshold <= 1`).
+    pub fn symmetric(regime_threshold: Decimal, hysteresis: Decimal) -> MMResult<Self> {
+        if hysteresis < Decimal::ZERO || hysteresis >= Decimal::ONE {
+            return Err(MMError::InvalidConfiguration(
+                "hysteresis must be in [0, 1)".to_string(),
+            ));
+        }
+        if regime_threshold <= Decimal::ZERO {
+            return Err(MMError::InvalidConfiguration(
+                "regime_threshold must be positive".to_string(),
+            ));
+        }
+        let low = Decimal::ONE / regime_threshold;
+        let high = regime_threshold;
+        let extreme = regime_threshold + (regime_threshold - Decimal::ONE);
+        let wider = Decimal::ONE + hysteresis;
+        let narrower = Decimal::ONE - hysteresis;
+        Self::new(
+            low * narrower,
+            low * wider,
+            high * wider,
+            high * narrower,
+            extreme * wider,
+            extreme * narrower,
+        )
+    }
+
+    /// Classifies `ratio` given the `current` regime.
+    #[must_use]
+    pub fn classify(&self, current: VolatilityRegime, ratio: Decimal) -> VolatilityRegime {
+        let in_extreme = current == VolatilityRegime::Extreme;
+        let in_high = current.is_high_risk();
+        if ratio > self.extreme_enter || (in_extreme && ratio > self.extreme_exit) {
+            VolatilityRegime::Extreme
+        } else if ratio > self.high_enter || (in_high && ratio > self.high_exit) {
+            VolatilityRegime::High
+        } else if ratio < self.low_enter
+            || (current == VolatilityRegime::Low && ratio < self.low_exit)
+        {
+            VolatilityRegime::Low
+        } else {
+            VolatilityRegime::Normal
+        }
+    }
+}
+
+/// Gaussian hidden Markov model over the log volatility ratio.
+///
+/// Each hidden state emits `ln(current / baseline)` from a normal
+/// distribution. States are ordered by mean and mapped to regimes: two
+/// states are Normal/High, three Low/Normal/High and four
+/// Low/Normal/High/Extreme. [`HmmRegimeClassifier::observe`] runs one step
+/// of the forward filter and returns the most likely state's regime; the
+/// transition matrix supplies the persistence that thresholds get from
+/// hysteresis.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HmmRegimeClassifier {
+    means: Vec<f64>,
+    std_devs: Vec<f64>,
+    transitions: Vec<Vec<f64>>,
+    probabilities: Vec<f64>,
+}
+
+impl HmmRegimeClassifier {
+    /// Creates a model from per-state log-ratio means and standard
+    /// deviations.
+    ///
+    /// `persistence` is the probability of staying in the same state
+    /// between observations; the remainder is split evenly across the
+    /// other states.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if there are not 2 to 4
+    /// states, the lengths differ, means are not strictly increasing, a
+    /// standard deviation is not positive, or `persistence` is outside
+    /// [0, 1).
+    pub fn new(means: &[Decimal], std_devs: &[Decimal], persistence: Decimal) -> MMResult<Self> {
+        if !(2..=4).contains(&means.len()) {
+            return Err(MMError::InvalidConfiguration(
+                "HMM must have 2 to 4 states".to_string(),
+            ));
+        }
+        if std_devs.len() != means.len() {
+            return Err(MMError::InvalidConfiguration(
+                "means and std_devs must have the same length".to_string(),
+            ));
+        }
+        if means.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(MMError::InvalidConfiguration(
+                "HMM means must be strictly increasing".to_string(),
+            ));
+        }
+        if std_devs.iter().any(|&s| s <= Decimal::ZERO) {
+            return Err(MMError::InvalidConfiguration(
+                "HMM std_devs must be positive".to_string(),
+            ));
+        }
+        if persistence < Decimal::ZERO || persistence >= Decimal::ONE {
+            return Err(MMError::InvalidConfiguration(
+                "persistence must be in [0, 1)".to_string(),
+            ));
+        }
+        let n = means.len();
+        let stay = to_f64(persistence)?;
+        let leave = (1.0 - stay) / (n - 1) as f64;
+        let transitions = (0..n)
+            .map(|i| (0..n).map(|j| if i == j { stay } else { leave }).collect())
+            .collect();
+        Ok(Self {
+            means: means.iter().map(|&m| to_f64(m)).collect::<MMResult<_>>()?,
+            std_devs: std_devs
+                .iter()
+                .map(|&s| to_f64(s))
+                .collect::<MMResult<_>>()?,
+            transitions,
+            probabilities: vec![1.0 / n as f64; n],
+        })
+    }
+
+    /// Fits a model with `states` hidden states to volatility ratios using
+    /// the Baum-Welch algorithm.
+    ///
+    /// Non-positive ratios are ignored. Fitting stops after `iterations`
+    /// rounds or once the log-likelihood stops improving.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `states` is not 2 to 4 or
+    /// `iterations` is zero, `MMError::InvalidMarketState` if there are
+    /// fewer than 10 positive ratios per state or they have no dispersion.
+    pub fn fit(ratios: &[Decimal], states: usize, iterations: usize) -> MMResult<Self> {
+        if !(2..=4).contains(&states) {
+            return Err(MMError::InvalidConfiguration(
+                "HMM must have 2 to 4 states".to_string(),
+            ));
+        }
+        if iterations == 0 {
+            return Err(MMError::InvalidConfiguration(
+                "iterations must be positive".to_string(),
+            ));
+        }
+        let observations = ratios
+            .iter()
+            .filter(|&&r| r > Decimal::ZERO)
+            .map(|&r| to_f64(r).map(f64::ln))
+            .collect::<MMResult<Vec<f64>>>()?;
+        let required = states * MIN_OBSERVATIONS_PER_STATE;
+        if observations.len() < required {
+            return Err(MMError::InvalidMarketState(format!(
+                "insufficient ratios: {} < {required}",
+                observations.len()
+            )));
+        }
+
+        let len = observations.len() as f64;
+        let mean = observations.iter().sum::<f64>() / len;
+        let std_dev = (observations.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len).sqrt();
+        if std_dev <= MIN_STD_DEV {
+            return Err(MMError::InvalidMarketState(
+                "ratios must have positive dispersion".to_string(),
+            ));
+        }
+        let std_floor = (std_dev * 0.01).max(MIN_STD_DEV);
+
+        // Quantile initialisation keeps the states ordered from the start.
+        let mut sorted = observations.clone();
+        sorted.sort_by(f64::total_cmp);
+        let means = (0..states)
+            .map(|i| sorted[((2 * i + 1) * sorted.len()) / (2 * states)])
+            .collect();
+        let stay = 0.9;
+        let leave = (1.0 - stay) / (states - 1) as f64;
+        let mut model = Self {
+            means,
+            std_devs: vec![(std_dev / states as f64).max(std_floor); states],
+            transitions: (0..states)
+                .map(|i| {
+                    (0..states)
+                        .map(|j| if i == j { stay } else { leave })
+                        .collect()
+                })
+                .collect(),
+            probabilities: vec![1.0 / states as f64; states],
+        };
+
+        let mut previous = f64::NEG_INFINITY;
+        for _ in 0..iterations {
+            let log_likelihood = model.baum_welch_step(&observations, std_floor);
+            if !log_likelihood.is_finite() {
+                return Err(MMError::NumericalError(
+                    "HMM likelihood is not finite".to_string(),
+                ));
+            }
+            if log_likelihood - previous < CONVERGENCE_TOLERANCE {
+                break;
+            }
+            previous = log_likelihood;
+        }
+        model.sort_states();
+        model.reset();
+        Ok(model)
+    }
+
+    /// Returns the number of hidden states.
+    #[must_use]
+    pub fn num_states(&self) -> usize {
+        self.means.len()
+    }
+
+    /// Returns the regime each hidden state maps to, in state order.
+    #[must_use]
+    pub fn regimes(&self) -> &'static [VolatilityRegime] {
+        state_regimes(self.means.len())
+    }
+
+    /// Returns the per-state log-ratio means.
+    #[must_use]
+    pub fn means(&self) -> Vec<Decimal> {
+        self.means.iter().map(|&m| lossy_decimal(m)).collect()
+    }
+
+    /// Returns the per-state log-ratio standard deviations.
+    #[must_use]
+    pub fn std_devs(&self) -> Vec<Decimal> {
+        self.std_devs.iter().map(|&s| lossy_decimal(s)).collect()
+    }
+
+    /// Returns the filtered state probabilities after the last observation.
+    #[must_use]
+    pub fn state_probabilities(&self) -> Vec<Decimal> {
+        self.probabilities
+            .iter()
+            .map(|&p| lossy_decimal(p))
+            .collect()
+    }
+
+    /// Filters one volatility ratio and returns the most likely regime.
+    ///
+    /// Returns `None` without updating the filter if `ratio` is not
+    /// positive.
+    pub fn observe(&mut self, ratio: Decimal) -> Option<VolatilityRegime> {
+        if ratio <= Decimal::ZERO {
+            return None;
+        }
+        let x = ratio.to_f64()?.ln();
+        let emissions = self.scaled_emissions(x);
+        let n = self.means.len();
+        let mut posterior: Vec<f64> = (0..n)
+            .map(|j| {
+                let predicted: f64 = (0..n)
+                    .map(|i| self.probabilities[i] * self.transitions[i][j])
+                    .sum();
+                predicted * emissions[j]
+            })
+            .collect();
+        let total: f64 = posterior.iter().sum();
+        if total > 0.0 && total.is_finite() {
+            posterior.iter_mut().for_each(|p| *p /= total);
+        } else {
+            // The prediction ruled out every state that can explain x.
+            posterior = emissions;
+            let total: f64 = posterior.iter().sum();
+            posterior.iter_mut().for_each(|p| *p /= total);
+        }
+        self.probabilities = posterior;
+        Some(self.most_likely())
+    }
+
+    /// Returns the regime of the most likely state.
+    #[must_use]
+    pub fn most_likely(&self) -> VolatilityRegime {
+        let state = self
+            .probabilities
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map_or(0, |(i, _)| i);
+        self.regimes()[state]
+    }
+
+    /// Resets the filter to uniform state probabilities.
+    pub fn reset(&mut self) {
+        let n = self.means.len();
+        self.probabilities = vec![1.0 / n as f64; n];
+    }
+
+    /// Emission densities for `x`, scaled so the largest is 1.
+    fn scaled_emissions(&self, x: f64) -> Vec<f64> {
+        let log_densities: Vec<f64> = self
+            .means
+            .iter()
+            .zip(&self.std_devs)
+            .map(|(mean, std_dev)| -0.5 * ((x - mean) / std_dev).powi(2) - std_dev.ln())
+            .collect();
+        let max = log_densities
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        log_densities.iter().map(|l| (l - max).exp()).collect()
+    }
+
+    /// Runs one scaled forward-backward pass and re-estimates the
+    /// parameters. Returns the log-likelihood (up to a constant) of the
+    /// parameters before the update.
+    fn baum_welch_step(&mut self, observations: &[f64], std_floor: f64) -> f64 {
+        let n = self.means.len();
+        let t_len = observations.len();
+        let mut log_likelihood = 0.0;
+
+        let mut emissions = Vec::with_capacity(t_len);
+        let mut offsets = Vec::with_capacity(t_len);
+        for &x in observations {
+            let log_densities: Vec<f64> = self
+                .means
+                .iter()
+                .zip(&self.std_devs)
+                .map(|(mean, std_dev)| -0.5 * ((x - mean) / std_dev).powi(2) - std_dev.ln())
+                .collect();
+            let max = log_densities
+                .iter()
+                .copied()
+                .fold(f64::NEG_INFINITY, f64::max);
+            offsets.push(max);
+            emissions.push(
+                log_densities
+                    .iter()
+                    .map(|l| (l - max).exp())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        // Forward pass with per-step normalisation.
+        let mut alpha = vec![vec![0.0; n]; t_len];
+        let mut scale = vec![0.0; t_len];
+        for t in 0..t_len {
+            for j in 0..n {
+                let prior = if t == 0 {
+                    self.probabilities[j]
+                } else {
+                    (0..n)
+                        .map(|i| alpha[t - 1][i] * self.transitions[i][j])
+                        .sum()
+                };
+                alpha[t][j] = prior * emissions[t][j];
+            }
+            scale[t] = alpha[t].iter().sum::<f64>().max(f64::MIN_POSITIVE);
+            alpha[t].iter_mut().for_each(|a| *a /= scale[t]);
+            log_likelihood += scale[t].ln() + offsets[t];
+        }
+
+        // Backward pass using the forward scale factors.
+        let mut beta = vec![vec![1.0; n]; t_len];
+        for t in (0..t_len - 1).rev() {
+            for i in 0..n {
+                beta[t][i] = (0..n)
+                    .map(|j| self.transitions[i][j] * emissions[t + 1][j] * beta[t + 1][j])
+                    .sum::<f64>()
+                    / scale[t + 1];
+            }
+        }
+
+        let mut gamma_sum = vec![0.0; n];
+        let mut gamma_from = vec![0.0; n];
+        let mut weighted_x = vec![0.0; n];
+        let mut xi_sum = vec![vec![0.0; n]; n];
+        let mut gammas = vec![vec![0.0; n]; t_len];
+        for t in 0..t_len {
+            let mut gamma: Vec<f64> = (0..n).map(|i| alpha[t][i] * beta[t][i]).collect();
+            let total = gamma.iter().sum::<f64>().max(f64::MIN_POSITIVE);
+            gamma.iter_mut().for_each(|g| *g /= total);
+            for i in 0..n {
+                gamma_sum[i] += gamma[i];
+                weighted_x[i] += gamma[i] * observations[t];
+                if t + 1 < t_len {
+                    gamma_from[i] += gamma[i];
+                }
+            }
+            if t + 1 < t_len {
+                let xi: Vec<Vec<f64>> = (0..n)
+                    .map(|i| {
+                        (0..n)
+                            .map(|j| {
+                                alpha[t][i]
+                                    * self.transitions[i][j]
+                                    * emissions[t + 1][j]
+                                    * beta[t + 1][j]
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let total = xi.iter().flatten().sum::<f64>().max(f64::MIN_POSITIVE);
+                for (sum_row, xi_row) in xi_sum.iter_mut().zip(&xi) {
+                    for (sum, value) in sum_row.iter_mut().zip(xi_row) {
+                        *sum += value / total;
+                    }
+                }
+            }
+            gammas[t] = gamma;
+        }
+
+        self.probabilities = gammas[0].clone();
+        for i in 0..n {
+            if gamma_sum[i] < 1e-12 {
+                // Empty state: keep its previous parameters.
+                continue;
+            }
+            let mean = weighted_x[i] / gamma_sum[i];
+            let variance = gammas
+                .iter()
+                .zip(observations)
+                .map(|(g, x)| g[i] * (x - mean).powi(2))
+                .sum::<f64>()
+                / gamma_sum[i];
+            self.means[i] = mean;
+            self.std_devs[i] = variance.sqrt().max(std_floor);
+            if gamma_from[i] > 1e-12 {
+                self.transitions[i] = xi_sum[i].iter().map(|x| x / gamma_from[i]).collect();
+            }
+        }
+        log_likelihood
+    }
+
+    /// Reorders states by increasing mean.
+    fn sort_states(&mut self) {
+        let n = self.means.len();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| self.means[a].total_cmp(&self.means[b]));
+        self.means = order.iter().map(|&i| self.means[i]).collect();
+        self.std_devs = order.iter().map(|&i| self.std_devs[i]).collect();
+        self.transitions = order
+            .iter()
+            .map(|&i| order.iter().map(|&j| self.transitions[i][j]).collect())
+            .collect();
+    }
+}
+
+/// How raw volatility regimes are classified before confirmation.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RegimeClassifier {
+    /// Hysteresis bands on the volatility ratio.
+    Thresholds(RegimeThresholds),
+    /// Forward-filtered Gaussian hidden Markov model.
+    Hmm(HmmRegimeClassifier),
+}
+
+impl RegimeClassifier {
+    /// Classifies `ratio`; `None` means the observation was not usable.
+    fn classify(&mut self, current: VolatilityRegime, ratio: Decimal) -> Option<VolatilityRegime> {
+        match self {
+            Self::Thresholds(thresholds) => Some(thresholds.classify(current, ratio)),
+            Self::Hmm(model) => model.observe(ratio),
+        }
+    }
+}
+
+/// Configuration for [`VolatilityRegimeTracker`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegimeTrackerConfig {
+    /// Raw regime classifier.
+    pub classifier: RegimeClassifier,
+
+    /// Minimum time in milliseconds spent in a regime before leaving it.
+    pub min_dwell_ms: u64,
+
+    /// Time in milliseconds a new regime must persist before it is
+    /// confirmed.
+    pub confirmation_ms: u64,
+}
+
+impl RegimeTrackerConfig {
+    /// Creates a configuration with no dwell or confirmation delay.
+    #[must_use]
+    pub fn new(classifier: RegimeClassifier) -> Self {
+        Self {
+            classifier,
+            min_dwell_ms: 0,
+            confirmation_ms: 0,
+        }
+    }
+
+    /// Creates a configuration that matches `detector` exactly: its
+    /// boundaries without hysteresis, dwell or confirmation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if the detector's regime
+    /// threshold does not produce increasing boundaries.
+    pub fn from_detector(detector: &VolatilityRegimeDetector) -> MMResult<Self> {
+        let thresholds = RegimeThresholds::symmetric(detector.regime_threshold(), Decimal::ZERO)?;
+        Ok(Self::new(RegimeClassifier::Thresholds(thresholds)))
+    }
+
+    /// Sets the minimum dwell time.
+    #[must_use]
+    pub fn with_min_dwell_ms(mut self, min_dwell_ms: u64) -> Self {
+        self.min_dwell_ms = min_dwell_ms;
+        self
+    }
+
+    /// Sets the confirmation time.
+    #[must_use]
+    pub fn with_confirmation_ms(mut self, confirmation_ms: u64) -> Self {
+        self.confirmation_ms = confirmation_ms;
+        self
+    }
+}
+
+/// A confirmed change of volatility regime.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegimeTransition {
+    /// Regime left.
+    pub from: VolatilityRegime,
+    /// Regime entered.
+    pub to: VolatilityRegime,
+    /// Ratio of current to baseline volatility at confirmation, or zero
+    /// when the baseline was not positive.
+    pub volatility_ratio: Decimal,
+    /// Timestamp of the confirming observation in milliseconds.
+    pub timestamp: u64,
+}
+
+#[cfg(feature = "events")]
+impl From<VolatilityRegime> for VolatilityRegimeLevel {
+    fn from(regime: VolatilityRegime) -> Self {
+        match regime {
+            VolatilityRegime::Low => Self::Low,
+            VolatilityRegime::Normal => Self::Normal,
+            VolatilityRegime::High => Self::High,
+            VolatilityRegime::Extreme => Self::Extreme,
+        }
+    }
+}
+
+#[cfg(feature = "events")]
+impl RegimeTransition {
+    /// Converts the transition into a `VolatilityRegimeChanged` event.
+    #[must_use]
+    pub fn to_event(&self, symbol: Option<&str>) -> MarketMakerEvent {
+        MarketMakerEvent::VolatilityRegimeChanged {
+            symbol: symbol.map(str::to_string),
+            previous_regime: self.from.into(),
+            new_regime: self.to.into(),
+            volatility_ratio: self.volatility_ratio.to_f64().unwrap_or_default(),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Tracks the confirmed volatility regime across observations.
+#[derive(Debug)]
+pub struct VolatilityRegimeTracker {
+    detector: VolatilityRegimeDetector,
+    config: RegimeTrackerConfig,
+    regime: VolatilityRegime,
+    entered_at: Option<u64>,
+    candidate: Option<(VolatilityRegime, u64)>,
+    transitions: Vec<RegimeTransition>,
+    alert_manager: Option<AlertManager>,
+}
+
+impl VolatilityRegimeTracker {
+    /// Creates a tracker starting in the Normal regime.
+    ///
+    /// `detector` supplies the parameter adjustments for each regime.
+    #[must_use]
+    pub fn new(detector: VolatilityRegimeDetector, config: RegimeTrackerConfig) -> Self {
+        Self {
+            detector,
+            config,
+            regime: VolatilityRegime::Normal,
+            entered_at: None,
+            candidate: None,
+            transitions: Vec::new(),
+            alert_manager: None,
+        }
+    }
+
+    /// Raises an alert through `manager` on every confirmed transition.
+    ///
+    /// Entering Extreme is raised as [`AlertSeverity::Critical`], entering
+    /// High as [`AlertSeverity::Warning`] and anything else as
+    /// [`AlertSeverity::Info`].
+    #[must_use]
+    pub fn with_alert_manager(mut self, manager: AlertManager) -> Self {
+        self.alert_manager = Some(manager);
+        self
+    }
+
+    /// Returns the detector.
+    #[must_use]
+    pub fn detector(&self) -> &VolatilityRegimeDetector {
+        &self.detector
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn config(&self) -> &RegimeTrackerConfig {
+        &self.config
+    }
+
+    /// Returns the confirmed regime.
+    #[must_use]
+    pub fn regime(&self) -> VolatilityRegime {
+        self.regime
+    }
+
+    /// Returns the adjustments for the confirmed regime.
+    #[must_use]
+    pub fn adjustments(&self) -> RegimeAdjustments {
+        self.detector.regime_adjustments(self.regime)
+    }
+
+    /// Returns the pending regime and the timestamp it was first seen, if
+    /// it differs from the confirmed regime.
+    #[must_use]
+    pub fn candidate(&self) -> Option<(VolatilityRegime, u64)> {
+        self.candidate
+    }
+
+    /// Returns the attached alert manager.
+    #[must_use]
+    pub fn alert_manager(&self) -> Option<&AlertManager> {
+        self.alert_manager.as_ref()
+    }
+
+    /// Returns and clears the transitions recorded since the last call.
+    pub fn take_transitions(&mut self) -> Vec<RegimeTransition> {
+        std::mem::take(&mut self.transitions)
+    }
+
+    /// Feeds one volatility observation.
+    ///
+    /// A non-positive `baseline_volatility` is classified as Normal, as in
+    /// [`VolatilityRegimeDetector::detect_regime`], except by an HMM
+    /// classifier, which ignores it.
+    ///
+    /// # Returns
+    ///
+    /// The transition, if this observation confirmed a new regime.
+    pub fn update(
+        &mut self,
+        current_volatility: Decimal,
+        baseline_volatility: Decimal,
+        timestamp: u64,
+    ) -> Option<RegimeTransition> {
+        let entered_at = *self.entered_at.get_or_insert(timestamp);
+        let (raw, ratio) = if baseline_volatility > Decimal::ZERO {
+            let ratio = current_volatility / baseline_volatility;
+            (self.config.classifier.classify(self.regime, ratio)?, ratio)
+        } else if matches!(self.config.classifier, RegimeClassifier::Hmm(_)) {
+            return None;
+        } else {
+            (VolatilityRegime::Normal, Decimal::ZERO)
+        };
+
+        if raw == self.regime {
+            self.candidate = None;
+            return None;
+        }
+        let since = match self.candidate {
+            Some((regime, since)) if regime == raw => since,
+            _ => {
+                self.candidate = Some((raw, timestamp));
+                timestamp
+            }
+        };
+        if timestamp.saturating_sub(since) < self.config.confirmation_ms
+            || timestamp.saturating_sub(entered_at) < self.config.min_dwell_ms
+        {
+            return None;
+        }
+
+        let transition = RegimeTransition {
+            from: self.regime,
+            to: raw,
+            volatility_ratio: ratio,
+            timestamp,
+        };
+        self.regime = raw;
+        self.entered_at = Some(timestamp);
+        self.candidate = None;
+        self.raise_alert(&transition);
+        self.transitions.push(transition.clone());
+        Some(transition)
+    }
+
+    /// Feeds one observation and broadcasts a confirmed transition.
+    #[cfg(feature = "events")]
+    pub async fn update_and_broadcast(
+        &mut self,
+        current_volatility: Decimal,
+        baseline_volatility: Decimal,
+        timestamp: u64,
+        symbol: Option<&str>,
+        broadcaster: &EventBroadcaster,
+    ) -> Option<RegimeTransition> {
+        let transition = self.update(current_volatility, baseline_volatility, timestamp)?;
+        broadcaster.broadcast(transition.to_event(symbol)).await;
+        Some(transition)
+    }
+
+    /// Returns to the Normal regime and clears pending state.
+    pub fn reset(&mut self) {
+        self.regime = VolatilityRegime::Normal;
+        self.entered_at = None;
+        self.candidate = None;
+        self.transitions.clear();
+        if let RegimeClassifier::Hmm(model) = &mut self.config.classifier {
+            model.reset();
+        }
+    }
+
+    fn raise_alert(&mut self, transition: &RegimeTransition) {
+        let Some(manager) = self.alert_manager.as_mut() else {
+            return;
+        };
+        let severity = match transition.to {
+            VolatilityRegime::Extreme => AlertSeverity::Critical,
+            VolatilityRegime::High => AlertSeverity::Warning,
+            VolatilityRegime::Low | VolatilityRegime::Normal => AlertSeverity::Info,
+        };
+        manager.alert(
+            AlertType::MarketCondition {
+                condition: REGIME_CHANGED_CONDITION.to_string(),
+                details: format!(
+                    "volatility {}x baseline, regime {} -> {}",
+                    transition.volatility_ratio.round_dp(4),
+                    transition.from,
+                    transition.to
+                ),
+            },
+            severity,
+            transition.timestamp,
+        );
+    }
+}
+
+/// Regimes assigned to HMM states ordered by mean.
+fn state_regimes(states: usize) -> &'static [VolatilityRegime] {
+    match states {
+        2 => &[VolatilityRegime::Normal, VolatilityRegime::High],
+        3 => &[
+            VolatilityRegime::Low,
+            VolatilityRegime::Normal,
+            VolatilityRegime::High,
+        ],
+        _ => VolatilityRegime::all(),
+    }
+}
+
+fn to_f64(value: Decimal) -> MMResult<f64> {
+    value
+        .to_f64()
+        .ok_or_else(|| MMError::NumericalError(format!("cannot represent {value} as f64")))
+}
+
+fn lossy_decimal(value: f64) -> Decimal {
+    Decimal::from_f64(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+    use crate::risk::alerts::CollectingAlertHandler;
+    use crate::types::numerics::Lcg;
+    use std::sync::Arc;
+
+    fn thresholds(hysteresis: Decimal) -> RegimeClassifier {
+        RegimeClassifier::Thresholds(RegimeThresholds::symmetric(dec!(1.5), hysteresis).unwrap())
+    }
+
+    fn tracker(config: RegimeTrackerConfig) -> VolatilityRegimeTracker {
+        VolatilityRegimeTracker::new(VolatilityRegimeDetector::default(), config)
+    }
+
+    #[test]
+    fn test_thresholds_validation() {
+        assert!(RegimeThresholds::symmetric(dec!(1.5), dec!(-0.1)).is_err());
+        assert!(RegimeThresholds::symmetric(dec!(1.5), dec!(1.0)).is_err());
+        assert!(RegimeThresholds::symmetric(dec!(1.0), dec!(0.0)).is_err());
+        assert!(
+            RegimeThresholds::new(
+                dec!(0.5),
+                dec!(0.6),
+                dec!(1.4),
+                dec!(1.6),
+                dec!(3.0),
+                dec!(2.0)
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_zero_hysteresis_matches_detector() {
+        let detector = VolatilityRegimeDetector::default();
+        let mut tracker = tracker(RegimeTrackerConfig::from_detector(&detector).unwrap());
+        for (i, ratio) in [
+            dec!(1.0),
+            dec!(1.6),
+            dec!(0.6),
+            dec!(2.1),
+            dec!(1.5),
+            dec!(2.0),
+            dec!(0.7),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            tracker.update(ratio, dec!(1.0), i as u64);
+            assert_eq!(tracker.regime(), detector.detect_regime(ratio, dec!(1.0)));
+        }
+    }
+
+    #[test]
+    fn test_hysteresis_prevents_flapping() {
+        let detector = VolatilityRegimeDetector::default();
+        let mut stateless = tracker(RegimeTrackerConfig::from_detector(&detector).unwrap());
+        let mut banded = tracker(RegimeTrackerConfig::new(thresholds(dec!(0.1))));
+
+        // Oscillates around the 1.5x boundary after one clear breakout.
+        let ratios = [dec!(1.7), dec!(1.45), dec!(1.55), dec!(1.45), dec!(1.55)];
+        for (i, &ratio) in ratios.iter().enumerate() {
+            stateless.update(ratio, dec!(1.0), i as u64);
+            banded.update(ratio, dec!(1.0), i as u64);
+        }
+        assert_eq!(stateless.take_transitions().len(), 5);
+        assert_eq!(banded.take_transitions().len(), 1);
+        assert_eq!(banded.regime(), VolatilityRegime::High);
+
+        // Below the 1.35 exit level it finally leaves High.
+        let transition = banded.update(dec!(1.3), dec!(1.0), 10).unwrap();
+        assert_eq!(transition.from, VolatilityRegime::High);
+        assert_eq!(transition.to, VolatilityRegime::Normal);
+        assert_eq!(transition.volatility_ratio, dec!(1.3));
+    }
+
+    #[test]
+    fn test_confirmation_and_dwell() {
+        let config = RegimeTrackerConfig::new(thresholds(dec!(0.0)))
+            .with_confirmation_ms(100)
+            .with_min_dwell_ms(1_000);
+        let mut tracker = tracker(config);
+
+        assert!(tracker.update(dec!(1.0), dec!(1.0), 0).is_none());
+        // Candidate High is pending until it has persisted for 100ms.
+        assert!(tracker.update(dec!(2.0), dec!(1.0), 1_000).is_none());
+        assert_eq!(tracker.candidate(), Some((VolatilityRegime::High, 1_000)));
+        // A blip back to Normal clears it.
+        assert!(tracker.update(dec!(1.0), dec!(1.0), 1_050).is_none());
+        assert_eq!(tracker.candidate(), None);
+        assert!(tracker.update(dec!(2.0), dec!(1.0), 1_100).is_none());
+        assert!(
+            tracker
+                .update(dec!(2.0), dec!(1.0), 1_200)
+                .is_some_and(|t| t.to == VolatilityRegime::High)
+        );
+        assert_eq!(tracker.adjustments().spread_multiplier, dec!(1.5));
+
+        // Confirmed Normal still has to wait out the 1s dwell in High.
+        assert!(tracker.update(dec!(1.0), dec!(1.0), 1_300).is_none());
+        assert!(tracker.update(dec!(1.0), dec!(1.0), 2_100).is_none());
+        assert_eq!(tracker.regime(), VolatilityRegime::High);
+        assert!(tracker.update(dec!(1.0), dec!(1.0), 2_200).is_some());
+        assert_eq!(tracker.regime(), VolatilityRegime::Normal);
+
+        tracker.reset();
+        assert!(tracker.take_transitions().is_empty());
+        assert_eq!(tracker.candidate(), None);
+    }
+
+    #[test]
+    fn test_hmm_fit_and_classify() {
+        // Calm at 1x, a volatile stretch at 2x, then calm again.
+        let mut rng = Lcg::new(7);
+        let mut noise = || Decimal::from(rng.index(1_000)) / dec!(10_000) - dec!(0.05);
+        let ratios: Vec<Decimal> = (0..300)
+            .map(|i| {
+                let level = if (100..200).contains(&i) {
+                    dec!(2.0)
+                } else {
+                    dec!(1.0)
+                };
+                level * (Decimal::ONE + noise())
+            })
+            .collect();
+
+        let model = HmmRegimeClassifier::fit(&ratios, 2, 50).unwrap();
+        let means = model.means();
+        assert!((means[0] - Decimal::ZERO).abs() < dec!(0.05));
+        assert!((means[1] - dec!(0.6931)).abs() < dec!(0.05));
+        assert_eq!(
+            model.regimes(),
+            &[VolatilityRegime::Normal, VolatilityRegime::High]
+        );
+
+        let mut tracker = tracker(RegimeTrackerConfig::new(RegimeClassifier::Hmm(model)));
+        for i in 0..5 {
+            tracker.update(dec!(0.1), dec!(0.1), i);
+        }
+        assert_eq!(tracker.regime(), VolatilityRegime::Normal);
+        for i in 5..10 {
+            tracker.update(dec!(0.2), dec!(0.1), i);
+        }
+        assert_eq!(tracker.regime(), VolatilityRegime::High);
+        // Unusable observations are skipped by the HMM.
+        assert!(tracker.update(dec!(0.2), dec!(0.0), 10).is_none());
+
+        assert!(HmmRegimeClassifier::fit(&ratios[..15], 2, 50).is_err());
+        assert!(HmmRegimeClassifier::fit(&ratios, 5, 50).is_err());
+        assert!(
+            HmmRegimeClassifier::new(&[dec!(0.5), dec!(0.0)], &[dec!(0.1), dec!(0.1)], dec!(0.9))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_alerts_on_transitions() {
+        struct Shared(Arc<CollectingAlertHandler>);
+        impl crate::risk::alerts::AlertHandler for Shared {
+            fn handle(&self, alert: &crate::risk::alerts::Alert) {
+                self.0.handle(alert);
+            }
+        }
+
+        let collector = Arc::new(CollectingAlertHandler::new(AlertSeverity::Info));
+        let mut manager = AlertManager::new(10, 0);
+        manager.add_handler(Box::new(Shared(Arc::clone(&collector))));
+
+        let mut tracker =
+            tracker(RegimeTrackerConfig::new(thresholds(dec!(0.1)))).with_alert_manager(manager);
+        tracker.update(dec!(3.0), dec!(1.0), 1);
+        tracker.update(dec!(1.0), dec!(1.0), 2);
+
+        let alerts = tracker.alert_manager().unwrap().get_recent_alerts(10);
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[1].severity, AlertSeverity::Critical);
+        assert_eq!(alerts[0].severity, AlertSeverity::Info);
+        assert!(matches!(
+            &alerts[0].alert_type,
+            AlertType::MarketCondition { condition, .. } if condition == REGIME_CHANGED_CONDITION
+        ));
+    }
+
+    #[cfg(feature = "events")]
+    #[test]
+    fn test_transition_to_event() {
+        let transition = RegimeTransition {
+            from: VolatilityRegime::Normal,
+            to: VolatilityRegime::Extreme,
+            volatility_ratio: dec!(2.5),
+            timestamp: 42,
+        };
+        assert_eq!(
+            transition.to_event(Some("BTC")),
+            MarketMakerEvent::VolatilityRegimeChanged {
+                symbol: Some("BTC".to_string()),
+                previous_regime: VolatilityRegimeLevel::Normal,
+                new_regime: VolatilityRegimeLevel::Extreme,
+                volatility_ratio: 2.5,
+                timestamp: 42,
+            }
+        );
+    }
+}
@@ -0,0 +1,641 @@
+//! VPIN-driven protection against informed order flow.
+//!
+//! A [`ToxicityGuard`] is attached to a
+//! [`ToxicityOverlay`](crate::strategy::pipeline::ToxicityOverlay) with
+//! [`ToxicityOverlay::with_guard`](crate::strategy::pipeline::ToxicityOverlay::with_guard)
+//! and extends its linear widening in two ways:
+//!
+//! - **Curves**: piecewise-linear [`ToxicityCurve`]s map VPIN to a spread
+//!   multiplier (applied to each level's distance from the quote center) and
+//!   a size multiplier.
+//! - **Pulling**: once VPIN reaches the pull threshold all quotes are
+//!   removed, and they stay removed until VPIN falls back to the resume
+//!   threshold. The gap between the two thresholds is the hysteresis band
+//!   that prevents flapping around a single level.
+//!
+//! Every pull/resume transition is recorded as a [`ToxicityTransition`] and,
+//! if an [`AlertManager`] is attached, raised as a
+//! [`AlertType::MarketCondition`] alert.
+//!
+//! # Example
+//!
+//! ```rust
+//! use market_maker_rs::analytics::order_flow::{Trade, TradeSide};
+//! use market_maker_rs::analytics::vpin::{VPINCalculator, VPINConfig};
+//! use market_maker_rs::strategy::pipeline::{QuoteOverlay, ToxicityOverlay};
+//! use market_maker_rs::strategy::quoting::{QuoteContext, QuoteSet};
+//! use market_maker_rs::strategy::toxicity_guard::{
+//!     ToxicityGuard, ToxicityGuardConfig, ToxicityState,
+//! };
+//! use market_maker_rs::dec;
+//!
+//! let calculator = VPINCalculator::new(VPINConfig::new(dec!(10.0), 1, dec!(0.7)).unwrap());
+//! let mut overlay = ToxicityOverlay::new(calculator, dec!(0.0))
+//!     .unwrap()
+//!     .with_guard(ToxicityGuard::new(ToxicityGuardConfig::default()));
+//!
+//! // A fully one-sided bucket drives VPIN to 1.
+//! overlay
+//!     .calculator_mut()
+//!     .add_trade(&Trade::new(dec!(100.0), dec!(10.0), TradeSide::Buy, 0));
+//!
+//! let context = QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), 0);
+//! let mut quotes = QuoteSet::single(dec!(99.0), dec!(1.0), dec!(101.0), dec!(1.0), 0);
+//! overlay.apply(&context, &mut quotes).unwrap();
+//!
+//! assert!(quotes.is_empty());
+//! let guard = overlay.guard_mut().unwrap();
+//! assert_eq!(guard.state(), ToxicityState::Pulled);
+//! assert_eq!(guard.take_transitions().len(), 1);
+//! ```
+
+use crate::Decimal;
+use crate::risk::alerts::{AlertManager, AlertSeverity, AlertType};
+use crate::types::error::{MMError, MMResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Alert condition raised when quotes are pulled.
+const PULLED_CONDITION: &str = "toxic_flow_pulled";
+
+/// Alert condition raised when quoting resumes.
+const RESUMED_CONDITION: &str = "toxic_flow_resumed";
+
+/// Piecewise-linear mapping from VPIN to a multiplier.
+///
+/// Between points the multiplier is interpolated linearly; outside the
+/// first and last point it is held flat.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "ToxicityCurveData")
+)]
+pub struct ToxicityCurve {
+    points: Vec<(Decimal, Decimal)>,
+}
+
+/// Unchecked fields of a deserialized [`ToxicityCurve`].
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ToxicityCurveData {
+    points: Vec<(Decimal, Decimal)>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ToxicityCurveData> for ToxicityCurve {
+    type Error = MMError;
+
+    fn try_from(data: ToxicityCurveData) -> MMResult<Self> {
+        Self::new(data.points)
+    }
+}
+
+impl ToxicityCurve {
+    /// Creates a curve from `(vpin, multiplier)` points.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` if `points` is empty, a VPIN
+    /// is outside [0, 1], VPINs are not strictly increasing, or a
+    /// multiplier is negative.
+    pub fn new(points: Vec<(Decimal, Decimal)>) -> MMResult<Self> {
+        if points.is_empty() {
+            return Err(MMError::InvalidConfiguration(
+                "toxicity curve needs at least one point".to_string(),
+            ));
+        }
+        if points
+            .iter()
+            .any(|&(vpin, _)| vpin < Decimal::ZERO || vpin > Decimal::ONE)
+        {
+            return Err(MMError::InvalidConfiguration(
+                "curve VPIN values must be in [0, 1]".to_string(),
+            ));
+        }
+        if points.windows(2).any(|w| w[1].0 <= w[0].0) {
+            return Err(MMError::InvalidConfiguration(
+                "curve VPIN values must be strictly increasing".to_string(),
+            ));
+        }
+        if points.iter().any(|&(_, m)| m < Decimal::ZERO) {
+            return Err(MMError::InvalidConfiguration(
+                "curve multipliers must be non-negative".to_string(),
+            ));
+        }
+        Ok(Self { points })
+    }
+
+    /// Creates a curve that returns `multiplier` for every VPIN.
+    #[must_use]
+    pub fn constant(multiplier: Decimal) -> Self {
+        Self {
+            points: vec![(Decimal::ZERO, multiplier)],
+        }
+    }
+
+    /// Returns the curve points.
+    #[must_use]
+    pub fn points(&self) -> &[(Decimal, Decimal)] {
+        &self.points
+    }
+
+    /// Evaluates the multiplier at `vpin`.
+    #[must_use]
+    pub fn evaluate(&self, vpin: Decimal) -> Decimal {
+        let (first_x, first_y) = self.points[0];
+        if vpin <= first_x {
+            return first_y;
+        }
+        for window in self.points.windows(2) {
+            let (x0, y0) = window[0];
+            let (x1, y1) = window[1];
+            if vpin <= x1 {
+                return y0 + (y1 - y0) * (vpin - x0) / (x1 - x0);
+            }
+        }
+        self.points[self.points.len() - 1].1
+    }
+}
+
+/// Configuration for [`ToxicityGuard`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "ToxicityGuardConfigData")
+)]
+pub struct ToxicityGuardConfig {
+    /// VPIN to spread multiplier.
+    pub spread_curve: ToxicityCurve,
+    /// VPIN to size multiplier.
+    pub size_curve: ToxicityCurve,
+    /// VPIN at or above which all quotes are pulled.
+    pub pull_threshold: Decimal,
+    /// VPIN at or below which quoting resumes after a pull.
+    pub resume_threshold: Decimal,
+}
+
+/// Unchecked fields of a deserialized [`ToxicityGuardConfig`].
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ToxicityGuardConfigData {
+    spread_curve: ToxicityCurve,
+    size_curve: ToxicityCurve,
+    pull_threshold: Decimal,
+    resume_threshold: Decimal,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ToxicityGuardConfigData> for ToxicityGuardConfig {
+    type Error = MMError;
+
+    fn try_from(data: ToxicityGuardConfigData) -> MMResult<Self> {
+        Ok(Self::new(data.pull_threshold, data.resume_threshold)?
+            .with_spread_curve(data.spread_curve)
+            .with_size_curve(data.size_curve))
+    }
+}
+
+impl ToxicityGuardConfig {
+    /// Creates a configuration with neutral curves.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MMError::InvalidConfiguration` unless
+    /// `0 <= resume_threshold < pull_threshold <= 1`.
+    pub fn new(pull_threshold: Decimal, resume_threshold: Decimal) -> MMResult<Self> {
+        if pull_threshold <= Decimal::ZERO || pull_threshold > Decimal::ONE {
+            return Err(MMError::InvalidConfiguration(
+                "pull_threshold must be in (0, 1]".to_string(),
+            ));
+        }
+        if resume_threshold < Decimal::ZERO || resume_threshold >= pull_threshold {
+            return Err(MMError::InvalidConfiguration(
+                "resume_threshold must be non-negative and below pull_threshold".to_string(),
+            ));
+        }
+        Ok(Self {
+            spread_curve: ToxicityCurve::constant(Decimal::ONE),
+            size_curve: ToxicityCurve::constant(Decimal::ONE),
+            pull_threshold,
+            resume_threshold,
+        })
+    }
+
+    /// Sets the spread curve.
+    #[must_use]
+    pub fn with_spread_curve(mut self, curve: ToxicityCurve) -> Self {
+        self.spread_curve = curve;
+        self
+    }
+
+    /// Sets the size curve.
+    #[must_use]
+    pub fn with_size_curve(mut self, curve: ToxicityCurve) -> Self {
+        self.size_curve = curve;
+        self
+    }
+}
+
+impl Default for ToxicityGuardConfig {
+    /// Neutral below VPIN 0.3, spread x2 and size x0.5 by 0.7, pull at 0.9
+    /// and resume at 0.7.
+    fn default() -> Self {
+        let spread_curve = ToxicityCurve {
+            points: vec![
+                (Decimal::new(3, 1), Decimal::ONE),
+                (Decimal::new(7, 1), Decimal::TWO),
+            ],
+        };
+        let size_curve = ToxicityCurve {
+            points: vec![
+                (Decimal::new(3, 1), Decimal::ONE),
+                (Decimal::new(7, 1), Decimal::new(5, 1)),
+            ],
+        };
+        Self {
+            spread_curve,
+            size_curve,
+            pull_threshold: Decimal::new(9, 1),
+            resume_threshold: Decimal::new(7, 1),
+        }
+    }
+}
+
+/// Quoting state of a [`ToxicityGuard`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ToxicityState {
+    /// Quoting, with curve adjustments.
+    #[default]
+    Quoting,
+    /// All quotes pulled.
+    Pulled,
+}
+
+/// A change of [`ToxicityState`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ToxicityTransition {
+    /// State entered.
+    pub state: ToxicityState,
+    /// VPIN that triggered the transition.
+    pub vpin: Decimal,
+    /// Context timestamp in milliseconds.
+    pub timestamp: u64,
+}
+
+/// Pull/resume state and VPIN curves for a
+/// [`ToxicityOverlay`](crate::strategy::pipeline::ToxicityOverlay).
+///
+/// The overlay feeds it the current VPIN on every quote; the guard can also
+/// be driven directly with [`ToxicityGuard::update`].
+#[derive(Debug)]
+pub struct ToxicityGuard {
+    config: ToxicityGuardConfig,
+    state: ToxicityState,
+    transitions: Vec<ToxicityTransition>,
+    alert_manager: Option<AlertManager>,
+}
+
+impl ToxicityGuard {
+    /// Creates a toxicity guard.
+    #[must_use]
+    pub fn new(config: ToxicityGuardConfig) -> Self {
+        Self {
+            config,
+            state: ToxicityState::Quoting,
+            transitions: Vec::new(),
+            alert_manager: None,
+        }
+    }
+
+    /// Raises pull/resume alerts through `manager`.
+    ///
+    /// Pulls are raised as [`AlertSeverity::Critical`], resumes as
+    /// [`AlertSeverity::Info`].
+    #[must_use]
+    pub fn with_alert_manager(mut self, manager: AlertManager) -> Self {
+        self.alert_manager = Some(manager);
+        self
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn config(&self) -> &ToxicityGuardConfig {
+        &self.config
+    }
+
+    /// Returns the current state.
+    #[must_use]
+    pub fn state(&self) -> ToxicityState {
+        self.state
+    }
+
+    /// Returns `true` while quotes are pulled.
+    #[must_use]
+    pub fn is_pulled(&self) -> bool {
+        self.state == ToxicityState::Pulled
+    }
+
+    /// Returns the attached alert manager.
+    #[must_use]
+    pub fn alert_manager(&self) -> Option<&AlertManager> {
+        self.alert_manager.as_ref()
+    }
+
+    /// Returns and clears the transitions recorded since the last call.
+    pub fn take_transitions(&mut self) -> Vec<ToxicityTransition> {
+        std::mem::take(&mut self.transitions)
+    }
+
+    /// Returns the spread and size multipliers of the curves at `vpin`.
+    #[must_use]
+    pub fn multipliers(&self, vpin: Decimal) -> (Decimal, Decimal) {
+        (
+            self.config.spread_curve.evaluate(vpin),
+            self.config.size_curve.evaluate(vpin),
+        )
+    }
+
+    /// Moves between quoting and pulled if VPIN crossed the matching
+    /// threshold, and returns the resulting state.
+    pub fn update(&mut self, vpin: Decimal, timestamp: u64) -> ToxicityState {
+        let next = match self.state {
+            ToxicityState::Quoting if vpin >= self.config.pull_threshold => ToxicityState::Pulled,
+            ToxicityState::Pulled if vpin <= self.config.resume_threshold => ToxicityState::Quoting,
+            _ => return self.state,
+        };
+        self.state = next;
+        self.transitions.push(ToxicityTransition {
+            state: next,
+            vpin,
+            timestamp,
+        });
+
+        if let Some(manager) = self.alert_manager.as_mut() {
+            let vpin = vpin.round_dp(4);
+            let (condition, severity, details) = match next {
+                ToxicityState::Pulled => (
+                    PULLED_CONDITION,
+                    AlertSeverity::Critical,
+                    format!(
+                        "VPIN {vpin} reached {}, quotes pulled",
+                        self.config.pull_threshold
+                    ),
+                ),
+                ToxicityState::Quoting => (
+                    RESUMED_CONDITION,
+                    AlertSeverity::Info,
+                    format!(
+                        "VPIN {vpin} fell to {}, quoting resumed",
+                        self.config.resume_threshold
+                    ),
+                ),
+            };
+            manager.alert(
+                AlertType::MarketCondition {
+                    condition: condition.to_string(),
+                    details,
+                },
+                severity,
+                timestamp,
+            );
+        }
+        next
+    }
+
+    /// Returns to quoting and drops recorded transitions.
+    pub fn reset(&mut self) {
+        self.state = ToxicityState::Quoting;
+        self.transitions.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::order_flow::{Trade, TradeSide};
+    use crate::analytics::vpin::{VPINCalculator, VPINConfig};
+    use crate::dec;
+    use crate::risk::alerts::CollectingAlertHandler;
+    use crate::strategy::pipeline::{QuoteOverlay, ToxicityOverlay};
+    use crate::strategy::quoting::{QuoteContext, QuoteSet};
+    use std::sync::Arc;
+
+    /// Calculator with two buckets of 10 so VPIN moves in steps.
+    fn calculator() -> VPINCalculator {
+        VPINCalculator::new(VPINConfig::new(dec!(10.0), 2, dec!(0.7)).unwrap())
+    }
+
+    /// Overlay without linear widening, so only the guard acts.
+    fn guarded(guard: ToxicityGuard) -> ToxicityOverlay {
+        ToxicityOverlay::new(calculator(), Decimal::ZERO)
+            .unwrap()
+            .with_guard(guard)
+    }
+
+    fn bucket(overlay: &mut ToxicityOverlay, buy: Decimal) {
+        let calc = overlay.calculator_mut();
+        if buy > Decimal::ZERO {
+            calc.add_trade(&Trade::new(dec!(100.0), buy, TradeSide::Buy, 0));
+        }
+        if buy < dec!(10.0) {
+            calc.add_trade(&Trade::new(
+                dec!(100.0),
+                dec!(10.0) - buy,
+                TradeSide::Sell,
+                0,
+            ));
+        }
+    }
+
+    fn context(timestamp: u64) -> QuoteContext {
+        QuoteContext::new(dec!(100.0), dec!(0.2), dec!(0.0), timestamp)
+    }
+
+    fn quotes() -> QuoteSet {
+        QuoteSet::single(dec!(99.0), dec!(10.0), dec!(101.0), dec!(10.0), 0)
+    }
+
+    #[test]
+    fn test_curve_interpolates_and_clamps() {
+        let curve =
+            ToxicityCurve::new(vec![(dec!(0.2), dec!(1.0)), (dec!(0.6), dec!(3.0))]).unwrap();
+        assert_eq!(curve.evaluate(dec!(0.0)), dec!(1.0));
+        assert_eq!(curve.evaluate(dec!(0.4)), dec!(2.0));
+        assert_eq!(curve.evaluate(dec!(0.9)), dec!(3.0));
+        assert_eq!(
+            ToxicityCurve::constant(dec!(1.5)).evaluate(dec!(0.8)),
+            dec!(1.5)
+        );
+    }
+
+    #[test]
+    fn test_curve_validation() {
+        assert!(ToxicityCurve::new(vec![]).is_err());
+        assert!(ToxicityCurve::new(vec![(dec!(1.5), dec!(1.0))]).is_err());
+        assert!(ToxicityCurve::new(vec![(dec!(0.5), dec!(1.0)), (dec!(0.5), dec!(2.0))]).is_err());
+        assert!(ToxicityCurve::new(vec![(dec!(0.5), dec!(-1.0))]).is_err());
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(ToxicityGuardConfig::new(dec!(0.8), dec!(0.6)).is_ok());
+        assert!(ToxicityGuardConfig::new(dec!(0.6), dec!(0.6)).is_err());
+        assert!(ToxicityGuardConfig::new(dec!(1.2), dec!(0.6)).is_err());
+        assert!(ToxicityGuardConfig::new(dec!(0.8), dec!(-0.1)).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialization_is_validated() {
+        let config = ToxicityGuardConfig::default();
+        let value = serde_json::to_value(&config).unwrap();
+        let restored: ToxicityGuardConfig = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(restored, config);
+
+        for points in [
+            serde_json::json!([]),
+            serde_json::json!([["0.7", "2"], ["0.3", "1"]]),
+            serde_json::json!([["0.5", "1"], ["0.5", "2"]]),
+        ] {
+            let curve = serde_json::json!({ "points": points });
+            assert!(serde_json::from_value::<ToxicityCurve>(curve).is_err());
+        }
+
+        let mut inverted = value.clone();
+        inverted["resume_threshold"] = serde_json::json!("0.95");
+        assert!(serde_json::from_value::<ToxicityGuardConfig>(inverted).is_err());
+
+        let mut empty_curve = value;
+        empty_curve["size_curve"]["points"] = serde_json::json!([]);
+        assert!(serde_json::from_value::<ToxicityGuardConfig>(empty_curve).is_err());
+    }
+
+    #[test]
+    fn test_neutral_without_vpin() {
+        let mut overlay = guarded(ToxicityGuard::new(ToxicityGuardConfig::default()));
+        let mut quotes = quotes();
+        assert_eq!(overlay.apply(&context(0), &mut quotes).unwrap(), None);
+        assert_eq!(quotes.spread(), Some(dec!(2.0)));
+    }
+
+    #[test]
+    fn test_curves_scale_spread_and_size() {
+        let mut overlay = guarded(ToxicityGuard::new(ToxicityGuardConfig::default()));
+        // Buckets with imbalance 10 and 0: VPIN 0.5.
+        bucket(&mut overlay, dec!(10.0));
+        bucket(&mut overlay, dec!(5.0));
+
+        let mut quotes = quotes();
+        overlay.apply(&context(0), &mut quotes).unwrap();
+
+        // Spread x1.5, size x0.75 halfway along the default curves.
+        assert_eq!(quotes.spread(), Some(dec!(3.0)));
+        assert_eq!(quotes.total_bid_size(), dec!(7.5));
+        assert_eq!(overlay.guard().unwrap().state(), ToxicityState::Quoting);
+    }
+
+    #[test]
+    fn test_curves_compose_with_linear_widening() {
+        let mut overlay = ToxicityOverlay::new(calculator(), dec!(1.0))
+            .unwrap()
+            .with_guard(ToxicityGuard::new(ToxicityGuardConfig::default()));
+        bucket(&mut overlay, dec!(10.0));
+        bucket(&mut overlay, dec!(5.0));
+
+        let mut quotes = quotes();
+        overlay.apply(&context(0), &mut quotes).unwrap();
+
+        // Linear x1.5 times curve x1.5.
+        assert_eq!(quotes.spread(), Some(dec!(4.5)));
+    }
+
+    #[test]
+    fn test_pull_and_resume_with_hysteresis() {
+        let config = ToxicityGuardConfig::new(dec!(0.9), dec!(0.5)).unwrap();
+        let mut overlay = guarded(ToxicityGuard::new(config));
+
+        bucket(&mut overlay, dec!(10.0));
+        bucket(&mut overlay, dec!(10.0));
+        let mut q = quotes();
+        overlay.apply(&context(1), &mut q).unwrap();
+        assert!(q.is_empty());
+        assert!(overlay.guard().unwrap().is_pulled());
+
+        // VPIN 0.75: inside the band, still pulled.
+        bucket(&mut overlay, dec!(7.5));
+        let mut q = quotes();
+        overlay.apply(&context(2), &mut q).unwrap();
+        assert!(q.is_empty());
+
+        // VPIN 0.5: resume.
+        bucket(&mut overlay, dec!(7.5));
+        let mut q = quotes();
+        overlay.apply(&context(3), &mut q).unwrap();
+        assert_eq!(q.spread(), Some(dec!(2.0)));
+
+        let guard = overlay.guard_mut().unwrap();
+        let transitions = guard.take_transitions();
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[0].state, ToxicityState::Pulled);
+        assert_eq!(transitions[0].timestamp, 1);
+        assert_eq!(transitions[1].state, ToxicityState::Quoting);
+        assert_eq!(transitions[1].vpin, dec!(0.5));
+        assert!(guard.take_transitions().is_empty());
+    }
+
+    #[test]
+    fn test_alerts_on_transitions() {
+        struct Shared(Arc<CollectingAlertHandler>);
+        impl crate::risk::alerts::AlertHandler for Shared {
+            fn handle(&self, alert: &crate::risk::alerts::Alert) {
+                self.0.handle(alert);
+            }
+        }
+
+        let collector = Arc::new(CollectingAlertHandler::new(AlertSeverity::Info));
+        let mut manager = AlertManager::new(10, 0);
+        manager.add_handler(Box::new(Shared(Arc::clone(&collector))));
+
+        let config = ToxicityGuardConfig::new(dec!(0.9), dec!(0.5)).unwrap();
+        let mut overlay = guarded(ToxicityGuard::new(config).with_alert_manager(manager));
+
+        bucket(&mut overlay, dec!(10.0));
+        bucket(&mut overlay, dec!(10.0));
+        overlay.apply(&context(1), &mut quotes()).unwrap();
+        bucket(&mut overlay, dec!(5.0));
+        bucket(&mut overlay, dec!(5.0));
+        overlay.apply(&context(2), &mut quotes()).unwrap();
+
+        let alerts = overlay
+            .guard()
+            .unwrap()
+            .alert_manager()
+            .unwrap()
+            .get_recent_alerts(10);
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[1].severity, AlertSeverity::Critical);
+        assert_eq!(alerts[1].alert_type.type_key(), "market_toxic_flow_pulled");
+        assert_eq!(alerts[0].severity, AlertSeverity::Info);
+        assert_eq!(collector.count(), 2);
+    }
+
+    #[test]
+    fn test_reset_clears_state() {
+        let mut overlay = guarded(ToxicityGuard::new(ToxicityGuardConfig::default()));
+        bucket(&mut overlay, dec!(10.0));
+        bucket(&mut overlay, dec!(10.0));
+        overlay.apply(&context(0), &mut quotes()).unwrap();
+        assert!(overlay.guard().unwrap().is_pulled());
+
+        overlay.reset();
+        assert!(overlay.calculator().get_vpin().is_none());
+        let guard = overlay.guard_mut().unwrap();
+        assert_eq!(guard.state(), ToxicityState::Quoting);
+        assert!(guard.take_transitions().is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 //! Decimal helper functions for mathematical operations.
 //!
 //! Provides mathematical operations not natively supported by rust_decimal,
-//! such as logarithms, powers, and square roots.
+//! such as logarithms, exponentials, powers, and square roots.
 
 use crate::types::error::{MMError, MMResult};
 use rust_decimal::Decimal;
@@ -142,6 +142,48 @@ pub fn decimal_sqrt(value: Decimal) -> MMResult<Decimal> {
         .ok_or_else(|| MMError::NumericalError("decimal_sqrt: conversion error".to_string()))
 }
 
+/// Calculates the exponential function (e^x) of a Decimal value.
+///
+/// Since `Decimal` does not natively support exponentials, this function
+/// temporarily converts to `f64`, performs the calculation, and converts back.
+///
+/// # Arguments
+///
+/// * `value` - The exponent
+///
+/// # Returns
+///
+/// `e` raised to the given power.
+///
+/// # Errors
+///
+/// Returns `MMError::NumericalError` if:
+/// - The value cannot be converted to f64
+/// - The result overflows the range of `Decimal`
+///
+/// # Examples
+///
+/// ```
+/// use market_maker_rs::types::decimal::decimal_exp;
+/// use market_maker_rs::dec;
+///
+/// let result = decimal_exp(dec!(0)).unwrap();
+/// assert_eq!(result, dec!(1));
+/// ```
+pub fn decimal_exp(value: Decimal) -> MMResult<Decimal> {
+    let float_value = match value.to_f64() {
+        Some(v) => v,
+        None => {
+            return Err(MMError::NumericalError(
+                "decimal_exp: invalid value".to_string(),
+            ));
+        }
+    };
+    let result = float_value.exp();
+    Decimal::from_f64(result)
+        .ok_or_else(|| MMError::NumericalError("decimal_exp: conversion error".to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,4 +318,23 @@ mod tests {
         let result = decimal_ln(dec!(0.0));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_decimal_exp() {
+        // e^0 = 1
+        assert_eq!(decimal_exp(dec!(0)).unwrap(), dec!(1));
+
+        // e^1 ≈ 2.71828
+        let result = decimal_exp(dec!(1)).unwrap();
+        assert!((result - dec!(2.71828)).abs() < dec!(0.0001));
+
+        // e^(ln 2) ≈ 2
+        let result = decimal_exp(decimal_ln(dec!(2)).unwrap()).unwrap();
+        assert!((result - dec!(2)).abs() < dec!(0.0001));
+    }
+
+    #[test]
+    fn test_decimal_exp_overflow() {
+        assert!(decimal_exp(dec!(1000)).is_err());
+    }
 }
@@ -4,6 +4,7 @@
 //! - Error types using `thiserror`
 //! - Type aliases for domain concepts
 //! - Common enums and shared data structures
+//! - Numerical optimizers and a seeded random number generator
 
 /// Error types and result handling.
 pub mod error;
@@ -13,3 +14,6 @@ pub mod decimal;
 
 /// Common type aliases for prices, quantities, and time.
 pub mod primitives;
+
+/// Derivative-free optimization and seeded random sampling.
+pub(crate) mod numerics;
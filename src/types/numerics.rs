@@ -0,0 +1,112 @@
+//! Numerical helpers shared across modules: a Nelder-Mead optimizer and
+//! a seeded random number generator.
+
+/// Minimizes `f` with the Nelder-Mead simplex method.
+pub(crate) fn nelder_mead<F>(
+    f: F,
+    start: [f64; 3],
+    max_iterations: usize,
+    tolerance: f64,
+) -> ([f64; 3], f64)
+where
+    F: Fn(&[f64; 3]) -> f64,
+{
+    let mut simplex: Vec<([f64; 3], f64)> = (0..4)
+        .map(|i| {
+            let mut point = start;
+            if i > 0 {
+                point[i - 1] += 1.0;
+            }
+            let value = f(&point);
+            (point, value)
+        })
+        .collect();
+
+    let along = |a: &[f64; 3], b: &[f64; 3], t: f64| -> [f64; 3] {
+        [
+            a[0] + t * (b[0] - a[0]),
+            a[1] + t * (b[1] - a[1]),
+            a[2] + t * (b[2] - a[2]),
+        ]
+    };
+
+    for _ in 0..max_iterations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if (simplex[3].1 - simplex[0].1).abs() < tolerance {
+            break;
+        }
+
+        let mut centroid = [0.0; 3];
+        for (point, _) in &simplex[..3] {
+            for (c, p) in centroid.iter_mut().zip(point) {
+                *c += p / 3.0;
+            }
+        }
+        let worst = simplex[3];
+
+        let reflected = along(&centroid, &worst.0, -1.0);
+        let reflected_value = f(&reflected);
+
+        if reflected_value < simplex[0].1 {
+            let expanded = along(&centroid, &worst.0, -2.0);
+            let expanded_value = f(&expanded);
+            simplex[3] = if expanded_value < reflected_value {
+                (expanded, expanded_value)
+            } else {
+                (reflected, reflected_value)
+            };
+        } else if reflected_value < simplex[2].1 {
+            simplex[3] = (reflected, reflected_value);
+        } else {
+            let contracted = along(&centroid, &worst.0, 0.5);
+            let contracted_value = f(&contracted);
+            if contracted_value < worst.1 {
+                simplex[3] = (contracted, contracted_value);
+            } else {
+                let best = simplex[0].0;
+                for vertex in simplex.iter_mut().skip(1) {
+                    let shrunk = along(&best, &vertex.0, 0.5);
+                    *vertex = (shrunk, f(&shrunk));
+                }
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    simplex[0]
+}
+
+/// Seeded linear congruential generator for reproducible sampling.
+#[derive(Debug, Clone)]
+pub(crate) struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Uniform sample in `[0, 1)`.
+    pub(crate) fn uniform(&mut self) -> f64 {
+        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        (self.state >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal sample (Box-Muller).
+    pub(crate) fn gaussian(&mut self) -> f64 {
+        let u1 = self.uniform().max(f64::MIN_POSITIVE);
+        let u2 = self.uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    /// Uniform index in `0..len`.
+    pub(crate) fn index(&mut self, len: usize) -> usize {
+        ((self.uniform() * len as f64) as usize).min(len - 1)
+    }
+
+    /// Point in the unit cube of `dims` dimensions.
+    pub(crate) fn point(&mut self, dims: usize) -> Vec<f64> {
+        (0..dims).map(|_| self.uniform()).collect()
+    }
+}